@@ -0,0 +1,93 @@
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// The files whose changes can affect the result of a verification.
+const SOURCES: &[&str] = &[
+    "*.rs", "*.toml", "Cargo.lock", "rust-toolchain", ":!prusti-tests", ":!test-crates",
+];
+
+fn main() {
+    // The commit, together with the uncommitted changes of the sources, is part of the key of
+    // cached verification results.
+    if let Some(commit_hash) = commit_hash() {
+        println!("cargo:rustc-env=COMMIT_HASH={}", commit_hash);
+    }
+}
+
+fn commit_hash() -> Option<String> {
+    let git_dir = git(&["rev-parse", "--absolute-git-dir"])?;
+    let top_level = git(&["rev-parse", "--show-toplevel"])?;
+    let git_dir = Path::new(git_dir.trim());
+    let top_level = Path::new(top_level.trim());
+
+    // Run again when a commit is checked out or created, and when a source is modified.
+    // A new file only affects the result once it is used by a modified source.
+    rerun_if_changed(&git_dir.join("HEAD"));
+    match git(&["symbolic-ref", "-q", "HEAD"]) {
+        Some(head_ref) if git_dir.join(head_ref.trim()).exists() => {
+            rerun_if_changed(&git_dir.join(head_ref.trim()));
+        }
+        _ => rerun_if_changed(&git_dir.join("packed-refs")),
+    }
+    let sources = git_sources(top_level, &["ls-files"])?;
+    for source in sources.lines() {
+        rerun_if_changed(&top_level.join(source));
+    }
+
+    let commit_hash = git(&["rev-parse", "--short", "HEAD"])?;
+    let diff_args = [&["diff", "HEAD", "--binary", "--"][..], SOURCES].concat();
+    let changes = git_output(top_level, &diff_args)?;
+    let untracked = git_sources(top_level, &["ls-files", "--others", "--exclude-standard"])?;
+    if changes.is_empty() && untracked.is_empty() {
+        return Some(commit_hash.trim().to_string());
+    }
+
+    // The tree is dirty, so the commit alone does not identify the sources.
+    let mut contents = changes;
+    for source in untracked.lines() {
+        contents.extend(source.as_bytes());
+        contents.extend(std::fs::read(top_level.join(source)).ok()?);
+    }
+    let changes_hash = hash_object(top_level, &contents)?;
+    Some(format!("{}-dirty-{}", commit_hash.trim(), &changes_hash.trim()[..10]))
+}
+
+fn rerun_if_changed(path: &Path) {
+    println!("cargo:rerun-if-changed={}", path.display());
+}
+
+fn git(args: &[&str]) -> Option<String> {
+    git_in(Path::new("."), args)
+}
+
+/// Run `git` with `args` restricted to the paths of `SOURCES`.
+fn git_sources(dir: &Path, args: &[&str]) -> Option<String> {
+    git_in(dir, &[args, &["--"], SOURCES].concat())
+}
+
+fn git_in(dir: &Path, args: &[&str]) -> Option<String> {
+    String::from_utf8(git_output(dir, args)?).ok()
+}
+
+fn git_output(dir: &Path, args: &[&str]) -> Option<Vec<u8>> {
+    let output = Command::new("git").current_dir(dir).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(output.stdout)
+}
+
+/// Hash `contents` with `git hash-object`, which is stable across builds.
+fn hash_object(dir: &Path, contents: &[u8]) -> Option<String> {
+    let mut child = Command::new("git")
+        .current_dir(dir)
+        .args(&["hash-object", "--stdin"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .ok()?;
+    child.stdin.take()?.write_all(contents).ok()?;
+    let output = child.wait_with_output().ok()?;
+    String::from_utf8(output.stdout).ok()
+}
@@ -67,6 +67,8 @@ lazy_static! {
         settings.set_default("CHECK_FOLDUNFOLD_STATE", false).unwrap();
         settings.set_default("CHECK_OVERFLOWS", false).unwrap();
         settings.set_default("CHECK_PANICS", true).unwrap();
+        settings.set_default("CHECK_LOOP_TERMINATION", false).unwrap();
+        settings.set_default("CHECK_PURE_FUNCTION_TERMINATION", false).unwrap();
         settings.set_default("ENCODE_UNSIGNED_NUM_CONSTRAINT", false).unwrap();
         settings.set_default("SIMPLIFY_ENCODING", true).unwrap();
         settings.set_default("LOG_DIR", "./log/").unwrap();
@@ -90,6 +92,12 @@ lazy_static! {
         settings.set_default("FULL_COMPILATION", false).unwrap();
         settings.set_default("JSON_COMMUNICATION", false).unwrap();
         settings.set_default("JSON_COMMUNICATION", false).unwrap();
+        settings.set_default("INVALIDATE_CACHE", false).unwrap();
+        settings.set_default("NUM_PARALLEL_VERIFIERS", 1).unwrap();
+        settings.set_default("SERVER_WORKER_PROCESSES", false).unwrap();
+        settings.set_default("SERVER_MAX_CACHED_RESULTS", 100).unwrap();
+        settings.set_default("SERVER_JOB_TTL", 600).unwrap();
+        settings.set_default("SERVER_JOB_TIMEOUT", 3600).unwrap();
         settings.set_default("OPTIMIZATIONS","all").unwrap();
         settings.set_default("INTERN_NAMES", true).unwrap();
         settings.set_default("ENABLE_PURIFICATION_OPTIMIZATION", false).unwrap();
@@ -236,6 +244,16 @@ pub fn check_overflows() -> bool {
     read_setting("CHECK_OVERFLOWS")
 }
 
+/// Check that loops annotated with `body_variant!` terminate
+pub fn check_loop_termination() -> bool {
+    read_setting("CHECK_LOOP_TERMINATION")
+}
+
+/// Require a termination measure on every recursive pure function
+pub fn check_pure_function_termination() -> bool {
+    read_setting("CHECK_PURE_FUNCTION_TERMINATION")
+}
+
 /// Encode (and check) that unsigned integers are non-negative.
 pub fn encode_unsigned_num_constraint() -> bool {
     read_setting("ENCODE_UNSIGNED_NUM_CONSTRAINT")
@@ -310,6 +328,38 @@ pub fn server_max_concurrency() -> Option<usize> {
     read_optional_setting("SERVER_MAX_CONCURRENCY")
 }
 
+/// If true, the server verifies requests in `SERVER_MAX_CONCURRENCY` child processes, each with
+/// its own JVM, instead of verifying them one at a time in its own JVM. Crashed processes are
+/// restarted.
+pub fn server_worker_processes() -> bool {
+    read_setting("SERVER_WORKER_PROCESSES")
+}
+
+/// The maximum amount of verification results the server keeps in memory, to answer identical
+/// requests without verifying them again. Set to 0 to keep no results in memory.
+/// With `SERVER_WORKER_PROCESSES`, the results are shared by all worker processes.
+pub fn server_max_cached_results() -> usize {
+    read_setting("SERVER_MAX_CACHED_RESULTS")
+}
+
+/// When set, the server also stores all its verification results in this directory, so that
+/// they are reused after the server restarts.
+pub fn server_cache_path() -> Option<String> {
+    read_optional_setting("SERVER_CACHE_PATH")
+}
+
+/// The number of seconds for which the server keeps the results of a finished job that its
+/// client did not cancel.
+pub fn server_job_ttl() -> u64 {
+    read_setting("SERVER_JOB_TTL")
+}
+
+/// The maximum number of seconds that a client waits for the server to verify a program.
+/// The job of a program that takes longer is cancelled. Set to 0 to wait indefinitely.
+pub fn server_job_timeout() -> u64 {
+    read_setting("SERVER_JOB_TIMEOUT")
+}
+
 /// When set, Prusti will connect to this server and use it for its verification backend (i.e. the things using the JVM/Viper).
 /// Set to "MOCK" to run the server off-thread, effectively mocking connecting to a server without having to start it up separately.
 /// e.g. "127.0.0.1:2468"
@@ -322,6 +372,26 @@ pub fn json_communication() -> bool {
     read_setting("JSON_COMMUNICATION")
 }
 
+/// When set, the verification results of methods are stored in this directory and reused
+/// when verifying unchanged methods again. Each method is then verified as a separate Viper
+/// program that contains only the items used by the method.
+pub fn cache_path() -> Option<String> {
+    read_optional_setting("CACHE_PATH")
+}
+
+/// Discard all the results stored in the cache at `CACHE_PATH` before verifying.
+pub fn invalidate_cache() -> bool {
+    read_setting("INVALIDATE_CACHE")
+}
+
+/// The number of methods that are verified at the same time. When greater than 1, each method
+/// is verified as a separate Viper program, and the programs are distributed over this many
+/// verifiers. Without `SERVER_ADDRESS`, each verifier is a `prusti-server-driver` process
+/// with its own JVM.
+pub fn num_parallel_verifiers() -> usize {
+    read_setting("NUM_PARALLEL_VERIFIERS")
+}
+
 /// Disable mangling of generated Viper names.
 ///
 /// **Note:** This is very likely to result in invalid programs being
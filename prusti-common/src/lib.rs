@@ -10,6 +10,7 @@
 
 #![allow(warnings)]
 
+extern crate bincode;
 #[macro_use]
 extern crate log;
 extern crate config as config_crate;
@@ -19,6 +20,8 @@ extern crate lazy_static;
 extern crate regex;
 #[macro_use]
 extern crate serde;
+extern crate serde_json;
+extern crate sha2;
 extern crate uuid;
 extern crate viper;
 
@@ -26,6 +29,7 @@ pub mod config;
 pub mod report;
 mod stopwatch;
 pub mod utils;
+pub mod verification_cache;
 pub mod verification_context;
 pub mod verification_service;
 pub mod vir;
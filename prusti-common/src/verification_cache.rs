@@ -0,0 +1,183 @@
+// © 2021, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! A persistent cache of verification results, used to avoid verifying unchanged methods again.
+
+use bincode;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use viper::VerificationResult;
+
+/// Bump this when the format of the cached results or the meaning of the requests changes, so
+/// that results stored by older versions are ignored.
+const CACHE_VERSION: u32 = 2;
+
+/// A cache of verification results stored on disk, with one file per verification request.
+///
+/// Requests are identified by the hash of their `CacheKey`, and the results are stored with
+/// the positions of the key. Only successes and verification failures are stored; consistency
+/// errors and exceptions are always reported again.
+pub struct VerificationCache {
+    path: PathBuf,
+}
+
+impl VerificationCache {
+    /// Open the cache stored in the directory `path`, creating the directory if necessary.
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        let path = path.into();
+        if let Err(error) = fs::create_dir_all(&path) {
+            warn!("Could not create the cache directory {:?}: {}", path, error);
+        }
+        VerificationCache { path }
+    }
+
+    /// Remove all cached results.
+    pub fn clear(&self) {
+        info!("Clearing the verification cache at {:?}", self.path);
+        let result = fs::read_dir(&self.path).and_then(|entries| {
+            for entry in entries {
+                fs::remove_file(entry?.path())?;
+            }
+            Ok(())
+        });
+        if let Err(error) = result {
+            warn!("Could not clear the cache directory {:?}: {}", self.path, error);
+        }
+    }
+
+    /// Look up the result of an earlier verification of the request whose hash is `key`, as
+    /// computed by `VerificationRequest::cache_key`.
+    pub fn get(&self, key: u64) -> Option<VerificationResult> {
+        let file = fs::File::open(self.entry_path(key)).ok()?;
+        match bincode::deserialize_from(io::BufReader::new(file)) {
+            Ok(result) => Some(result),
+            Err(error) => {
+                warn!("Ignoring a corrupted entry of the verification cache: {}", error);
+                None
+            }
+        }
+    }
+
+    /// Store the result of the verification of the request whose hash is `key`.
+    pub fn insert(&self, key: u64, result: &VerificationResult) {
+        match result {
+            VerificationResult::Success() | VerificationResult::Failure(_) => {}
+            VerificationResult::ConsistencyErrors(_) | VerificationResult::JavaException(_) => {
+                return;
+            }
+        }
+        let path = self.entry_path(key);
+        // Write to a temporary file first, so that concurrent readers never see a partially
+        // written entry.
+        let tmp_path = path.with_extension(format!("tmp{}", std::process::id()));
+        let write_result = fs::File::create(&tmp_path)
+            .and_then(|file| {
+                let mut writer = io::BufWriter::new(file);
+                bincode::serialize_into(&mut writer, result)
+                    .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+                writer.flush()
+            })
+            .and_then(|()| fs::rename(&tmp_path, &path));
+        if let Err(error) = write_result {
+            warn!("Could not write the verification cache entry {:?}: {}", path, error);
+            fs::remove_file(&tmp_path).ok();
+        }
+    }
+
+    fn entry_path(&self, key: u64) -> PathBuf {
+        self.path.join(format!("v{}-{:016x}.bin", CACHE_VERSION, key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+    use viper::VerificationError;
+
+    /// A cache in a fresh directory, which is removed when the test finishes.
+    struct TestCache {
+        cache: VerificationCache,
+    }
+
+    impl TestCache {
+        fn new(name: &str) -> Self {
+            let path = env::temp_dir().join(format!("prusti-cache-{}-{}", name, process::id()));
+            fs::remove_dir_all(&path).ok();
+            TestCache { cache: VerificationCache::new(path) }
+        }
+    }
+
+    impl Drop for TestCache {
+        fn drop(&mut self) {
+            fs::remove_dir_all(&self.cache.path).ok();
+        }
+    }
+
+    fn failure() -> VerificationResult {
+        VerificationResult::Failure(vec![VerificationError::new(
+            "assert.failed:assertion.false".to_string(),
+            Some("1".to_string()),
+            None,
+            "Assert might fail.".to_string(),
+        )])
+    }
+
+    #[test]
+    fn test_insert_and_get() {
+        let test_cache = TestCache::new("insert");
+        let cache = &test_cache.cache;
+        assert_eq!(cache.get(1), None);
+        cache.insert(1, &VerificationResult::Success());
+        cache.insert(2, &failure());
+        assert_eq!(cache.get(1), Some(VerificationResult::Success()));
+        assert_eq!(cache.get(2), Some(failure()));
+        cache.insert(1, &failure());
+        assert_eq!(cache.get(1), Some(failure()));
+    }
+
+    #[test]
+    fn test_unsuccessful_results_are_not_stored() {
+        let test_cache = TestCache::new("unsuccessful");
+        let cache = &test_cache.cache;
+        cache.insert(1, &VerificationResult::ConsistencyErrors(vec!["error".to_string()]));
+        assert_eq!(cache.get(1), None);
+    }
+
+    #[test]
+    fn test_results_persist() {
+        let test_cache = TestCache::new("persist");
+        test_cache.cache.insert(1, &VerificationResult::Success());
+        let reopened = VerificationCache::new(test_cache.cache.path.clone());
+        assert_eq!(reopened.get(1), Some(VerificationResult::Success()));
+    }
+
+    #[test]
+    fn test_clear() {
+        let test_cache = TestCache::new("clear");
+        let cache = &test_cache.cache;
+        cache.insert(1, &VerificationResult::Success());
+        cache.insert(2, &failure());
+        cache.clear();
+        assert_eq!(cache.get(1), None);
+        assert_eq!(cache.get(2), None);
+        // the cache is still usable after clearing it
+        cache.insert(1, &VerificationResult::Success());
+        assert_eq!(cache.get(1), Some(VerificationResult::Success()));
+    }
+
+    #[test]
+    fn test_corrupted_entries_are_ignored() {
+        let test_cache = TestCache::new("corrupted");
+        let cache = &test_cache.cache;
+        fs::write(cache.entry_path(1), b"not a result").unwrap();
+        assert_eq!(cache.get(1), None);
+        cache.insert(1, &VerificationResult::Success());
+        assert_eq!(cache.get(1), Some(VerificationResult::Success()));
+    }
+}
@@ -1,7 +1,44 @@
 use config;
-use viper::{self, VerificationBackend};
+use serde_json::{self, Value};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::env;
+use std::fs;
+use viper::{self, VerificationBackend, VerificationResult};
 use vir::Program;
 
+lazy_static! {
+    /// The versions of Prusti and Viper, which determine the result of a verification as much
+    /// as the request itself. The Viper version is identified by the JAR files of `VIPER_HOME`.
+    static ref VERSIONS: String = format!(
+        "prusti {} ({}), viper {}",
+        env!("CARGO_PKG_VERSION"),
+        option_env!("COMMIT_HASH").unwrap_or("unknown"),
+        viper_version().unwrap_or_else(|| "unknown".to_string()),
+    );
+}
+
+fn viper_version() -> Option<String> {
+    let viper_home = env::var_os("VIPER_HOME")?;
+    let mut jars = fs::read_dir(viper_home)
+        .ok()?
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let metadata = entry.metadata().ok()?;
+            let modified = metadata.modified().ok()?;
+            Some(format!(
+                "{}:{}:{:?}",
+                entry.file_name().to_string_lossy(),
+                metadata.len(),
+                modified
+            ))
+        })
+        .collect::<Vec<_>>();
+    jars.sort();
+    Some(jars.join(","))
+}
+
 pub trait VerificationService {
     fn verify(&self, request: VerificationRequest) -> viper::VerificationResult;
 }
@@ -13,6 +50,144 @@ pub struct VerificationRequest {
     pub backend_config: ViperBackendConfig,
 }
 
+impl VerificationRequest {
+    /// A hash of the program and of the backend configuration, which together determine the
+    /// result of the verification. The name of the program is not included.
+    pub fn get_hash(&self) -> u64 {
+        self.cache_key().hash
+    }
+
+    /// The key under which the result of the request is cached; see `CacheKey`.
+    pub fn cache_key(&self) -> CacheKey {
+        let mut program = serde_json::to_value(&self.program).expect("error encoding Viper program");
+        let mut pos_ids = vec![0];
+        renumber_positions(&mut program, &mut pos_ids, &mut HashMap::new());
+
+        let mut hasher = Sha256::new();
+        hasher.input(VERSIONS.as_bytes());
+        hasher.input(serde_json::to_vec(&program).unwrap());
+        hasher.input(serde_json::to_vec(&self.backend_config).unwrap());
+        let digest = hasher.result();
+        CacheKey {
+            hash: u64::from_be_bytes(digest[..8].try_into().unwrap()),
+            pos_ids,
+        }
+    }
+
+    /// Split the request into one request per method of the program, as done by
+    /// `Program::split_by_method`. The program of each request is named after its method.
+    pub fn split_by_method(&self) -> Vec<VerificationRequest> {
+        self.program
+            .split_by_method()
+            .into_iter()
+            .map(|slice| {
+                let slice_name = match slice.methods.first() {
+                    Some(method) => method.name(),
+                    None => "preamble".to_string(),
+                };
+                VerificationRequest {
+                    program: slice,
+                    program_name: format!("{}.{}", self.program_name, slice_name),
+                    backend_config: self.backend_config.clone(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Replace the id of every position in the serialized program by the number of distinct ids
+/// that occur before it, and drop its line and column. `pos_ids` collects the original ids,
+/// indexed by their new ones. The default position keeps the id 0.
+fn renumber_positions(value: &mut Value, pos_ids: &mut Vec<u64>, new_ids: &mut HashMap<u64, u64>) {
+    match value {
+        Value::Object(fields) if is_position(fields) => {
+            let id = fields["id"].as_u64().unwrap();
+            let new_id = if id == 0 {
+                0
+            } else {
+                *new_ids.entry(id).or_insert_with(|| {
+                    pos_ids.push(id);
+                    pos_ids.len() as u64 - 1
+                })
+            };
+            fields.clear();
+            fields.insert("id".to_string(), Value::from(new_id));
+        }
+        Value::Object(fields) => {
+            for field in fields.values_mut() {
+                renumber_positions(field, pos_ids, new_ids);
+            }
+        }
+        Value::Array(elements) => {
+            for element in elements {
+                renumber_positions(element, pos_ids, new_ids);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_position(fields: &serde_json::Map<String, Value>) -> bool {
+    fields.len() == 3 && ["line", "column", "id"].iter().all(|key| fields.contains_key(*key))
+}
+
+/// The key under which the result of a verification request is cached.
+///
+/// The ids of positions are allocated from a counter that is shared by all methods of a
+/// crate, and their lines and columns shift with every edit of the code above them. So that
+/// the key of a method only changes when the method does, the positions of its program are
+/// numbered in the order in which they occur and their lines and columns are ignored. The
+/// positions in cached results are numbered in the same way.
+pub struct CacheKey {
+    pub hash: u64,
+    /// The original id of every position, indexed by its renumbered id.
+    pos_ids: Vec<u64>,
+}
+
+impl CacheKey {
+    /// Translate the positions of a result of the request to the ones stored in the cache.
+    pub fn normalize_result(&self, result: &VerificationResult) -> VerificationResult {
+        let new_ids: HashMap<u64, u64> = self.pos_ids.iter()
+            .enumerate()
+            .map(|(new_id, &id)| (id, new_id as u64))
+            .collect();
+        map_pos_ids(result, |id| new_ids.get(&id).cloned())
+    }
+
+    /// Translate the positions of a cached result to the ones of the request.
+    pub fn denormalize_result(&self, result: &VerificationResult) -> VerificationResult {
+        map_pos_ids(result, |new_id| self.pos_ids.get(new_id as usize).cloned())
+    }
+}
+
+/// Apply `map` to the ids of the positions of the errors in `result`. Ids that cannot be
+/// mapped are kept.
+fn map_pos_ids<F>(result: &VerificationResult, map: F) -> VerificationResult
+where
+    F: Fn(u64) -> Option<u64>,
+{
+    let map_pos_id = |pos_id: &Option<String>| {
+        pos_id.as_ref().map(|pos_id| {
+            match pos_id.parse().ok().and_then(&map) {
+                Some(id) => id.to_string(),
+                None => pos_id.clone(),
+            }
+        })
+    };
+    match result {
+        VerificationResult::Failure(errors) => VerificationResult::Failure(
+            errors.iter()
+                .map(|error| viper::VerificationError {
+                    pos_id: map_pos_id(&error.pos_id),
+                    reason_pos_id: map_pos_id(&error.reason_pos_id),
+                    ..error.clone()
+                })
+                .collect()
+        ),
+        _ => result.clone(),
+    }
+}
+
 /**
 The configuration for the viper backend, (i.e. verifier).
 Expresses which backend (silicon or carbon) should be used, and provides command-line arguments to the viper verifier.
@@ -51,3 +226,72 @@ impl Default for ViperBackendConfig {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use viper::VerificationError;
+    use vir::{CfgMethod, Expr, FoldingBehaviour, Position, Stmt, Successor};
+
+    /// A request for a method that asserts `0 == 0` at each of `positions`.
+    fn request(positions: &[Position]) -> VerificationRequest {
+        let mut method = CfgMethod::new("m".to_string(), 0, vec![], vec![], vec![]);
+        let stmts = positions.iter()
+            .map(|&pos| Stmt::Assert(
+                Expr::eq_cmp(0.into(), 0.into()),
+                FoldingBehaviour::Stmt,
+                pos,
+            ))
+            .collect();
+        let block = method.add_block("start", stmts);
+        method.set_successor(block, Successor::Return);
+        VerificationRequest {
+            program: Program {
+                domains: vec![],
+                fields: vec![],
+                builtin_methods: vec![],
+                methods: vec![method],
+                functions: vec![],
+                viper_predicates: vec![],
+            },
+            program_name: "program".to_string(),
+            backend_config: ViperBackendConfig {
+                backend: VerificationBackend::Silicon,
+                verifier_args: vec![],
+            },
+        }
+    }
+
+    fn failure(pos_id: u64, reason_pos_id: u64) -> VerificationResult {
+        VerificationResult::Failure(vec![VerificationError::new(
+            "assert.failed:assertion.false".to_string(),
+            Some(pos_id.to_string()),
+            Some(reason_pos_id.to_string()),
+            "Assert might fail.".to_string(),
+        )])
+    }
+
+    #[test]
+    fn test_moved_positions_have_equal_keys() {
+        let first = request(&[Position::new(3, 4, 10), Position::new(5, 4, 11)]);
+        let second = request(&[Position::new(13, 8, 20), Position::new(15, 8, 25)]);
+        assert_eq!(first.cache_key().hash, second.cache_key().hash);
+        let swapped = request(&[Position::new(5, 4, 11), Position::new(3, 4, 10)]);
+        assert_eq!(first.cache_key().hash, swapped.cache_key().hash);
+        // which statements share a position is relevant
+        let shared = request(&[Position::new(3, 4, 10), Position::new(3, 4, 10)]);
+        assert_ne!(first.cache_key().hash, shared.cache_key().hash);
+    }
+
+    #[test]
+    fn test_positions_of_cached_results_are_translated() {
+        let first = request(&[Position::new(3, 4, 10), Position::new(5, 4, 11)]).cache_key();
+        let second = request(&[Position::new(13, 8, 20), Position::new(15, 8, 25)]).cache_key();
+        let cached = first.normalize_result(&failure(11, 10));
+        assert_ne!(cached, failure(11, 10));
+        assert_eq!(first.denormalize_result(&cached), failure(11, 10));
+        assert_eq!(second.denormalize_result(&cached), failure(25, 20));
+        // positions that are not part of the program are kept
+        assert_eq!(second.denormalize_result(&failure(7, 0)), failure(7, 0));
+    }
+}
@@ -12,6 +12,7 @@ use std::{
     mem::discriminant,
     ops,
 };
+use vir::ast::FloatSize;
 
 pub trait WithIdentifier {
     fn get_identifier(&self) -> String;
@@ -144,6 +145,14 @@ pub enum Type {
     /// TypedRef: the first parameter is the name of the predicate that encodes the type
     TypedRef(String),
     Domain(String),
+    /// Seq: the type of the elements of a Viper sequence
+    Seq(Box<Type>),
+    /// Set: the type of the elements of a Viper set
+    Set(Box<Type>),
+    /// Map: the types of the keys and of the values of a Viper map
+    Map(Box<Type>, Box<Type>),
+    /// Float: a floating-point number of the SMT-LIB theory of floating-point numbers
+    Float(FloatSize),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -152,6 +161,10 @@ pub enum TypeId {
     Bool,
     Ref,
     Domain,
+    Seq,
+    Set,
+    Map,
+    Float,
 }
 
 impl fmt::Display for Type {
@@ -162,6 +175,12 @@ impl fmt::Display for Type {
             //&Type::Ref => write!(f, "Ref"),
             &Type::TypedRef(ref name) => write!(f, "Ref({})", name),
             &Type::Domain(ref name) => write!(f, "Domain({})", name),
+            &Type::Seq(ref elem_type) => write!(f, "Seq[{}]", elem_type),
+            &Type::Set(ref elem_type) => write!(f, "Set[{}]", elem_type),
+            &Type::Map(ref key_type, ref value_type) => {
+                write!(f, "Map[{}, {}]", key_type, value_type)
+            }
+            &Type::Float(size) => write!(f, "{}", size.domain_name()),
         }
     }
 }
@@ -182,12 +201,41 @@ impl Type {
         }
     }
 
+    pub fn is_seq(&self) -> bool {
+        match self {
+            &Type::Seq(_) => true,
+            _ => false,
+        }
+    }
+
     pub fn name(&self) -> String {
         match self {
             &Type::Bool => "bool".to_string(),
             &Type::Int => "int".to_string(),
             &Type::TypedRef(ref pred_name) => format!("{}", pred_name),
             &Type::Domain(ref pred_name) => format!("{}", pred_name),
+            &Type::Seq(ref elem_type) => format!("seq${}", elem_type.name()),
+            &Type::Set(ref elem_type) => format!("set${}", elem_type.name()),
+            &Type::Map(ref key_type, ref value_type) => {
+                format!("map${}${}", key_type.name(), value_type.name())
+            }
+            &Type::Float(size) => size.to_string(),
+        }
+    }
+
+    /// The type of the elements of a sequence type.
+    pub fn seq_elem_type(&self) -> &Type {
+        match self {
+            Type::Seq(box elem_type) => elem_type,
+            _ => unreachable!("{} is not a sequence type", self),
+        }
+    }
+
+    /// The type of the values of a map type.
+    pub fn map_value_type(&self) -> &Type {
+        match self {
+            Type::Map(_, box value_type) => value_type,
+            _ => unreachable!("{} is not a map type", self),
         }
     }
 
@@ -222,6 +270,10 @@ impl Type {
             Type::Int => TypeId::Int,
             Type::TypedRef(_) => TypeId::Ref,
             Type::Domain(_) => TypeId::Domain,
+            Type::Seq(_) => TypeId::Seq,
+            Type::Set(_) => TypeId::Set,
+            Type::Map(..) => TypeId::Map,
+            Type::Float(_) => TypeId::Float,
         }
     }
 }
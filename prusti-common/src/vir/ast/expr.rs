@@ -37,6 +37,8 @@ pub enum Expr {
     Cond(Box<Expr>, Box<Expr>, Box<Expr>, Position),
     /// ForAll: variables, triggers, body
     ForAll(Vec<LocalVar>, Vec<Trigger>, Box<Expr>, Position),
+    /// Exists: variables, triggers, body
+    Exists(Vec<LocalVar>, Vec<Trigger>, Box<Expr>, Position),
     /// let variable == (expr) in body
     LetExpr(LocalVar, Box<Expr>, Box<Expr>, Position),
     /// FuncApp: function_name, args, formal_args, return_type, Viper position
@@ -47,6 +49,28 @@ pub enum Expr {
     // DomainFuncApp(String, Vec<Expr>, Vec<LocalVar>, Type, String, Position),
     /// Inhale Exhale: inhale expression, exhale expression, Viper position (unused)
     InhaleExhale(Box<Expr>, Box<Expr>, Position),
+    /// Seq: type of the sequence, elements
+    Seq(Type, Vec<Expr>, Position),
+    /// SeqIndex: sequence, index
+    SeqIndex(Box<Expr>, Box<Expr>, Position),
+    /// SeqLen: sequence
+    SeqLen(Box<Expr>, Position),
+    /// SeqUpdate: sequence, index, new element
+    SeqUpdate(Box<Expr>, Box<Expr>, Box<Expr>, Position),
+    /// SeqSlice: sequence, start index (inclusive), end index (exclusive)
+    SeqSlice(Box<Expr>, Box<Expr>, Box<Expr>, Position),
+    /// Set: type of the set, elements
+    Set(Type, Vec<Expr>, Position),
+    /// SetLen: set
+    SetLen(Box<Expr>, Position),
+    /// Map: type of the map, pairs of keys and values
+    Map(Type, Vec<(Expr, Expr)>, Position),
+    /// MapLen: map
+    MapLen(Box<Expr>, Position),
+    /// MapUpdate: map, key, new value
+    MapUpdate(Box<Expr>, Box<Expr>, Box<Expr>, Position),
+    /// ContainerOp: operation on sequences, sets or maps, left operand, right operand
+    ContainerOp(ContainerOpKind, Box<Expr>, Box<Expr>, Position),
 }
 
 /// A component that can be used to represent a place as a vector.
@@ -80,11 +104,31 @@ pub enum BinOpKind {
     Implies,
 }
 
+/// Binary operations on the mathematical containers of Viper. For the membership tests, the
+/// left operand is the element and the right operand is the container.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ContainerOpKind {
+    SeqConcat,
+    SeqContains,
+    SetUnion,
+    SetIntersection,
+    SetMinus,
+    SetSubset,
+    SetContains,
+    MapContains,
+    /// The value of a key of a map; the left operand is the map.
+    MapLookup,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Const {
     Bool(bool),
     Int(i64),
     BigInt(String),
+    /// The IEEE 754 bit pattern of an `f32`
+    Float32(u32),
+    /// The IEEE 754 bit pattern of an `f64`
+    Float64(u64),
     /// All function pointers share the same constant, because their function
     /// is determined by the type system.
     FnPtr,
@@ -147,6 +191,20 @@ impl fmt::Display for Expr {
                     .join(", "),
                 body.to_string()
             ),
+            Expr::Exists(ref vars, ref triggers, ref body, ref _pos) => write!(
+                f,
+                "exists {} {} :: {}",
+                vars.iter()
+                    .map(|x| format!("{:?}", x))
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                triggers
+                    .iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                body.to_string()
+            ),
             Expr::LetExpr(ref var, ref expr, ref body, ref _pos) => write!(
                 f,
                 "(let {:?} == ({}) in {})",
@@ -199,6 +257,68 @@ impl fmt::Display for Expr {
 
             Expr::InhaleExhale(ref inhale_expr, ref exhale_expr, _) =>
                 write!(f, "[({}), ({})]", inhale_expr, exhale_expr),
+            Expr::Seq(ref typ, ref elems, ref _pos) => write!(
+                f,
+                "{}({})",
+                typ,
+                elems.iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", "),
+            ),
+            Expr::SeqIndex(ref seq, ref index, ref _pos) => write!(f, "({})[{}]", seq, index),
+            Expr::SeqLen(ref seq, ref _pos) => write!(f, "|{}|", seq),
+            Expr::SeqUpdate(ref seq, ref index, ref elem, ref _pos) => {
+                write!(f, "({})[{} := {}]", seq, index, elem)
+            }
+            Expr::SeqSlice(ref seq, ref from, ref to, ref _pos) => {
+                write!(f, "({})[{}..{}]", seq, from, to)
+            }
+            Expr::Set(ref typ, ref elems, ref _pos) => write!(
+                f,
+                "{}({})",
+                typ,
+                elems.iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", "),
+            ),
+            Expr::SetLen(ref set, ref _pos) => write!(f, "|{}|", set),
+            Expr::Map(ref typ, ref pairs, ref _pos) => write!(
+                f,
+                "{}({})",
+                typ,
+                pairs.iter()
+                    .map(|(key, value)| format!("{} := {}", key, value))
+                    .collect::<Vec<String>>()
+                    .join(", "),
+            ),
+            Expr::MapLen(ref map, ref _pos) => write!(f, "|{}|", map),
+            Expr::MapUpdate(ref map, ref key, ref value, ref _pos) => {
+                write!(f, "({})[{} := {}]", map, key, value)
+            }
+            Expr::ContainerOp(ContainerOpKind::MapLookup, ref map, ref key, ref _pos) => {
+                write!(f, "({})[{}]", map, key)
+            }
+            Expr::ContainerOp(op, ref left, ref right, ref _pos) => {
+                write!(f, "({}) {} ({})", left, op, right)
+            }
+        }
+    }
+}
+
+impl fmt::Display for ContainerOpKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            &ContainerOpKind::SeqConcat => write!(f, "++"),
+            &ContainerOpKind::SetUnion => write!(f, "union"),
+            &ContainerOpKind::SetIntersection => write!(f, "intersection"),
+            &ContainerOpKind::SetMinus => write!(f, "setminus"),
+            &ContainerOpKind::SetSubset => write!(f, "subset"),
+            &ContainerOpKind::SeqContains
+            | &ContainerOpKind::SetContains
+            | &ContainerOpKind::MapContains => write!(f, "in"),
+            &ContainerOpKind::MapLookup => write!(f, "lookup"),
         }
     }
 }
@@ -239,6 +359,8 @@ impl fmt::Display for Const {
             &Const::Bool(val) => write!(f, "{}", val),
             &Const::Int(val) => write!(f, "{}", val),
             &Const::BigInt(ref val) => write!(f, "{}", val),
+            &Const::Float32(bits) => write!(f, "{:?}f32", f32::from_bits(bits)),
+            &Const::Float64(bits) => write!(f, "{:?}f64", f64::from_bits(bits)),
             &Const::FnPtr => write!(f, "FnPtr"),
         }
     }
@@ -261,11 +383,23 @@ impl Expr {
             Expr::Unfolding(_, _, _, _, _, p) => p,
             Expr::Cond(_, _, _, p) => p,
             Expr::ForAll(_, _, _, p) => p,
+            Expr::Exists(_, _, _, p) => p,
             Expr::LetExpr(_, _, _, p) => p,
             Expr::FuncApp(_, _, _, _, p) => p,
             Expr::DomainFuncApp(_, _, p) => p,
             // TODO Expr::DomainFuncApp(_, _, _, _, _, p) => p,
             Expr::InhaleExhale(_, _, p) => p,
+            Expr::Seq(_, _, p) => p,
+            Expr::SeqIndex(_, _, p) => p,
+            Expr::SeqLen(_, p) => p,
+            Expr::SeqUpdate(_, _, _, p) => p,
+            Expr::SeqSlice(_, _, _, p) => p,
+            Expr::Set(_, _, p) => p,
+            Expr::SetLen(_, p) => p,
+            Expr::Map(_, _, p) => p,
+            Expr::MapLen(_, p) => p,
+            Expr::MapUpdate(_, _, _, p) => p,
+            Expr::ContainerOp(_, _, _, p) => p,
         }
     }
 
@@ -289,11 +423,23 @@ impl Expr {
             },
             Expr::Cond(x, y, z, _) => Expr::Cond(x, y, z, pos),
             Expr::ForAll(x, y, z, _) => Expr::ForAll(x, y, z, pos),
+            Expr::Exists(x, y, z, _) => Expr::Exists(x, y, z, pos),
             Expr::LetExpr(x, y, z, _) => Expr::LetExpr(x, y, z, pos),
             Expr::FuncApp(x, y, z, k, _) => Expr::FuncApp(x, y, z, k, pos),
             Expr::DomainFuncApp(x,y,_) => Expr::DomainFuncApp(x,y,pos),
             // TODO Expr::DomainFuncApp(u,v, w, x, y ,_) => Expr::DomainFuncApp(u,v,w,x,y,pos),
             Expr::InhaleExhale(x, y, _) => Expr::InhaleExhale(x, y, pos),
+            Expr::Seq(x, y, _) => Expr::Seq(x, y, pos),
+            Expr::SeqIndex(x, y, _) => Expr::SeqIndex(x, y, pos),
+            Expr::SeqLen(x, _) => Expr::SeqLen(x, pos),
+            Expr::SeqUpdate(x, y, z, _) => Expr::SeqUpdate(x, y, z, pos),
+            Expr::SeqSlice(x, y, z, _) => Expr::SeqSlice(x, y, z, pos),
+            Expr::Set(x, y, _) => Expr::Set(x, y, pos),
+            Expr::SetLen(x, _) => Expr::SetLen(x, pos),
+            Expr::Map(x, y, _) => Expr::Map(x, y, pos),
+            Expr::MapLen(x, _) => Expr::MapLen(x, pos),
+            Expr::MapUpdate(x, y, z, _) => Expr::MapUpdate(x, y, z, pos),
+            Expr::ContainerOp(x, y, z, _) => Expr::ContainerOp(x, y, z, pos),
         }
     }
 
@@ -391,6 +537,25 @@ impl Expr {
         Expr::BinOp(BinOpKind::Mod, box left, box right, Position::default())
     }
 
+    /// Encode Rust division, which rounds towards zero. This is *not* Viper division.
+    pub fn rust_div(left: Expr, right: Expr) -> Self {
+        let towards_zero = Expr::ite(
+            Expr::gt_cmp(right.clone(), 0.into()),
+            Expr::add(Expr::div(left.clone(), right.clone()), 1.into()),
+            Expr::sub(Expr::div(left.clone(), right.clone()), 1.into()),
+        );
+        Expr::ite(
+            Expr::or(
+                Expr::ge_cmp(left.clone(), 0.into()),
+                Expr::eq_cmp(Expr::modulo(left.clone(), right.clone()), 0.into()),
+            ),
+            // positive value or left % right == 0
+            Expr::div(left, right),
+            // negative value, which Viper rounds away from zero
+            towards_zero,
+        )
+    }
+
     /// Encode Rust reminder. This is *not* Viper modulo.
     pub fn rem(left: Expr, right: Expr) -> Self {
         let abs_right = Expr::ite(
@@ -430,6 +595,10 @@ impl Expr {
         Expr::ForAll(vars, triggers, box body, Position::default())
     }
 
+    pub fn exists(vars: Vec<LocalVar>, triggers: Vec<Trigger>, body: Expr) -> Self {
+        Expr::Exists(vars, triggers, box body, Position::default())
+    }
+
     pub fn ite(guard: Expr, left: Expr, right: Expr) -> Self {
         Expr::Cond(box guard, box left, box right, Position::default())
     }
@@ -468,6 +637,53 @@ impl Expr {
         Expr::DomainFuncApp(func, args, Position::default())
     }
 
+    /// Create an explicit sequence of type `Seq[elem_type]`.
+    pub fn seq(elem_type: Type, elems: Vec<Expr>) -> Self {
+        Expr::Seq(Type::Seq(box elem_type), elems, Position::default())
+    }
+
+    pub fn seq_index(seq: Expr, index: Expr) -> Self {
+        Expr::SeqIndex(box seq, box index, Position::default())
+    }
+
+    pub fn seq_len(seq: Expr) -> Self {
+        Expr::SeqLen(box seq, Position::default())
+    }
+
+    pub fn seq_update(seq: Expr, index: Expr, elem: Expr) -> Self {
+        Expr::SeqUpdate(box seq, box index, box elem, Position::default())
+    }
+
+    pub fn seq_slice(seq: Expr, from: Expr, to: Expr) -> Self {
+        Expr::SeqSlice(box seq, box from, box to, Position::default())
+    }
+
+    /// Create an explicit set of type `Set[elem_type]`.
+    pub fn set(elem_type: Type, elems: Vec<Expr>) -> Self {
+        Expr::Set(Type::Set(box elem_type), elems, Position::default())
+    }
+
+    pub fn set_len(set: Expr) -> Self {
+        Expr::SetLen(box set, Position::default())
+    }
+
+    /// Create an explicit map of type `Map[key_type, value_type]`.
+    pub fn map(key_type: Type, value_type: Type, pairs: Vec<(Expr, Expr)>) -> Self {
+        Expr::Map(Type::Map(box key_type, box value_type), pairs, Position::default())
+    }
+
+    pub fn map_len(map: Expr) -> Self {
+        Expr::MapLen(box map, Position::default())
+    }
+
+    pub fn map_update(map: Expr, key: Expr, value: Expr) -> Self {
+        Expr::MapUpdate(box map, box key, box value, Position::default())
+    }
+
+    pub fn container_op(op_kind: ContainerOpKind, left: Expr, right: Expr) -> Self {
+        Expr::ContainerOp(op_kind, box left, box right, Position::default())
+    }
+
     pub fn magic_wand(lhs: Expr, rhs: Expr, borrow: Option<Borrow>) -> Self {
         Expr::MagicWand(box lhs, box rhs, borrow, Position::default())
     }
@@ -862,6 +1078,8 @@ impl Expr {
     pub fn get_type(&self) -> &Type {
         lazy_static! {
             static ref FN_PTR_TYPE: Type = Type::TypedRef("FnPtr".to_string());
+            static ref FLOAT32_TYPE: Type = Type::Float(FloatSize::F32);
+            static ref FLOAT64_TYPE: Type = Type::Float(FloatSize::F64);
         }
         match self {
             Expr::Local(LocalVar { ref typ, .. }, _)
@@ -886,6 +1104,8 @@ impl Expr {
                 match constant {
                     Const::Bool(..) => &Type::Bool,
                     Const::Int(..) | Const::BigInt(..) => &Type::Int,
+                    Const::Float32(..) => &FLOAT32_TYPE,
+                    Const::Float64(..) => &FLOAT64_TYPE,
                     Const::FnPtr => &FN_PTR_TYPE,
                 }
             }
@@ -918,9 +1138,44 @@ impl Expr {
                 assert_eq!(typ1, typ2, "expr: {:?}", self);
                 typ1
             }
-            Expr::ForAll(..) => {
+            Expr::ForAll(..) | Expr::Exists(..) => {
                 &Type::Bool
             }
+            Expr::Seq(ref typ, _, _) => {
+                &typ
+            }
+            Expr::SeqIndex(box ref seq, _, _) => {
+                seq.get_type().seq_elem_type()
+            }
+            Expr::SeqLen(..) => {
+                &Type::Int
+            }
+            Expr::SeqUpdate(box ref seq, _, _, _)
+            | Expr::SeqSlice(box ref seq, _, _, _) => {
+                seq.get_type()
+            }
+            Expr::Set(ref typ, _, _) | Expr::Map(ref typ, _, _) => {
+                &typ
+            }
+            Expr::SetLen(..) | Expr::MapLen(..) => {
+                &Type::Int
+            }
+            Expr::MapUpdate(box ref map, _, _, _) => {
+                map.get_type()
+            }
+            Expr::ContainerOp(op_kind, box ref left, _, _) => {
+                match op_kind {
+                    ContainerOpKind::SeqConcat |
+                    ContainerOpKind::SetUnion |
+                    ContainerOpKind::SetIntersection |
+                    ContainerOpKind::SetMinus => left.get_type(),
+                    ContainerOpKind::SeqContains |
+                    ContainerOpKind::SetSubset |
+                    ContainerOpKind::SetContains |
+                    ContainerOpKind::MapContains => &Type::Bool,
+                    ContainerOpKind::MapLookup => left.get_type().map_value_type(),
+                }
+            }
             Expr::MagicWand(..) |
             Expr::PredicateAccessPredicate(..) |
             Expr::FieldAccessPredicate(..) |
@@ -940,7 +1195,8 @@ impl Expr {
                 Expr::Const(Const::Bool(_), _) |
                 Expr::UnaryOp(UnaryOpKind::Not, _, _) |
                 Expr::FuncApp(_, _, _, Type::Bool, _) |
-                Expr::ForAll(..) => {
+                Expr::ForAll(..) |
+                Expr::Exists(..) => {
                     true
                 },
                 Expr::BinOp(kind, _, _, _) => {
@@ -1064,6 +1320,29 @@ impl Expr {
                     )
                 }
             }
+
+            fn fold_exists(
+                &mut self,
+                vars: Vec<LocalVar>,
+                triggers: Vec<Trigger>,
+                body: Box<Expr>,
+                pos: Position,
+            ) -> Expr {
+                if vars.contains(&self.target.get_base()) {
+                    // Do nothing
+                    Expr::Exists(vars, triggers, body, pos)
+                } else {
+                    Expr::Exists(
+                        vars,
+                        triggers
+                            .into_iter()
+                            .map(|x| x.replace_place(self.target, self.replacement))
+                            .collect(),
+                        self.fold_boxed(body),
+                        pos,
+                    )
+                }
+            }
         }
         let typaram_substs = match (&target, &replacement) {
             (Expr::Local(tv, _), Expr::Local(rv, _)) => {
@@ -1191,6 +1470,34 @@ impl Expr {
                     pos,
                 )
             }
+
+            fn fold_exists(
+                &mut self,
+                vars: Vec<LocalVar>,
+                triggers: Vec<Trigger>,
+                body: Box<Expr>,
+                pos: Position,
+            ) -> Expr {
+                // TODO: same limitation as for `fold_forall`.
+                for (src, dst) in self.replacements.iter() {
+                    if vars.contains(&src.get_base()) || vars.contains(&dst.get_base()) {
+                        unimplemented!(
+                            "replace_multiple_places doesn't handle replacements that conflict \
+                            with quantified variables"
+                        )
+                    }
+                }
+
+                Expr::Exists(
+                    vars,
+                    triggers
+                        .into_iter()
+                        .map(|x| x.replace_multiple_places(self.replacements))
+                        .collect(),
+                    self.fold_boxed(body),
+                    pos,
+                )
+            }
         }
         let typaram_substs = replacements.into_iter().map(
             |(target, replacement)| {
@@ -1269,10 +1576,22 @@ impl Expr {
                     | Expr::AddrOf(..)
                     | Expr::LabelledOld(..)
                     | Expr::ForAll(..)
+                    | Expr::Exists(..)
                     | Expr::LetExpr(..)
                     | Expr::FuncApp(..)
                     | Expr::DomainFuncApp(..)
-                    | Expr::InhaleExhale(..) => true.into(),
+                    | Expr::InhaleExhale(..)
+                    | Expr::Seq(..)
+                    | Expr::SeqIndex(..)
+                    | Expr::SeqLen(..)
+                    | Expr::SeqUpdate(..)
+                    | Expr::SeqSlice(..)
+                    | Expr::Set(..)
+                    | Expr::SetLen(..)
+                    | Expr::Map(..)
+                    | Expr::MapLen(..)
+                    | Expr::MapUpdate(..)
+                    | Expr::ContainerOp(..) => true.into(),
                 }
             }
         }
@@ -1435,6 +1754,10 @@ impl PartialEq for Expr {
                 Expr::ForAll(ref self_vars, ref self_triggers, box ref self_expr, _),
                 Expr::ForAll(ref other_vars, ref other_triggers, box ref other_expr, _),
             ) => (self_vars, self_triggers, self_expr) == (other_vars, other_triggers, other_expr),
+            (
+                Expr::Exists(ref self_vars, ref self_triggers, box ref self_expr, _),
+                Expr::Exists(ref other_vars, ref other_triggers, box ref other_expr, _),
+            ) => (self_vars, self_triggers, self_expr) == (other_vars, other_triggers, other_expr),
             (
                 Expr::LetExpr(ref self_var, box ref self_def, box ref self_expr, _),
                 Expr::LetExpr(ref other_var, box ref other_def, box ref other_expr, _),
@@ -1450,6 +1773,50 @@ impl PartialEq for Expr {
                 (self_name, self_args, self_base, self_perm, self_variant)
                     == (other_name, other_args, other_base, other_perm, other_variant)
             }
+            (
+                Expr::Seq(_, ref self_elems, _),
+                Expr::Seq(_, ref other_elems, _),
+            ) => self_elems == other_elems,
+            (
+                Expr::SeqIndex(box ref self_seq, box ref self_index, _),
+                Expr::SeqIndex(box ref other_seq, box ref other_index, _),
+            ) => (self_seq, self_index) == (other_seq, other_index),
+            (
+                Expr::SeqLen(box ref self_seq, _),
+                Expr::SeqLen(box ref other_seq, _),
+            ) => self_seq == other_seq,
+            (
+                Expr::SeqUpdate(box ref self_seq, box ref self_index, box ref self_elem, _),
+                Expr::SeqUpdate(box ref other_seq, box ref other_index, box ref other_elem, _),
+            ) => (self_seq, self_index, self_elem) == (other_seq, other_index, other_elem),
+            (
+                Expr::SeqSlice(box ref self_seq, box ref self_from, box ref self_to, _),
+                Expr::SeqSlice(box ref other_seq, box ref other_from, box ref other_to, _),
+            ) => (self_seq, self_from, self_to) == (other_seq, other_from, other_to),
+            (
+                Expr::Set(_, ref self_elems, _),
+                Expr::Set(_, ref other_elems, _),
+            ) => self_elems == other_elems,
+            (
+                Expr::SetLen(box ref self_set, _),
+                Expr::SetLen(box ref other_set, _),
+            ) => self_set == other_set,
+            (
+                Expr::Map(_, ref self_pairs, _),
+                Expr::Map(_, ref other_pairs, _),
+            ) => self_pairs == other_pairs,
+            (
+                Expr::MapLen(box ref self_map, _),
+                Expr::MapLen(box ref other_map, _),
+            ) => self_map == other_map,
+            (
+                Expr::MapUpdate(box ref self_map, box ref self_key, box ref self_value, _),
+                Expr::MapUpdate(box ref other_map, box ref other_key, box ref other_value, _),
+            ) => (self_map, self_key, self_value) == (other_map, other_key, other_value),
+            (
+                Expr::ContainerOp(self_op, box ref self_left, box ref self_right, _),
+                Expr::ContainerOp(other_op, box ref other_left, box ref other_right, _),
+            ) => (self_op, self_left, self_right) == (other_op, other_left, other_right),
             (a, b) => {
                 debug_assert_ne!(discriminant(a), discriminant(b));
                 false
@@ -1484,6 +1851,9 @@ impl Hash for Expr {
             Expr::ForAll(ref vars, ref triggers, box ref expr, _) => {
                 (vars, triggers, expr).hash(state)
             }
+            Expr::Exists(ref vars, ref triggers, box ref expr, _) => {
+                (vars, triggers, expr).hash(state)
+            }
             Expr::LetExpr(ref var, box ref def, box ref expr, _) => (var, def, expr).hash(state),
             Expr::FuncApp(ref name, ref args, _, _, _) => (name, args).hash(state),
             Expr::DomainFuncApp(ref function, ref args, _) => (&function.name, args).hash(state),
@@ -1494,6 +1864,25 @@ impl Hash for Expr {
             Expr::InhaleExhale(box ref inhale_expr, box ref exhale_expr, _) => {
                 (inhale_expr, exhale_expr).hash(state)
             }
+            Expr::Seq(_, ref elems, _) => elems.hash(state),
+            Expr::SeqIndex(box ref seq, box ref index, _) => (seq, index).hash(state),
+            Expr::SeqLen(box ref seq, _) => seq.hash(state),
+            Expr::SeqUpdate(box ref seq, box ref index, box ref elem, _) => {
+                (seq, index, elem).hash(state)
+            }
+            Expr::SeqSlice(box ref seq, box ref from, box ref to, _) => {
+                (seq, from, to).hash(state)
+            }
+            Expr::Set(_, ref elems, _) => elems.hash(state),
+            Expr::SetLen(box ref set, _) => set.hash(state),
+            Expr::Map(_, ref pairs, _) => pairs.hash(state),
+            Expr::MapLen(box ref map, _) => map.hash(state),
+            Expr::MapUpdate(box ref map, box ref key, box ref value, _) => {
+                (map, key, value).hash(state)
+            }
+            Expr::ContainerOp(op, box ref left, box ref right, _) => {
+                (op, left, right).hash(state)
+            }
         }
     }
 }
@@ -170,6 +170,15 @@ pub trait ExprFolder: Sized {
     ) -> Expr {
         Expr::ForAll(x, y, self.fold_boxed(z), p)
     }
+    fn fold_exists(
+        &mut self,
+        x: Vec<LocalVar>,
+        y: Vec<Trigger>,
+        z: Box<Expr>,
+        p: Position,
+    ) -> Expr {
+        Expr::Exists(x, y, self.fold_boxed(z), p)
+    }
     fn fold_let_expr(
         &mut self,
         var: LocalVar,
@@ -240,6 +249,82 @@ pub trait ExprFolder: Sized {
             pos
         )
     }
+    fn fold_seq(&mut self, typ: Type, elems: Vec<Expr>, pos: Position) -> Expr {
+        Expr::Seq(typ, elems.into_iter().map(|e| self.fold(e)).collect(), pos)
+    }
+    fn fold_seq_index(&mut self, seq: Box<Expr>, index: Box<Expr>, pos: Position) -> Expr {
+        Expr::SeqIndex(self.fold_boxed(seq), self.fold_boxed(index), pos)
+    }
+    fn fold_seq_len(&mut self, seq: Box<Expr>, pos: Position) -> Expr {
+        Expr::SeqLen(self.fold_boxed(seq), pos)
+    }
+    fn fold_seq_update(
+        &mut self,
+        seq: Box<Expr>,
+        index: Box<Expr>,
+        elem: Box<Expr>,
+        pos: Position,
+    ) -> Expr {
+        Expr::SeqUpdate(
+            self.fold_boxed(seq),
+            self.fold_boxed(index),
+            self.fold_boxed(elem),
+            pos
+        )
+    }
+    fn fold_seq_slice(
+        &mut self,
+        seq: Box<Expr>,
+        from: Box<Expr>,
+        to: Box<Expr>,
+        pos: Position,
+    ) -> Expr {
+        Expr::SeqSlice(
+            self.fold_boxed(seq),
+            self.fold_boxed(from),
+            self.fold_boxed(to),
+            pos
+        )
+    }
+    fn fold_set(&mut self, typ: Type, elems: Vec<Expr>, pos: Position) -> Expr {
+        Expr::Set(typ, elems.into_iter().map(|e| self.fold(e)).collect(), pos)
+    }
+    fn fold_set_len(&mut self, set: Box<Expr>, pos: Position) -> Expr {
+        Expr::SetLen(self.fold_boxed(set), pos)
+    }
+    fn fold_map(&mut self, typ: Type, pairs: Vec<(Expr, Expr)>, pos: Position) -> Expr {
+        Expr::Map(
+            typ,
+            pairs.into_iter().map(|(k, v)| (self.fold(k), self.fold(v))).collect(),
+            pos
+        )
+    }
+    fn fold_map_len(&mut self, map: Box<Expr>, pos: Position) -> Expr {
+        Expr::MapLen(self.fold_boxed(map), pos)
+    }
+    fn fold_map_update(
+        &mut self,
+        map: Box<Expr>,
+        key: Box<Expr>,
+        value: Box<Expr>,
+        pos: Position,
+    ) -> Expr {
+        Expr::MapUpdate(
+            self.fold_boxed(map),
+            self.fold_boxed(key),
+            self.fold_boxed(value),
+            pos
+        )
+    }
+    fn fold_container_op(
+        &mut self,
+        op_kind: ContainerOpKind,
+        left: Box<Expr>,
+        right: Box<Expr>,
+        pos: Position,
+    ) -> Expr {
+        Expr::ContainerOp(op_kind, self.fold_boxed(left), self.fold_boxed(right), pos)
+    }
 }
 
 pub fn default_fold_expr<T: ExprFolder>(this: &mut T, e: Expr) -> Expr {
@@ -262,11 +347,23 @@ pub fn default_fold_expr<T: ExprFolder>(this: &mut T, e: Expr) -> Expr {
         },
         Expr::Cond(x, y, z, p) => this.fold_cond(x, y, z, p),
         Expr::ForAll(x, y, z, p) => this.fold_forall(x, y, z, p),
+        Expr::Exists(x, y, z, p) => this.fold_exists(x, y, z, p),
         Expr::LetExpr(x, y, z, p) => this.fold_let_expr(x, y, z, p),
         Expr::FuncApp(x, y, z, k, p) => this.fold_func_app(x, y, z, k, p),
         Expr::DomainFuncApp(x, y, p) => this.fold_domain_func_app(x,y,p),
         // TODO Expr::DomainFuncApp(u, v, w, x, y, p) => this.fold_domain_func_app(u,v,w,x,y,p),
         Expr::InhaleExhale(x, y, p) => this.fold_inhale_exhale(x, y, p),
+        Expr::Seq(x, y, p) => this.fold_seq(x, y, p),
+        Expr::SeqIndex(x, y, p) => this.fold_seq_index(x, y, p),
+        Expr::SeqLen(x, p) => this.fold_seq_len(x, p),
+        Expr::SeqUpdate(x, y, z, p) => this.fold_seq_update(x, y, z, p),
+        Expr::SeqSlice(x, y, z, p) => this.fold_seq_slice(x, y, z, p),
+        Expr::Set(x, y, p) => this.fold_set(x, y, p),
+        Expr::SetLen(x, p) => this.fold_set_len(x, p),
+        Expr::Map(x, y, p) => this.fold_map(x, y, p),
+        Expr::MapLen(x, p) => this.fold_map_len(x, p),
+        Expr::MapUpdate(x, y, z, p) => this.fold_map_update(x, y, z, p),
+        Expr::ContainerOp(x, y, z, p) => this.fold_container_op(x, y, z, p),
     }
 }
 
@@ -358,6 +455,18 @@ pub trait ExprWalker: Sized {
         }
         self.walk(body);
     }
+    fn walk_exists(
+        &mut self,
+        vars: &Vec<LocalVar>,
+        _triggers: &Vec<Trigger>,
+        body: &Expr,
+        _pos: &Position
+    ) {
+        for var in vars {
+            self.walk_local_var(var);
+        }
+        self.walk(body);
+    }
     fn walk_let_expr(&mut self, bound_var: &LocalVar, expr: &Expr, body: &Expr, _pos: &Position) {
         self.walk_local_var(bound_var);
         self.walk(expr);
@@ -407,6 +516,60 @@ pub trait ExprWalker: Sized {
         self.walk(inhale_expr);
         self.walk(exhale_expr);
     }
+    fn walk_seq(&mut self, _typ: &Type, elems: &Vec<Expr>, _pos: &Position) {
+        for elem in elems {
+            self.walk(elem)
+        }
+    }
+    fn walk_seq_index(&mut self, seq: &Expr, index: &Expr, _pos: &Position) {
+        self.walk(seq);
+        self.walk(index);
+    }
+    fn walk_seq_len(&mut self, seq: &Expr, _pos: &Position) {
+        self.walk(seq);
+    }
+    fn walk_seq_update(&mut self, seq: &Expr, index: &Expr, elem: &Expr, _pos: &Position) {
+        self.walk(seq);
+        self.walk(index);
+        self.walk(elem);
+    }
+    fn walk_seq_slice(&mut self, seq: &Expr, from: &Expr, to: &Expr, _pos: &Position) {
+        self.walk(seq);
+        self.walk(from);
+        self.walk(to);
+    }
+    fn walk_set(&mut self, _typ: &Type, elems: &Vec<Expr>, _pos: &Position) {
+        for elem in elems {
+            self.walk(elem)
+        }
+    }
+    fn walk_set_len(&mut self, set: &Expr, _pos: &Position) {
+        self.walk(set);
+    }
+    fn walk_map(&mut self, _typ: &Type, pairs: &Vec<(Expr, Expr)>, _pos: &Position) {
+        for (key, value) in pairs {
+            self.walk(key);
+            self.walk(value);
+        }
+    }
+    fn walk_map_len(&mut self, map: &Expr, _pos: &Position) {
+        self.walk(map);
+    }
+    fn walk_map_update(&mut self, map: &Expr, key: &Expr, value: &Expr, _pos: &Position) {
+        self.walk(map);
+        self.walk(key);
+        self.walk(value);
+    }
+    fn walk_container_op(
+        &mut self,
+        _op_kind: ContainerOpKind,
+        left: &Expr,
+        right: &Expr,
+        _pos: &Position,
+    ) {
+        self.walk(left);
+        self.walk(right);
+    }
 }
 
 pub fn default_walk_expr<T: ExprWalker>(this: &mut T, e: &Expr) {
@@ -429,11 +592,23 @@ pub fn default_walk_expr<T: ExprWalker>(this: &mut T, e: &Expr) {
         },
         Expr::Cond(ref x, ref y, ref z, ref p) => this.walk_cond(x, y, z, p),
         Expr::ForAll(ref x, ref y, ref z, ref p) => this.walk_forall(x, y, z, p),
+        Expr::Exists(ref x, ref y, ref z, ref p) => this.walk_exists(x, y, z, p),
         Expr::LetExpr(ref x, ref y, ref z, ref p) => this.walk_let_expr(x, y, z, p),
         Expr::FuncApp(ref x, ref y, ref z, ref k, ref p) => this.walk_func_app(x, y, z, k, p),
         Expr::DomainFuncApp(ref x, ref y,ref p) => this.walk_domain_func_app(x,y,p),
         // TODO Expr::DomainFuncApp(ref u, ref v, ref w, ref x, ref y,ref p) => this.walk_domain_func_app(u, v, w, x,y,p),
         Expr::InhaleExhale(ref x, ref y, ref p) => this.walk_inhale_exhale(x, y, p),
+        Expr::Seq(ref x, ref y, ref p) => this.walk_seq(x, y, p),
+        Expr::SeqIndex(ref x, ref y, ref p) => this.walk_seq_index(x, y, p),
+        Expr::SeqLen(ref x, ref p) => this.walk_seq_len(x, p),
+        Expr::SeqUpdate(ref x, ref y, ref z, ref p) => this.walk_seq_update(x, y, z, p),
+        Expr::SeqSlice(ref x, ref y, ref z, ref p) => this.walk_seq_slice(x, y, z, p),
+        Expr::Set(ref x, ref y, ref p) => this.walk_set(x, y, p),
+        Expr::SetLen(ref x, ref p) => this.walk_set_len(x, p),
+        Expr::Map(ref x, ref y, ref p) => this.walk_map(x, y, p),
+        Expr::MapLen(ref x, ref p) => this.walk_map_len(x, p),
+        Expr::MapUpdate(ref x, ref y, ref z, ref p) => this.walk_map_update(x, y, z, p),
+        Expr::ContainerOp(x, ref y, ref z, ref p) => this.walk_container_op(x, y, z, p),
     }
 }
 
@@ -567,6 +742,15 @@ pub trait FallibleExprFolder: Sized {
     ) -> Result<Expr, Self::Error> {
         Ok(Expr::ForAll(x, y, self.fallible_fold_boxed(z)?, p))
     }
+    fn fallible_fold_exists(
+        &mut self,
+        x: Vec<LocalVar>,
+        y: Vec<Trigger>,
+        z: Box<Expr>,
+        p: Position,
+    ) -> Result<Expr, Self::Error> {
+        Ok(Expr::Exists(x, y, self.fallible_fold_boxed(z)?, p))
+    }
     fn fallible_fold_let_expr(
         &mut self,
         var: LocalVar,
@@ -647,6 +831,137 @@ pub trait FallibleExprFolder: Sized {
             pos
         ))
     }
+    fn fallible_fold_seq(
+        &mut self,
+        typ: Type,
+        elems: Vec<Expr>,
+        pos: Position,
+    ) -> Result<Expr, Self::Error> {
+        Ok(Expr::Seq(
+            typ,
+            elems.into_iter()
+                .map(|e| self.fallible_fold(e))
+                .collect::<Result<Vec<_>, Self::Error>>()?,
+            pos
+        ))
+    }
+    fn fallible_fold_seq_index(
+        &mut self,
+        seq: Box<Expr>,
+        index: Box<Expr>,
+        pos: Position,
+    ) -> Result<Expr, Self::Error> {
+        Ok(Expr::SeqIndex(
+            self.fallible_fold_boxed(seq)?,
+            self.fallible_fold_boxed(index)?,
+            pos
+        ))
+    }
+    fn fallible_fold_seq_len(
+        &mut self,
+        seq: Box<Expr>,
+        pos: Position,
+    ) -> Result<Expr, Self::Error> {
+        Ok(Expr::SeqLen(self.fallible_fold_boxed(seq)?, pos))
+    }
+    fn fallible_fold_seq_update(
+        &mut self,
+        seq: Box<Expr>,
+        index: Box<Expr>,
+        elem: Box<Expr>,
+        pos: Position,
+    ) -> Result<Expr, Self::Error> {
+        Ok(Expr::SeqUpdate(
+            self.fallible_fold_boxed(seq)?,
+            self.fallible_fold_boxed(index)?,
+            self.fallible_fold_boxed(elem)?,
+            pos
+        ))
+    }
+    fn fallible_fold_seq_slice(
+        &mut self,
+        seq: Box<Expr>,
+        from: Box<Expr>,
+        to: Box<Expr>,
+        pos: Position,
+    ) -> Result<Expr, Self::Error> {
+        Ok(Expr::SeqSlice(
+            self.fallible_fold_boxed(seq)?,
+            self.fallible_fold_boxed(from)?,
+            self.fallible_fold_boxed(to)?,
+            pos
+        ))
+    }
+    fn fallible_fold_set(
+        &mut self,
+        typ: Type,
+        elems: Vec<Expr>,
+        pos: Position,
+    ) -> Result<Expr, Self::Error> {
+        Ok(Expr::Set(
+            typ,
+            elems.into_iter()
+                .map(|e| self.fallible_fold(e))
+                .collect::<Result<Vec<_>, Self::Error>>()?,
+            pos
+        ))
+    }
+    fn fallible_fold_set_len(
+        &mut self,
+        set: Box<Expr>,
+        pos: Position,
+    ) -> Result<Expr, Self::Error> {
+        Ok(Expr::SetLen(self.fallible_fold_boxed(set)?, pos))
+    }
+    fn fallible_fold_map(
+        &mut self,
+        typ: Type,
+        pairs: Vec<(Expr, Expr)>,
+        pos: Position,
+    ) -> Result<Expr, Self::Error> {
+        Ok(Expr::Map(
+            typ,
+            pairs.into_iter()
+                .map(|(k, v)| Ok((self.fallible_fold(k)?, self.fallible_fold(v)?)))
+                .collect::<Result<Vec<_>, Self::Error>>()?,
+            pos
+        ))
+    }
+    fn fallible_fold_map_len(
+        &mut self,
+        map: Box<Expr>,
+        pos: Position,
+    ) -> Result<Expr, Self::Error> {
+        Ok(Expr::MapLen(self.fallible_fold_boxed(map)?, pos))
+    }
+    fn fallible_fold_map_update(
+        &mut self,
+        map: Box<Expr>,
+        key: Box<Expr>,
+        value: Box<Expr>,
+        pos: Position,
+    ) -> Result<Expr, Self::Error> {
+        Ok(Expr::MapUpdate(
+            self.fallible_fold_boxed(map)?,
+            self.fallible_fold_boxed(key)?,
+            self.fallible_fold_boxed(value)?,
+            pos
+        ))
+    }
+    fn fallible_fold_container_op(
+        &mut self,
+        op_kind: ContainerOpKind,
+        left: Box<Expr>,
+        right: Box<Expr>,
+        pos: Position,
+    ) -> Result<Expr, Self::Error> {
+        Ok(Expr::ContainerOp(
+            op_kind,
+            self.fallible_fold_boxed(left)?,
+            self.fallible_fold_boxed(right)?,
+            pos
+        ))
+    }
 
     //Expr::InhaleExhale(x, y, p) => this.fallible_inhale_exhale(x,y,p),
 }
@@ -673,10 +988,22 @@ pub fn default_fallible_fold_expr<U, T: FallibleExprFolder<Error=U>>(
         },
         Expr::Cond(x, y, z, p) => this.fallible_fold_cond(x, y, z, p),
         Expr::ForAll(x, y, z, p) => this.fallible_fold_forall(x, y, z, p),
+        Expr::Exists(x, y, z, p) => this.fallible_fold_exists(x, y, z, p),
         Expr::LetExpr(x, y, z, p) => this.fallible_fold_let_expr(x, y, z, p),
         Expr::FuncApp(x, y, z, k, p) => this.fallible_fold_func_app(x, y, z, k, p),
         Expr::DomainFuncApp(x, y, p) => this.fallible_fold_domain_func_app(x,y,p),
         // TODO Expr::DomainFuncApp(u, v, w, x, y, p) => this.fallible_fold_domain_func_app(u,v,w,x,y,p),
         Expr::InhaleExhale(x, y, p) => this.fallible_inhale_exhale(x,y,p),
+        Expr::Seq(x, y, p) => this.fallible_fold_seq(x, y, p),
+        Expr::SeqIndex(x, y, p) => this.fallible_fold_seq_index(x, y, p),
+        Expr::SeqLen(x, p) => this.fallible_fold_seq_len(x, p),
+        Expr::SeqUpdate(x, y, z, p) => this.fallible_fold_seq_update(x, y, z, p),
+        Expr::SeqSlice(x, y, z, p) => this.fallible_fold_seq_slice(x, y, z, p),
+        Expr::Set(x, y, p) => this.fallible_fold_set(x, y, p),
+        Expr::SetLen(x, p) => this.fallible_fold_set_len(x, p),
+        Expr::Map(x, y, p) => this.fallible_fold_map(x, y, p),
+        Expr::MapLen(x, p) => this.fallible_fold_map_len(x, p),
+        Expr::MapUpdate(x, y, z, p) => this.fallible_fold_map_update(x, y, z, p),
+        Expr::ContainerOp(x, y, z, p) => this.fallible_fold_container_op(x, y, z, p),
     }
 }
\ No newline at end of file
@@ -0,0 +1,222 @@
+// © 2021, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Floating-point numbers are encoded with the SMT-LIB theory of floating-point numbers, as
+//! types and functions that are interpreted by the backend. In VIR, the functions on floats are
+//! the functions of a domain, one per size; `to_viper` maps them onto the theory. The only
+//! function without a counterpart in the theory is the remainder, which stays an uninterpreted
+//! function of the domain.
+
+use std::fmt;
+use vir::ast::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FloatSize {
+    F32,
+    F64,
+}
+
+impl FloatSize {
+    pub fn domain_name(self) -> &'static str {
+        match self {
+            FloatSize::F32 => "Float32",
+            FloatSize::F64 => "Float64",
+        }
+    }
+
+    pub fn bit_width(self) -> u32 {
+        match self {
+            FloatSize::F32 => 32,
+            FloatSize::F64 => 64,
+        }
+    }
+
+    pub fn exponent_bits(self) -> u32 {
+        match self {
+            FloatSize::F32 => 8,
+            FloatSize::F64 => 11,
+        }
+    }
+
+    /// The number of bits of the significand, including the implicit leading bit.
+    pub fn significand_bits(self) -> u32 {
+        self.bit_width() - self.exponent_bits()
+    }
+
+    /// The SMT-LIB sort of the floats of this size.
+    pub fn smt_sort(self) -> String {
+        format!("(_ FloatingPoint {} {})", self.exponent_bits(), self.significand_bits())
+    }
+
+    /// The Boogie type of the floats of this size.
+    pub fn boogie_type(self) -> String {
+        format!("float{}e{}", self.significand_bits(), self.exponent_bits())
+    }
+
+    /// The other floating-point size, used for conversions.
+    pub fn other(self) -> Self {
+        match self {
+            FloatSize::F32 => FloatSize::F64,
+            FloatSize::F64 => FloatSize::F32,
+        }
+    }
+}
+
+impl fmt::Display for FloatSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FloatSize::F32 => write!(f, "f32"),
+            FloatSize::F64 => write!(f, "f64"),
+        }
+    }
+}
+
+/// The functions of a floating-point domain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FloatFunc {
+    /// The float with the given IEEE 754 bit pattern
+    FromBits,
+    IsNan,
+    /// The equality of Rust's `==`, under which NaN is different from itself
+    Eq,
+    Lt,
+    Le,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    /// The remainder of Rust's `%`, whose quotient is truncated. It is uninterpreted because
+    /// `fp.rem` rounds the quotient to the nearest integer instead.
+    Rem,
+    Neg,
+    /// The float closest to an integer
+    FromInt,
+    /// The integer obtained by truncating a finite float
+    ToInt,
+    /// The conversion to the other floating-point size
+    Convert,
+}
+
+impl FloatFunc {
+    pub fn all() -> Vec<FloatFunc> {
+        vec![
+            FloatFunc::FromBits,
+            FloatFunc::IsNan,
+            FloatFunc::Eq,
+            FloatFunc::Lt,
+            FloatFunc::Le,
+            FloatFunc::Add,
+            FloatFunc::Sub,
+            FloatFunc::Mul,
+            FloatFunc::Div,
+            FloatFunc::Rem,
+            FloatFunc::Neg,
+            FloatFunc::FromInt,
+            FloatFunc::ToInt,
+            FloatFunc::Convert,
+        ]
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            FloatFunc::FromBits => "from_bits",
+            FloatFunc::IsNan => "is_nan",
+            FloatFunc::Eq => "eq",
+            FloatFunc::Lt => "lt",
+            FloatFunc::Le => "le",
+            FloatFunc::Add => "add",
+            FloatFunc::Sub => "sub",
+            FloatFunc::Mul => "mul",
+            FloatFunc::Div => "div",
+            FloatFunc::Rem => "rem",
+            FloatFunc::Neg => "neg",
+            FloatFunc::FromInt => "from_int",
+            FloatFunc::ToInt => "to_int",
+            FloatFunc::Convert => "convert",
+        }
+    }
+
+    /// Whether the function is mapped onto the SMT-LIB theory of floating-point numbers rather
+    /// than declared in the domain of floats.
+    pub fn is_interpreted(self) -> bool {
+        self != FloatFunc::Rem
+    }
+
+    /// The SMT-LIB function applied to the arguments of an interpreted function that maps onto a
+    /// single function of the theory. Rust's arithmetic rounds to the nearest even float.
+    pub fn smt_name(self, size: FloatSize) -> Option<String> {
+        Some(match self {
+            FloatFunc::IsNan => "fp.isNaN".to_string(),
+            FloatFunc::Eq => "fp.eq".to_string(),
+            FloatFunc::Lt => "fp.lt".to_string(),
+            FloatFunc::Le => "fp.leq".to_string(),
+            FloatFunc::Add => "fp.add RNE".to_string(),
+            FloatFunc::Sub => "fp.sub RNE".to_string(),
+            FloatFunc::Mul => "fp.mul RNE".to_string(),
+            FloatFunc::Div => "fp.div RNE".to_string(),
+            FloatFunc::Neg => "fp.neg".to_string(),
+            FloatFunc::FromInt => format!(
+                "(_ to_fp {} {}) RNE",
+                size.exponent_bits(),
+                size.significand_bits()
+            ),
+            FloatFunc::Convert => format!(
+                "(_ to_fp {} {}) RNE",
+                size.other().exponent_bits(),
+                size.other().significand_bits()
+            ),
+            FloatFunc::FromBits | FloatFunc::ToInt | FloatFunc::Rem => return None,
+        })
+    }
+
+    /// Returns the size and the function of a function of a domain of floats.
+    pub fn of_domain_func(domain_func: &DomainFunc) -> Option<(FloatSize, FloatFunc)> {
+        let size = match domain_func.domain_name.as_str() {
+            "Float32" => FloatSize::F32,
+            "Float64" => FloatSize::F64,
+            _ => return None,
+        };
+        FloatFunc::all()
+            .into_iter()
+            .find(|func| float_domain_func(size, *func).name == domain_func.name)
+            .map(|func| (size, func))
+    }
+}
+
+/// Returns the function `func` of the domain of floats of the given size.
+pub fn float_domain_func(size: FloatSize, func: FloatFunc) -> DomainFunc {
+    let float_type = Type::Float(size);
+    let (arg_types, return_type) = match func {
+        FloatFunc::FromBits | FloatFunc::FromInt => (vec![Type::Int], float_type.clone()),
+        FloatFunc::ToInt => (vec![float_type.clone()], Type::Int),
+        FloatFunc::IsNan => (vec![float_type.clone()], Type::Bool),
+        FloatFunc::Eq | FloatFunc::Lt | FloatFunc::Le => {
+            (vec![float_type.clone(), float_type.clone()], Type::Bool)
+        }
+        FloatFunc::Add | FloatFunc::Sub | FloatFunc::Mul | FloatFunc::Div | FloatFunc::Rem => {
+            (vec![float_type.clone(), float_type.clone()], float_type.clone())
+        }
+        FloatFunc::Neg => (vec![float_type.clone()], float_type.clone()),
+        FloatFunc::Convert => (vec![float_type.clone()], Type::Float(size.other())),
+    };
+    let formal_args = arg_types
+        .into_iter()
+        .enumerate()
+        .map(|(i, typ)| LocalVar::new(format!("_{}", i), typ))
+        .collect();
+    DomainFunc {
+        name: format!("{}${}", size.domain_name(), func.name()),
+        formal_args,
+        return_type,
+        unique: false,
+        domain_name: size.domain_name().to_string(),
+    }
+}
+
+/// Returns the application of the function `func` of the domain of floats of the given size.
+pub fn float_func_app(size: FloatSize, func: FloatFunc, args: Vec<Expr>) -> Expr {
+    Expr::domain_func_app(float_domain_func(size, func), args)
+}
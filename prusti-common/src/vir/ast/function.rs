@@ -74,6 +74,10 @@ pub fn compute_identifier(name: &str, formal_args: &[LocalVar], return_type: &Ty
             Type::Bool => "$bool$",
             Type::TypedRef(ref name) => name,
             Type::Domain(ref name) => name,
+            Type::Seq(..) => "$seq$",
+            Type::Set(..) => "$set$",
+            Type::Map(..) => "$map$",
+            Type::Float(size) => size.domain_name(),
         }
     }
     for arg in formal_args {
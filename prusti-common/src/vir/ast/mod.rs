@@ -9,6 +9,7 @@ pub use self::common::*;
 pub use self::domain::*;
 pub use self::expr::*;
 pub use self::expr_transformers::*;
+pub use self::float::*;
 pub use self::function::*;
 pub use self::predicate::*;
 pub use self::stmt::*;
@@ -19,6 +20,7 @@ mod common;
 mod domain;
 mod expr;
 mod expr_transformers;
+mod float;
 mod function;
 mod predicate;
 mod stmt;
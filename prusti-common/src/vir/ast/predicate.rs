@@ -71,6 +71,44 @@ impl Predicate {
             body: Some(body),
         })
     }
+    /// Construct a new predicate that represents an array or a slice. The elements are stored
+    /// in the sequence `field`.
+    pub fn new_array(
+        typ: Type,
+        field: Field,
+        elem_bounds: Option<(Expr, Expr)>,
+    ) -> Predicate {
+        let predicate_name = typ.name();
+        let this = Self::construct_this(typ);
+        let val_field = Expr::from(this.clone()).field(field);
+        let perm = Expr::acc_permission(val_field.clone(), PermAmount::Write);
+        let mut conjuncts = vec![perm];
+        if let Some((lower, upper)) = elem_bounds {
+            let index = LocalVar::new("i", Type::Int);
+            let elem = Expr::seq_index(val_field.clone(), Expr::local(index.clone()));
+            let index_in_bounds = Expr::and(
+                Expr::le_cmp(0.into(), Expr::local(index.clone())),
+                Expr::lt_cmp(Expr::local(index.clone()), Expr::seq_len(val_field)),
+            );
+            conjuncts.push(Expr::forall(
+                vec![index],
+                vec![Trigger::new(vec![elem.clone()])],
+                Expr::implies(
+                    index_in_bounds,
+                    Expr::and(
+                        Expr::le_cmp(lower, elem.clone()),
+                        Expr::le_cmp(elem, upper),
+                    ),
+                ),
+            ));
+        }
+        let body = conjuncts.into_iter().conjoin();
+        Predicate::Struct(StructPredicate {
+            name: predicate_name,
+            this: this,
+            body: Some(body),
+        })
+    }
     /// Construct a predicate that corresponds to a composite type that has only one variant such
     /// as `struct` or `tuple`.
     pub fn new_struct(typ: Type, fields: Vec<Field>) -> Predicate {
@@ -22,6 +22,8 @@ pub struct CfgMethod {
     pub(in super::super) formal_arg_count: usize,
     pub(in super::super) formal_returns: Vec<LocalVar>,
     pub(in super::super) local_vars: Vec<LocalVar>,
+    // Serialized in a deterministic order, so that equal methods have equal hashes.
+    #[serde(serialize_with = "serialize_sorted")]
     pub(super) labels: HashSet<String>,
     #[serde(skip)]
     pub(super) reserved_labels: HashSet<String>,
@@ -33,6 +35,15 @@ pub struct CfgMethod {
     fresh_label_index: i32,
 }
 
+fn serialize_sorted<S: ::serde::Serializer>(
+    set: &HashSet<String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut elements: Vec<_> = set.iter().collect();
+    elements.sort();
+    ::serde::Serialize::serialize(&elements, serializer)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CfgBlock {
     pub stmts: Vec<Stmt>, // FIXME: Hack, should be pub(super).
@@ -185,6 +196,12 @@ impl CfgMethod {
         candidate_name
     }
 
+    /// Returns a fresh name for a variable bound by a quantifier. Unlike
+    /// `add_fresh_local_var`, the variable is not declared in the method.
+    pub fn get_fresh_bound_var_name(&mut self) -> String {
+        self.generate_fresh_local_var_name()
+    }
+
     pub fn get_fresh_label_name(&mut self) -> String {
         let mut candidate_name = format!("l{}", self.fresh_label_index);
         self.fresh_label_index += 1;
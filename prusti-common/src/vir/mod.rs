@@ -19,4 +19,5 @@ pub mod optimizations;
 mod to_viper;
 pub mod utils;
 mod program;
+mod slicing;
 mod gather_labels;
@@ -184,6 +184,19 @@ impl ast::ExprWalker for VarCollector {
             self.all_vars.remove(var);
         }
     }
+    fn walk_exists(
+        &mut self,
+        vars: &Vec<ast::LocalVar>,
+        _triggers: &Vec<ast::Trigger>,
+        body: &ast::Expr,
+        _pos: &ast::Position,
+    ) {
+        self.walk(body);
+        for var in vars {
+            // TODO: This is not bullet proof against name collisions.
+            self.all_vars.remove(var);
+        }
+    }
 }
 
 impl ast::StmtWalker for VarCollector {
@@ -420,7 +433,11 @@ impl ast::StmtFolder for VarPurifier {
                 ast::Type::Int => "builtin$havoc_int",
                 ast::Type::Bool => "builtin$havoc_bool",
                 ast::Type::TypedRef(_) => "builtin$havoc_ref",
-                ast::Type::Domain(_) => unreachable!(),
+                ast::Type::Domain(_)
+                | ast::Type::Seq(_)
+                | ast::Type::Set(_)
+                | ast::Type::Map(..)
+                | ast::Type::Float(_) => unreachable!(),
             }.to_string();
             targets = vec![replacement];
         }
@@ -16,6 +16,8 @@ use log::debug;
 /// 3.  Replace all arithmetic expressions inside `forall` that do not depend on bound variables
 ///     with `let tmp == (...) in forall ..`.
 ///
+/// The same is done for `exists ..`.
+///
 /// Note: this seems to be required to workaround some Silicon incompleteness.
 pub fn fix_quantifiers(cfg: vir::CfgMethod) -> vir::CfgMethod {
     let mut optimizer = Optimizer::new();
@@ -61,8 +63,41 @@ impl Optimizer {
         use self::vir::ExprFolder;
         unfolding_extractor.fold(expr)
     }
+
+    /// Fix a quantifier, which is built with the `quantifier` constructor.
+    fn fold_quantifier(
+        &mut self,
+        quantifier: QuantifierConstructor,
+        variables: Vec<vir::LocalVar>,
+        triggers: Vec<vir::Trigger>,
+        body: Box<vir::Expr>,
+        pos: vir::Position,
+    ) -> vir::Expr {
+        use self::vir::ExprFolder;
+        debug!("original body: {}", body);
+        let folded_body = self.fold_boxed(body);
+        debug!("Folded body: {}", folded_body);
+        let old_counter = self.counter;
+        let mut replacer = Replacer::new(&variables, &mut self.counter);
+        let replaced_body = replacer.fold_boxed(folded_body);
+        debug!("replaced body: {}", replaced_body);
+        let mut quantified_expr = quantifier(variables, triggers, replaced_body, pos);
+
+        if *replacer.counter > old_counter {
+            for (expr, variable) in replacer.map {
+                quantified_expr = vir::Expr::LetExpr(variable, box expr, box quantified_expr, pos);
+            }
+            debug!("replaced quantifier: {}", quantified_expr);
+        }
+
+        quantified_expr
+    }
 }
 
+/// Either `vir::Expr::ForAll` or `vir::Expr::Exists`.
+type QuantifierConstructor =
+    fn(Vec<vir::LocalVar>, Vec<vir::Trigger>, Box<vir::Expr>, vir::Position) -> vir::Expr;
+
 impl vir::StmtFolder for Optimizer {
     fn fold_assert(
         &mut self,
@@ -92,23 +127,16 @@ impl vir::ExprFolder for Optimizer {
         body: Box<vir::Expr>,
         pos: vir::Position,
     ) -> vir::Expr {
-        debug!("original body: {}", body);
-        let folded_body = self.fold_boxed(body);
-        debug!("Folded body: {}", folded_body);
-        let old_counter = self.counter;
-        let mut replacer = Replacer::new(&variables, &mut self.counter);
-        let replaced_body = replacer.fold_boxed(folded_body);
-        debug!("replaced body: {}", replaced_body);
-        let mut forall = vir::Expr::ForAll(variables, triggers, replaced_body, pos);
-
-        if *replacer.counter > old_counter {
-            for (expr, variable) in replacer.map {
-                forall = vir::Expr::LetExpr(variable, box expr, box forall, pos);
-            }
-            debug!("replaced quantifier: {}", forall);
-        }
-
-        forall
+        self.fold_quantifier(vir::Expr::ForAll, variables, triggers, body, pos)
+    }
+    fn fold_exists(
+        &mut self,
+        variables: Vec<vir::LocalVar>,
+        triggers: Vec<vir::Trigger>,
+        body: Box<vir::Expr>,
+        pos: vir::Position,
+    ) -> vir::Expr {
+        self.fold_quantifier(vir::Expr::Exists, variables, triggers, body, pos)
     }
 }
 
@@ -210,6 +238,15 @@ impl<'a> vir::ExprFolder for Replacer<'a> {
     ) -> vir::Expr {
         vir::Expr::ForAll(variables, triggers, body, pos)
     }
+    fn fold_exists(
+        &mut self,
+        variables: Vec<vir::LocalVar>,
+        triggers: Vec<vir::Trigger>,
+        body: Box<vir::Expr>,
+        pos: vir::Position,
+    ) -> vir::Expr {
+        vir::Expr::Exists(variables, triggers, body, pos)
+    }
 }
 
 struct UnfoldingExtractor {
@@ -220,14 +257,18 @@ struct UnfoldingExtractor {
     in_quantifier: bool,
 }
 
-impl vir::ExprFolder for UnfoldingExtractor {
-    fn fold_forall(
+impl UnfoldingExtractor {
+    /// Pull the unfoldings out of a quantifier, which is built with the `quantifier`
+    /// constructor.
+    fn fold_quantifier(
         &mut self,
+        quantifier: QuantifierConstructor,
         variables: Vec<vir::LocalVar>,
         triggers: Vec<vir::Trigger>,
         body: Box<vir::Expr>,
         pos: vir::Position,
     ) -> vir::Expr {
+        use self::vir::ExprFolder;
         assert!(
             self.unfoldings.is_empty(),
             "Nested quantifiers are not supported."
@@ -238,17 +279,38 @@ impl vir::ExprFolder for UnfoldingExtractor {
         let replaced_body = self.fold_boxed(body);
         self.in_quantifier = false;
 
-        let mut forall = vir::Expr::ForAll(variables, triggers, replaced_body, pos);
+        let mut quantified_expr = quantifier(variables, triggers, replaced_body, pos);
 
         let unfoldings = mem::replace(&mut self.unfoldings, HashMap::new());
 
         for ((name, args), (perm_amount, variant, _)) in unfoldings {
-            forall =
-                vir::Expr::Unfolding(name, args, box forall, perm_amount, variant, pos);
+            quantified_expr =
+                vir::Expr::Unfolding(name, args, box quantified_expr, perm_amount, variant, pos);
         }
-        debug!("replaced quantifier: {}", forall);
+        debug!("replaced quantifier: {}", quantified_expr);
 
-        forall
+        quantified_expr
+    }
+}
+
+impl vir::ExprFolder for UnfoldingExtractor {
+    fn fold_forall(
+        &mut self,
+        variables: Vec<vir::LocalVar>,
+        triggers: Vec<vir::Trigger>,
+        body: Box<vir::Expr>,
+        pos: vir::Position,
+    ) -> vir::Expr {
+        self.fold_quantifier(vir::Expr::ForAll, variables, triggers, body, pos)
+    }
+    fn fold_exists(
+        &mut self,
+        variables: Vec<vir::LocalVar>,
+        triggers: Vec<vir::Trigger>,
+        body: Box<vir::Expr>,
+        pos: vir::Position,
+    ) -> vir::Expr {
+        self.fold_quantifier(vir::Expr::Exists, variables, triggers, body, pos)
     }
     fn fold_unfolding(
         &mut self,
@@ -0,0 +1,486 @@
+// © 2021, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Splitting of a Viper program into smaller programs that can be verified independently.
+
+use std::collections::{BTreeSet, HashMap};
+use vir::ast::*;
+use vir::cfg::CfgMethod;
+use vir::utils::walk_methods;
+use vir::Program;
+
+/// Predicates that are used by the Viper encoding without appearing in VIR.
+const IMPLICITLY_USED_PREDICATES: &[&str] = &["DeadBorrowToken$"];
+
+impl Program {
+    /// Split the program into one program per method, which contains only the domains, fields,
+    /// builtin methods, functions and predicates that the method (transitively) uses.
+    ///
+    /// The functions and predicates that are not used by any method are put into an
+    /// additional program without methods, so that they are still checked by the verifier.
+    pub fn split_by_method(&self) -> Vec<Program> {
+        let items = ProgramItems::new(self);
+        let mut used_functions = BTreeSet::new();
+        let mut used_predicates = BTreeSet::new();
+        let mut slices = vec![];
+        for method in &self.methods {
+            let mut collector = UsedItemsCollector::default();
+            collector.collect_method(method);
+            let used = items.close(collector);
+            used_functions.extend(used.functions.iter().cloned());
+            used_predicates.extend(used.predicates.iter().cloned());
+            slices.push(self.slice(vec![method.clone()], &used));
+        }
+
+        let mut collector = UsedItemsCollector::default();
+        collector.functions.extend(
+            self.functions.iter()
+                .map(|function| function.name.clone())
+                .filter(|name| !used_functions.contains(name))
+        );
+        collector.predicates.extend(
+            self.viper_predicates.iter()
+                .map(|predicate| predicate.name().to_string())
+                .filter(|name| !used_predicates.contains(name))
+        );
+        if !collector.functions.is_empty() || !collector.predicates.is_empty() {
+            let used = items.close(collector);
+            slices.push(self.slice(vec![], &used));
+        }
+        slices
+    }
+
+    fn slice(&self, methods: Vec<CfgMethod>, used: &UsedItemsCollector) -> Program {
+        Program {
+            domains: self.domains.iter()
+                .filter(|domain| used.domains.contains(&domain.name))
+                .cloned()
+                .collect(),
+            fields: self.fields.iter()
+                .filter(|field| used.fields.contains(&field.name))
+                .cloned()
+                .collect(),
+            builtin_methods: self.builtin_methods.iter()
+                .filter(|method| used.builtin_methods.contains(&method.name))
+                .cloned()
+                .collect(),
+            methods,
+            functions: self.functions.iter()
+                .filter(|function| used.functions.contains(&function.name))
+                .cloned()
+                .collect(),
+            viper_predicates: self.viper_predicates.iter()
+                .filter(|predicate| used.predicates.contains(predicate.name()))
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+/// The items of a program that can use other items, by name.
+struct ProgramItems<'a> {
+    domains: HashMap<&'a str, &'a Domain>,
+    fields: HashMap<&'a str, &'a Field>,
+    builtin_methods: HashMap<&'a str, &'a BodylessMethod>,
+    functions: HashMap<&'a str, &'a Function>,
+    predicates: HashMap<&'a str, &'a Predicate>,
+}
+
+impl<'a> ProgramItems<'a> {
+    fn new(program: &'a Program) -> Self {
+        ProgramItems {
+            domains: program.domains.iter().map(|d| (d.name.as_str(), d)).collect(),
+            fields: program.fields.iter().map(|f| (f.name.as_str(), f)).collect(),
+            builtin_methods: program.builtin_methods.iter()
+                .map(|m| (m.name.as_str(), m))
+                .collect(),
+            functions: program.functions.iter().map(|f| (f.name.as_str(), f)).collect(),
+            predicates: program.viper_predicates.iter().map(|p| (p.name(), p)).collect(),
+        }
+    }
+
+    /// Extend the items found by `collector` with the items that they use.
+    fn close(&self, mut collector: UsedItemsCollector) -> UsedItemsCollector {
+        collector.predicates.extend(IMPLICITLY_USED_PREDICATES.iter().map(|p| p.to_string()));
+        let mut visited = UsedItemsCollector::default();
+        loop {
+            let mut changed = false;
+            let domains = collector.domains.difference(&visited.domains)
+                .cloned()
+                .collect::<Vec<_>>();
+            for name in domains {
+                if let Some(domain) = self.domains.get(name.as_str()) {
+                    collector.collect_domain(domain);
+                }
+                visited.domains.insert(name);
+                changed = true;
+            }
+            let fields = collector.fields.difference(&visited.fields)
+                .cloned()
+                .collect::<Vec<_>>();
+            for name in fields {
+                if let Some(field) = self.fields.get(name.as_str()) {
+                    collector.collect_type(&field.typ);
+                }
+                visited.fields.insert(name);
+                changed = true;
+            }
+            let builtin_methods = collector.builtin_methods.difference(&visited.builtin_methods)
+                .cloned()
+                .collect::<Vec<_>>();
+            for name in builtin_methods {
+                if let Some(method) = self.builtin_methods.get(name.as_str()) {
+                    method.formal_args.iter()
+                        .chain(method.formal_returns.iter())
+                        .for_each(|var| collector.collect_type(&var.typ));
+                }
+                visited.builtin_methods.insert(name);
+                changed = true;
+            }
+            let functions = collector.functions.difference(&visited.functions)
+                .cloned()
+                .collect::<Vec<_>>();
+            for name in functions {
+                if let Some(function) = self.functions.get(name.as_str()) {
+                    collector.collect_function(function);
+                }
+                visited.functions.insert(name);
+                changed = true;
+            }
+            let predicates = collector.predicates.difference(&visited.predicates)
+                .cloned()
+                .collect::<Vec<_>>();
+            for name in predicates {
+                if let Some(predicate) = self.predicates.get(name.as_str()) {
+                    collector.collect_predicate(predicate);
+                }
+                visited.predicates.insert(name);
+                changed = true;
+            }
+            if !changed {
+                return collector;
+            }
+        }
+    }
+}
+
+/// Collects the names of the items used by methods, functions, predicates and domains.
+#[derive(Default)]
+struct UsedItemsCollector {
+    domains: BTreeSet<String>,
+    fields: BTreeSet<String>,
+    builtin_methods: BTreeSet<String>,
+    functions: BTreeSet<String>,
+    predicates: BTreeSet<String>,
+}
+
+impl UsedItemsCollector {
+    fn collect_method(&mut self, method: &CfgMethod) {
+        walk_methods(std::slice::from_ref(method), self);
+        for var in method.local_vars.iter().chain(method.formal_returns.iter()) {
+            self.collect_type(&var.typ);
+        }
+    }
+
+    fn collect_function(&mut self, function: &Function) {
+        for var in &function.formal_args {
+            self.collect_type(&var.typ);
+        }
+        self.collect_type(&function.return_type);
+        for expr in function.pres.iter().chain(function.posts.iter()).chain(function.body.iter()) {
+            ExprWalker::walk(self, expr);
+        }
+    }
+
+    fn collect_predicate(&mut self, predicate: &Predicate) {
+        match predicate {
+            Predicate::Struct(StructPredicate { this, body, .. }) => {
+                self.collect_type(&this.typ);
+                if let Some(body) = body {
+                    ExprWalker::walk(self, body);
+                }
+            }
+            Predicate::Enum(enum_predicate) => {
+                self.collect_type(&enum_predicate.this.typ);
+                ExprWalker::walk(self, &enum_predicate.body());
+            }
+            Predicate::Bodyless(_, this) => self.collect_type(&this.typ),
+        }
+    }
+
+    fn collect_domain(&mut self, domain: &Domain) {
+        for function in &domain.functions {
+            for var in &function.formal_args {
+                self.collect_type(&var.typ);
+            }
+            self.collect_type(&function.return_type);
+        }
+        for axiom in &domain.axioms {
+            ExprWalker::walk(self, &axiom.expr);
+        }
+    }
+
+    fn collect_type(&mut self, typ: &Type) {
+        match typ {
+            Type::Int | Type::Bool | Type::TypedRef(_) => {}
+            Type::Domain(name) => {
+                self.domains.insert(name.clone());
+            }
+            Type::Float(size) => {
+                self.domains.insert(size.domain_name().to_string());
+            }
+            Type::Seq(elem_type) | Type::Set(elem_type) => self.collect_type(elem_type),
+            Type::Map(key_type, value_type) => {
+                self.collect_type(key_type);
+                self.collect_type(value_type);
+            }
+        }
+    }
+}
+
+impl ExprWalker for UsedItemsCollector {
+    fn walk_local_var(&mut self, var: &LocalVar) {
+        self.collect_type(&var.typ);
+    }
+
+    fn walk_variant(&mut self, base: &Expr, variant: &Field, _pos: &Position) {
+        self.fields.insert(variant.name.clone());
+        ExprWalker::walk(self, base);
+    }
+
+    fn walk_field(&mut self, receiver: &Expr, field: &Field, _pos: &Position) {
+        self.fields.insert(field.name.clone());
+        ExprWalker::walk(self, receiver);
+    }
+
+    fn walk_addr_of(&mut self, receiver: &Expr, typ: &Type, _pos: &Position) {
+        self.collect_type(typ);
+        ExprWalker::walk(self, receiver);
+    }
+
+    fn walk_const(&mut self, constant: &Const, _pos: &Position) {
+        match constant {
+            Const::Float32(_) => self.collect_type(&Type::Float(FloatSize::F32)),
+            Const::Float64(_) => self.collect_type(&Type::Float(FloatSize::F64)),
+            _ => {}
+        }
+    }
+
+    fn walk_predicate_access_predicate(
+        &mut self,
+        name: &str,
+        arg: &Expr,
+        _perm_amount: PermAmount,
+        _pos: &Position,
+    ) {
+        self.predicates.insert(name.to_string());
+        ExprWalker::walk(self, arg);
+    }
+
+    fn walk_unfolding(
+        &mut self,
+        name: &str,
+        args: &Vec<Expr>,
+        body: &Expr,
+        _perm: PermAmount,
+        _variant: &MaybeEnumVariantIndex,
+        _pos: &Position,
+    ) {
+        self.predicates.insert(name.to_string());
+        for arg in args {
+            ExprWalker::walk(self, arg);
+        }
+        ExprWalker::walk(self, body);
+    }
+
+    fn walk_func_app(
+        &mut self,
+        name: &str,
+        args: &Vec<Expr>,
+        formal_args: &Vec<LocalVar>,
+        return_type: &Type,
+        _pos: &Position,
+    ) {
+        self.functions.insert(name.to_string());
+        self.collect_type(return_type);
+        for arg in args {
+            ExprWalker::walk(self, arg);
+        }
+        for arg in formal_args {
+            self.collect_type(&arg.typ);
+        }
+    }
+
+    fn walk_domain_func_app(&mut self, func: &DomainFunc, args: &Vec<Expr>, _pos: &Position) {
+        self.domains.insert(func.domain_name.clone());
+        self.collect_type(&func.return_type);
+        for arg in args {
+            ExprWalker::walk(self, arg);
+        }
+        for arg in &func.formal_args {
+            self.collect_type(&arg.typ);
+        }
+    }
+
+    fn walk_seq(&mut self, typ: &Type, elems: &Vec<Expr>, _pos: &Position) {
+        self.collect_type(typ);
+        for elem in elems {
+            ExprWalker::walk(self, elem);
+        }
+    }
+
+    fn walk_set(&mut self, typ: &Type, elems: &Vec<Expr>, _pos: &Position) {
+        self.collect_type(typ);
+        for elem in elems {
+            ExprWalker::walk(self, elem);
+        }
+    }
+
+    fn walk_map(&mut self, typ: &Type, pairs: &Vec<(Expr, Expr)>, _pos: &Position) {
+        self.collect_type(typ);
+        for (key, value) in pairs {
+            ExprWalker::walk(self, key);
+            ExprWalker::walk(self, value);
+        }
+    }
+}
+
+impl StmtWalker for UsedItemsCollector {
+    fn walk_expr(&mut self, expr: &Expr) {
+        ExprWalker::walk(self, expr);
+    }
+
+    fn walk_local_var(&mut self, local_var: &LocalVar) {
+        self.collect_type(&local_var.typ);
+    }
+
+    fn walk_method_call(&mut self, method_name: &str, args: &Vec<Expr>, targets: &Vec<LocalVar>) {
+        self.builtin_methods.insert(method_name.to_string());
+        for arg in args {
+            ExprWalker::walk(self, arg);
+        }
+        for target in targets {
+            self.collect_type(&target.typ);
+        }
+    }
+
+    fn walk_fold(
+        &mut self,
+        predicate_name: &str,
+        args: &Vec<Expr>,
+        _perm: &PermAmount,
+        _variant: &MaybeEnumVariantIndex,
+        _pos: &Position,
+    ) {
+        self.predicates.insert(predicate_name.to_string());
+        for arg in args {
+            ExprWalker::walk(self, arg);
+        }
+    }
+
+    fn walk_unfold(
+        &mut self,
+        predicate_name: &str,
+        args: &Vec<Expr>,
+        _perm: &PermAmount,
+        _variant: &MaybeEnumVariantIndex,
+    ) {
+        self.predicates.insert(predicate_name.to_string());
+        for arg in args {
+            ExprWalker::walk(self, arg);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use verification_service::{VerificationRequest, ViperBackendConfig};
+    use viper::VerificationBackend;
+    use vir::Successor;
+
+    fn call(function_name: &str) -> Expr {
+        Expr::func_app(function_name.to_string(), vec![], vec![], Type::Int, Position::default())
+    }
+
+    fn function(name: &str, body: Expr) -> Function {
+        Function {
+            name: name.to_string(),
+            formal_args: vec![],
+            return_type: Type::Int,
+            pres: vec![],
+            posts: vec![],
+            body: Some(body),
+        }
+    }
+
+    fn method(name: &str, function_name: &str) -> CfgMethod {
+        let mut method = CfgMethod::new(name.to_string(), 0, vec![], vec![], vec![]);
+        let block = method.add_block("start", vec![
+            Stmt::Label("l1".to_string()),
+            Stmt::Label("l2".to_string()),
+            Stmt::Label("l3".to_string()),
+            Stmt::Assert(
+                Expr::eq_cmp(call(function_name), 0.into()),
+                FoldingBehaviour::Stmt,
+                Position::default(),
+            ),
+        ]);
+        method.set_successor(block, Successor::Return);
+        method
+    }
+
+    fn program() -> Program {
+        Program {
+            domains: vec![],
+            fields: vec![],
+            builtin_methods: vec![],
+            methods: vec![method("m1", "f1"), method("m2", "f3")],
+            functions: vec![
+                function("f1", call("f2")),
+                function("f2", 0.into()),
+                function("f3", 0.into()),
+                function("f4", 0.into()),
+            ],
+            viper_predicates: vec![],
+        }
+    }
+
+    fn function_names(program: &Program) -> Vec<&str> {
+        program.functions.iter().map(|function| function.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_split_by_method() {
+        let slices = program().split_by_method();
+        assert_eq!(slices.len(), 3);
+        assert_eq!(slices[0].methods[0].name(), "m1");
+        assert_eq!(function_names(&slices[0]), vec!["f1", "f2"]);
+        assert_eq!(slices[1].methods[0].name(), "m2");
+        assert_eq!(function_names(&slices[1]), vec!["f3"]);
+        assert!(slices[2].methods.is_empty());
+        assert_eq!(function_names(&slices[2]), vec!["f4"]);
+    }
+
+    #[test]
+    fn test_equal_slices_have_equal_hashes() {
+        let hash = |program: Program| VerificationRequest {
+            program,
+            program_name: "program".to_string(),
+            backend_config: ViperBackendConfig {
+                backend: VerificationBackend::Silicon,
+                verifier_args: vec![],
+            },
+        }.get_hash();
+        let first = program().split_by_method();
+        let second = program().split_by_method();
+        for (first_slice, second_slice) in first.into_iter().zip(second) {
+            assert_eq!(hash(first_slice), hash(second_slice));
+        }
+        let slices = program().split_by_method();
+        assert_ne!(hash(slices[0].clone()), hash(slices[1].clone()));
+    }
+}
@@ -77,6 +77,14 @@ impl<'v> ToViper<'v, viper::Type<'v>> for Type {
             //&Type::Ref |
             &Type::TypedRef(_) => ast.ref_type(),
             &Type::Domain(ref name) => ast.domain_type(&name, &[], &[]),
+            &Type::Seq(ref elem_type) => ast.seq_type(elem_type.to_viper(ast)),
+            &Type::Set(ref elem_type) => ast.set_type(elem_type.to_viper(ast)),
+            &Type::Map(ref key_type, ref value_type) => {
+                ast.map_type(key_type.to_viper(ast), value_type.to_viper(ast))
+            }
+            &Type::Float(size) => {
+                ast.backend_type(&size.to_string(), &size.smt_sort(), &size.boogie_type())
+            }
         }
     }
 }
@@ -410,6 +418,12 @@ impl<'v> ToViper<'v, viper::Expr<'v>> for Expr {
                 body.to_viper(ast),
                 pos.to_viper(ast),
             ),
+            &Expr::Exists(ref vars, ref triggers, ref body, ref pos) => ast.exists_with_pos(
+                &vars.to_viper_decl(ast)[..],
+                &(triggers, pos).to_viper(ast),
+                body.to_viper(ast),
+                pos.to_viper(ast),
+            ),
             &Expr::LetExpr(ref var, ref expr, ref body, ref pos) => ast.let_expr_with_pos(
                 var.to_viper_decl(ast),
                 expr.to_viper(ast),
@@ -431,12 +445,17 @@ impl<'v> ToViper<'v, viper::Expr<'v>> for Expr {
                     pos.to_viper(ast),
                 )
             }
-            &Expr::DomainFuncApp(ref function, ref args, ref _pos) => {
-                ast.domain_func_app(
-                    function.to_viper(ast),
-                    &args.to_viper(ast),
-                    &[], // TODO not necessary so far
-                )
+            &Expr::DomainFuncApp(ref function, ref args, ref pos) => {
+                match FloatFunc::of_domain_func(function) {
+                    Some((size, func)) if func.is_interpreted() => {
+                        float_func_app_to_viper(size, func, function, args, pos, ast)
+                    }
+                    _ => ast.domain_func_app(
+                        function.to_viper(ast),
+                        &args.to_viper(ast),
+                        &[], // TODO not necessary so far
+                    ),
+                }
             }
             /* TODO use once DomainFuncApp has been updated
             &Expr::DomainFuncApp(
@@ -461,6 +480,77 @@ impl<'v> ToViper<'v, viper::Expr<'v>> for Expr {
             &Expr::InhaleExhale(ref inhale_expr, ref exhale_expr, ref _pos) => {
                 ast.inhale_exhale_pred(inhale_expr.to_viper(ast), exhale_expr.to_viper(ast))
             }
+            &Expr::Seq(ref typ, ref elems, ref _pos) => {
+                if elems.is_empty() {
+                    ast.empty_seq(typ.seq_elem_type().to_viper(ast))
+                } else {
+                    ast.explicit_seq(&elems.to_viper(ast))
+                }
+            }
+            &Expr::SeqIndex(ref seq, ref index, ref _pos) => {
+                ast.seq_index(seq.to_viper(ast), index.to_viper(ast))
+            }
+            &Expr::SeqLen(ref seq, ref _pos) => ast.seq_length(seq.to_viper(ast)),
+            &Expr::SeqUpdate(ref seq, ref index, ref elem, ref _pos) => ast.seq_update(
+                seq.to_viper(ast),
+                index.to_viper(ast),
+                elem.to_viper(ast),
+            ),
+            &Expr::SeqSlice(ref seq, ref from, ref to, ref _pos) => {
+                // `s[from..to]` is encoded as `s[..to][from..]`
+                ast.seq_drop(
+                    ast.seq_take(seq.to_viper(ast), to.to_viper(ast)),
+                    from.to_viper(ast),
+                )
+            }
+            &Expr::Set(ref typ, ref elems, ref _pos) => {
+                if elems.is_empty() {
+                    match typ {
+                        Type::Set(elem_type) => ast.empty_set(elem_type.to_viper(ast)),
+                        _ => unreachable!("{} is not a set type", typ),
+                    }
+                } else {
+                    ast.explicit_set(&elems.to_viper(ast))
+                }
+            }
+            &Expr::SetLen(ref set, ref _pos) => ast.any_set_cardinality(set.to_viper(ast)),
+            &Expr::Map(ref typ, ref pairs, ref _pos) => {
+                if pairs.is_empty() {
+                    match typ {
+                        Type::Map(key_type, value_type) => ast.empty_map(
+                            key_type.to_viper(ast),
+                            value_type.to_viper(ast),
+                        ),
+                        _ => unreachable!("{} is not a map type", typ),
+                    }
+                } else {
+                    let maplets: Vec<_> = pairs.iter()
+                        .map(|(key, value)| ast.maplet(key.to_viper(ast), value.to_viper(ast)))
+                        .collect();
+                    ast.explicit_map(&maplets)
+                }
+            }
+            &Expr::MapLen(ref map, ref _pos) => ast.map_cardinality(map.to_viper(ast)),
+            &Expr::MapUpdate(ref map, ref key, ref value, ref _pos) => ast.map_update(
+                map.to_viper(ast),
+                key.to_viper(ast),
+                value.to_viper(ast),
+            ),
+            &Expr::ContainerOp(op_kind, ref left, ref right, ref _pos) => {
+                let left = left.to_viper(ast);
+                let right = right.to_viper(ast);
+                match op_kind {
+                    ContainerOpKind::SeqConcat => ast.seq_append(left, right),
+                    ContainerOpKind::SeqContains => ast.seq_contains(left, right),
+                    ContainerOpKind::SetUnion => ast.any_set_union(left, right),
+                    ContainerOpKind::SetIntersection => ast.any_set_intersection(left, right),
+                    ContainerOpKind::SetMinus => ast.any_set_minus(left, right),
+                    ContainerOpKind::SetSubset => ast.any_set_subset(left, right),
+                    ContainerOpKind::SetContains => ast.any_set_contains(left, right),
+                    ContainerOpKind::MapContains => ast.map_contains(left, right),
+                    ContainerOpKind::MapLookup => ast.map_lookup(left, right),
+                }
+            }
         };
         if config::simplify_encoding() {
             ast.simplified_expression(expr)
@@ -470,6 +560,72 @@ impl<'v> ToViper<'v, viper::Expr<'v>> for Expr {
     }
 }
 
+/// Encodes a function on floats with the SMT-LIB theory of floating-point numbers. Conversions
+/// from bit patterns and to integers go through bit vectors and reals respectively.
+fn float_func_app_to_viper<'v>(
+    size: FloatSize,
+    func: FloatFunc,
+    function: &DomainFunc,
+    args: &Vec<Expr>,
+    pos: &Position,
+    ast: &AstFactory<'v>,
+) -> viper::Expr<'v> {
+    let pos = pos.to_viper(ast);
+    let apply = |name: &str, smt_name: &str, arg_type, return_type, args: &[viper::Expr<'v>]| {
+        let formal_args = [ast.local_var_decl("_0", arg_type)];
+        ast.backend_func_app(name, smt_name, &formal_args, return_type, args, pos)
+    };
+    let float_type = Type::Float(size).to_viper(ast);
+    let args = args.to_viper(ast);
+    match func {
+        FloatFunc::FromBits => {
+            let width = size.bit_width();
+            let bv_name = format!("bv{}", width);
+            let bv_type = ast.backend_type(&bv_name, &format!("(_ BitVec {})", width), &bv_name);
+            let bits = apply(
+                &format!("{}$int_to_bv", size),
+                &format!("(_ int2bv {})", width),
+                ast.int_type(),
+                bv_type,
+                &args,
+            );
+            apply(
+                &format!("{}$from_bv", size),
+                &format!("(_ to_fp {} {})", size.exponent_bits(), size.significand_bits()),
+                bv_type,
+                float_type,
+                &[bits],
+            )
+        }
+        FloatFunc::ToInt => {
+            let real_type = ast.backend_type("Real", "Real", "real");
+            let truncated = apply(
+                &format!("{}$round_toward_zero", size),
+                "fp.roundToIntegral RTZ",
+                float_type,
+                float_type,
+                &args,
+            );
+            let real = apply(
+                &format!("{}$to_real", size),
+                "fp.to_real",
+                float_type,
+                real_type,
+                &[truncated],
+            );
+            apply("real$to_int", "to_int", real_type, ast.int_type(), &[real])
+        }
+        _ => ast.backend_func_app(
+            &function.name,
+            &func.smt_name(size).unwrap(),
+            &function.formal_args.to_viper_decl(ast),
+            function.return_type.to_viper(ast),
+            &args,
+            pos,
+        ),
+    }
+}
+
 impl<'v, 'a, 'b> ToViper<'v, viper::Trigger<'v>> for (&'a Trigger, &'b Position) {
     fn to_viper(&self, ast: &AstFactory<'v>) -> viper::Trigger<'v> {
         ast.trigger_with_pos(&self.0.elements().to_viper(ast)[..], self.1.to_viper(ast))
@@ -483,6 +639,12 @@ impl<'v, 'a, 'b> ToViper<'v, viper::Expr<'v>> for (&'a Const, &'b Position) {
             &Const::Bool(false) => ast.false_lit_with_pos(self.1.to_viper(ast)),
             &Const::Int(x) => ast.int_lit_with_pos(x, self.1.to_viper(ast)),
             &Const::BigInt(ref x) => ast.int_lit_from_ref_with_pos(x, self.1.to_viper(ast)),
+            &Const::Float32(bits) => {
+                float_func_app(FloatSize::F32, FloatFunc::FromBits, vec![bits.into()]).to_viper(ast)
+            }
+            &Const::Float64(bits) => {
+                float_func_app(FloatSize::F64, FloatFunc::FromBits, vec![bits.into()]).to_viper(ast)
+            }
             &Const::FnPtr => ast.null_lit_with_pos(self.1.to_viper(ast)),
         }
     }
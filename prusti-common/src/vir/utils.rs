@@ -119,6 +119,23 @@ impl vir::Expr {
         }
         ExprLabelSubstitutor { substitutor }.fold(self)
     }
+
+    /// Check whether the expression contains an old expression with the given label.
+    pub fn contains_old_label(&self, label: &str) -> bool {
+        struct ExprLabelFinder<'a> {
+            label: &'a str,
+            found: bool,
+        }
+        impl<'a> vir::ExprWalker for ExprLabelFinder<'a> {
+            fn walk_labelled_old(&mut self, x: &str, y: &vir::Expr, _p: &vir::Position) {
+                self.found |= x == self.label;
+                self.walk(y);
+            }
+        }
+        let mut finder = ExprLabelFinder { label, found: false };
+        finder.walk(self);
+        finder.found
+    }
 }
 
 /// Walks all Statements and Expressions in the provided methods
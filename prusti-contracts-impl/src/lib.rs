@@ -3,15 +3,29 @@ extern crate proc_macro;
 use proc_macro2::Span;
 use proc_macro::TokenStream;
 use quote::quote_spanned;
+use prusti_specs::SpecAttributeKind;
+use prusti_specs::runtime_checks;
 
 #[proc_macro_attribute]
-pub fn requires(_attr: TokenStream, tokens: TokenStream) -> TokenStream {
-    tokens
+pub fn requires(attr: TokenStream, tokens: TokenStream) -> TokenStream {
+    if cfg!(feature = "runtime-checks") {
+        runtime_checks::rewrite_prusti_attributes(
+            SpecAttributeKind::Requires, attr.into(), tokens.into()
+        ).into()
+    } else {
+        tokens
+    }
 }
 
 #[proc_macro_attribute]
-pub fn ensures(_attr: TokenStream, tokens: TokenStream) -> TokenStream {
-    tokens
+pub fn ensures(attr: TokenStream, tokens: TokenStream) -> TokenStream {
+    if cfg!(feature = "runtime-checks") {
+        runtime_checks::rewrite_prusti_attributes(
+            SpecAttributeKind::Ensures, attr.into(), tokens.into()
+        ).into()
+    } else {
+        tokens
+    }
 }
 
 #[proc_macro_attribute]
@@ -34,17 +48,72 @@ pub fn trusted(_attr: TokenStream, tokens: TokenStream) -> TokenStream {
     tokens
 }
 
+#[proc_macro_attribute]
+pub fn decreases(_attr: TokenStream, tokens: TokenStream) -> TokenStream {
+    tokens
+}
+
+#[proc_macro_attribute]
+pub fn invariant(_attr: TokenStream, tokens: TokenStream) -> TokenStream {
+    tokens
+}
+
+#[proc_macro]
+pub fn body_invariant(tokens: TokenStream) -> TokenStream {
+    if cfg!(feature = "runtime-checks") {
+        return runtime_checks::body_invariant(tokens.into()).into();
+    }
+    let callsite_span = Span::call_site();
+    (quote_spanned!(callsite_span=> ())).into()
+}
+
+#[proc_macro]
+pub fn body_variant(_tokens: TokenStream) -> TokenStream {
+    let callsite_span = Span::call_site();
+    (quote_spanned!(callsite_span=> ())).into()
+}
+
 #[proc_macro]
-pub fn body_invariant(_tokens: TokenStream) -> TokenStream {
+pub fn prusti_assert(tokens: TokenStream) -> TokenStream {
+    if cfg!(feature = "runtime-checks") {
+        return runtime_checks::prusti_assertion(tokens.into()).into();
+    }
     let callsite_span = Span::call_site();
     (quote_spanned!(callsite_span=> ())).into()
 }
 
+#[proc_macro]
+pub fn prusti_assume(_tokens: TokenStream) -> TokenStream {
+    let callsite_span = Span::call_site();
+    (quote_spanned!(callsite_span=> ())).into()
+}
+
+#[proc_macro]
+pub fn prusti_label(_tokens: TokenStream) -> TokenStream {
+    TokenStream::new()
+}
+
+#[proc_macro]
+pub fn ghost(tokens: TokenStream) -> TokenStream {
+    if cfg!(feature = "runtime-checks") {
+        return runtime_checks::ghost(tokens.into()).into();
+    }
+    TokenStream::new()
+}
+
 #[proc_macro]
 pub fn closure(tokens: TokenStream) -> TokenStream {
     prusti_specs::closure(tokens.into(), true).into()
 }
 
+#[proc_macro]
+pub fn predicate(tokens: TokenStream) -> TokenStream {
+    if cfg!(feature = "runtime-checks") {
+        return runtime_checks::predicate(tokens.into()).into();
+    }
+    prusti_specs::predicate(tokens.into(), true).into()
+}
+
 #[proc_macro_attribute]
 pub fn refine_trait_spec(_attr: TokenStream, tokens: TokenStream) -> TokenStream {
     tokens
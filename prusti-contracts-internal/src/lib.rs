@@ -33,16 +33,56 @@ pub fn trusted(attr: TokenStream, tokens: TokenStream) -> TokenStream {
     rewrite_prusti_attributes(SpecAttributeKind::Trusted, attr.into(), tokens.into()).into()
 }
 
+#[proc_macro_attribute]
+pub fn decreases(attr: TokenStream, tokens: TokenStream) -> TokenStream {
+    rewrite_prusti_attributes(SpecAttributeKind::Decreases, attr.into(), tokens.into()).into()
+}
+
+#[proc_macro_attribute]
+pub fn invariant(attr: TokenStream, tokens: TokenStream) -> TokenStream {
+    prusti_specs::invariant(attr.into(), tokens.into()).into()
+}
+
 #[proc_macro]
 pub fn body_invariant(tokens: TokenStream) -> TokenStream {
     prusti_specs::body_invariant(tokens.into()).into()
 }
 
+#[proc_macro]
+pub fn body_variant(tokens: TokenStream) -> TokenStream {
+    prusti_specs::body_variant(tokens.into()).into()
+}
+
+#[proc_macro]
+pub fn prusti_assert(tokens: TokenStream) -> TokenStream {
+    prusti_specs::prusti_assertion(tokens.into()).into()
+}
+
+#[proc_macro]
+pub fn prusti_assume(tokens: TokenStream) -> TokenStream {
+    prusti_specs::prusti_assumption(tokens.into()).into()
+}
+
+#[proc_macro]
+pub fn prusti_label(tokens: TokenStream) -> TokenStream {
+    prusti_specs::prusti_label(tokens.into()).into()
+}
+
+#[proc_macro]
+pub fn ghost(tokens: TokenStream) -> TokenStream {
+    prusti_specs::ghost(tokens.into()).into()
+}
+
 #[proc_macro]
 pub fn closure(tokens: TokenStream) -> TokenStream {
     prusti_specs::closure(tokens.into(), false).into()
 }
 
+#[proc_macro]
+pub fn predicate(tokens: TokenStream) -> TokenStream {
+    prusti_specs::predicate(tokens.into(), false).into()
+}
+
 #[proc_macro_attribute]
 pub fn refine_trait_spec(attr: TokenStream, tokens: TokenStream) -> TokenStream {
     prusti_specs::refine_trait_spec(attr.into(), tokens.into()).into()
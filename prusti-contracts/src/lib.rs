@@ -20,12 +20,39 @@ mod private {
     /// A macro for marking a function as trusted.
     pub use prusti_contracts_impl::trusted;
 
+    /// A macro for writing a termination measure on a pure function.
+    pub use prusti_contracts_impl::decreases;
+
+    /// A macro for writing a type invariant on a struct or enum.
+    pub use prusti_contracts_impl::invariant;
+
     /// A macro for writing a loop body invariant.
     pub use prusti_contracts_impl::body_invariant;
 
+    /// A macro for writing a loop variant, used to prove termination of a loop.
+    pub use prusti_contracts_impl::body_variant;
+
+    /// A macro for asserting a property in the middle of a function body.
+    pub use prusti_contracts_impl::prusti_assert;
+
+    /// A macro for assuming a property in the middle of a function body.
+    pub use prusti_contracts_impl::prusti_assume;
+
+    /// A macro for labelling a program point, to which specifications can refer with
+    /// `old[label](expr)`.
+    pub use prusti_contracts_impl::prusti_label;
+
+    /// A macro for ghost code, which only exists for verification. The locals declared in a
+    /// ghost block can be used in later ghost blocks and in specifications. With the
+    /// `runtime-checks` feature, ghost code is executed, since the checks may use its locals.
+    pub use prusti_contracts_impl::ghost;
+
     /// A macro for defining a closure with a specification.
     pub use prusti_contracts_impl::closure;
 
+    /// A macro for defining a predicate that can only be used in specifications.
+    pub use prusti_contracts_impl::predicate;
+
     /// A macro for impl blocks that refine trait specifications.
     pub use prusti_contracts_impl::refine_trait_spec;
 
@@ -53,12 +80,38 @@ mod private {
     /// A macro for marking a function as trusted.
     pub use prusti_contracts_internal::trusted;
 
+    /// A macro for writing a termination measure on a pure function.
+    pub use prusti_contracts_internal::decreases;
+
+    /// A macro for writing a type invariant on a struct or enum.
+    pub use prusti_contracts_internal::invariant;
+
     /// A macro for writing a loop body invariant.
     pub use prusti_contracts_internal::body_invariant;
 
+    /// A macro for writing a loop variant, used to prove termination of a loop.
+    pub use prusti_contracts_internal::body_variant;
+
+    /// A macro for asserting a property in the middle of a function body.
+    pub use prusti_contracts_internal::prusti_assert;
+
+    /// A macro for assuming a property in the middle of a function body.
+    pub use prusti_contracts_internal::prusti_assume;
+
+    /// A macro for labelling a program point, to which specifications can refer with
+    /// `old[label](expr)`.
+    pub use prusti_contracts_internal::prusti_label;
+
+    /// A macro for ghost code, which only exists for verification. The locals declared in a
+    /// ghost block can be used in later ghost blocks and in specifications.
+    pub use prusti_contracts_internal::ghost;
+
     /// A macro for defining a closure with a specification.
     pub use prusti_contracts_internal::closure;
 
+    /// A macro for defining a predicate that can only be used in specifications.
+    pub use prusti_contracts_internal::predicate;
+
     /// A macro for impl blocks that refine trait specifications.
     pub use prusti_contracts_internal::refine_trait_spec;
 
@@ -79,4 +132,265 @@ pub fn old<T>(arg: T) -> T {
     arg
 }
 
+/// This function is used to evaluate an expression in the context at the
+/// program point marked with a label. Prefer the `old[label](expr)` syntax.
+pub fn labelled_old<L, T>(arg: T) -> T {
+    arg
+}
+
+/// The built-in label of the context just before entering a loop, which
+/// can be used in loop invariants as `old[loop_entry](expr)`.
+#[doc(hidden)]
+#[allow(non_camel_case_types)]
+pub struct prusti_label_loop_entry {}
+
+/// This function is used to express ownership of the target of a raw
+/// pointer. Prefer the `own!(*ptr)` syntax.
+pub fn own<T: ?Sized>(_ptr: *const T) -> bool {
+    true
+}
+
+/// A macro for requiring or returning ownership of the target of a raw
+/// pointer in a specification, as in `#[requires(own!(*ptr))]`.
+#[macro_export]
+macro_rules! own {
+    (*$ptr:expr) => {
+        $crate::own($ptr)
+    };
+}
+
+/// The body of the operations of the mathematical types, which exist only in
+/// specifications and are never executed.
+fn ghost<T>() -> T {
+    panic!("mathematical types of Prusti cannot be used in executable code")
+}
+
+/// A mathematical integer, which is unbounded and cannot overflow.
+#[derive(Clone, Copy)]
+pub struct Int(());
+
+impl Int {
+    pub fn new(_value: i64) -> Self {
+        ghost()
+    }
+
+    pub fn new_usize(_value: usize) -> Self {
+        ghost()
+    }
+}
+
+impl std::ops::Add for Int {
+    type Output = Self;
+    fn add(self, _other: Self) -> Self {
+        ghost()
+    }
+}
+
+impl std::ops::Sub for Int {
+    type Output = Self;
+    fn sub(self, _other: Self) -> Self {
+        ghost()
+    }
+}
+
+impl std::ops::Mul for Int {
+    type Output = Self;
+    fn mul(self, _other: Self) -> Self {
+        ghost()
+    }
+}
+
+/// The division rounds towards zero, like the division of Rust integers.
+impl std::ops::Div for Int {
+    type Output = Self;
+    #[requires(_other != Int::new(0))]
+    fn div(self, _other: Self) -> Self {
+        ghost()
+    }
+}
+
+/// The remainder has the sign of the dividend, like the remainder of Rust integers.
+impl std::ops::Rem for Int {
+    type Output = Self;
+    #[requires(_other != Int::new(0))]
+    fn rem(self, _other: Self) -> Self {
+        ghost()
+    }
+}
+
+impl std::ops::Neg for Int {
+    type Output = Self;
+    fn neg(self) -> Self {
+        ghost()
+    }
+}
+
+impl PartialEq for Int {
+    fn eq(&self, _other: &Self) -> bool {
+        ghost()
+    }
+}
+
+impl PartialOrd for Int {
+    fn partial_cmp(&self, _other: &Self) -> Option<std::cmp::Ordering> {
+        ghost()
+    }
+}
+
+/// A mathematical sequence, whose elements are indexed from zero.
+pub struct Seq<T>(std::marker::PhantomData<T>);
+
+impl<T> Seq<T> {
+    pub fn empty() -> Self {
+        ghost()
+    }
+
+    pub fn single(_elem: T) -> Self {
+        ghost()
+    }
+
+    /// The sequence extended with `elem` at the end.
+    pub fn push(self, _elem: T) -> Self {
+        ghost()
+    }
+
+    pub fn concat(self, _other: Self) -> Self {
+        ghost()
+    }
+
+    pub fn len(self) -> Int {
+        ghost()
+    }
+
+    pub fn is_empty(self) -> bool {
+        ghost()
+    }
+
+    /// The element at `index`, which must be between zero and the length of the sequence.
+    #[requires(Int::new(0) <= _index && _index < self.len())]
+    pub fn lookup(self, _index: Int) -> T {
+        ghost()
+    }
+
+    /// The sequence with the element at `index` replaced by `elem`.
+    pub fn update(self, _index: Int, _elem: T) -> Self {
+        ghost()
+    }
+
+    /// The subsequence from `from` (inclusive) to `to` (exclusive). Indices past the ends of
+    /// the sequence are clamped to its ends.
+    pub fn slice(self, _from: Int, _to: Int) -> Self {
+        ghost()
+    }
+
+    pub fn contains(self, _elem: T) -> bool {
+        ghost()
+    }
+}
+
+/// The characters of a string slice, which index the string slice by characters in
+/// specifications, as in `chars(s).lookup(Int::new(0))`.
+pub fn chars(_s: &str) -> Seq<char> {
+    ghost()
+}
+
+/// A mathematical set.
+pub struct Set<T>(std::marker::PhantomData<T>);
+
+impl<T> Set<T> {
+    pub fn empty() -> Self {
+        ghost()
+    }
+
+    pub fn single(_elem: T) -> Self {
+        ghost()
+    }
+
+    pub fn insert(self, _elem: T) -> Self {
+        ghost()
+    }
+
+    pub fn union(self, _other: Self) -> Self {
+        ghost()
+    }
+
+    pub fn intersection(self, _other: Self) -> Self {
+        ghost()
+    }
+
+    pub fn difference(self, _other: Self) -> Self {
+        ghost()
+    }
+
+    pub fn is_subset(self, _other: Self) -> bool {
+        ghost()
+    }
+
+    pub fn contains(self, _elem: T) -> bool {
+        ghost()
+    }
+
+    pub fn len(self) -> Int {
+        ghost()
+    }
+
+    pub fn is_empty(self) -> bool {
+        ghost()
+    }
+}
+
+/// A mathematical map, which associates each key of its domain with a value.
+pub struct Map<K, V>(std::marker::PhantomData<(K, V)>);
+
+impl<K, V> Map<K, V> {
+    pub fn empty() -> Self {
+        ghost()
+    }
+
+    /// The map that associates `key` with `value`, and any other key as this map does.
+    pub fn insert(self, _key: K, _value: V) -> Self {
+        ghost()
+    }
+
+    /// The value of `key`, which must be in the domain of the map.
+    pub fn lookup(self, _key: K) -> V {
+        ghost()
+    }
+
+    pub fn contains_key(self, _key: K) -> bool {
+        ghost()
+    }
+
+    pub fn len(self) -> Int {
+        ghost()
+    }
+
+    pub fn is_empty(self) -> bool {
+        ghost()
+    }
+}
+
+// The containers are values, independently of the type of their elements.
+macro_rules! impl_ghost_value {
+    ($($name:ident<$($param:ident),*>),*) => {
+        $(
+            impl<$($param),*> Clone for $name<$($param),*> {
+                fn clone(&self) -> Self {
+                    *self
+                }
+            }
+
+            impl<$($param),*> Copy for $name<$($param),*> {}
+
+            impl<$($param),*> PartialEq for $name<$($param),*> {
+                fn eq(&self, _other: &Self) -> bool {
+                    ghost()
+                }
+            }
+        )*
+    };
+}
+
+impl_ghost_value!(Seq<T>, Set<T>, Map<K, V>);
+
 pub use private::*;
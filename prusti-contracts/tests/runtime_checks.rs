@@ -0,0 +1,164 @@
+#![cfg(feature = "runtime-checks")]
+
+use prusti_contracts::*;
+
+#[requires(x < 100)]
+#[ensures(result == x + 1)]
+fn increment(x: u32) -> u32 {
+    x + 1
+}
+
+#[ensures(result == x)]
+fn wrong_identity(x: u32) -> u32 {
+    x + 1
+}
+
+#[ensures(v.len() == old(v.len()) + 1)]
+#[ensures(v[v.len() - 1] == x)]
+fn push(v: &mut Vec<i32>, x: i32) {
+    v.push(x);
+}
+
+#[ensures(v.len() == old(v.len()) + 1)]
+fn push_twice_and_pop(v: &mut Vec<i32>, x: i32) {
+    v.push(x);
+    v.push(x);
+    v.pop();
+}
+
+struct Counter {
+    value: u32,
+}
+
+impl Counter {
+    #[requires(self.value < u32::MAX)]
+    #[ensures(self.value == old(self.value) + 1)]
+    fn increment(&mut self) {
+        self.value += 1;
+    }
+}
+
+#[requires(forall(|i: usize| (i < v.len()) ==> v[i] >= 0))]
+#[ensures(forall(|i: usize, j: usize| (i < j && j < v.len()) ==> result[i] <= result[j]))]
+#[ensures(v.contains(&0) ==> exists(|i: usize| i < result.len() && result[i] == 0))]
+fn sorted(v: &[i32]) -> Vec<i32> {
+    let mut result = v.to_vec();
+    result.sort_unstable();
+    result
+}
+
+#[ensures(result >= 0)]
+fn early_return(x: i32) -> i32 {
+    if x < 0 {
+        return x;
+    }
+    x
+}
+
+#[ensures(result.clone().all(|x| x < n))]
+fn below(n: u32) -> impl Iterator<Item = u32> + Clone {
+    0..n
+}
+
+#[ensures(result.as_ref().map_or(true, |value| *value > 0))]
+fn parse_positive(s: &str) -> Result<u32, std::num::ParseIntError> {
+    let value: u32 = s.parse()?;
+    Ok(value + 1)
+}
+
+#[ensures(result.is_some())]
+fn first_even(v: &[u32]) -> Option<u32> {
+    let first = v.iter().find(|x| *x % 2 == 0)?;
+    Some(*first)
+}
+
+predicate! {
+    fn all_positive(v: &[i32]) -> bool {
+        forall(|i: usize| (i < v.len()) ==> v[i] > 0)
+    }
+}
+
+fn sum_positive(v: &[i32]) -> i32 {
+    let mut sum = 0;
+    let mut i = 0;
+    while i < v.len() {
+        body_invariant!(sum >= 0);
+        sum += v[i];
+        i += 1;
+    }
+    prusti_assert!(sum >= 0);
+    sum
+}
+
+fn double(x: u32) -> u32 {
+    ghost! {
+        let original = x;
+    }
+    let result = x * 2;
+    prusti_assert!(result == original + original);
+    result
+}
+
+#[test]
+fn satisfied_contracts() {
+    assert_eq!(increment(1), 2);
+    let mut counter = Counter { value: 0 };
+    counter.increment();
+    assert_eq!(counter.value, 1);
+    let mut v = vec![1];
+    push(&mut v, 2);
+    push_twice_and_pop(&mut v, 3);
+    assert_eq!(v, vec![1, 2, 3]);
+    assert_eq!(sorted(&[3, 0, 2]), vec![0, 2, 3]);
+    assert_eq!(early_return(4), 4);
+    assert_eq!(below(3).count(), 3);
+    assert_eq!(parse_positive("1"), Ok(2));
+    assert!(parse_positive("x").is_err());
+    assert_eq!(first_even(&[1, 2]), Some(2));
+    assert!(all_positive(&[1, 2]));
+    assert!(!all_positive(&[1, 0]));
+    assert_eq!(sum_positive(&[1, 2]), 3);
+    assert_eq!(double(2), 4);
+}
+
+#[test]
+#[should_panic(expected = "precondition might not hold")]
+fn violated_precondition() {
+    increment(100);
+}
+
+#[test]
+#[should_panic(expected = "precondition might not hold")]
+fn violated_method_precondition() {
+    Counter { value: u32::MAX }.increment();
+}
+
+#[test]
+#[should_panic(expected = "precondition might not hold")]
+fn violated_quantified_precondition() {
+    sorted(&[1, -1]);
+}
+
+#[test]
+#[should_panic(expected = "postcondition might not hold")]
+fn violated_postcondition() {
+    wrong_identity(1);
+}
+
+#[test]
+#[should_panic(expected = "postcondition might not hold")]
+fn violated_postcondition_on_early_return() {
+    early_return(-1);
+}
+
+#[test]
+#[should_panic(expected = "postcondition might not hold")]
+fn violated_postcondition_on_question_mark() {
+    first_even(&[1, 3]);
+}
+
+#[test]
+#[should_panic(expected = "loop invariant might not hold")]
+fn violated_loop_invariant() {
+    sum_positive(&[-1, -2]);
+}
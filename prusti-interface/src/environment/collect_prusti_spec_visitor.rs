@@ -40,7 +40,11 @@ impl<'a, 'tcx> ItemLikeVisitor<'tcx> for CollectPrustiSpecVisitor<'a, 'tcx> {
         if has_spec_only_attr(&item.attrs) || has_extern_spec_attr(&item.attrs) {
             return;
         }
-        if let hir::ItemKind::Fn(..) = item.kind {
+        if let hir::ItemKind::Fn(ref sig, ..) = item.kind {
+            // The body of an `async fn` is verified through its generator
+            if sig.header.asyncness == hir::IsAsync::Async {
+                return;
+            }
             let def_id = self.tcx.hir().local_def_id(item.hir_id).to_def_id();
             let item_def_path = self.env.get_item_def_path(def_id);
             trace!("Add {} to result", item_def_path);
@@ -76,8 +80,11 @@ impl<'a, 'tcx> ItemLikeVisitor<'tcx> for CollectPrustiSpecVisitor<'a, 'tcx> {
         }
 
         // Skip associated types and other non-methods items
-        if let hir::ImplItemKind::Fn(..) = impl_item.kind {
-            // continue
+        if let hir::ImplItemKind::Fn(ref sig, _) = impl_item.kind {
+            // The body of an `async fn` is verified through its generator
+            if sig.header.asyncness == hir::IsAsync::Async {
+                return;
+            }
         } else {
             return;
         }
@@ -496,7 +496,7 @@ impl<'a, 'tcx> MirInfoPrinter<'a, 'tcx> {
             // FIXME: this computes the wrong loop invariant permission
             let (write_leaves, mut_borrow_leaves, read_leaves) = self
                 .loops
-                .compute_read_and_write_leaves(bb, self.mir, Some(&definitely_initalised_paths));
+                .compute_read_and_write_leaves(bb, self.mir, self.tcx, Some(&definitely_initalised_paths));
             // Construct the permission forest.
             let forest = PermissionForest::new(
                 self.mir,
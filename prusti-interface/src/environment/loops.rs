@@ -8,6 +8,7 @@ use crate::utils;
 use crate::environment::place_set::PlaceSet;
 use crate::environment::procedure::BasicBlockIndex;
 use rustc_middle::mir;
+use rustc_middle::ty::TyCtxt;
 use rustc_middle::mir::visit::Visitor;
 use rustc_data_structures::graph::dominators::Dominators;
 use std::collections::{HashMap, HashSet};
@@ -80,6 +81,7 @@ pub struct PlaceAccess<'tcx> {
 /// `defined_places` are disjoint
 #[derive(Debug)]
 struct AccessCollector<'b, 'tcx> {
+    pub tcx: TyCtxt<'tcx>,
     /// Loop body.
     pub body: &'b HashSet<BasicBlockIndex>,
     /// The places that are defined before the loop and accessed inside a loop.
@@ -116,9 +118,11 @@ impl<'b, 'tcx> Visitor<'tcx> for AccessCollector<'b, 'tcx> {
                 NonUse(_) => unreachable!(),
                 x => unimplemented!("{:?}", x),
             };
+            // Accesses to array elements are accesses to the whole array.
+            let place = utils::truncate_array_index(self.tcx, *place);
             let access = PlaceAccess {
                 location: location,
-                place: place.clone(),
+                place,
                 kind: access_kind,
             };
             self.accessed_places.push(access);
@@ -454,9 +458,11 @@ impl ProcedureLoops {
         &self,
         loop_head: BasicBlockIndex,
         mir: &'a mir::Body<'tcx>,
+        tcx: TyCtxt<'tcx>,
     ) -> Vec<PlaceAccess<'tcx>> {
         let body = self.loop_bodies.get(&loop_head).unwrap();
         let mut visitor = AccessCollector {
+            tcx,
             body: body,
             accessed_places: Vec::new(),
         };
@@ -470,6 +476,7 @@ impl ProcedureLoops {
         &self,
         loop_head: BasicBlockIndex,
         mir: &'a mir::Body<'tcx>,
+        tcx: TyCtxt<'tcx>,
         definitely_initalised_paths: Option<&PlaceSet>,
     ) -> (
         Vec<mir::Place<'tcx>>,
@@ -497,7 +504,7 @@ impl ProcedureLoops {
         //         bodies without unreachable elements instead of predicates.
 
         // Paths accessed inside the loop body.
-        let accesses = self.compute_used_paths(loop_head, mir);
+        let accesses = self.compute_used_paths(loop_head, mir, tcx);
         debug!("accesses = {:?}", accesses);
         let mut accesses_pairs: Vec<_> = accesses
             .iter()
@@ -0,0 +1,44 @@
+use rustc_index::vec::Idx;
+use rustc_middle::mir;
+
+pub trait AsyncFnParameters<'tcx> {
+    fn get_async_fn_parameters(&self) -> Vec<(mir::Location, mir::Local)>;
+}
+
+impl<'tcx> AsyncFnParameters<'tcx> for mir::Body<'tcx> {
+    /// The body of an `async fn` is a generator that captures the parameters of the function.
+    /// It starts by moving (or copying) each of them out of the generator (`_1`) into a local. Returns the
+    /// location of each such move together with the local that receives the parameter, in the
+    /// order of the parameters.
+    fn get_async_fn_parameters(&self) -> Vec<(mir::Location, mir::Local)> {
+        let generator = mir::Local::new(1);
+        let mut parameters = vec![];
+        let mut block = mir::START_BLOCK;
+        loop {
+            for (index, stmt) in self[block].statements.iter().enumerate() {
+                if let mir::StatementKind::Assign(box (ref lhs, mir::Rvalue::Use(ref operand))) =
+                    stmt.kind
+                {
+                    let rhs = match operand {
+                        mir::Operand::Move(rhs) | mir::Operand::Copy(rhs) => rhs,
+                        mir::Operand::Constant(_) => continue,
+                    };
+                    if let (Some(local), [mir::ProjectionElem::Field(field, _)]) =
+                        (lhs.as_local(), &rhs.projection[..])
+                    {
+                        if rhs.local == generator {
+                            let location = mir::Location { block, statement_index: index };
+                            parameters.push((field.index(), location, local));
+                        }
+                    }
+                }
+            }
+            match self[block].terminator().kind {
+                mir::TerminatorKind::Goto { target } if target > block => block = target,
+                _ => break,
+            }
+        }
+        parameters.sort_by_key(|(field, _, _)| *field);
+        parameters.into_iter().map(|(_, location, local)| (location, local)).collect()
+    }
+}
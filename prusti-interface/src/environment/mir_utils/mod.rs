@@ -8,6 +8,7 @@
 
 mod all_places;
 mod args_for_mir;
+mod async_fn_parameters;
 mod real_edges;
 mod split_aggregate_assignment;
 mod statement_as_assign;
@@ -17,6 +18,7 @@ mod ty_as_ty_ref;
 
 pub use self::all_places::*;
 pub use self::args_for_mir::*;
+pub use self::async_fn_parameters::*;
 pub use self::real_edges::*;
 pub use self::split_aggregate_assignment::*;
 pub use self::statement_as_assign::*;
@@ -82,8 +82,11 @@ fn real_targets(terminator: &mir::Terminator) -> Vec<mir::BasicBlock> {
             ref real_target, ..
         } => vec![*real_target],
 
-        TerminatorKind::Yield { .. }
-        | TerminatorKind::GeneratorDrop
-        | TerminatorKind::InlineAsm { .. } => unimplemented!(),
+        // The drop target of a yield is only reached when the suspended generator is dropped.
+        TerminatorKind::Yield { ref resume, .. } => vec![*resume],
+
+        TerminatorKind::GeneratorDrop => vec![],
+
+        TerminatorKind::InlineAsm { .. } => unimplemented!(),
     }
 }
@@ -10,10 +10,12 @@ use rustc_ast::ast;
 use rustc_hir as hir;
 use rustc_middle::mir;
 use rustc_hir::hir_id::HirId;
+use rustc_hir::def::DefKind;
 use rustc_hir::def_id::{DefId, LocalDefId};
 use rustc_middle::ty::{self, TyCtxt, ParamEnv, WithOptConstParam};
 use std::path::PathBuf;
 use std::cell::Ref;
+use std::ops::Deref;
 use rustc_span::{Span, MultiSpan, symbol::Symbol};
 use std::collections::HashSet;
 use log::debug;
@@ -43,6 +45,24 @@ use crate::data::ProcedureDefId;
 // use utils::get_attr_value;
 use rustc_span::source_map::SourceMap;
 
+/// The MIR body of a procedure or closure, which is either borrowed from the local crate
+/// or loaded from the metadata of a dependency.
+pub enum MirBody<'a, 'tcx> {
+    Local(Ref<'a, mir::Body<'tcx>>),
+    External(&'a mir::Body<'tcx>),
+}
+
+impl<'a, 'tcx> Deref for MirBody<'a, 'tcx> {
+    type Target = mir::Body<'tcx>;
+
+    fn deref(&self) -> &mir::Body<'tcx> {
+        match self {
+            MirBody::Local(body) => &*body,
+            MirBody::External(body) => body,
+        }
+    }
+}
+
 /// Facade to the Rust compiler.
 // #[derive(Copy, Clone)]
 pub struct Environment<'tcx> {
@@ -204,6 +224,33 @@ impl<'tcx> Environment<'tcx> {
         // self.tcx().item_path_str(def_id)
     }
 
+    /// Is the procedure an `async fn`, of this crate or of a dependency?
+    pub fn is_async_fn(&self, def_id: ProcedureDefId) -> bool {
+        if !def_id.is_local() {
+            return matches!(self.tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn)
+                && self.tcx.asyncness(def_id) == hir::IsAsync::Async;
+        }
+        match self.tcx.hir().get_if_local(def_id) {
+            Some(hir::Node::Item(hir::Item { kind: hir::ItemKind::Fn(sig, ..), .. }))
+            | Some(hir::Node::ImplItem(hir::ImplItem { kind: hir::ImplItemKind::Fn(sig, _), .. })) => {
+                sig.header.asyncness == hir::IsAsync::Async
+            }
+            _ => false,
+        }
+    }
+
+    /// If the procedure is the generator that implements the body of an `async fn`, get the
+    /// `async fn`.
+    pub fn get_async_fn_of_body(&self, def_id: ProcedureDefId) -> Option<ProcedureDefId> {
+        if !self.tcx.is_closure(def_id) {
+            return None;
+        }
+        match self.tcx.generator_kind(def_id) {
+            Some(hir::GeneratorKind::Async(hir::AsyncGeneratorKind::Fn)) => self.tcx.parent(def_id),
+            _ => None,
+        }
+    }
+
     /// Get a Procedure.
     pub fn get_procedure<'a>(&'a self, proc_def_id: ProcedureDefId) -> Procedure<'a, 'tcx> {
         Procedure::new(self.tcx(), proc_def_id)
@@ -221,6 +268,16 @@ impl<'tcx> Environment<'tcx> {
         self.tcx().optimized_mir_or_const_arg_mir(WithOptConstParam::unknown(def_id))
     }
 
+    /// Get the MIR body of a procedure or closure, which may be defined in another crate.
+    /// This is used for specifications, which can be imported from dependencies.
+    pub fn mir<'a>(&self, def_id: DefId) -> MirBody<'a, 'tcx> {
+        if let Some(local_def_id) = def_id.as_local() {
+            MirBody::Local(self.local_mir(local_def_id))
+        } else {
+            MirBody::External(self.external_mir(def_id))
+        }
+    }
+
     /// Get all relevant trait declarations for some type.
     pub fn get_traits_decls_for_type(&self, ty: &ty::Ty<'tcx>) -> HashSet<DefId> {
         let mut res = HashSet::new();
@@ -261,10 +261,7 @@ pub enum PoloniusInfoError {
         loop_head: mir::BasicBlock,
         variable: mir::Local,
     },
-    LoansInNestedLoops(mir::Location, mir::BasicBlock, mir::Location, mir::BasicBlock),
     ReborrowingDagHasNoMagicWands(mir::Location),
-    /// We currently support only one reborrowing chain per loop
-    MultipleMagicWandsPerLoop(mir::Location),
     MagicWandHasNoRepresentativeLoan(mir::Location),
     PlaceRegionsError(PlaceRegionsError, mir::Location),
     LoanInUnsupportedStatement(String, mir::Location),
@@ -593,6 +590,14 @@ fn get_borrowed_places<'a, 'tcx: 'a>(
                         })
                         .collect())
                 }
+                &mir::Rvalue::Cast(
+                    mir::CastKind::Pointer(ty::adjustment::PointerCast::Unsize),
+                    mir::Operand::Copy(ref place) | mir::Operand::Move(ref place),
+                    target_ty,
+                ) if is_slice_ref(target_ty) || is_trait_object_ref(target_ty) => {
+                    // Unsizing a reference to an array or creating a trait object is a reborrow.
+                    Ok(vec![place])
+                }
                 &mir::Rvalue::Cast(..) => {
                     Err(PoloniusInfoError::LoanInUnsupportedStatement(
                         "cast statements that create loans are not supported".to_string(),
@@ -606,6 +611,20 @@ fn get_borrowed_places<'a, 'tcx: 'a>(
     }
 }
 
+fn is_slice_ref(ty: ty::Ty) -> bool {
+    match ty.kind() {
+        ty::TyKind::Ref(_, ty, _) => matches!(ty.kind(), ty::TyKind::Slice(..)),
+        _ => false,
+    }
+}
+
+fn is_trait_object_ref(ty: ty::Ty) -> bool {
+    match ty.kind() {
+        ty::TyKind::Ref(_, ty, _) => matches!(ty.kind(), ty::TyKind::Dynamic(..)),
+        _ => false,
+    }
+}
+
 fn compute_loan_conflict_sets(
     procedure: &Procedure,
     loan_position: &HashMap<facts::Loan, mir::Location>,
@@ -664,7 +683,7 @@ fn compute_loan_conflict_sets(
 impl<'a, 'tcx: 'a> PoloniusInfo<'a, 'tcx> {
     pub fn new(
         procedure: &'a Procedure<'a, 'tcx>,
-        loop_invariant_block: &HashMap<mir::BasicBlock, mir::BasicBlock>,
+        _loop_invariant_block: &HashMap<mir::BasicBlock, mir::BasicBlock>,
     ) -> Result<Self, PoloniusInfoError> {
         let tcx = procedure.get_tcx();
         let def_id = procedure.get_id();
@@ -773,7 +792,7 @@ impl<'a, 'tcx: 'a> PoloniusInfo<'a, 'tcx> {
             liveness: liveness,
             loan_conflict_sets: loan_conflict_sets,
         };
-        // info.compute_loop_magic_wands(loop_invariant_block)?; FIXME
+        info.compute_loop_magic_wands()?;
         Ok(info)
     }
 
@@ -810,51 +829,128 @@ impl<'a, 'tcx: 'a> PoloniusInfo<'a, 'tcx> {
         Ok(())
     }
 
-    fn compute_loop_magic_wands(
+    /// Compute the magic wands of the references that are reassigned in loops. For each
+    /// loop, these are the `&mut` references stored in local variables that are write
+    /// leaves of the loop, such as `cur` in `cur = &mut cur.next;`.
+    fn compute_loop_magic_wands(&mut self) -> Result<(), PoloniusInfoError> {
+        trace!("[enter] compute_loop_magic_wands");
+        let mut loop_heads: Vec<_> = self.loops.loop_heads.iter().cloned().collect();
+        loop_heads.sort();
+        for loop_head in loop_heads {
+            debug!("loop_head = {:?}", loop_head);
+            let definitely_initalised_paths = self.initialization.get_before_block(loop_head);
+            let (write_leaves, _mut_borrow_leaves, _read_leaves) =
+                self.loops.compute_read_and_write_leaves(
+                    loop_head,
+                    self.mir,
+                    self.tcx,
+                    Some(definitely_initalised_paths),
+                );
+            debug!("write_leaves = {:?}", write_leaves);
+            let reborrows: Vec<(mir::Local, facts::Region)> = write_leaves
+                .iter()
+                // Only locals – we do not support references in fields.
+                .flat_map(|place| place.as_local())
+                // Only mutable references (shared reborrows do not need to be restored).
+                .filter(|&local| matches!(
+                    self.mir.local_decls[local].ty.kind(),
+                    ty::TyKind::Ref(_, _, mir::Mutability::Mut)
+                ))
+                .flat_map(|local| {
+                    self.place_regions
+                        .for_local(local)
+                        .map(|region| (local, region))
+                })
+                .collect();
+            debug!("reborrows = {:?}", reborrows);
+            for (local, region) in reborrows {
+                self.add_loop_magic_wand(loop_head, local, region)?;
+            }
+        }
+        trace!("[exit] compute_loop_magic_wands");
+        Ok(())
+    }
+
+    fn add_loop_magic_wand(
         &mut self,
-        _loop_invariant_block: &HashMap<mir::BasicBlock, mir::BasicBlock>,
+        loop_head: mir::BasicBlock,
+        variable: mir::Local,
+        region: facts::Region,
     ) -> Result<(), PoloniusInfoError> {
-        // trace!("[enter] compute_loop_magic_wands");
-        // let loop_heads = self.loops.loop_heads.clone();
-        // for &loop_head in loop_heads.iter() {
-        //     debug!("loop_head = {:?}", loop_head);
-        //     // TODO: Check whether we should use mut_borrow_leaves instead of write_leaves.
-        //     let definitely_initalised_paths = self.initialization.get_before_block(loop_head);
-        //     let (write_leaves, _mut_borrow_leaves, _read_leaves) =
-        //         self.loops.compute_read_and_write_leaves(
-        //             loop_head,
-        //             self.mir,
-        //             Some(&definitely_initalised_paths),
-        //         );
-        //     debug!("write_leaves = {:?}", write_leaves);
-        //     let reborrows: Vec<(mir::Local, facts::Region)> = write_leaves
-        //         .iter()
-        //         .flat_map(|place| {
-        //             // Only locals – we do not support references in fields.
-        //             match place {
-        //                 mir::Place::Local(local) => Some(local),
-        //                 _ => None,
-        //             }
-        //         })
-        //         .flat_map(|local| {
-        //             // Only references (variables that have regions).
-        //             self.place_regions
-        //                 .for_local(*local)
-        //                 .map(|region| (*local, region))
-        //         })
-        //         // Note: With our restrictions these two checks are sufficient to ensure
-        //         // that we have reborrowing. For example, we do not need to check that
-        //         // at least one of the loans is coming from inside of the loop body.
-        //         .collect();
-        //     debug!("reborrows = {:?}", reborrows);
-        //     for &(local, _) in reborrows.iter() {
-        //         debug!("loop_head = {:?} reborrow={:?}", loop_head, local);
-        //         self.add_loop_magic_wand(loop_head, local)?;
-        //     }
-        // }
-        // trace!("[exit] compute_loop_magic_wands");
-        // Ok(())
-        unimplemented!();
+        // A reference that is reassigned without reborrowing from itself, such as
+        // `x = &mut y;`, does not need a magic wand.
+        if let Some(root_loan) = self.compute_root_loan(loop_head, variable, region)? {
+            let magic_wand = LoopMagicWand {
+                loop_id: loop_head,
+                variable,
+                region,
+                root_loan,
+            };
+            debug!("magic_wand = {:?}", magic_wand);
+            self.loop_magic_wands
+                .entry(loop_head)
+                .or_insert_with(Vec::new)
+                .push(magic_wand);
+        }
+        Ok(())
+    }
+
+    /// Find the root loan for a specific magic wand: the loan created in the loop body by
+    /// the last assignment to `variable` before reaching the loop head again.
+    fn compute_root_loan(
+        &self,
+        loop_head: mir::BasicBlock,
+        variable: mir::Local,
+        region: facts::Region,
+    ) -> Result<Option<facts::Loan>, PoloniusInfoError> {
+        let liveness = self.liveness.get_before_block(loop_head);
+        let loop_loans = self.compute_loop_loans(loop_head, region);
+        let mut root_loans = Vec::new();
+        for assignment in liveness.iter() {
+            if assignment.target == variable {
+                for loan in loop_loans.iter() {
+                    debug!("loan: {:?} position: {:?}", loan, self.loan_position[loan]);
+                    if assignment.location == self.loan_position[loan] {
+                        root_loans.push(*loan);
+                    }
+                }
+            }
+        }
+        match root_loans.len() {
+            0 => Ok(None),
+            1 => Ok(Some(root_loans[0])),
+            _ => Err(PoloniusInfoError::UnsupportedLoanInLoop {
+                loop_head,
+                variable,
+            }),
+        }
+    }
+
+    /// Find loans created in the loop that are kept alive by the given region at the loop head.
+    fn compute_loop_loans(
+        &self,
+        loop_head: mir::BasicBlock,
+        region: facts::Region,
+    ) -> Vec<facts::Loan> {
+        let point = self.get_point(loop_head.start_location(), facts::PointType::Start);
+        let (all_loans, _) = self.get_all_loans_kept_alive_by(point, region);
+        let loop_body = self.loops.get_loop_body(loop_head);
+        all_loans
+            .into_iter()
+            .filter(|loan| {
+                self.loan_position
+                    .get(loan)
+                    .map_or(false, |location| loop_body.contains(&location.block))
+            })
+            .collect()
+    }
+
+    /// Get the magic wands of the references that are reassigned in the given loop.
+    pub fn get_loop_magic_wands(&self, loop_head: mir::BasicBlock) -> &[LoopMagicWand] {
+        self.loop_magic_wands
+            .get(&loop_head)
+            .map(|magic_wands| magic_wands.as_slice())
+            .unwrap_or(&[])
     }
 
     pub fn get_point(
@@ -1353,12 +1449,18 @@ impl<'a, 'tcx: 'a> PoloniusInfo<'a, 'tcx> {
         )
     }
 
-    /// Get loops in which loans are defined (if any).
-    pub fn get_loan_loops(
+    /// Get the loans that may flow from one iteration of a loop to the next one, together with
+    /// the head of that loop, as seen from `location`. Only the loops that contain the loan
+    /// but not `location` are considered, because `location` is still inside the others. Of
+    /// these, the outermost loop that carries the loan is chosen: its magic wand covers the
+    /// reborrows done in all the nested loops.
+    pub fn get_loop_carried_loans(
         &self,
-        loans: &[facts::Loan]
-    ) -> Result<Vec<(facts::Loan, mir::BasicBlock)>, PoloniusInfoError> {
-        let pairs: Vec<_> = loans
+        loans: &[facts::Loan],
+        location: mir::Location,
+    ) -> Vec<(facts::Loan, mir::BasicBlock)> {
+        let enclosing_loop_heads = self.loops.get_enclosing_loop_heads(location.block);
+        loans
             .iter()
             .flat_map(|loan| {
                 let loan_location = if let Some(location) = self.loan_position.get(loan) {
@@ -1369,25 +1471,21 @@ impl<'a, 'tcx: 'a> PoloniusInfo<'a, 'tcx> {
                     return None;
                 };
                 self.loops
-                    .get_loop_head(loan_location.block)
-                    .map(|loop_head| (*loan, loop_head))
+                    .get_enclosing_loop_heads(loan_location.block)
+                    .iter()
+                    .filter(|loop_head| !enclosing_loop_heads.contains(loop_head))
+                    .find(|&&loop_head| self.is_loan_live_at_loop_head(*loan, loop_head))
+                    .map(|&loop_head| (*loan, loop_head))
             })
-            .collect();
-        for (loan1, loop1) in pairs.iter() {
-            let location1 = self.loan_position[loan1];
-            for (loan2, loop2) in pairs.iter() {
-                let location2 = self.loan_position[loan2];
-                if loop1 != loop2 {
-                    return Err(PoloniusInfoError::LoansInNestedLoops(
-                        location1,
-                        *loop1,
-                        location2,
-                        *loop2
-                    ));
-                }
-            }
-        }
-        Ok(pairs)
+            .collect()
+    }
+
+    fn is_loan_live_at_loop_head(&self, loan: facts::Loan, loop_head: mir::BasicBlock) -> bool {
+        let point = self.get_point(loop_head.start_location(), facts::PointType::Start);
+        self.borrowck_out_facts
+            .borrow_live_at
+            .get(&point)
+            .map_or(false, |loans| loans.contains(&loan))
     }
 
     /// ``loans`` – all loans, including the zombie loans.
@@ -1413,45 +1511,56 @@ impl<'a, 'tcx: 'a> PoloniusInfo<'a, 'tcx> {
             )
         }).cloned().collect();
 
-        // The representative_loan is a loan that is the root of the
-        // reborrowing in some loop. Since it has no proper
-        // reborrows_direct relation (because of the cycles), it needs
-        // manual treatment in the visit function.
-        let mut representative_loan = None;
-        if let Some(loop_head) = self.loops.get_loop_head(location.block) {
-            let depth = self.loops.get_loop_head_depth(loop_head);
-            debug!("loop_head: {:?} depth: {:?}", loop_head, depth);
-            // It is fine to have loans defined in an outer loop that is not `loop_head`, because
-            // `return` or panic statements might need to jump out of many loops at once.
-        } else {
-            let loan_loops = self.get_loan_loops(&loans)?;
-            if !loan_loops.is_empty() {
-                for (loan, loop_head) in loan_loops.iter() {
-                    debug!("loan={:?} loop_head={:?}", loan, loop_head);
-                }
-                let (_, loop_head) = loan_loops[0];
+        // The representative loans are the roots of the reborrowing in some
+        // loop. Since they have no proper reborrows_direct relation (because
+        // of the cycles), they need manual treatment in the visit function.
+        //
+        // Only the loans that flow between the iterations of a loop that does
+        // not contain `location` need a loop magic wand. A loan that does not
+        // flow between iterations can only be alive after the loop if it was
+        // created in the last iteration, so it is treated like any other loan.
+        // It is fine to have loans carried by a loop that contains `location`,
+        // because `return` or panic statements might need to jump out of many
+        // loops at once.
+        let loan_loops = self.get_loop_carried_loans(&loans, location);
+        let mut representative_loans = Vec::new();
+        let mut representative_magic_wands = Vec::new();
+        if !loan_loops.is_empty() {
+            for (loan, loop_head) in loan_loops.iter() {
+                debug!("loan={:?} loop_head={:?}", loan, loop_head);
+            }
+            if self.loop_magic_wands.is_empty() {
+                return Err(PoloniusInfoError::ReborrowingDagHasNoMagicWands(location));
+            }
+            let mut loop_heads: Vec<_> = loan_loops.iter().map(|(_, loop_head)| *loop_head).collect();
+            loop_heads.sort();
+            loop_heads.dedup();
+            for loop_head in loop_heads {
                 debug!("loop_head = {:?}", loop_head);
-                if self.loop_magic_wands.is_empty() {
-                    return Err(PoloniusInfoError::ReborrowingDagHasNoMagicWands(location));
-                }
-                let loop_magic_wands = &self.loop_magic_wands[&loop_head];
-                if loop_magic_wands.len() != 1 {
-                    return Err(PoloniusInfoError::MultipleMagicWandsPerLoop(location));
-                }
-                let magic_wand = &loop_magic_wands[0];
-                representative_loan = Some(magic_wand.root_loan);
-                if representative_loan.is_none() {
+                // A loop may reborrow several references, each with its own magic wand. We
+                // need the magic wands of the reborrowing chains that are expiring here.
+                let magic_wands: Vec<_> = self
+                    .get_loop_magic_wands(loop_head)
+                    .iter()
+                    .filter(|magic_wand| loans.contains(&magic_wand.root_loan))
+                    .cloned()
+                    .collect();
+                if magic_wands.is_empty() {
                     return Err(PoloniusInfoError::MagicWandHasNoRepresentativeLoan(location));
                 }
-                loans = loans
-                    .into_iter()
-                    .filter(|loan| {
-                        !loan_loops.iter().any(|(loop_loan, _)| {
-                            loop_loan == loan && Some(*loan) != representative_loan
-                        })
-                    })
-                    .collect();
+                representative_loans.extend(
+                    magic_wands.iter().map(|magic_wand| magic_wand.root_loan)
+                );
+                representative_magic_wands.extend(magic_wands);
             }
+            loans = loans
+                .into_iter()
+                .filter(|loan| {
+                    !loan_loops.iter().any(|(loop_loan, _)| {
+                        loop_loan == loan && !representative_loans.contains(loan)
+                    })
+                })
+                .collect();
         }
 
         // Topologically sort loans.
@@ -1460,7 +1569,7 @@ impl<'a, 'tcx: 'a> PoloniusInfo<'a, 'tcx> {
         let mut temporary_mark = vec![false; loans.len()];
         fn visit(
             this: &PoloniusInfo,
-            representative_loan: &Option<facts::Loan>,
+            representative_loans: &[facts::Loan],
             reborrows_direct: &Vec<(facts::Loan, facts::Loan)>,
             loans: &[facts::Loan],
             current: usize,
@@ -1473,8 +1582,8 @@ impl<'a, 'tcx: 'a> PoloniusInfo<'a, 'tcx> {
             }
             assert!(
                 !temporary_mark[current],
-                "Not a DAG!\nrepresentative_loan: {:?}\nreborrows_direct: {:?}\nloans: {:?}\ncurrent: {:?}\nsorted_loans: {:?}\npermanent_mark: {:?}\ntemporary_mark: {:?}\nloan_location: {:?}",
-                representative_loan,
+                "Not a DAG!\nrepresentative_loans: {:?}\nreborrows_direct: {:?}\nloans: {:?}\ncurrent: {:?}\nsorted_loans: {:?}\npermanent_mark: {:?}\ntemporary_mark: {:?}\nloan_location: {:?}",
+                representative_loans,
                 reborrows_direct,
                 loans,
                 current,
@@ -1485,7 +1594,7 @@ impl<'a, 'tcx: 'a> PoloniusInfo<'a, 'tcx> {
             );
             temporary_mark[current] = true;
             let current_loan = loans[current];
-            if Some(current_loan) == *representative_loan {
+            if representative_loans.contains(&current_loan) {
                 for (new_current, &loan) in loans.iter().enumerate() {
                     if loan == current_loan {
                         // The reborrows relation is reflexive, so we need this check.
@@ -1498,7 +1607,7 @@ impl<'a, 'tcx: 'a> PoloniusInfo<'a, 'tcx> {
                     {
                         visit(
                             this,
-                            representative_loan,
+                            representative_loans,
                             reborrows_direct,
                             loans,
                             new_current,
@@ -1510,7 +1619,7 @@ impl<'a, 'tcx: 'a> PoloniusInfo<'a, 'tcx> {
                 }
             } else {
                 for (new_current, &loan) in loans.iter().enumerate() {
-                    if Some(loan) == *representative_loan {
+                    if representative_loans.contains(&loan) {
                         if this
                             .additional_facts
                             .reborrows
@@ -1518,7 +1627,7 @@ impl<'a, 'tcx: 'a> PoloniusInfo<'a, 'tcx> {
                         {
                             visit(
                                 this,
-                                representative_loan,
+                                representative_loans,
                                 reborrows_direct,
                                 loans,
                                 new_current,
@@ -1531,7 +1640,7 @@ impl<'a, 'tcx: 'a> PoloniusInfo<'a, 'tcx> {
                         if reborrows_direct.contains(&(current_loan, loan)) {
                             visit(
                                 this,
-                                representative_loan,
+                                representative_loans,
                                 reborrows_direct,
                                 loans,
                                 new_current,
@@ -1554,7 +1663,7 @@ impl<'a, 'tcx: 'a> PoloniusInfo<'a, 'tcx> {
             };
             visit(
                 self,
-                &representative_loan,
+                &representative_loans,
                 reborrows_direct,
                 &loans,
                 index,
@@ -1572,7 +1681,7 @@ impl<'a, 'tcx: 'a> PoloniusInfo<'a, 'tcx> {
                 let reborrowed_loans = sorted_loans.iter().cloned()
                     .filter(|&l| self.additional_facts.reborrows_direct.contains(&(loan, l)))
                     .collect::<Vec<_>>();
-                let kind = self.construct_reborrowing_kind(loan, representative_loan);
+                let kind = self.construct_reborrowing_kind(loan, &representative_magic_wands);
                 let zombity = self.construct_reborrowing_zombity(
                     loan, &loans, zombie_loans, location);
                 let incoming_zombies = self.check_incoming_zombies(
@@ -1588,7 +1697,7 @@ impl<'a, 'tcx: 'a> PoloniusInfo<'a, 'tcx> {
     fn construct_reborrowing_kind(
         &self,
         loan: facts::Loan,
-        representative_loan: Option<facts::Loan>,
+        representative_magic_wands: &[LoopMagicWand],
     ) -> ReborrowingKind {
         if let Some(local) = self.call_magic_wands.get(&loan) {
             let region = self.place_regions.for_local(*local).unwrap();
@@ -1599,17 +1708,13 @@ impl<'a, 'tcx: 'a> PoloniusInfo<'a, 'tcx> {
             }
         } else if self.argument_moves.contains(&loan) {
             ReborrowingKind::ArgumentMove { loan: loan }
-        } else if Some(loan) == representative_loan {
-            for magic_wands in self.loop_magic_wands.values() {
-                for magic_wand in magic_wands.iter() {
-                    if magic_wand.root_loan == loan {
-                        return ReborrowingKind::Loop {
-                            magic_wand: (*magic_wand).clone(),
-                        };
-                    }
-                }
+        } else if let Some(magic_wand) = representative_magic_wands
+            .iter()
+            .find(|magic_wand| magic_wand.root_loan == loan)
+        {
+            ReborrowingKind::Loop {
+                magic_wand: magic_wand.clone(),
             }
-            unreachable!("Bug");
         } else {
             ReborrowingKind::Assignment { loan: loan }
         }
@@ -1763,75 +1868,6 @@ impl<'a, 'tcx: 'a> PoloniusInfo<'a, 'tcx> {
 //             branching: branching,
 //             zombity: self.construct_reborrowing_zombity(node, &loans, zombie_loans, location),
 //         }
-//     }
-
-//     fn add_loop_magic_wand(&mut self, loop_head: mir::BasicBlock, local: mir::Local) -> Result<(), PoloniusInfoError> {
-//         let region = self.variable_regions[&local];
-//         let magic_wand = LoopMagicWand {
-//             loop_id: loop_head,
-//             variable: local,
-//             region: region,
-//             root_loan: self.compute_root_loan(loop_head, local)?,
-//         };
-//         let entry = self.loop_magic_wands.entry(loop_head).or_insert(Vec::new());
-//         entry.push(magic_wand);
-//         Ok(())
-//     }
-
-//     /// Find the root loan for a specific magic wand.
-//     fn compute_root_loan(
-//         &mut self, loop_head: mir::BasicBlock, variable: mir::Local
-//     ) -> Result<facts::Loan, PoloniusInfoError> {
-//         let liveness = self.liveness.get_before_block(loop_head);
-//         let mut root_loans = Vec::new();
-//         let loop_loans = self.compute_loop_loans(loop_head, variable);
-//         for assignment in liveness.iter() {
-//             if assignment.target == variable {
-//                 for loan in loop_loans.iter() {
-//                     debug!("loan: {:?} position: {:?}", loan, self.loan_position[loan]);
-//                     if assignment.location == self.loan_position[loan] {
-//                         root_loans.push(*loan);
-//                     }
-//                 }
-//             }
-//         }
-//         if root_loans.len() != 1 {
-//             Err(PoloniusInfoError::UnsupportedLoanInLoop {
-//                 loop_head,
-//                 variable
-//             })
-//         } else {
-//             Ok(root_loans[0])
-//         }
-//     }
-
-//     /// Find loans created in the loop that are kept alive by the given variable.
-//     fn compute_loop_loans(
-//         &self,
-//         loop_head: mir::BasicBlock,
-//         variable: mir::Local,
-//     ) -> Vec<facts::Loan> {
-//         let location = mir::Location {
-//             block: loop_head,
-//             statement_index: 0,
-//         };
-//         let point = facts::Point {
-//             location: location,
-//             typ: facts::PointType::Start,
-//         };
-//         let point_index = self.interner.get_point_index(&point);
-//         let region = self.variable_regions[&variable];
-//         let (all_loans, _) = self.get_all_loans_kept_alive_by(point_index, region);
-//         let loop_loans: Vec<_> = all_loans
-//             .iter()
-//             .filter(|loan| {
-//                 let location = self.loan_position[loan];
-//                 let loop_body = &self.loops.loop_bodies[&loop_head];
-//                 loop_body.contains(&location.block)
-//             })
-//             .cloned()
-//             .collect();
-//         loop_loans
 //     }
 
     fn get_successors(&self, location: mir::Location) -> Vec<mir::Location> {
@@ -32,6 +32,8 @@ pub struct Procedure<'a, 'tcx: 'a> {
     loop_info: loops::ProcedureLoops,
     reachable_basic_blocks: HashSet<BasicBlock>,
     nonspec_basic_blocks: HashSet<BasicBlock>,
+    loop_invariant_basic_blocks: HashSet<BasicBlock>,
+    ghost_spans: Vec<Span>,
 }
 
 impl<'a, 'tcx> Procedure<'a, 'tcx> {
@@ -43,8 +45,10 @@ impl<'a, 'tcx> Procedure<'a, 'tcx> {
         let mir = mir.borrow();
         let real_edges = RealEdges::new(&mir);
         let reachable_basic_blocks = build_reachable_basic_blocks(&mir, &real_edges);
-        let nonspec_basic_blocks = build_nonspec_basic_blocks(&mir, &real_edges, &tcx);
+        let (nonspec_basic_blocks, loop_invariant_basic_blocks) =
+            build_nonspec_basic_blocks(&mir, &real_edges, &tcx);
         let loop_info = loops::ProcedureLoops::new(&mir, &real_edges);
+        let ghost_spans = build_ghost_spans(&mir, &tcx);
 
         Self {
             tcx,
@@ -54,6 +58,8 @@ impl<'a, 'tcx> Procedure<'a, 'tcx> {
             loop_info,
             reachable_basic_blocks,
             nonspec_basic_blocks,
+            loop_invariant_basic_blocks,
+            ghost_spans,
         }
     }
 
@@ -146,6 +152,23 @@ impl<'a, 'tcx> Procedure<'a, 'tcx> {
         !self.nonspec_basic_blocks.contains(&bbi)
     }
 
+    /// Check whether the block is used for typechecking a loop invariant, as
+    /// opposed to other specifications such as `prusti_assert!`
+    pub fn is_loop_invariant_block(&self, bbi: BasicBlockIndex) -> bool {
+        self.loop_invariant_basic_blocks.contains(&bbi)
+    }
+
+    /// Check whether the code at `span` is part of a `ghost!` block
+    pub fn is_ghost_span(&self, span: Span) -> bool {
+        self.ghost_spans.iter().any(|ghost_span| ghost_span.contains(span))
+    }
+
+    /// Check whether the code at `span` was generated for a `ghost!` block, rather than
+    /// written in it
+    pub fn is_ghost_marker_span(&self, span: Span) -> bool {
+        self.ghost_spans.contains(&span)
+    }
+
     /// Check whether the block is reachable
     pub fn is_reachable_block(&self, bbi: BasicBlockIndex) -> bool {
         self.reachable_basic_blocks.contains(&bbi)
@@ -183,6 +206,10 @@ impl<'a, 'tcx> Procedure<'a, 'tcx> {
     pub fn successors(&self, bbi: BasicBlockIndex) -> &[BasicBlockIndex] {
         self.real_edges.successors(bbi)
     }
+
+    pub fn predecessors(&self, bbi: BasicBlockIndex) -> &[BasicBlockIndex] {
+        self.real_edges.predecessors(bbi)
+    }
 }
 
 /// Returns the set of basic blocks that are not used as part of the typechecking of Prusti specifications
@@ -216,7 +243,40 @@ fn is_spec_closure(def_id: def_id::DefId, tcx: &TyCtxt) -> bool {
     crate::utils::has_spec_only_attr(tcx.get_attrs(def_id))
 }
 
-fn is_spec_basic_block(bb_data: &BasicBlockData, tcx: &TyCtxt) -> bool {
+/// Returns the spans of the `ghost!` blocks of the procedure. Each block is marked by
+/// specification closures with the `prusti::ghost_block` attribute, whose span covers the
+/// entire block.
+fn build_ghost_spans(mir: &Mir, tcx: &TyCtxt) -> Vec<Span> {
+    let mut ghost_spans = vec![];
+    for bb_data in mir.basic_blocks() {
+        for stmt in &bb_data.statements {
+            if let StatementKind::Assign(box (_, rvalue)) = &stmt.kind {
+                if let Rvalue::Aggregate(box AggregateKind::Closure(def_id, _), _) = rvalue {
+                    if crate::utils::has_prusti_attr(tcx.get_attrs(*def_id), "ghost_block")
+                        && !ghost_spans.contains(&stmt.source_info.span)
+                    {
+                        ghost_spans.push(stmt.source_info.span);
+                    }
+                }
+            }
+        }
+    }
+    ghost_spans
+}
+
+/// Loop variants are placed together with the loop invariants, so they are handled in the
+/// same way.
+fn is_loop_invariant_closure(def_id: def_id::DefId, tcx: &TyCtxt) -> bool {
+    let attrs = tcx.get_attrs(def_id);
+    crate::utils::has_prusti_attr(attrs, "loop_body_invariant_spec")
+        || crate::utils::has_prusti_attr(attrs, "loop_body_variant_spec")
+}
+
+fn is_spec_basic_block(
+    bb_data: &BasicBlockData,
+    tcx: &TyCtxt,
+    is_spec_closure: fn(def_id::DefId, &TyCtxt) -> bool,
+) -> bool {
     for stmt in &bb_data.statements {
         if let StatementKind::Assign(box (_, rvalue)) = &stmt.kind {
             if let Rvalue::Aggregate(box aggr, _) = rvalue {
@@ -257,22 +317,46 @@ fn blocks_definitely_leading_to<'a>(bb_graph: &HashMap<BasicBlock, BasicBlockNod
     blocks
 }
 
-fn get_nonspec_basic_blocks(bb_graph: HashMap<BasicBlock, BasicBlockNode>, mir: &Mir, tcx: &TyCtxt) -> HashSet<BasicBlock>{
+fn get_spec_basic_blocks(
+    bb_graph: &HashMap<BasicBlock, BasicBlockNode>,
+    mir: &Mir,
+    tcx: &TyCtxt,
+    is_spec_closure: fn(def_id::DefId, &TyCtxt) -> bool,
+) -> HashSet<BasicBlock> {
     let mut spec_basic_blocks: HashSet<BasicBlock> = HashSet::new();
     for (bb, _) in bb_graph.iter() {
-        if is_spec_basic_block(&mir[*bb], &tcx) {
+        if is_spec_basic_block(&mir[*bb], &tcx, is_spec_closure) {
             spec_basic_blocks.insert(*bb);
             spec_basic_blocks.extend(blocks_definitely_leading_to(&bb_graph, *bb).into_iter());
         }
     }
+    spec_basic_blocks
+}
+
+fn get_nonspec_basic_blocks(
+    bb_graph: HashMap<BasicBlock, BasicBlockNode>,
+    mir: &Mir,
+    tcx: &TyCtxt,
+) -> (HashSet<BasicBlock>, HashSet<BasicBlock>) {
+    let spec_basic_blocks = get_spec_basic_blocks(&bb_graph, mir, tcx, is_spec_closure);
     debug!("spec basic blocks: {:#?}", spec_basic_blocks);
+    let loop_invariant_basic_blocks =
+        get_spec_basic_blocks(&bb_graph, mir, tcx, is_loop_invariant_closure);
+    debug!("loop invariant basic blocks: {:#?}", loop_invariant_basic_blocks);
 
     let all_basic_blocks: HashSet<BasicBlock> = bb_graph.keys().cloned().collect();
-    all_basic_blocks.difference(&spec_basic_blocks).cloned().collect()
+    let nonspec_basic_blocks = all_basic_blocks.difference(&spec_basic_blocks).cloned().collect();
+    (nonspec_basic_blocks, loop_invariant_basic_blocks)
 }
 
-/// Returns the set of basic blocks that are not used as part of the typechecking of Prusti specifications
-fn build_nonspec_basic_blocks(mir: &Mir, real_edges: &RealEdges, tcx: &TyCtxt) -> HashSet<BasicBlock> {
+/// Returns the set of basic blocks that are not used as part of the typechecking of Prusti
+/// specifications, together with the subset of specification blocks that typecheck loop
+/// invariants
+fn build_nonspec_basic_blocks(
+    mir: &Mir,
+    real_edges: &RealEdges,
+    tcx: &TyCtxt,
+) -> (HashSet<BasicBlock>, HashSet<BasicBlock>) {
     let dominators = mir.dominators();
     let mut loop_heads: HashSet<BasicBlock> = HashSet::new();
 
@@ -0,0 +1,182 @@
+//! Specifications shared between crates.
+//!
+//! When a library is compiled with Prusti, the specifications of its public procedures are
+//! written to a `.specs` file next to the metadata of the library. When a crate is verified, the
+//! `.specs` files of its dependencies are loaded, so that calls to their procedures use the
+//! contracts declared in the dependencies. The specifications are only exported once the
+//! library verified successfully, and they are ignored if the library was recompiled since.
+//!
+//! The expressions of an assertion are identified by the closures that type-check them. These
+//! are items of the library, so they are stored by the index of their `DefId` and their MIR is
+//! read from the metadata of the library, which Prusti always encodes.
+
+use super::{build_procedure_spec, typed, ProcedureSpecRef, SpecCollector};
+use prusti_specs::specifications::common::{SpecIdRef, SpecificationId};
+use prusti_specs::specifications::json::Assertion as JsonAssertion;
+use rustc_hir::def_id::{CrateNum, DefId, DefIndex, LOCAL_CRATE};
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_session::config::CrateType;
+use rustc_session::output::filename_for_metadata;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use log::debug;
+use typed::StructuralToTyped;
+
+/// The specifications exported by a library.
+#[derive(Serialize, Deserialize)]
+struct CrateSpecs {
+    /// The hash of the crate that the specifications were exported from, since the indices
+    /// below are only meaningful for this version of the crate.
+    crate_hash: u64,
+    /// The specifications of the public procedures, keyed by the index of their `DefId`.
+    procedures: Vec<(u32, ProcedureSpecRef)>,
+    /// The assertions used by the procedures, in the format of the `prusti::assertion`
+    /// attribute.
+    assertions: Vec<(SpecificationId, String)>,
+    /// The index of the `DefId` of the closure of each expression, keyed by
+    /// `<spec_id>_<expr_id>`.
+    expressions: HashMap<String, u32>,
+}
+
+/// The path of the file containing the specifications of the crate whose metadata is at
+/// `metadata_path`.
+fn specs_path(metadata_path: &Path) -> PathBuf {
+    metadata_path.with_extension("specs")
+}
+
+/// The specifications of a library that are written next to its metadata after it was
+/// verified.
+pub struct ExportedSpecs {
+    crate_specs: CrateSpecs,
+}
+
+impl ExportedSpecs {
+    pub fn write(&self, tcx: TyCtxt) {
+        let crate_name = tcx.crate_name(LOCAL_CRATE);
+        let outputs = tcx.output_filenames(LOCAL_CRATE);
+        let path = specs_path(&filename_for_metadata(tcx.sess, &crate_name.as_str(), &outputs));
+        debug!("Exporting the specifications of {} to {}", crate_name, path.display());
+        if let Err(err) = fs::write(&path, serde_json::to_string(&self.crate_specs).unwrap()) {
+            tcx.sess.warn(&format!(
+                "cannot export the specifications of crate '{}' to {}: {}",
+                crate_name,
+                path.display(),
+                err,
+            ));
+        }
+    }
+}
+
+impl<'tcx> SpecCollector<'tcx> {
+    /// Collect the specifications of the public procedures of the crate, if the crate is a
+    /// library.
+    pub fn exported_specs(&self) -> Option<ExportedSpecs> {
+        let tcx = self.tcx;
+        if !tcx.sess.crate_types().contains(&CrateType::Rlib) {
+            return None;
+        }
+
+        let procedures: Vec<_> = self.procedure_specs.iter()
+            .filter(|(local_id, _)| {
+                tcx.visibility(local_id.to_def_id()) == ty::Visibility::Public
+            })
+            .map(|(local_id, refs)| (local_id.local_def_index.as_u32(), refs.clone()))
+            .collect();
+        let used_spec_ids: HashSet<SpecificationId> = procedures.iter()
+            .flat_map(|(_, refs)| refs.spec_id_refs.iter())
+            .flat_map(|spec_id_ref| match *spec_id_ref {
+                SpecIdRef::Precondition(spec_id) |
+                SpecIdRef::Postcondition(spec_id) |
+                SpecIdRef::Decreases(spec_id) |
+                SpecIdRef::Predicate(spec_id) => vec![spec_id],
+                SpecIdRef::Pledge { lhs, rhs } => lhs.into_iter().chain(Some(rhs)).collect(),
+            })
+            .collect();
+        let assertions = self.spec_items.iter()
+            .filter(|spec_item| used_spec_ids.contains(&spec_item.spec_id))
+            .map(|spec_item| (
+                spec_item.spec_id,
+                serde_json::to_string(&spec_item.specification).unwrap(),
+            ))
+            .collect();
+        let expressions = self.typed_expressions.iter()
+            .filter_map(|(key, def_id)| def_id.as_local().map(
+                |local_id| (key.clone(), local_id.local_def_index.as_u32())
+            ))
+            .collect();
+        let crate_specs = CrateSpecs {
+            crate_hash: tcx.crate_hash(LOCAL_CRATE).as_u64(),
+            procedures,
+            assertions,
+            expressions,
+        };
+        Some(ExportedSpecs { crate_specs })
+    }
+
+    /// Load the specifications exported by the dependencies of the crate.
+    pub(super) fn import_specs(&self, def_spec: &mut typed::DefSpecificationMap<'tcx>) {
+        let tcx = self.tcx;
+        for &krate in tcx.crates().iter() {
+            let source = tcx.used_crate_source(krate);
+            let path = match source.rlib.as_ref().or(source.rmeta.as_ref()) {
+                Some((metadata_path, _)) => specs_path(metadata_path),
+                None => continue,
+            };
+            if !path.exists() {
+                continue;
+            }
+            debug!(
+                "Importing the specifications of {} from {}",
+                tcx.crate_name(krate),
+                path.display(),
+            );
+            match read_crate_specs(&path) {
+                Ok(crate_specs) if crate_specs.crate_hash != tcx.crate_hash(krate).as_u64() => {
+                    tcx.sess.warn(&format!(
+                        "ignoring the specifications of crate '{}' in {}, because they were \
+                        exported from a different version of the crate",
+                        tcx.crate_name(krate),
+                        path.display(),
+                    ))
+                }
+                Ok(crate_specs) => import_crate_specs(tcx, krate, crate_specs, def_spec),
+                Err(err) => tcx.sess.warn(&format!(
+                    "cannot import the specifications of crate '{}' from {}: {}",
+                    tcx.crate_name(krate),
+                    path.display(),
+                    err,
+                )),
+            }
+        }
+    }
+}
+
+fn read_crate_specs(path: &Path) -> Result<CrateSpecs, String> {
+    let content = fs::read_to_string(path).map_err(|err| err.to_string())?;
+    serde_json::from_str(&content).map_err(|err| err.to_string())
+}
+
+fn import_crate_specs<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    krate: CrateNum,
+    crate_specs: CrateSpecs,
+    def_spec: &mut typed::DefSpecificationMap<'tcx>,
+) {
+    let def_id = |index: u32| DefId { krate, index: DefIndex::from_u32(index) };
+    let typed_expressions: HashMap<String, DefId> = crate_specs.expressions
+        .into_iter()
+        .map(|(key, index)| (key, def_id(index)))
+        .collect();
+    let typed_specs: typed::SpecificationMap<'tcx> = crate_specs.assertions
+        .into_iter()
+        .map(|(spec_id, json)| {
+            let assertion = JsonAssertion::from_json_string(&json);
+            (spec_id, assertion.to_typed(&typed_expressions, tcx))
+        })
+        .collect();
+    for (index, refs) in crate_specs.procedures {
+        def_spec.imported_specs.insert(def_id(index), build_procedure_spec(&refs, &typed_specs));
+    }
+}
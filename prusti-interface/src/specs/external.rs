@@ -4,9 +4,10 @@ use rustc_middle::hir::map::Map;
 use rustc_middle::ty::TyCtxt;
 use rustc_span::{Span, MultiSpan};
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use crate::environment::Environment;
 use crate::PrustiError;
+use crate::utils::has_prusti_attr;
 
 /// This struct is used to build a mapping of external functions to their
 /// Prusti specifications (see `extern_fn_map`).
@@ -22,6 +23,11 @@ pub struct ExternSpecResolver<'tcx> {
     /// Duplicate specifications detected, keyed by the `DefId` of the function
     /// to be specified.
     spec_duplicates: HashMap<DefId, Vec<(DefId, Span)>>,
+
+    /// The functions of `extern_fn_map` whose specification is declared by
+    /// `prusti_std::import_std_specs!()`. The specifications that the crate
+    /// declares itself replace these ones.
+    std_spec_fns: HashSet<DefId>,
 }
 
 impl<'tcx> ExternSpecResolver<'tcx> {
@@ -30,6 +36,7 @@ impl<'tcx> ExternSpecResolver<'tcx> {
             tcx: tcx,
             extern_fn_map: HashMap::new(),
             spec_duplicates: HashMap::new(),
+            std_spec_fns: HashSet::new(),
         }
     }
 
@@ -39,7 +46,8 @@ impl<'tcx> ExternSpecResolver<'tcx> {
     ///
     /// In case of duplicates, the function is added to `spec_duplicates`, and
     /// will later (in `check_duplicates`) be reported as an error. Otherwise,
-    /// the function is added to `extern_fn_map`.
+    /// the function is added to `extern_fn_map`. A specification of the
+    /// standard library is only used if the crate does not declare its own.
     pub fn add_extern_fn(
         &mut self,
         fn_kind: intravisit::FnKind<'tcx>,
@@ -55,8 +63,12 @@ impl<'tcx> ExternSpecResolver<'tcx> {
         visitor.visit_fn(fn_kind, fn_decl, body_id, span, id);
         let current_def_id = self.tcx.hir().local_def_id(id).to_def_id();
         if let Some((def_id, impl_ty, span)) = visitor.spec_found {
+            let is_std_spec = self.is_std_spec(id);
             match self.extern_fn_map.get(&def_id) {
-                Some((existing_impl_ty, _)) if existing_impl_ty == &impl_ty => {
+                Some(_) if is_std_spec => {}
+                Some((existing_impl_ty, _))
+                    if existing_impl_ty == &impl_ty && !self.std_spec_fns.contains(&def_id) =>
+                {
                     match self.spec_duplicates.get_mut(&def_id) {
                         Some(dups) => {
                             dups.push((current_def_id, span));
@@ -68,12 +80,25 @@ impl<'tcx> ExternSpecResolver<'tcx> {
                 }
                 _ => {
                     // TODO: what if def_id was present, but impl_ty was different?
+                    if is_std_spec {
+                        self.std_spec_fns.insert(def_id);
+                    } else {
+                        self.std_spec_fns.remove(&def_id);
+                    }
                     self.extern_fn_map.insert(def_id, (impl_ty, current_def_id));
                 }
             }
         }
     }
 
+    /// Whether the specification function is declared by
+    /// `prusti_std::import_std_specs!()`.
+    fn is_std_spec(&self, id: rustc_hir::hir_id::HirId) -> bool {
+        let hir = self.tcx.hir();
+        hir.parent_iter(id)
+            .any(|(parent_id, _)| has_prusti_attr(hir.attrs(parent_id), "std_specs"))
+    }
+
     /// Report errors for duplicate specifications found during specification
     /// collection.
     pub fn check_duplicates(&self, env: &Environment<'tcx>) {
@@ -14,7 +14,9 @@ use crate::utils::{
     has_spec_only_attr, has_extern_spec_attr, read_prusti_attr, read_prusti_attrs, has_prusti_attr
 };
 use log::debug;
+use serde::{Deserialize, Serialize};
 
+pub mod cross_crate;
 pub mod external;
 pub mod typed;
 
@@ -38,6 +40,7 @@ impl fmt::Debug for SpecItem {
     }
 }
 
+#[derive(Clone, Serialize, Deserialize)]
 struct ProcedureSpecRef {
     spec_id_refs: Vec<prusti_specs::specifications::common::SpecIdRef>,
     pure: bool,
@@ -55,7 +58,7 @@ pub struct SpecCollector<'tcx> {
     /// Collected assertions before deserialisation.
     spec_items: Vec<SpecItem>,
 
-    typed_expressions: HashMap<String, LocalDefId>,
+    typed_expressions: HashMap<String, DefId>,
 
     /// Collected, deserialised assertions, keyed by their specification id.
     typed_specs: typed::SpecificationMap<'tcx>,
@@ -63,6 +66,9 @@ pub struct SpecCollector<'tcx> {
     /// Resolved specifications.
     procedure_specs: HashMap<LocalDefId, ProcedureSpecRef>,
     loop_specs: HashMap<LocalDefId, Vec<SpecificationId>>,
+    loop_variants: HashMap<LocalDefId, SpecificationId>,
+    struct_specs: HashMap<LocalDefId, Vec<SpecificationId>>,
+    statement_specs: HashMap<LocalDefId, (SpecType, SpecificationId)>,
 }
 
 impl<'tcx> SpecCollector<'tcx> {
@@ -73,6 +79,9 @@ impl<'tcx> SpecCollector<'tcx> {
             typed_specs: HashMap::new(),
             procedure_specs: HashMap::new(),
             loop_specs: HashMap::new(),
+            loop_variants: HashMap::new(),
+            struct_specs: HashMap::new(),
+            statement_specs: HashMap::new(),
             typed_expressions: HashMap::new(),
             extern_resolver: ExternSpecResolver::new(tcx),
         }
@@ -101,6 +110,8 @@ impl<'tcx> SpecCollector<'tcx> {
         self.determine_extern_specs(&mut def_spec, env);
         self.determine_loop_specs(&mut def_spec);
         self.determine_struct_specs(&mut def_spec);
+        self.determine_statement_specs(&mut def_spec);
+        self.import_specs(&mut def_spec);
         def_spec
     }
 
@@ -125,36 +136,7 @@ impl<'tcx> SpecCollector<'tcx> {
 
     fn determine_procedure_specs(&self, def_spec: &mut typed::DefSpecificationMap<'tcx>) {
         for (local_id, refs) in self.procedure_specs.iter() {
-            let mut pres = Vec::new();
-            let mut posts = Vec::new();
-            let mut pledges = Vec::new();
-            for spec_id_ref in &refs.spec_id_refs {
-                match spec_id_ref {
-                    SpecIdRef::Precondition(spec_id) => {
-                        pres.push(self.typed_specs.get(&spec_id).unwrap().clone());
-                    }
-                    SpecIdRef::Postcondition(spec_id) => {
-                        posts.push(self.typed_specs.get(&spec_id).unwrap().clone());
-                    }
-                    SpecIdRef::Pledge{ lhs, rhs } => {
-                        pledges.push(typed::Pledge {
-                            reference: None,    // FIXME: Currently only `result` is supported.
-                            lhs: lhs.map(|spec_id| self.typed_specs.get(&spec_id).unwrap().clone()),
-                            rhs: self.typed_specs.get(&rhs).unwrap().clone(),
-                        })
-                    }
-                }
-            }
-            def_spec.specs.insert(
-                *local_id,
-                typed::SpecificationSet::Procedure(typed::ProcedureSpecification {
-                    pres,
-                    posts,
-                    pledges,
-                    pure: refs.pure,
-                    trusted: refs.trusted,
-                })
-            );
+            def_spec.specs.insert(*local_id, build_procedure_spec(refs, &self.typed_specs));
         }
     }
 
@@ -164,13 +146,88 @@ impl<'tcx> SpecCollector<'tcx> {
                 .map(|spec_id| self.typed_specs.get(&spec_id).unwrap().clone())
                 .collect();
             def_spec.specs.insert(*local_id, typed::SpecificationSet::Loop(typed::LoopSpecification {
-                invariant: specs
+                invariant: specs,
+                variant: None,
+            }));
+        }
+        for (local_id, spec_id) in self.loop_variants.iter() {
+            let variant = typed::Measure::from_assertion(self.typed_specs.get(&spec_id).unwrap());
+            def_spec.specs.insert(*local_id, typed::SpecificationSet::Loop(typed::LoopSpecification {
+                invariant: vec![],
+                variant: Some(variant),
             }));
         }
     }
 
-    // TODO: struct specs
-    fn determine_struct_specs(&self, def_spec: &mut typed::DefSpecificationMap<'tcx>) {}
+    fn determine_struct_specs(&self, def_spec: &mut typed::DefSpecificationMap<'tcx>) {
+        for (local_id, spec_ids) in self.struct_specs.iter() {
+            let specs = spec_ids.iter()
+                .map(|spec_id| typed::Specification {
+                    typ: SpecType::Invariant,
+                    assertion: self.typed_specs.get(&spec_id).unwrap().clone(),
+                })
+                .collect();
+            def_spec.specs.insert(*local_id, typed::SpecificationSet::Struct(specs));
+        }
+    }
+
+    fn determine_statement_specs(&self, def_spec: &mut typed::DefSpecificationMap<'tcx>) {
+        for (local_id, (spec_type, spec_id)) in self.statement_specs.iter() {
+            let assertion = self.typed_specs.get(&spec_id).unwrap().clone();
+            let spec = match spec_type {
+                SpecType::Assertion => typed::SpecificationSet::Assertion(assertion),
+                SpecType::Assumption => typed::SpecificationSet::Assumption(assertion),
+                _ => unreachable!("unexpected statement specification: {:?}", spec_type),
+            };
+            def_spec.specs.insert(*local_id, spec);
+        }
+    }
+}
+
+/// Build the specification of a procedure from the ids of its specifications.
+fn build_procedure_spec<'tcx>(
+    refs: &ProcedureSpecRef,
+    typed_specs: &typed::SpecificationMap<'tcx>,
+) -> typed::SpecificationSet<'tcx> {
+    let mut pres = Vec::new();
+    let mut posts = Vec::new();
+    let mut pledges = Vec::new();
+    let mut decreases = None;
+    let mut predicate_body = None;
+    for spec_id_ref in &refs.spec_id_refs {
+        match spec_id_ref {
+            SpecIdRef::Precondition(spec_id) => {
+                pres.push(typed_specs.get(&spec_id).unwrap().clone());
+            }
+            SpecIdRef::Postcondition(spec_id) => {
+                posts.push(typed_specs.get(&spec_id).unwrap().clone());
+            }
+            SpecIdRef::Pledge{ lhs, rhs } => {
+                pledges.push(typed::Pledge {
+                    reference: None,    // FIXME: Currently only `result` is supported.
+                    lhs: lhs.map(|spec_id| typed_specs.get(&spec_id).unwrap().clone()),
+                    rhs: typed_specs.get(&rhs).unwrap().clone(),
+                })
+            }
+            SpecIdRef::Decreases(spec_id) => {
+                decreases = Some(typed::Measure::from_assertion(
+                    typed_specs.get(&spec_id).unwrap()
+                ));
+            }
+            SpecIdRef::Predicate(spec_id) => {
+                predicate_body = Some(typed_specs.get(&spec_id).unwrap().clone());
+            }
+        }
+    }
+    typed::SpecificationSet::Procedure(typed::ProcedureSpecification {
+        pres,
+        posts,
+        pledges,
+        decreases,
+        predicate_body,
+        pure: refs.pure,
+        trusted: refs.trusted,
+    })
 }
 
 fn get_procedure_spec_ids(def_id: DefId, attrs: &[ast::Attribute]) -> Option<ProcedureSpecRef> {
@@ -208,6 +265,16 @@ fn get_procedure_spec_ids(def_id: DefId, attrs: &[ast::Attribute]) -> Option<Pro
             }
         )
     );
+    spec_id_refs.extend(
+        read_prusti_attrs("decreases_spec_id_ref", attrs).into_iter().map(
+            |raw_spec_id| SpecIdRef::Decreases(parse_spec_id(raw_spec_id))
+        )
+    );
+    spec_id_refs.extend(
+        read_prusti_attr("pred_spec_id_ref", attrs).into_iter().map(
+            |raw_spec_id| SpecIdRef::Predicate(parse_spec_id(raw_spec_id))
+        )
+    );
     debug!("Function {:?} has specification ids {:?}", def_id, spec_id_refs);
 
     let pure = has_prusti_attr(attrs, "pure");
@@ -226,7 +293,7 @@ fn get_procedure_spec_ids(def_id: DefId, attrs: &[ast::Attribute]) -> Option<Pro
 
 fn reconstruct_typed_assertion<'tcx>(
     assertion: JsonAssertion,
-    typed_expressions: &HashMap<String, LocalDefId>,
+    typed_expressions: &HashMap<String, DefId>,
     tcx: TyCtxt<'tcx>
 ) -> typed::Assertion<'tcx> {
     assertion.to_typed(typed_expressions, tcx)
@@ -246,6 +313,27 @@ impl<'tcx> intravisit::Visitor<'tcx> for SpecCollector<'tcx> {
         intravisit::NestedVisitorMap::All(map)
     }
 
+    fn visit_item(
+        &mut self,
+        item: &'tcx rustc_hir::Item,
+    ) {
+        intravisit::walk_item(self, item);
+
+        // Collect struct and enum invariants
+        if let ItemKind::Struct(..) | ItemKind::Enum(..) = item.kind {
+            let local_id = self.tcx.hir().local_def_id(item.hir_id);
+            let spec_ids: Vec<SpecificationId> = read_prusti_attrs("inv_spec_id_ref", item.attrs)
+                .into_iter()
+                .map(|raw_spec_id| raw_spec_id.try_into().expect(
+                    &format!("cannot parse the spec_id attached to {:?}", local_id)
+                ))
+                .collect();
+            if !spec_ids.is_empty() {
+                self.struct_specs.insert(local_id, spec_ids);
+            }
+        }
+    }
+
     fn visit_trait_item(
         &mut self,
         ti: &'tcx rustc_hir::TraitItem,
@@ -289,7 +377,7 @@ impl<'tcx> intravisit::Visitor<'tcx> for SpecCollector<'tcx> {
 
         // Collect a typed expression
         if let Some(expr_id) = read_prusti_attr("expr_id", attrs) {
-            self.typed_expressions.insert(expr_id, local_id);
+            self.typed_expressions.insert(expr_id, def_id);
         }
 
         // Collect a specification id and its assertion
@@ -304,15 +392,26 @@ impl<'tcx> intravisit::Visitor<'tcx> for SpecCollector<'tcx> {
             // to its precondition with a #[pre_spec_id_ref=<id>] attribute,
             // where <id> is the unique identifier of the specification. Same
             // for postconditions and invariants.
-            let spec_type = if has_prusti_attr(attrs, "loop_body_invariant_spec") {
+            let is_loop_invariant = has_prusti_attr(attrs, "loop_body_invariant_spec");
+            let is_loop_variant = has_prusti_attr(attrs, "loop_body_variant_spec");
+            let is_assertion = has_prusti_attr(attrs, "prusti_assertion");
+            let is_assumption = has_prusti_attr(attrs, "prusti_assumption");
+            let spec_type = if is_loop_invariant {
                 SpecType::Invariant
+            } else if is_loop_variant {
+                SpecType::Decreases
+            } else if is_assertion {
+                SpecType::Assertion
+            } else if is_assumption {
+                SpecType::Assumption
             } else {
                 let fn_name = match fn_kind {
                     intravisit::FnKind::ItemFn(ref ident, ..) |
                     intravisit::FnKind::Method(ref ident, ..) => ident.name.to_ident_string(),
                     intravisit::FnKind::Closure(..) => unreachable!(
                         "a closure is annotated with prusti::spec_id but not with \
-                        prusti::loop_body_invariant_spec"
+                        prusti::loop_body_invariant_spec, prusti::loop_body_variant_spec, \
+                        prusti::prusti_assertion or prusti::prusti_assumption"
                     ),
                 };
                 if fn_name.starts_with("prusti_pre_item_")
@@ -321,6 +420,12 @@ impl<'tcx> intravisit::Visitor<'tcx> for SpecCollector<'tcx> {
                 } else if fn_name.starts_with("prusti_post_item_")
                     || fn_name.starts_with("prusti_post_closure_") {
                     SpecType::Postcondition
+                } else if fn_name.starts_with("prusti_inv_item_") {
+                    SpecType::Invariant
+                } else if fn_name.starts_with("prusti_decreases_item_") {
+                    SpecType::Decreases
+                } else if fn_name.starts_with("prusti_pred_item_") {
+                    SpecType::Predicate
                 } else {
                     unreachable!()
                 }
@@ -330,12 +435,22 @@ impl<'tcx> intravisit::Visitor<'tcx> for SpecCollector<'tcx> {
             self.spec_items.push(spec_item);
 
             // Collect loop invariant
-            if spec_type == SpecType::Invariant {
+            if is_loop_invariant {
                 self.loop_specs
                     .entry(local_id)
                     .or_insert(vec![])
                     .push(spec_id);
             }
+
+            // Collect loop variant
+            if is_loop_variant {
+                self.loop_variants.insert(local_id, spec_id);
+            }
+
+            // Collect `prusti_assert!` and `prusti_assume!` statements
+            if is_assertion || is_assumption {
+                self.statement_specs.insert(local_id, (spec_type, spec_id));
+            }
         }
     }
 
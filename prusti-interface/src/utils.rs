@@ -133,6 +133,33 @@ pub fn try_pop_one_level<'tcx>(tcx: TyCtxt<'tcx>, place: mir::Place<'tcx>) -> Op
     }
 }
 
+/// Is the place an element of an array or slice?
+pub fn is_array_elem_place(place: &mir::Place) -> bool {
+    matches!(
+        place.projection.last(),
+        Some(mir::ProjectionElem::Index(_)) | Some(mir::ProjectionElem::ConstantIndex { .. })
+    )
+}
+
+/// Truncate the place just before its first array or slice projection, since
+/// the elements of an array are not tracked as separate places.
+pub fn truncate_array_index<'tcx>(tcx: TyCtxt<'tcx>, place: mir::Place<'tcx>) -> mir::Place<'tcx> {
+    let first_index = place.projection.iter().position(|elem| matches!(
+        elem,
+        mir::ProjectionElem::Index(_)
+        | mir::ProjectionElem::ConstantIndex { .. }
+        | mir::ProjectionElem::Subslice { .. }
+    ));
+    if let Some(first_index) = first_index {
+        mir::Place {
+            local: place.local,
+            projection: tcx.intern_place_elems(&place.projection[..first_index]),
+        }
+    } else {
+        place
+    }
+}
+
 /// Pop the last element from the place if it is a dereference.
 pub fn try_pop_deref<'tcx>(tcx: TyCtxt<'tcx>, place: mir::Place<'tcx>) -> Option<mir::Place<'tcx>> {
     try_pop_one_level(tcx, place).and_then(|(elem, base)| {
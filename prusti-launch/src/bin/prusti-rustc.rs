@@ -114,6 +114,16 @@ fn process(mut args: Vec<String>) -> Result<(), i32> {
             .expect("the Prusti contracts path contains invalid UTF-8")
     ));
 
+    cmd.arg("--extern");
+    let prusti_std_path = prusti_home.join("libprusti_std.rlib");
+    cmd.arg(format!(
+        "prusti_std={}",
+        prusti_std_path
+            .as_os_str()
+            .to_str()
+            .expect("the Prusti std specifications path contains invalid UTF-8")
+    ));
+
     // cmd.arg("-Zreport-delayed-bugs");
     // cmd.arg("-Ztreat-err-as-bug=1");
 
@@ -0,0 +1,278 @@
+// © 2021, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use super::{RemoteVerificationResult, VerifierPanicked};
+use prusti_common::verification_service::VerificationRequest;
+use std::{
+    collections::HashMap,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+pub type JobId = u64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JobState {
+    Running,
+    Finished,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobStatus {
+    pub state: JobState,
+    /// The number of programs the request of the job was split into.
+    pub num_programs: usize,
+    /// The number of programs whose results are available.
+    pub num_verified: usize,
+}
+
+/// The result of one of the programs of a job, which usually contains a single method.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgramResult {
+    pub program_name: String,
+    pub result: RemoteVerificationResult,
+}
+
+struct Job {
+    num_programs: usize,
+    results: Vec<ProgramResult>,
+    cancelled: bool,
+    /// When the job stopped verifying programs, because all of them were verified or because
+    /// it was cancelled.
+    finished_at: Option<Instant>,
+}
+
+impl Job {
+    fn status(&self) -> JobStatus {
+        JobStatus {
+            state: if self.finished_at.is_none() {
+                JobState::Running
+            } else {
+                JobState::Finished
+            },
+            num_programs: self.num_programs,
+            num_verified: self.results.len(),
+        }
+    }
+
+    fn is_expired(&self, ttl: Duration) -> bool {
+        self.finished_at
+            .map_or(false, |finished_at| finished_at.elapsed() >= ttl)
+    }
+}
+
+/// Marks a job as finished when the threads that verify its programs stop, even if they panic.
+struct FinishGuard(Arc<Mutex<Job>>);
+
+impl Drop for FinishGuard {
+    fn drop(&mut self) {
+        // The lock is poisoned if the job panicked while holding it, but the job is still
+        // consistent since all its fields are updated at once.
+        let mut job = match self.0.lock() {
+            Ok(job) => job,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        job.finished_at = Some(Instant::now());
+    }
+}
+
+/// The verification jobs of a server. The methods of the request of a job are verified by up
+/// to `max_parallel` threads, so that their results can be fetched while the job is still
+/// running. Finished jobs are forgotten after `ttl`, if their client did not cancel them.
+pub struct JobManager {
+    next_id: AtomicU64,
+    jobs: Mutex<HashMap<JobId, Arc<Mutex<Job>>>>,
+    max_parallel: usize,
+    ttl: Duration,
+}
+
+impl JobManager {
+    pub fn new(max_parallel: usize, ttl: Duration) -> Self {
+        JobManager {
+            next_id: AtomicU64::new(0),
+            jobs: Mutex::new(HashMap::new()),
+            max_parallel: max_parallel.max(1),
+            ttl,
+        }
+    }
+
+    /// Start a job that verifies `request` with `verify`, one method at a time in each thread.
+    /// A panic of `verify` is reported as the result of the method that was being verified.
+    pub fn submit<F>(&self, request: VerificationRequest, verify: F) -> JobId
+    where
+        F: Fn(VerificationRequest) -> RemoteVerificationResult + Send + Sync + 'static,
+    {
+        self.remove_expired_jobs();
+
+        // Splitting a program drops the items that no method uses, which must still be checked
+        // when there is nothing to split. A request that the client already split contains a
+        // single method, and is verified as it is.
+        let requests = if request.program.methods.len() > 1 {
+            request.split_by_method()
+        } else {
+            vec![request]
+        };
+        let job = Arc::new(Mutex::new(Job {
+            num_programs: requests.len(),
+            results: Vec::with_capacity(requests.len()),
+            cancelled: false,
+            finished_at: None,
+        }));
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        self.jobs.lock().unwrap().insert(id, job.clone());
+
+        let num_threads = self.max_parallel.min(requests.len());
+        let queue = Arc::new(Mutex::new(requests.into_iter()));
+        let verify = Arc::new(verify);
+        let finish_guard = Arc::new(FinishGuard(job.clone()));
+        for _ in 0..num_threads {
+            let job = job.clone();
+            let queue = queue.clone();
+            let verify = verify.clone();
+            let finish_guard = finish_guard.clone();
+            thread::spawn(move || {
+                // The job is finished once the last thread drops its guard.
+                let _finish_guard = finish_guard;
+                loop {
+                    if job.lock().unwrap().cancelled {
+                        info!("Job {} was cancelled", id);
+                        return;
+                    }
+                    let request = match queue.lock().unwrap().next() {
+                        Some(request) => request,
+                        None => return,
+                    };
+                    let program_name = request.program_name.clone();
+                    let result = panic::catch_unwind(AssertUnwindSafe(|| verify(request)))
+                        .unwrap_or_else(|_| {
+                            error!("Panic while verifying {} in job {}", program_name, id);
+                            Err(VerifierPanicked)
+                        });
+                    job.lock().unwrap().results.push(ProgramResult {
+                        program_name,
+                        result,
+                    });
+                }
+            });
+        }
+        id
+    }
+
+    pub fn status(&self, id: JobId) -> Option<JobStatus> {
+        Some(self.get(id)?.lock().unwrap().status())
+    }
+
+    /// The results of the job that were not fetched yet, given that the first `from` results
+    /// were.
+    pub fn results(&self, id: JobId, from: usize) -> Option<Vec<ProgramResult>> {
+        let job = self.get(id)?;
+        let job = job.lock().unwrap();
+        Some(job.results.iter().skip(from).cloned().collect())
+    }
+
+    /// Stop the job after the methods that are currently being verified, and forget about it.
+    /// Returns false if there is no such job.
+    pub fn cancel(&self, id: JobId) -> bool {
+        match self.jobs.lock().unwrap().remove(&id) {
+            Some(job) => {
+                job.lock().unwrap().cancelled = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn get(&self, id: JobId) -> Option<Arc<Mutex<Job>>> {
+        self.remove_expired_jobs();
+        self.jobs.lock().unwrap().get(&id).cloned()
+    }
+
+    /// Forget the jobs that finished longer than `ttl` ago, since their clients are probably
+    /// gone without cancelling them.
+    fn remove_expired_jobs(&self) {
+        let ttl = self.ttl;
+        self.jobs.lock().unwrap().retain(|id, job| {
+            let expired = job.lock().unwrap().is_expired(ttl);
+            if expired {
+                info!("Job {} expired", id);
+            }
+            !expired
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prusti_common::{verification_service::ViperBackendConfig, vir::Program};
+    use viper::{VerificationBackend, VerificationResult};
+
+    fn request() -> VerificationRequest {
+        VerificationRequest {
+            program: Program {
+                domains: vec![],
+                fields: vec![],
+                builtin_methods: vec![],
+                methods: vec![],
+                functions: vec![],
+                viper_predicates: vec![],
+            },
+            program_name: "dummy".to_string(),
+            backend_config: ViperBackendConfig {
+                backend: VerificationBackend::Silicon,
+                verifier_args: vec![],
+            },
+        }
+    }
+
+    fn wait_until_finished(jobs: &JobManager, id: JobId) -> JobStatus {
+        loop {
+            let status = jobs.status(id).expect("the job does not exist");
+            if status.state == JobState::Finished {
+                return status;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn panics_are_reported_as_results() {
+        let jobs = JobManager::new(1, Duration::from_secs(60));
+        let id = jobs.submit(request(), |_| panic!("verifier crashed"));
+        let status = wait_until_finished(&jobs, id);
+        assert_eq!(status.num_verified, 1);
+        let results = jobs.results(id, 0).unwrap();
+        assert!(results[0].result.is_err());
+    }
+
+    #[test]
+    fn finished_jobs_expire() {
+        let jobs = JobManager::new(1, Duration::from_millis(200));
+        let id = jobs.submit(request(), |_| Ok(VerificationResult::Success()));
+        wait_until_finished(&jobs, id);
+        assert!(jobs.results(id, 0).is_some());
+        thread::sleep(Duration::from_millis(300));
+        assert!(jobs.status(id).is_none());
+        assert!(!jobs.cancel(id));
+    }
+
+    #[test]
+    fn running_jobs_do_not_expire() {
+        let jobs = JobManager::new(1, Duration::from_millis(0));
+        let id = jobs.submit(request(), |_| {
+            thread::sleep(Duration::from_millis(300));
+            Ok(VerificationResult::Success())
+        });
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(jobs.status(id).unwrap().state, JobState::Running);
+        assert!(jobs.cancel(id));
+    }
+}
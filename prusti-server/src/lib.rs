@@ -16,23 +16,32 @@ extern crate prusti_common;
 extern crate tokio;
 #[macro_use]
 extern crate serde;
+extern crate serde_json;
 
+mod jobs;
+mod result_cache;
+mod server_process;
 mod service;
 mod verifier_runner;
 mod verifier_thread;
+mod worker_pool;
 
 use futures::Future;
 use prusti_common::{verification_context::VerifierBuilder, verification_service::*, Stopwatch};
+pub use jobs::*;
+pub use result_cache::*;
+pub use server_process::*;
 pub use service::*;
 use std::{
     collections::VecDeque,
-    sync::{Arc, RwLock},
+    sync::{Arc, Mutex, RwLock},
 };
 pub use verifier_runner::*;
 use verifier_thread::*;
+pub use worker_pool::*;
 use viper::VerificationResult;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VerifierPanicked;
 pub type RemoteVerificationResult = Result<VerificationResult, VerifierPanicked>;
 
@@ -40,6 +49,8 @@ pub struct PrustiServer {
     verifier_builder: Arc<VerifierBuilder>,
     threads: RwLock<VecDeque<VerifierThread>>,
     cache_size: usize,
+    /// Held while verifying, since Viper does not support verifying programs in parallel.
+    verification_lock: Mutex<()>,
 }
 
 impl PrustiServer {
@@ -52,10 +63,13 @@ impl PrustiServer {
             verifier_builder,
             threads: RwLock::new(VecDeque::with_capacity(cache_size)),
             cache_size,
+            verification_lock: Mutex::new(()),
         }
     }
 
     pub fn run_verifier(&self, request: VerificationRequest) -> RemoteVerificationResult {
+        let _verification_guard = self.verification_lock.lock().unwrap();
+
         // try to find and take out an existing threads from our cache
         let existing_thread = {
             let mut threads = self.threads.write().unwrap();
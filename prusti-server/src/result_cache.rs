@@ -0,0 +1,322 @@
+// © 2021, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use super::{RemoteVerificationResult, VerifierPanicked};
+use prusti_common::{
+    verification_cache::VerificationCache, verification_service::VerificationRequest,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Condvar, Mutex},
+};
+use viper::VerificationResult;
+
+/// The result of a verification that is still running, for the identical requests that wait
+/// for it. Its positions are those of the cache key.
+#[derive(Default)]
+struct PendingResult {
+    result: Mutex<Option<RemoteVerificationResult>>,
+    available: Condvar,
+}
+
+/// Removes a pending result from the cache once the request that computes it finished. If the
+/// result was not filled in, because the verification panicked, the waiting requests receive
+/// `VerifierPanicked`.
+struct PendingGuard<'a> {
+    cache: &'a ResultCache,
+    key: u64,
+    pending_result: Arc<PendingResult>,
+}
+
+impl<'a> PendingGuard<'a> {
+    fn fill(&self, result: RemoteVerificationResult) {
+        *self.pending_result.result.lock().unwrap() = Some(result);
+    }
+}
+
+impl<'a> Drop for PendingGuard<'a> {
+    fn drop(&mut self) {
+        {
+            let mut result = match self.pending_result.result.lock() {
+                Ok(result) => result,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            if result.is_none() {
+                *result = Some(Err(VerifierPanicked));
+            }
+        }
+        self.pending_result.available.notify_all();
+        if let Ok(mut pending) = self.cache.pending.lock() {
+            pending.remove(&self.key);
+        }
+    }
+}
+
+/// Memoized results of verification requests, identified by `VerificationRequest::cache_key`.
+///
+/// The most recently used results are kept in memory, and all results are also stored on disk
+/// if a path is given. Identical requests that arrive while one of them is being verified wait
+/// for its result instead of being verified again.
+pub struct ResultCache {
+    max_size: usize,
+    /// The results in memory, most recently used first.
+    results: Mutex<VecDeque<(u64, VerificationResult)>>,
+    disk: Option<VerificationCache>,
+    pending: Mutex<HashMap<u64, Arc<PendingResult>>>,
+}
+
+impl ResultCache {
+    pub fn new(max_size: usize, disk_path: Option<String>) -> Self {
+        ResultCache {
+            max_size,
+            results: Mutex::new(VecDeque::with_capacity(max_size)),
+            disk: disk_path.map(VerificationCache::new),
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Look up the result of `request`, or wait for an identical request that is being
+    /// verified, or else verify it with `verify` and remember the result.
+    pub fn get_or_verify<F>(
+        &self,
+        request: VerificationRequest,
+        verify: F,
+    ) -> RemoteVerificationResult
+    where
+        F: FnOnce(VerificationRequest) -> RemoteVerificationResult,
+    {
+        let cache_key = request.cache_key();
+        let key = cache_key.hash;
+        if let Some(result) = self.get(key) {
+            info!("Reusing the cached result of {}", request.program_name);
+            return Ok(cache_key.denormalize_result(&result));
+        }
+
+        let (pending_result, is_first) = {
+            let mut pending = self.pending.lock().unwrap();
+            if let Some(pending_result) = pending.get(&key) {
+                (pending_result.clone(), false)
+            } else if let Some(result) = self.get_from_memory(key) {
+                // An identical request finished after we looked up its result.
+                return Ok(cache_key.denormalize_result(&result));
+            } else {
+                let pending_result = Arc::new(PendingResult::default());
+                pending.insert(key, pending_result.clone());
+                (pending_result, true)
+            }
+        };
+        if !is_first {
+            info!(
+                "Waiting for the result of an identical request to {}",
+                request.program_name
+            );
+            let mut result = pending_result.result.lock().unwrap();
+            while result.is_none() {
+                result = pending_result.available.wait(result).unwrap();
+            }
+            return result.clone().unwrap().map(|result| cache_key.denormalize_result(&result));
+        }
+
+        let pending_guard = PendingGuard {
+            cache: self,
+            key,
+            pending_result,
+        };
+        let result = verify(request);
+        let normalized_result = result.as_ref()
+            .map(|result| cache_key.normalize_result(result))
+            .map_err(|error| error.clone());
+        if let Ok(normalized_result) = &normalized_result {
+            self.insert(key, normalized_result);
+        }
+        pending_guard.fill(normalized_result);
+        result
+    }
+
+    fn get(&self, key: u64) -> Option<VerificationResult> {
+        if let Some(result) = self.get_from_memory(key) {
+            return Some(result);
+        }
+        let result = self.disk.as_ref()?.get(key)?;
+        self.insert_into_memory(key, result.clone());
+        Some(result)
+    }
+
+    fn get_from_memory(&self, key: u64) -> Option<VerificationResult> {
+        let mut results = self.results.lock().unwrap();
+        let index = results.iter().position(|(result_key, _)| *result_key == key)?;
+        // move the result to the front, since it is now the most recently used one
+        let entry = results.remove(index).unwrap();
+        let result = entry.1.clone();
+        results.push_front(entry);
+        Some(result)
+    }
+
+    fn insert(&self, key: u64, result: &VerificationResult) {
+        match result {
+            VerificationResult::Success() | VerificationResult::Failure(_) => {}
+            // These might not happen again, e.g. if they are caused by a bug that was fixed.
+            VerificationResult::ConsistencyErrors(_) | VerificationResult::JavaException(_) => {
+                return;
+            }
+        }
+        if let Some(disk) = &self.disk {
+            disk.insert(key, result);
+        }
+        self.insert_into_memory(key, result.clone());
+    }
+
+    fn insert_into_memory(&self, key: u64, result: VerificationResult) {
+        if self.max_size == 0 {
+            return;
+        }
+        let mut results = self.results.lock().unwrap();
+        if let Some(index) = results.iter().position(|(result_key, _)| *result_key == key) {
+            results.remove(index);
+        } else if results.len() >= self.max_size {
+            // evict the least-recently-used result
+            results.pop_back();
+        }
+        results.push_front((key, result));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prusti_common::{verification_service::ViperBackendConfig, vir::Program};
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Barrier,
+        },
+        thread,
+        time::Duration,
+    };
+    use viper::VerificationBackend;
+
+    fn request(program_name: &str, num_fields: usize) -> VerificationRequest {
+        let mut program = Program {
+            domains: vec![],
+            fields: vec![],
+            builtin_methods: vec![],
+            methods: vec![],
+            functions: vec![],
+            viper_predicates: vec![],
+        };
+        for index in 0..num_fields {
+            program.fields.push(prusti_common::vir::Field {
+                name: format!("f{}", index),
+                typ: prusti_common::vir::Type::Int,
+            });
+        }
+        VerificationRequest {
+            program,
+            program_name: program_name.to_string(),
+            backend_config: ViperBackendConfig {
+                backend: VerificationBackend::Silicon,
+                verifier_args: vec![],
+            },
+        }
+    }
+
+    #[test]
+    fn identical_requests_are_verified_once() {
+        let cache = ResultCache::new(10, None);
+        let calls = AtomicUsize::new(0);
+        let verify = |_| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Ok(VerificationResult::Success())
+        };
+        // the name of the program does not matter
+        cache.get_or_verify(request("first", 1), verify).unwrap();
+        cache.get_or_verify(request("second", 1), verify).unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        cache.get_or_verify(request("first", 2), verify).unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn least_recently_used_results_are_evicted() {
+        let cache = ResultCache::new(2, None);
+        let calls = AtomicUsize::new(0);
+        let verify = |_| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Ok(VerificationResult::Success())
+        };
+        for num_fields in &[0, 1, 0, 2, 0, 1] {
+            cache.get_or_verify(request("dummy", *num_fields), verify).unwrap();
+        }
+        // the result for one field is evicted when the one for two fields is added
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn unsuccessful_results_are_not_cached() {
+        let cache = ResultCache::new(10, None);
+        let calls = AtomicUsize::new(0);
+        let verify = |_| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Ok(VerificationResult::ConsistencyErrors(vec![]))
+        };
+        cache.get_or_verify(request("dummy", 0), verify).unwrap();
+        cache.get_or_verify(request("dummy", 0), verify).unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn in_flight_requests_are_coalesced() {
+        let cache = Arc::new(ResultCache::new(0, None));
+        let calls = Arc::new(AtomicUsize::new(0));
+        let barrier = Arc::new(Barrier::new(4));
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let cache = cache.clone();
+                let calls = calls.clone();
+                let barrier = barrier.clone();
+                thread::spawn(move || {
+                    barrier.wait();
+                    cache.get_or_verify(request("dummy", 0), |_| {
+                        calls.fetch_add(1, Ordering::SeqCst);
+                        thread::sleep(Duration::from_millis(500));
+                        Ok(VerificationResult::Success())
+                    })
+                })
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap().unwrap(), VerificationResult::Success());
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn panics_are_reported_to_waiting_requests() {
+        let cache = Arc::new(ResultCache::new(10, None));
+        let started = Arc::new(Barrier::new(2));
+        let first = {
+            let cache = cache.clone();
+            let started = started.clone();
+            thread::spawn(move || {
+                cache.get_or_verify(request("dummy", 0), |_| {
+                    started.wait();
+                    thread::sleep(Duration::from_millis(500));
+                    panic!("verifier crashed");
+                })
+            })
+        };
+        started.wait();
+        let waiting_result = cache.get_or_verify(request("dummy", 0), |_| {
+            panic!("identical requests should wait for the first one");
+        });
+        assert!(waiting_result.is_err());
+        assert!(first.join().is_err());
+        // the request is verified again once the panicking verification finished
+        let result =
+            cache.get_or_verify(request("dummy", 0), |_| Ok(VerificationResult::Success()));
+        assert_eq!(result.unwrap(), VerificationResult::Success());
+    }
+}
@@ -0,0 +1,88 @@
+// © 2021, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::{
+    env,
+    io::{self, BufRead, BufReader},
+    net::{Ipv4Addr, SocketAddr},
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+};
+
+/// A Prusti server running in a child process, with its own JVM.
+///
+/// The process is killed when this value is dropped.
+pub struct ServerProcess {
+    child: Child,
+    address: SocketAddr,
+}
+
+impl ServerProcess {
+    /// The path of the `prusti-server-driver` executable, which is installed next to the
+    /// running executable.
+    pub fn default_driver_path() -> PathBuf {
+        let mut path =
+            env::current_exe().expect("could not determine the path of the current executable");
+        path.set_file_name(format!("prusti-server-driver{}", env::consts::EXE_SUFFIX));
+        path
+    }
+
+    /// Start the server executable at `driver_path` on a port assigned by the OS, and wait
+    /// until it accepts requests.
+    pub fn spawn(driver_path: &Path) -> io::Result<Self> {
+        let mut command = Command::new(driver_path);
+        command
+            .args(&["--port", "0"])
+            .stdout(Stdio::piped())
+            // The process verifies requests itself instead of starting workers of its own.
+            .env("PRUSTI_SERVER_WORKER_PROCESSES", "false");
+        if env::var_os("RUST_LOG").is_none() {
+            // The server logs every request at the info level.
+            command.env("RUST_LOG", "warn");
+        }
+        let mut child = command.spawn()?;
+
+        // The server prints `port: <port>` to its stdout once it is listening.
+        let mut stdout = BufReader::new(child.stdout.take().unwrap());
+        let port = loop {
+            let mut line = String::new();
+            if stdout.read_line(&mut line)? == 0 {
+                child.kill().ok();
+                child.wait().ok();
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "the server exited before reporting its port",
+                ));
+            }
+            if let Some(port) = line.trim().strip_prefix("port: ") {
+                break port.parse::<u16>().map_err(|error| {
+                    io::Error::new(io::ErrorKind::InvalidData, error)
+                })?;
+            }
+        };
+
+        Ok(Self {
+            child,
+            address: SocketAddr::from((Ipv4Addr::LOCALHOST, port)),
+        })
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Whether the process has not exited yet.
+    pub fn is_running(&mut self) -> bool {
+        matches!(self.child.try_wait(), Ok(None))
+    }
+}
+
+impl Drop for ServerProcess {
+    fn drop(&mut self) {
+        self.child.kill().ok();
+        self.child.wait().ok();
+    }
+}
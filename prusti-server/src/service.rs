@@ -4,46 +4,86 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use super::{PrustiServer, RemoteVerificationResult};
+use super::{
+    JobId, JobManager, JobState, JobStatus, PrustiServer, ProgramResult, RemoteVerificationResult,
+    ResultCache, ServerProcess, WorkerPool,
+};
 use prusti_common::{config, verification_service::*};
 
 use bincode;
 use futures::{self};
 use num_cpus;
-use reqwest::{self, Client, Url, UrlError};
+use reqwest::{self, Client, RequestBuilder, Url, UrlError};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json;
 use std::{
+    fmt,
     net::{Ipv4Addr, SocketAddr},
     sync::{mpsc, Arc},
     thread,
+    time::{Duration, Instant},
 };
 use tokio;
 use viper::VerificationResult;
-use warp::{self, Buf, Filter};
+use warp::{self, Buf, Filter, Rejection, Reply};
+
+/// Where the server verifies the requests it receives.
+enum ServerBackend {
+    /// All requests share the JVM of the server process, and are verified one at a time.
+    InProcess(PrustiServer),
+    /// Each request is verified by one of several child processes with their own JVMs.
+    Workers(WorkerPool),
+}
 
 #[derive(Clone)]
 pub struct ServerSideService {
-    server: Arc<PrustiServer>,
+    backend: Arc<ServerBackend>,
+    /// The results of earlier requests, which are shared by all backends.
+    results: Arc<ResultCache>,
+    jobs: Arc<JobManager>,
     max_concurrency: usize,
 }
 
 impl ServerSideService {
     pub fn new() -> Self {
+        let max_concurrency = config::server_max_concurrency().unwrap_or_else(num_cpus::get);
+        let results = Arc::new(ResultCache::new(
+            config::server_max_cached_results(),
+            config::server_cache_path(),
+        ));
+
+        if config::server_worker_processes() {
+            let driver_path = ServerProcess::default_driver_path();
+            let workers = WorkerPool::new(driver_path, max_concurrency)
+                .unwrap_or_else(|error| panic!("Could not start the worker processes: {}", error));
+            return Self {
+                max_concurrency,
+                backend: Arc::new(ServerBackend::Workers(workers)),
+                results,
+                jobs: Arc::new(Self::new_job_manager(max_concurrency)),
+            };
+        }
+
         // FIXME: since viper seems to dislike using verifiers in parallel, this is what we're doing to ensure correctness for now.
-        // Eventually, we should lock only specific parts, instantiate multiple JVMs, or even address the root cause.
-        let _max_concurrency = config::server_max_concurrency().unwrap_or_else(num_cpus::get);
+        // Eventually, we should lock only specific parts or address the root cause. Until then, PRUSTI_SERVER_WORKER_PROCESSES runs requests in parallel in separate JVMs.
         let max_concurrency = 1;
 
         let cache_size = config::server_max_stored_verifiers().unwrap_or(max_concurrency);
         if cache_size < max_concurrency {
             warn!("PRUSTI_SERVER_MAX_STORED_VERIFIERS is lower than PRUSTI_SERVER_MAX_CONCURRENCY—you probably don't want to do this, since it means the server will likely have to keep creating new verifiers, reducing the performance gained from reuse.");
         }
-
         Self {
             max_concurrency,
-            server: Arc::new(PrustiServer::new(cache_size)),
+            backend: Arc::new(ServerBackend::InProcess(PrustiServer::new(cache_size))),
+            results,
+            jobs: Arc::new(Self::new_job_manager(max_concurrency)),
         }
     }
 
+    fn new_job_manager(max_concurrency: usize) -> JobManager {
+        JobManager::new(max_concurrency, Duration::from_secs(config::server_job_ttl()))
+    }
+
     pub fn spawn_off_thread() -> SocketAddr {
         let (sender, receiver) = mpsc::channel();
         thread::spawn(move || {
@@ -99,7 +139,10 @@ impl ServerSideService {
                 )
             });
 
-        let endpoints = json_verify.or(bincode_verify);
+        let endpoints = json_verify
+            .or(bincode_verify)
+            .or(self.job_endpoints(Encoding::Json))
+            .or(self.job_endpoints(Encoding::Bincode));
 
         info!("Prusti Server binding to port {}", port);
         let (address, server_handle) =
@@ -123,9 +166,160 @@ impl ServerSideService {
         thread::park();
     }
 
+    /// Verify the request, or reuse the result of an identical request.
     fn verify(&self, request: VerificationRequest) -> RemoteVerificationResult {
         info!("Handling verification request for {}", request.program_name);
-        self.server.run_verifier(request)
+        self.results.get_or_verify(request, |request| match self.backend.as_ref() {
+            ServerBackend::InProcess(server) => server.run_verifier(request),
+            ServerBackend::Workers(workers) => workers.run_verifier(request),
+        })
+    }
+
+    /// The endpoints of the job API, under `/json/jobs` or `/bincode/jobs`:
+    /// - `POST /jobs` starts verifying the request in the body, and returns the id of the job;
+    /// - `GET /jobs/<id>` returns the `JobStatus` of the job;
+    /// - `GET /jobs/<id>/results/<from>` returns the results that are available, skipping the
+    ///   first `from` of them;
+    /// - `DELETE /jobs/<id>` cancels the job and forgets about it.
+    fn job_endpoints(
+        &self,
+        encoding: Encoding,
+    ) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
+        let jobs = warp::path(encoding.path_segment()).and(warp::path("jobs"));
+
+        let clone = self.clone();
+        let submit = jobs
+            .clone()
+            .and(warp::path::end())
+            .and(warp::post2())
+            .and(warp::body::concat())
+            .and_then(move |buf: warp::body::FullBody| {
+                encoding.decode(buf.bytes()).map_err(|err| {
+                    info!("request body error: {}", err);
+                    warp::reject::custom(err)
+                })
+            })
+            .map(move |request: VerificationRequest| encoding.reply(&clone.submit_job(request)));
+
+        let clone = self.clone();
+        let status = jobs
+            .clone()
+            .and(warp::path::param::<JobId>())
+            .and(warp::path::end())
+            .and(warp::get2())
+            .and_then(move |id| {
+                clone
+                    .jobs
+                    .status(id)
+                    .map(|status| encoding.reply(&status))
+                    .ok_or_else(warp::reject::not_found)
+            });
+
+        let clone = self.clone();
+        let results = jobs
+            .clone()
+            .and(warp::path::param::<JobId>())
+            .and(warp::path("results"))
+            .and(warp::path::param::<usize>())
+            .and(warp::path::end())
+            .and(warp::get2())
+            .and_then(move |id, from| {
+                clone
+                    .jobs
+                    .results(id, from)
+                    .map(|results| encoding.reply(&results))
+                    .ok_or_else(warp::reject::not_found)
+            });
+
+        let clone = self.clone();
+        let cancel = jobs
+            .and(warp::path::param::<JobId>())
+            .and(warp::path::end())
+            .and(warp::delete2())
+            .and_then(move |id| {
+                if clone.jobs.cancel(id) {
+                    info!("Cancelled job {}", id);
+                    Ok(encoding.reply(&()))
+                } else {
+                    Err(warp::reject::not_found())
+                }
+            });
+
+        submit.or(status).or(results).or(cancel)
+    }
+
+    fn submit_job(&self, request: VerificationRequest) -> JobId {
+        let program_name = request.program_name.clone();
+        let clone = self.clone();
+        let id = self.jobs.submit(request, move |request| clone.verify(request));
+        info!("Started job {} for {}", id, program_name);
+        id
+    }
+}
+
+/// The encoding of the bodies of requests and responses.
+#[derive(Clone, Copy)]
+enum Encoding {
+    Json,
+    Bincode,
+}
+
+impl Encoding {
+    fn path_segment(self) -> &'static str {
+        match self {
+            Encoding::Json => "json",
+            Encoding::Bincode => "bincode",
+        }
+    }
+
+    fn decode<T: DeserializeOwned>(self, bytes: &[u8]) -> Result<T, String> {
+        match self {
+            Encoding::Json => serde_json::from_slice(bytes).map_err(|err| err.to_string()),
+            Encoding::Bincode => bincode::deserialize(bytes).map_err(|err| err.to_string()),
+        }
+    }
+
+    fn reply<T: Serialize>(self, value: &T) -> warp::http::Response<Vec<u8>> {
+        let body = match self {
+            Encoding::Json => serde_json::to_vec(value).expect("could not encode response"),
+            Encoding::Bincode => bincode::serialize(value).expect("could not encode response"),
+        };
+        warp::http::Response::new(body)
+    }
+}
+
+/// The timeout of each HTTP request to the server. Verifying a program can take much longer,
+/// since the client only polls the job that verifies it.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How long to wait before asking the server again for the results of a job.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// An error while verifying a program on the server.
+#[derive(Debug)]
+pub enum ServerConnectionError {
+    /// A request to the server failed.
+    Request(reqwest::Error),
+    /// The server did not verify the program within `config::server_job_timeout()`.
+    Timeout,
+}
+
+impl From<reqwest::Error> for ServerConnectionError {
+    fn from(error: reqwest::Error) -> Self {
+        ServerConnectionError::Request(error)
+    }
+}
+
+impl fmt::Display for ServerConnectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ServerConnectionError::Request(error) => write!(f, "{}", error),
+            ServerConnectionError::Timeout => write!(
+                f,
+                "the server did not verify the program within {} seconds",
+                config::server_job_timeout()
+            ),
+        }
     }
 }
 
@@ -141,33 +335,128 @@ impl PrustiServerConnection {
             address = format!("http://{}", address);
         }
         Ok(Self {
-            client: Client::builder().timeout(None).build().unwrap(),
+            client: Client::builder().timeout(REQUEST_TIMEOUT).build().unwrap(),
             server_url: Url::parse(address.as_str())?,
         })
     }
 
+    /// Verify the request in a job on the server, and combine the results of its methods.
     pub fn verify_checked(
         &self,
         request: VerificationRequest,
-    ) -> reqwest::Result<RemoteVerificationResult> {
-        let use_json = config::json_communication();
-        let base = self.client.post(
-            self.server_url
-                .join(if use_json { "json/" } else { "bincode/" })
-                .unwrap()
-                .join("verify/")
-                .unwrap(),
-        );
-        let response = if use_json {
-            base.json(&request).send()?.error_for_status()?.json()?
+    ) -> Result<RemoteVerificationResult, ServerConnectionError> {
+        let results = self.verify_streaming(request, |result| {
+            debug!("Received the result of {}", result.program_name);
+        })?;
+        Ok(combine_results(results))
+    }
+
+    /// Verify the request in a job on the server, and call `on_result` with the result of each
+    /// of its methods as soon as it is available. Returns all the results once the job finished.
+    /// The job is cancelled if it does not finish within `config::server_job_timeout()`.
+    pub fn verify_streaming<F>(
+        &self,
+        request: VerificationRequest,
+        mut on_result: F,
+    ) -> Result<Vec<ProgramResult>, ServerConnectionError>
+    where
+        F: FnMut(&ProgramResult),
+    {
+        let timeout = config::server_job_timeout();
+        let deadline = if timeout > 0 {
+            Some(Instant::now() + Duration::from_secs(timeout))
         } else {
-            let raw = base
-                .body(bincode::serialize(&request).expect("error encoding verification request"))
-                .send()?
-                .error_for_status()?;
-            bincode::deserialize_from(raw).expect("error decoding verification result")
+            None
         };
-        Ok(response)
+        let id = self.submit_job(&request)?;
+        let mut results = vec![];
+        loop {
+            // All results are available once the job is reported to be finished.
+            let status = self.job_status(id)?;
+            for result in self.job_results(id, results.len())? {
+                on_result(&result);
+                results.push(result);
+            }
+            if status.state == JobState::Finished {
+                break;
+            }
+            if deadline.map_or(false, |deadline| Instant::now() >= deadline) {
+                warn!("Cancelling job {} of {}, which timed out", id, request.program_name);
+                self.cancel_job(id)?;
+                return Err(ServerConnectionError::Timeout);
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+        // Free the results stored by the server.
+        self.cancel_job(id)?;
+        Ok(results)
+    }
+
+    /// Start verifying the request in a job on the server.
+    pub fn submit_job(&self, request: &VerificationRequest) -> reqwest::Result<JobId> {
+        let builder = self.client.post(self.jobs_url(""));
+        let builder = if config::json_communication() {
+            builder.json(request)
+        } else {
+            builder.body(bincode::serialize(request).expect("error encoding verification request"))
+        };
+        self.send(builder)
+    }
+
+    pub fn job_status(&self, id: JobId) -> reqwest::Result<JobStatus> {
+        self.send(self.client.get(self.jobs_url(&id.to_string())))
+    }
+
+    /// The results of the job that are available, skipping the first `from` of them.
+    pub fn job_results(&self, id: JobId, from: usize) -> reqwest::Result<Vec<ProgramResult>> {
+        self.send(self.client.get(self.jobs_url(&format!("{}/results/{}", id, from))))
+    }
+
+    /// Stop the job after the method that the server is currently verifying, and make the
+    /// server forget about it.
+    pub fn cancel_job(&self, id: JobId) -> reqwest::Result<()> {
+        self.client
+            .delete(self.jobs_url(&id.to_string()))
+            .send()?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    fn jobs_url(&self, path: &str) -> Url {
+        let encoding = if config::json_communication() { "json/" } else { "bincode/" };
+        self.server_url
+            .join(encoding)
+            .unwrap()
+            .join("jobs/")
+            .unwrap()
+            .join(path)
+            .unwrap()
+    }
+
+    fn send<T: DeserializeOwned>(&self, builder: RequestBuilder) -> reqwest::Result<T> {
+        let mut response = builder.send()?.error_for_status()?;
+        if config::json_communication() {
+            response.json()
+        } else {
+            Ok(bincode::deserialize_from(response).expect("error decoding server response"))
+        }
+    }
+}
+
+/// Combine the results of the methods of a program into the result of the whole program.
+pub fn combine_results(results: Vec<ProgramResult>) -> RemoteVerificationResult {
+    let mut errors = vec![];
+    for program_result in results {
+        match program_result.result? {
+            VerificationResult::Success() => {}
+            VerificationResult::Failure(program_errors) => errors.extend(program_errors),
+            result => return Ok(result),
+        }
+    }
+    if errors.is_empty() {
+        Ok(VerificationResult::Success())
+    } else {
+        Ok(VerificationResult::Failure(errors))
     }
 }
 
@@ -0,0 +1,147 @@
+// © 2021, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use super::{PrustiServerConnection, RemoteVerificationResult, ServerProcess, VerifierPanicked};
+use prusti_common::verification_service::VerificationRequest;
+use std::{
+    io,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    sync::{Condvar, Mutex},
+};
+
+/// A server process together with a connection to it.
+struct Worker {
+    process: ServerProcess,
+    connection: PrustiServerConnection,
+}
+
+impl Worker {
+    fn spawn(driver_path: &Path) -> io::Result<Self> {
+        let process = ServerProcess::spawn(driver_path)?;
+        let connection = PrustiServerConnection::new(process.address())
+            .expect("invalid address of a worker process");
+        Ok(Worker {
+            process,
+            connection,
+        })
+    }
+}
+
+struct WorkerPoolState {
+    idle_workers: Vec<Worker>,
+    /// The number of workers, both idle and busy.
+    num_workers: usize,
+}
+
+/// A pool of worker processes, each of which runs a Prusti server with its own JVM.
+///
+/// Every worker verifies one request at a time, so that requests verified by different workers
+/// do not share a JVM. Workers that crash are replaced by new ones when the next request needs
+/// them, so a worker that could not be restarted is started again later.
+pub struct WorkerPool {
+    driver_path: PathBuf,
+    max_workers: usize,
+    state: Mutex<WorkerPoolState>,
+    worker_released: Condvar,
+}
+
+impl WorkerPool {
+    /// Start `num_workers` processes of the server executable at `driver_path`.
+    pub fn new(driver_path: PathBuf, num_workers: usize) -> io::Result<Self> {
+        info!("Starting {} worker processes of {:?}", num_workers, driver_path);
+        let idle_workers = (0..num_workers)
+            .map(|_| Worker::spawn(&driver_path))
+            .collect::<io::Result<Vec<_>>>()?;
+        Ok(WorkerPool {
+            driver_path,
+            max_workers: num_workers,
+            state: Mutex::new(WorkerPoolState {
+                idle_workers,
+                num_workers,
+            }),
+            worker_released: Condvar::new(),
+        })
+    }
+
+    /// Verify `request` on the next idle worker, waiting for one to become idle if necessary.
+    pub fn run_verifier(&self, request: VerificationRequest) -> RemoteVerificationResult {
+        let worker = self.acquire_worker()?;
+        let program_name = request.program_name.clone();
+
+        // Decoding the response of a worker that crashed in the middle of it panics.
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            worker.connection.verify_checked(request)
+        }));
+        match result {
+            Ok(Ok(result)) => {
+                self.release_worker(Some(worker));
+                result
+            }
+            Ok(Err(_)) | Err(_) => {
+                error!(
+                    "Worker process at {} failed while handling verification request {}",
+                    worker.process.address(),
+                    program_name
+                );
+                drop(worker);
+                self.release_worker(None);
+                Err(VerifierPanicked)
+            }
+        }
+    }
+
+    /// Take an idle worker out of the pool. Workers whose process exited while they were idle
+    /// are dropped, and missing workers are started again if there is no idle one.
+    fn acquire_worker(&self) -> Result<Worker, VerifierPanicked> {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if let Some(mut worker) = state.idle_workers.pop() {
+                if worker.process.is_running() {
+                    return Ok(worker);
+                }
+                warn!("Worker process at {} exited", worker.process.address());
+                drop(worker);
+                state.num_workers -= 1;
+                continue;
+            }
+            if state.num_workers < self.max_workers {
+                // Start the worker without holding the lock, since it takes a while.
+                state.num_workers += 1;
+                drop(state);
+                info!("Starting a replacement worker process");
+                match Worker::spawn(&self.driver_path) {
+                    Ok(worker) => return Ok(worker),
+                    Err(error) => {
+                        error!("Could not start a worker process: {}", error);
+                        state = self.state.lock().unwrap();
+                        state.num_workers -= 1;
+                        // Without any other worker the request would wait forever. Later
+                        // requests try to start the worker again.
+                        if state.num_workers == 0 {
+                            return Err(VerifierPanicked);
+                        }
+                        // Wait for one of the other workers instead of retrying right away.
+                        state = self.worker_released.wait(state).unwrap();
+                        continue;
+                    }
+                }
+            }
+            state = self.worker_released.wait(state).unwrap();
+        }
+    }
+
+    /// Put a worker that was taken out of the pool back; `None` if it crashed.
+    fn release_worker(&self, worker: Option<Worker>) {
+        let mut state = self.state.lock().unwrap();
+        match worker {
+            Some(worker) => state.idle_workers.push(worker),
+            None => state.num_workers -= 1,
+        }
+        // Waiting threads can start a replacement for a crashed worker.
+        self.worker_released.notify_all();
+    }
+}
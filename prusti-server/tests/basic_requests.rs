@@ -8,7 +8,7 @@ use prusti_common::{
     verification_service::{VerificationRequest, VerificationService},
     vir::*,
 };
-use prusti_server::{PrustiServerConnection, ServerSideService};
+use prusti_server::{JobState, PrustiServerConnection, ServerSideService};
 use viper::VerificationResult;
 
 lazy_static! {
@@ -47,13 +47,57 @@ fn empty_program() {
     }
 }
 
+#[test]
+fn job_results_and_cancellation() {
+    let service = connect();
+    let id = service
+        .submit_job(&build_request(|_| ()))
+        .expect("could not submit job");
+
+    let status = loop {
+        let status = service.job_status(id).expect("could not get job status");
+        if status.state == JobState::Finished {
+            break status;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    };
+    assert_eq!(status.num_programs, 1);
+    assert_eq!(status.num_verified, 1);
+
+    let results = service.job_results(id, 0).expect("could not get job results");
+    assert_eq!(results.len(), 1);
+    match &results[0].result {
+        Ok(VerificationResult::Success()) => (),
+        other => panic!(
+            "empty program not verified successfully, instead found {:?}",
+            other
+        ),
+    }
+    assert!(service
+        .job_results(id, 1)
+        .expect("could not get job results")
+        .is_empty());
+
+    service.cancel_job(id).expect("could not cancel job");
+    assert!(service.job_status(id).is_err(), "cancelled job still exists");
+    assert!(service.cancel_job(id).is_err(), "cancelled job cancelled again");
+}
+
 fn process_program<F>(configure: F) -> VerificationResult
 where
     F: FnOnce(&mut Program),
 {
-    let service =
-        PrustiServerConnection::new(SERVER_ADDRESS.clone()).expect("Could not connect to server!");
+    connect().verify(build_request(configure))
+}
 
+fn connect() -> PrustiServerConnection {
+    PrustiServerConnection::new(SERVER_ADDRESS.clone()).expect("Could not connect to server!")
+}
+
+fn build_request<F>(configure: F) -> VerificationRequest
+where
+    F: FnOnce(&mut Program),
+{
     let mut program = Program {
         domains: vec![],
         fields: vec![],
@@ -64,11 +108,9 @@ where
     };
     configure(&mut program);
 
-    let request = VerificationRequest {
+    VerificationRequest {
         program,
         program_name: "dummy".to_string(),
         backend_config: Default::default(),
-    };
-
-    service.verify(request)
+    }
 }
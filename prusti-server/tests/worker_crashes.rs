@@ -0,0 +1,135 @@
+//! Tests that kill worker processes. They are in their own test executable, since they kill all
+//! child processes of the test process, which would include the workers of other tests.
+#![cfg(unix)]
+
+extern crate prusti_common;
+extern crate prusti_server;
+extern crate viper;
+
+use prusti_common::{verification_service::VerificationRequest, vir::*};
+use prusti_server::{VerifierPanicked, WorkerPool};
+use std::{
+    env,
+    path::PathBuf,
+    process::{self, Command},
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+use viper::VerificationResult;
+
+/// test that workers killed between requests and during a request are replaced, and that the
+/// requests on the other workers are not affected
+#[test]
+fn killed_workers() {
+    let pool = Arc::new(WorkerPool::new(server_path(), 2).expect("failed to start the workers"));
+    assert_verified(pool.run_verifier(empty_program_request(0)));
+
+    // Kill all workers while they are idle.
+    let workers = worker_process_ids();
+    assert_eq!(workers.len(), 2);
+    for &pid in &workers {
+        kill(pid);
+    }
+    thread::sleep(Duration::from_millis(100));
+    for result in run_concurrently(&pool, 1..5) {
+        assert_verified(result);
+    }
+
+    // Kill one of the workers while the requests are being verified.
+    let workers = worker_process_ids();
+    assert_eq!(workers.len(), 2);
+    let handles: Vec<_> = (5..13)
+        .map(|index| {
+            let pool = pool.clone();
+            thread::spawn(move || pool.run_verifier(empty_program_request(index)))
+        })
+        .collect();
+    thread::sleep(Duration::from_millis(20));
+    kill(workers[0]);
+    let mut num_failed = 0;
+    for handle in handles {
+        match handle.join().unwrap() {
+            Ok(VerificationResult::Success()) => (),
+            Err(VerifierPanicked) => num_failed += 1,
+            other => panic!("unexpected verification result {:?}", other),
+        }
+    }
+    // Only the request that the killed worker was verifying can fail.
+    assert!(num_failed <= 1, "{} requests failed", num_failed);
+
+    for result in run_concurrently(&pool, 13..17) {
+        assert_verified(result);
+    }
+}
+
+fn run_concurrently(
+    pool: &Arc<WorkerPool>,
+    indices: std::ops::Range<usize>,
+) -> Vec<Result<VerificationResult, VerifierPanicked>> {
+    let handles: Vec<_> = indices
+        .map(|index| {
+            let pool = pool.clone();
+            thread::spawn(move || pool.run_verifier(empty_program_request(index)))
+        })
+        .collect();
+    handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect()
+}
+
+fn assert_verified(result: Result<VerificationResult, VerifierPanicked>) {
+    match result {
+        Ok(VerificationResult::Success()) => (),
+        other => panic!(
+            "empty program not verified successfully, instead found {:?}",
+            other
+        ),
+    }
+}
+
+/// The worker processes are the only child processes of the test process.
+fn worker_process_ids() -> Vec<u32> {
+    let output = Command::new("pgrep")
+        .args(&["-P", &process::id().to_string()])
+        .output()
+        .expect("failed to run pgrep");
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| line.trim().parse().unwrap())
+        .collect()
+}
+
+fn kill(pid: u32) {
+    let status = Command::new("kill")
+        .args(&["-KILL", &pid.to_string()])
+        .status()
+        .expect("failed to run kill");
+    assert!(status.success());
+}
+
+fn server_path() -> PathBuf {
+    env::current_exe()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .with_file_name("prusti-server-driver")
+        .with_extension(env::consts::EXE_EXTENSION)
+}
+
+fn empty_program_request(index: usize) -> VerificationRequest {
+    VerificationRequest {
+        program: Program {
+            domains: vec![],
+            fields: vec![],
+            builtin_methods: vec![],
+            methods: vec![],
+            functions: vec![],
+            viper_predicates: vec![],
+        },
+        program_name: format!("killed_workers{}", index),
+        backend_config: Default::default(),
+    }
+}
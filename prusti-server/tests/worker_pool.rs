@@ -0,0 +1,62 @@
+extern crate prusti_common;
+extern crate prusti_server;
+extern crate viper;
+
+use prusti_common::{verification_service::VerificationRequest, vir::*};
+use prusti_server::WorkerPool;
+use std::{env, path::PathBuf, sync::Arc, thread};
+use viper::VerificationResult;
+
+/// test that concurrent requests are distributed over the workers and all get verified
+#[test]
+fn concurrent_requests() {
+    let pool = Arc::new(WorkerPool::new(server_path(), 2).expect("failed to start the workers"));
+
+    let handles: Vec<_> = (0..4)
+        .map(|index| {
+            let pool = pool.clone();
+            thread::spawn(move || pool.run_verifier(empty_program_request(index)))
+        })
+        .collect();
+
+    for handle in handles {
+        match handle.join().unwrap() {
+            Ok(VerificationResult::Success()) => (),
+            other => panic!(
+                "empty program not verified successfully, instead found {:?}",
+                other
+            ),
+        }
+    }
+}
+
+/// test that the pool reports workers that cannot be started
+#[test]
+fn missing_executable() {
+    let path = server_path().with_file_name("no-such-prusti-server-driver");
+    assert!(WorkerPool::new(path, 1).is_err());
+}
+
+fn server_path() -> PathBuf {
+    env::current_exe()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .with_file_name("prusti-server-driver")
+        .with_extension(env::consts::EXE_EXTENSION)
+}
+
+fn empty_program_request(index: usize) -> VerificationRequest {
+    VerificationRequest {
+        program: Program {
+            domains: vec![],
+            fields: vec![],
+            builtin_methods: vec![],
+            methods: vec![],
+            functions: vec![],
+            viper_predicates: vec![],
+        },
+        program_name: format!("empty{}", index),
+        backend_config: Default::default(),
+    }
+}
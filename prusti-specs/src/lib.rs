@@ -8,16 +8,19 @@ mod span_overrider;
 mod extern_spec_rewriter;
 mod rewriter;
 mod parse_closure_macro;
+mod parse_predicate_macro;
 mod spec_attribute_kind;
 pub mod specifications;
+pub mod runtime_checks;
 
 use proc_macro2::{Span, TokenStream};
-use quote::{quote, quote_spanned, ToTokens};
+use quote::{quote, quote_spanned, format_ident, ToTokens};
 use syn::spanned::Spanned;
 use std::convert::{TryFrom, TryInto};
 
 use specifications::untyped;
 use parse_closure_macro::ClosureWithSpec;
+use parse_predicate_macro::PredicateFn;
 pub use spec_attribute_kind::SpecAttributeKind;
 
 macro_rules! handle_result {
@@ -89,6 +92,11 @@ fn generate_spec_and_assertions(
             SpecAttributeKind::AfterExpiryIf => generate_for_after_expiry_if(attr_tokens, item),
             SpecAttributeKind::Pure => generate_for_pure(attr_tokens, item),
             SpecAttributeKind::Trusted => generate_for_trusted(attr_tokens, item),
+            SpecAttributeKind::Decreases => generate_for_decreases(attr_tokens, item),
+            SpecAttributeKind::Invariant => Err(syn::Error::new(
+                attr_tokens.span(),
+                "the `#[invariant]` attribute can only be applied to structs and enums"
+            )),
         };
         let (new_items, new_attributes) = rewriting_result?;
         generated_items.extend(new_items);
@@ -100,6 +108,7 @@ fn generate_spec_and_assertions(
 
 /// Generate spec items and attributes to typecheck the and later retrieve "requires" annotations.
 fn generate_for_requires(attr: TokenStream, item: &untyped::AnyFnItem) -> GeneratedResult {
+    check_no_labelled_old(&attr)?;
     let mut rewriter = rewriter::AstRewriter::new();
     let spec_id = rewriter.generate_spec_id();
     let spec_id_str = spec_id.to_string();
@@ -120,6 +129,7 @@ fn generate_for_requires(attr: TokenStream, item: &untyped::AnyFnItem) -> Genera
 
 /// Generate spec items and attributes to typecheck th and later retrieve "ensures" annotations.
 fn generate_for_ensures(attr: TokenStream, item: &untyped::AnyFnItem) -> GeneratedResult {
+    check_no_labelled_old(&attr)?;
     let mut rewriter = rewriter::AstRewriter::new();
     let spec_id = rewriter.generate_spec_id();
     let spec_id_str = spec_id.to_string();
@@ -138,6 +148,30 @@ fn generate_for_ensures(attr: TokenStream, item: &untyped::AnyFnItem) -> Generat
     ))
 }
 
+/// Check that a contract does not refer to a label with `old[label](..)`: the labels are
+/// declared in the body of the function, so they do not exist in the states of its contract.
+fn check_no_labelled_old(tokens: &TokenStream) -> syn::Result<()> {
+    let mut tokens = tokens.clone().into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            proc_macro2::TokenTree::Ident(ident) if ident == "old" => {
+                if let Some(proc_macro2::TokenTree::Group(group)) = tokens.peek() {
+                    if group.delimiter() == proc_macro2::Delimiter::Bracket {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            "`old[label](..)` can only be used in the body of a function, not \
+                            in its contract",
+                        ));
+                    }
+                }
+            }
+            proc_macro2::TokenTree::Group(group) => check_no_labelled_old(&group.stream())?,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
 /// Check if the given expression is identifier `result`.
 fn check_is_result(reference: &Option<untyped::Expression>) -> syn::Result<()> {
     if let Some(untyped::Expression { expr, ..}) = reference {
@@ -157,6 +191,7 @@ fn check_is_result(reference: &Option<untyped::Expression>) -> syn::Result<()> {
 
 /// Generate spec items and attributes to typecheck and later retrieve "after_expiry" annotations.
 fn generate_for_after_expiry(attr: TokenStream, item: &untyped::AnyFnItem) -> GeneratedResult {
+    check_no_labelled_old(&attr)?;
     let mut rewriter = rewriter::AstRewriter::new();
     let spec_id_rhs = rewriter.generate_spec_id();
     let spec_id_rhs_str = format!(":{}", spec_id_rhs);
@@ -180,6 +215,7 @@ fn generate_for_after_expiry(attr: TokenStream, item: &untyped::AnyFnItem) -> Ge
 /// Generate spec items and attributes to typecheck and later retrieve "after_expiry_if"
 /// annotations.
 fn generate_for_after_expiry_if(attr: TokenStream, item: &untyped::AnyFnItem) -> GeneratedResult {
+    check_no_labelled_old(&attr)?;
     let mut rewriter = rewriter::AstRewriter::new();
     let spec_id_lhs = rewriter.generate_spec_id();
     let spec_id_rhs = rewriter.generate_spec_id();
@@ -227,6 +263,21 @@ fn generate_for_pure(attr: TokenStream, item: &untyped::AnyFnItem) -> GeneratedR
     ))
 }
 
+/// Generate spec items and attributes to typecheck and later retrieve "decreases" annotations.
+fn generate_for_decreases(attr: TokenStream, item: &untyped::AnyFnItem) -> GeneratedResult {
+    let mut rewriter = rewriter::AstRewriter::new();
+    let spec_id = rewriter.generate_spec_id();
+    let spec_id_str = spec_id.to_string();
+    let measure = rewriter.parse_measure(spec_id, attr)?;
+    let spec_item = rewriter.generate_spec_item_decreases(spec_id, measure, &item);
+    Ok((
+        vec![spec_item],
+        vec![parse_quote_spanned! {item.span()=>
+            #[prusti::decreases_spec_id_ref = #spec_id_str]
+        }],
+    ))
+}
+
 /// Generate spec items and attributes to typecheck and later retrieve "trusted" annotations.
 fn generate_for_trusted(attr: TokenStream, item: &untyped::AnyFnItem) -> GeneratedResult {
     if !attr.is_empty() {
@@ -244,6 +295,61 @@ fn generate_for_trusted(attr: TokenStream, item: &untyped::AnyFnItem) -> Generat
     ))
 }
 
+/// Rewrite a struct or enum as required by *all* its `#[invariant(...)]` attributes.
+///
+/// Each invariant is type-checked in a spec method that takes `self` by value,
+/// generated in a separate inherent impl block of the type.
+pub fn invariant(attr: TokenStream, tokens: TokenStream) -> TokenStream {
+    let mut item: syn::Item = handle_result!(syn::parse2(tokens));
+    let item_span = item.span();
+    let (ident, generics, attrs) = match item {
+        syn::Item::Struct(ref mut item_struct) => (
+            item_struct.ident.clone(),
+            item_struct.generics.clone(),
+            &mut item_struct.attrs,
+        ),
+        syn::Item::Enum(ref mut item_enum) => (
+            item_enum.ident.clone(),
+            item_enum.generics.clone(),
+            &mut item_enum.attrs,
+        ),
+        _ => {
+            return syn::Error::new(
+                item_span,
+                "the `#[invariant]` attribute can only be applied to structs and enums"
+            ).to_compile_error();
+        }
+    };
+
+    // Start with the outer attribute and collect the remaining invariants,
+    // removing them from `item`.
+    let mut invariants = vec![attr];
+    invariants.extend(
+        attrs.drain_filter(|attr| attr.path.is_ident("invariant"))
+            .map(|attr| attr.tokens)
+    );
+
+    let mut rewriter = rewriter::AstRewriter::new();
+    let mut spec_items = vec![];
+    for tokens in invariants {
+        let spec_id = rewriter.generate_spec_id();
+        let spec_id_str = spec_id.to_string();
+        let assertion = handle_result!(rewriter.parse_assertion(spec_id, tokens));
+        spec_items.push(rewriter.generate_spec_item_invariant(spec_id, assertion, &ident));
+        attrs.push(parse_quote_spanned! {item_span=>
+            #[prusti::inv_spec_id_ref = #spec_id_str]
+        });
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote_spanned! {item_span=>
+        #item
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #(#spec_items)*
+        }
+    }
+}
+
 pub fn body_invariant(tokens: TokenStream) -> TokenStream {
     let mut rewriter = rewriter::AstRewriter::new();
     let spec_id = rewriter.generate_spec_id();
@@ -258,6 +364,86 @@ pub fn body_invariant(tokens: TokenStream) -> TokenStream {
     }
 }
 
+pub fn body_variant(tokens: TokenStream) -> TokenStream {
+    let mut rewriter = rewriter::AstRewriter::new();
+    let spec_id = rewriter.generate_spec_id();
+    let variant = handle_result!(rewriter.parse_measure(spec_id, tokens));
+    let check = rewriter.generate_spec_loop_variant(spec_id, variant);
+    let callsite_span = Span::call_site();
+    quote_spanned! {callsite_span=>
+        #[allow(unused_must_use, unused_variables)]
+        if false {
+            #check
+        }
+    }
+}
+
+pub fn prusti_assertion(tokens: TokenStream) -> TokenStream {
+    generate_ghost_statement(tokens, false)
+}
+
+pub fn prusti_assumption(tokens: TokenStream) -> TokenStream {
+    generate_ghost_statement(tokens, true)
+}
+
+fn generate_ghost_statement(tokens: TokenStream, is_assumption: bool) -> TokenStream {
+    let mut rewriter = rewriter::AstRewriter::new();
+    let spec_id = rewriter.generate_spec_id();
+    let assertion = handle_result!(rewriter.parse_assertion(spec_id, tokens));
+    let check = rewriter.generate_spec_statement(spec_id, assertion, is_assumption);
+    let callsite_span = Span::call_site();
+    quote_spanned! {callsite_span=>
+        #[allow(unused_must_use, unused_variables)]
+        if false {
+            #check
+        }
+    }
+}
+
+/// Declare a label at the current program point, to which specifications can refer with
+/// `old[label](..)`. The label is declared as a marker type, so that the compiler checks that
+/// every label used in a specification is in scope.
+pub fn prusti_label(tokens: TokenStream) -> TokenStream {
+    let label: syn::Ident = handle_result!(syn::parse2(tokens));
+    if label == "loop_entry" {
+        return syn::Error::new(label.span(), "`loop_entry` is a built-in label")
+            .to_compile_error();
+    }
+    let marker = format_ident!("prusti_label_{}", label);
+    let label_str = label.to_string();
+    let callsite_span = Span::call_site();
+    quote_spanned! {callsite_span=>
+        #[allow(non_camel_case_types)]
+        struct #marker {}
+        #[allow(unused_must_use, unused_variables)]
+        if false {
+            #[prusti::spec_only]
+            #[prusti::label = #label_str]
+            || {};
+        }
+    }
+}
+
+/// Keep the statements of a `ghost!` block, surrounded by marker closures whose span covers
+/// the entire block, so that the encoder can tell ghost code apart from the rest of the
+/// function. The marker after the statements is where the ghost code is left normally.
+pub fn ghost(tokens: TokenStream) -> TokenStream {
+    let callsite_span = Span::call_site();
+    let marker = quote_spanned! {callsite_span=>
+        #[allow(unused_must_use, unused_variables)]
+        if false {
+            #[prusti::spec_only]
+            #[prusti::ghost_block]
+            || {};
+        }
+    };
+    quote_spanned! {callsite_span=>
+        #marker
+        #tokens
+        #marker
+    }
+}
+
 /// Unlike the functions above, which are only called from
 /// prusti-contracts-internal, this function also needs to be called
 /// from prusti-contracts-impl, because we still need to parse the
@@ -346,6 +532,63 @@ pub fn closure(tokens: TokenStream, drop_spec: bool) -> TokenStream {
     }
 }
 
+/// Rewrite a `predicate!` into a trusted pure function that is only meant to be
+/// used in specifications. The body of the predicate is an assertion, which is
+/// type-checked in a spec item and later used by the encoder as the body of
+/// the function.
+///
+/// Like `closure`, this function is also called from prusti-contracts-impl,
+/// where `drop_spec` is set. The specifications are then erased, so the predicate
+/// is not needed and is not declared at all, which makes any call from ordinary
+/// code a compilation error.
+pub fn predicate(tokens: TokenStream, drop_spec: bool) -> TokenStream {
+    let pred: PredicateFn = handle_result!(syn::parse2(tokens));
+    let PredicateFn { attrs, vis, sig, body } = pred;
+    let sig_span = sig.span();
+
+    let returns_bool = match &sig.output {
+        syn::ReturnType::Type(_, box syn::Type::Path(type_path)) => type_path.path.is_ident("bool"),
+        _ => false,
+    };
+    if !returns_bool {
+        return syn::Error::new(sig.output.span(), "a predicate must return `bool`")
+            .to_compile_error();
+    }
+
+    if drop_spec {
+        return TokenStream::new();
+    }
+
+    // The body is never executed: Prusti does not run the code it verifies, and the
+    // encoder rejects calls to predicates outside of specifications.
+    let pred_fn = quote_spanned! {sig_span=>
+        #(#attrs)*
+        #[allow(unused_variables)]
+        #vis #sig {
+            unreachable!("predicates can only be used in specifications")
+        }
+    };
+
+    let item = untyped::AnyFnItem::Fn(handle_result!(syn::parse2(pred_fn)));
+    let mut rewriter = rewriter::AstRewriter::new();
+    let spec_id = rewriter.generate_spec_id();
+    let spec_id_str = spec_id.to_string();
+    let assertion = handle_result!(rewriter.parse_assertion(spec_id, body));
+    let spec_item = handle_result!(rewriter.generate_spec_item_fn(
+        rewriter::SpecItemType::Predicate,
+        spec_id,
+        assertion,
+        &item
+    ));
+    quote_spanned! {sig_span=>
+        #spec_item
+        #[prusti::pure]
+        #[prusti::trusted]
+        #[prusti::pred_spec_id_ref = #spec_id_str]
+        #item
+    }
+}
+
 pub fn refine_trait_spec(_attr: TokenStream, tokens: TokenStream) -> TokenStream {
     let mut impl_block: syn::ItemImpl = handle_result!(syn::parse2(tokens));
     let mut new_items = Vec::new();
@@ -0,0 +1,28 @@
+use proc_macro2::TokenStream;
+use syn::parse::{Parse, ParseStream};
+
+/// The input of a `predicate!` macro: a function signature whose body is an
+/// assertion rather than a Rust block.
+pub(crate) struct PredicateFn {
+    pub attrs: Vec<syn::Attribute>,
+    pub vis: syn::Visibility,
+    pub sig: syn::Signature,
+    pub body: TokenStream,
+}
+
+impl Parse for PredicateFn {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        let sig = input.parse()?;
+        let content;
+        syn::braced!(content in input);
+        let body = content.parse()?;
+
+        if !input.is_empty() {
+            return Err(syn::Error::new(input.span(), "unexpected tokens after the predicate"));
+        }
+
+        Ok(PredicateFn { attrs, vis, sig, body })
+    }
+}
@@ -14,6 +14,7 @@ pub(crate) struct AstRewriter {
 pub enum SpecItemType {
     Precondition,
     Postcondition,
+    Predicate,
 }
 
 impl std::fmt::Display for SpecItemType {
@@ -21,6 +22,7 @@ impl std::fmt::Display for SpecItemType {
         match self {
             SpecItemType::Precondition => write!(f, "pre"),
             SpecItemType::Postcondition => write!(f, "post"),
+            SpecItemType::Predicate => write!(f, "pred"),
         }
     }
 }
@@ -56,6 +58,15 @@ impl AstRewriter {
         untyped::Pledge::parse(tokens, spec_id_lhs, spec_id_rhs, &mut self.expr_id_generator)
     }
 
+    /// Parse a termination measure.
+    pub fn parse_measure(
+        &mut self,
+        spec_id: untyped::SpecificationId,
+        tokens: TokenStream,
+    ) -> syn::Result<untyped::Measure> {
+        untyped::Measure::parse(tokens, spec_id, &mut self.expr_id_generator)
+    }
+
     /// Check whether function `item` contains a parameter called `keyword`. If
     /// yes, return its span.
     fn check_contains_keyword_in_params(&self, item: &untyped::AnyFnItem, keyword: &str) -> Option<Span> {
@@ -91,9 +102,8 @@ impl AstRewriter {
         fn_arg
     }
 
-    /// Generate a dummy function for checking the given precondition or postcondition.
-    ///
-    /// `spec_type` should be either `"pre"` or `"post"`.
+    /// Generate a dummy function for checking the given precondition, postcondition or
+    /// predicate body.
     pub fn generate_spec_item_fn(
         &mut self,
         spec_type: SpecItemType,
@@ -134,6 +144,66 @@ impl AstRewriter {
         Ok(syn::Item::Fn(spec_item))
     }
 
+    /// Generate a dummy function for checking the given termination measure.
+    pub fn generate_spec_item_decreases(
+        &mut self,
+        spec_id: untyped::SpecificationId,
+        measure: untyped::Measure,
+        item: &untyped::AnyFnItem,
+    ) -> syn::Item {
+        let item_span = item.span();
+        let item_name = syn::Ident::new(
+            &format!("prusti_decreases_item_{}_{}", item.sig().ident, spec_id),
+            item_span,
+        );
+        let mut statements = TokenStream::new();
+        measure.encode_type_check(&mut statements);
+        let spec_id_str = spec_id.to_string();
+        let assertion_json = crate::specifications::json::to_json_string(&measure.to_assertion());
+        let mut spec_item: syn::ItemFn = parse_quote_spanned! {item_span=>
+            #[allow(unused_must_use, unused_variables, dead_code)]
+            #[prusti::spec_only]
+            #[prusti::spec_id = #spec_id_str]
+            #[prusti::assertion = #assertion_json]
+            fn #item_name() {
+                #statements
+            }
+        };
+        spec_item.sig.generics = item.sig().generics.clone();
+        spec_item.sig.inputs = item.sig().inputs.clone();
+        syn::Item::Fn(spec_item)
+    }
+
+    /// Generate a dummy method for checking the given struct or enum invariant.
+    ///
+    /// The method takes `self` by value, so that the invariant is encoded
+    /// with respect to the memory location of the value.
+    pub fn generate_spec_item_invariant(
+        &mut self,
+        spec_id: untyped::SpecificationId,
+        assertion: untyped::Assertion,
+        type_ident: &syn::Ident,
+    ) -> syn::ImplItemMethod {
+        let item_span = type_ident.span();
+        let item_name = syn::Ident::new(
+            &format!("prusti_inv_item_{}_{}", type_ident, spec_id),
+            item_span,
+        );
+        let mut statements = TokenStream::new();
+        assertion.encode_type_check(&mut statements);
+        let spec_id_str = spec_id.to_string();
+        let assertion_json = crate::specifications::json::to_json_string(&assertion);
+        parse_quote_spanned! {item_span=>
+            #[allow(unused_must_use, unused_variables, dead_code)]
+            #[prusti::spec_only]
+            #[prusti::spec_id = #spec_id_str]
+            #[prusti::assertion = #assertion_json]
+            fn #item_name(self) {
+                #statements
+            }
+        }
+    }
+
     /// Generate statements for checking the given loop invariant.
     pub fn generate_spec_loop(
         &mut self,
@@ -159,6 +229,63 @@ impl AstRewriter {
         }
     }
 
+    /// Generate statements for checking the given loop variant.
+    pub fn generate_spec_loop_variant(
+        &mut self,
+        spec_id: untyped::SpecificationId,
+        measure: untyped::Measure,
+    ) -> TokenStream {
+        let mut statements = TokenStream::new();
+        measure.encode_type_check(&mut statements);
+        let spec_id_str = spec_id.to_string();
+        let assertion_json = crate::specifications::json::to_json_string(&measure.to_assertion());
+        let callsite_span = Span::call_site();
+        quote_spanned! {callsite_span=>
+            #[allow(unused_must_use, unused_variables)]
+            {
+                #[prusti::spec_only]
+                #[prusti::loop_body_variant_spec]
+                #[prusti::spec_id = #spec_id_str]
+                #[prusti::assertion = #assertion_json]
+                || {
+                    #statements
+                };
+            }
+        }
+    }
+
+    /// Generate statements for checking the given `prusti_assert!` or
+    /// `prusti_assume!` statement.
+    pub fn generate_spec_statement(
+        &mut self,
+        spec_id: untyped::SpecificationId,
+        assertion: untyped::Assertion,
+        is_assumption: bool,
+    ) -> TokenStream {
+        let mut statements = TokenStream::new();
+        assertion.encode_type_check(&mut statements);
+        let spec_id_str = spec_id.to_string();
+        let assertion_json = crate::specifications::json::to_json_string(&assertion);
+        let callsite_span = Span::call_site();
+        let kind_attr = if is_assumption {
+            quote_spanned!(callsite_span=> #[prusti::prusti_assumption])
+        } else {
+            quote_spanned!(callsite_span=> #[prusti::prusti_assertion])
+        };
+        quote_spanned! {callsite_span=>
+            #[allow(unused_must_use, unused_variables)]
+            {
+                #[prusti::spec_only]
+                #kind_attr
+                #[prusti::spec_id = #spec_id_str]
+                #[prusti::assertion = #assertion_json]
+                || {
+                    #statements
+                };
+            }
+        }
+    }
+
     /// Generate statements for checking a closure specification.
     /// TODO: arguments, result (types are typically not known yet after parsing...)
     pub fn generate_cl_spec(
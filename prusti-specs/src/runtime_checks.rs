@@ -0,0 +1,509 @@
+//! Translation of specifications into runtime assertions, used by prusti-contracts-impl when
+//! the `runtime-checks` feature is enabled.
+//!
+//! Preconditions are asserted on entry of a function and postconditions on exit, where
+//! `old(expr)` is replaced by a clone of `expr` taken on entry. Quantifiers are executed as
+//! loops over the ranges that their guards give to the quantified variables.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned, format_ident, ToTokens};
+use syn::spanned::Spanned;
+use syn::visit_mut::VisitMut;
+
+use crate::parse_predicate_macro::PredicateFn;
+use crate::specifications::common::AssertionKind;
+use crate::specifications::preparser::{Arg, AssertionWithoutId, Parser};
+use crate::specifications::untyped::AnyFnItem;
+use crate::{extract_prusti_attributes, SpecAttributeKind};
+
+/// Rewrite a function such that its contract is checked at runtime. The specification
+/// attributes without a runtime meaning, such as `#[pure]`, are dropped.
+pub fn rewrite_prusti_attributes(
+    outer_attr_kind: SpecAttributeKind,
+    outer_attr_tokens: TokenStream,
+    item_tokens: TokenStream,
+) -> TokenStream {
+    rewrite_contract(outer_attr_kind, outer_attr_tokens, item_tokens)
+        .unwrap_or_else(|err| err.to_compile_error())
+}
+
+/// Check a loop invariant at the beginning of each iteration.
+pub fn body_invariant(tokens: TokenStream) -> TokenStream {
+    generate_check(tokens, "loop invariant might not hold")
+        .unwrap_or_else(|err| err.to_compile_error())
+}
+
+/// Check a `prusti_assert!` statement.
+pub fn prusti_assertion(tokens: TokenStream) -> TokenStream {
+    generate_check(tokens, "the asserted expression might not hold")
+        .unwrap_or_else(|err| err.to_compile_error())
+}
+
+/// Keep the code of a `ghost!` block, since the checks of later assertions may use its
+/// locals.
+pub fn ghost(tokens: TokenStream) -> TokenStream {
+    tokens
+}
+
+/// Define a `predicate!` as a function that evaluates its body.
+pub fn predicate(tokens: TokenStream) -> TokenStream {
+    generate_predicate(tokens).unwrap_or_else(|err| err.to_compile_error())
+}
+
+fn rewrite_contract(
+    outer_attr_kind: SpecAttributeKind,
+    outer_attr_tokens: TokenStream,
+    item_tokens: TokenStream,
+) -> syn::Result<TokenStream> {
+    let mut item: AnyFnItem = syn::parse2(item_tokens)?;
+    let mut prusti_attributes = vec![(outer_attr_kind, outer_attr_tokens)];
+    prusti_attributes.extend(extract_prusti_attributes(&mut item));
+
+    let (sig, block) = match &mut item {
+        AnyFnItem::Fn(item_fn) => (&item_fn.sig, &mut *item_fn.block),
+        AnyFnItem::ImplMethod(method) => (&method.sig, &mut method.block),
+        AnyFnItem::TraitMethod(syn::TraitItemMethod { sig, default: Some(block), .. }) => {
+            (&*sig, block)
+        }
+        // Without a body there is nothing to check.
+        AnyFnItem::TraitMethod(_) => return Ok(item.into_token_stream()),
+    };
+    if let Some(asyncness) = sig.asyncness {
+        return Err(syn::Error::new(
+            asyncness.span(),
+            "runtime checks do not support contracts of async functions",
+        ));
+    }
+    if let Some(constness) = sig.constness {
+        // The checks panic and clone the `old(..)` expressions, which const functions cannot.
+        return Err(syn::Error::new(
+            constness.span(),
+            "runtime checks do not support contracts of const functions",
+        ));
+    }
+
+    // Snapshots of `old(..)` expressions are taken after the preconditions are checked, so
+    // the preconditions cannot use them.
+    let mut pre_translator = Translator::new(false);
+    let mut post_translator = Translator::new(true);
+    let mut pre_checks = vec![];
+    let mut post_checks = vec![];
+    for (attr_kind, attr_tokens) in prusti_attributes {
+        let (translator, checks, message) = match attr_kind {
+            SpecAttributeKind::Requires => {
+                (&mut pre_translator, &mut pre_checks, "precondition might not hold")
+            }
+            SpecAttributeKind::Ensures => {
+                (&mut post_translator, &mut post_checks, "postcondition might not hold")
+            }
+            _ => continue,
+        };
+        let spec_str = attr_tokens.to_string();
+        let assertion = Parser::from_token_stream(attr_tokens).extract_assertion()?;
+        let check = translator.translate_assertion(&assertion)?;
+        checks.push(generate_assert(check, message, &spec_str));
+    }
+    let snapshots = &post_translator.snapshots;
+
+    // The body is evaluated in a labelled loop, from which the `return` statements and `?`
+    // operators of the body break out, such that the postconditions are also checked on the
+    // early returns. Unlike a closure, this also works in methods and with `impl Trait`.
+    let label = syn::Lifetime::new("'prusti_body", Span::call_site());
+    let mut body = block.clone();
+    ReturnRewriter::new(&label, &sig.output).visit_block_mut(&mut body);
+    let result_decl = match &sig.output {
+        syn::ReturnType::Type(_, ty) if can_annotate_result(ty) => quote! { result: #ty },
+        _ => quote! { result },
+    };
+    let callsite_span = Span::call_site();
+    *block = parse_quote_spanned! {callsite_span=>
+        {
+            #(#pre_checks)*
+            #(#snapshots)*
+            #[allow(clippy::never_loop, clippy::let_unit_value, unreachable_code)]
+            let #result_decl = #label: loop {
+                break #label #body;
+            };
+            #(#post_checks)*
+            result
+        }
+    };
+    Ok(item.into_token_stream())
+}
+
+fn generate_check(tokens: TokenStream, message: &str) -> syn::Result<TokenStream> {
+    let spec_str = tokens.to_string();
+    let assertion = Parser::from_token_stream(tokens).extract_assertion()?;
+    let check = Translator::new(false).translate_assertion(&assertion)?;
+    Ok(generate_assert(check, message, &spec_str))
+}
+
+fn generate_assert(check: TokenStream, message: &str, spec_str: &str) -> TokenStream {
+    let callsite_span = Span::call_site();
+    quote_spanned! {callsite_span=>
+        {
+            #[allow(unused_parens)]
+            let prusti_check = #check;
+            assert!(prusti_check, "{}: {}", #message, #spec_str);
+        }
+    }
+}
+
+fn generate_predicate(tokens: TokenStream) -> syn::Result<TokenStream> {
+    let PredicateFn { attrs, vis, sig, body } = syn::parse2(tokens)?;
+    let assertion = Parser::from_token_stream(body).extract_assertion()?;
+    let check = Translator::new(false).translate_assertion(&assertion)?;
+    let callsite_span = Span::call_site();
+    Ok(quote_spanned! {callsite_span=>
+        #(#attrs)*
+        #vis #sig {
+            #[allow(unused_parens)]
+            let prusti_check = #check;
+            prusti_check
+        }
+    })
+}
+
+/// The return type of the function can be used to annotate the result of the body, unless
+/// the type contains `impl Trait`.
+fn can_annotate_result(ty: &syn::Type) -> bool {
+    struct Checker {
+        annotatable: bool,
+    }
+    impl VisitMut for Checker {
+        fn visit_type_impl_trait_mut(&mut self, _: &mut syn::TypeImplTrait) {
+            self.annotatable = false;
+        }
+    }
+    let mut checker = Checker { annotatable: true };
+    checker.visit_type_mut(&mut ty.clone());
+    checker.annotatable
+}
+
+/// Replaces the `return` statements and `?` operators of a function body with breaks out of
+/// the loop labelled `label`. The `?` operator is supported for functions returning `Option`
+/// and `Result`, where any other return type is assumed to be a `Result`.
+struct ReturnRewriter<'a> {
+    label: &'a syn::Lifetime,
+    returns_option: bool,
+}
+
+impl<'a> ReturnRewriter<'a> {
+    fn new(label: &'a syn::Lifetime, output: &syn::ReturnType) -> Self {
+        let returns_option = match output {
+            syn::ReturnType::Type(_, box syn::Type::Path(type_path)) => type_path.path.segments
+                .last()
+                .map_or(false, |segment| segment.ident == "Option"),
+            _ => false,
+        };
+        Self { label, returns_option }
+    }
+}
+
+impl<'a> VisitMut for ReturnRewriter<'a> {
+    fn visit_expr_mut(&mut self, expr: &mut syn::Expr) {
+        let label = self.label;
+        let span = expr.span();
+        match expr {
+            // These have their own `return` statements and `?` operators.
+            syn::Expr::Closure(_) | syn::Expr::Async(_) => {}
+            syn::Expr::Return(syn::ExprReturn { expr: value, .. }) => {
+                if let Some(value) = value {
+                    self.visit_expr_mut(value);
+                }
+                let value = value.as_ref().map_or_else(|| quote! { () }, |value| quote! { #value });
+                *expr = parse_quote_spanned!(span=> break #label #value);
+            }
+            syn::Expr::Try(syn::ExprTry { expr: operand, .. }) => {
+                self.visit_expr_mut(operand);
+                *expr = if self.returns_option {
+                    parse_quote_spanned! {span=>
+                        match #operand {
+                            ::core::option::Option::Some(prusti_value) => prusti_value,
+                            ::core::option::Option::None => {
+                                break #label ::core::option::Option::None
+                            }
+                        }
+                    }
+                } else {
+                    parse_quote_spanned! {span=>
+                        match #operand {
+                            ::core::result::Result::Ok(prusti_value) => prusti_value,
+                            ::core::result::Result::Err(prusti_error) => {
+                                break #label ::core::result::Result::Err(
+                                    ::core::convert::From::from(prusti_error)
+                                )
+                            }
+                        }
+                    }
+                };
+            }
+            _ => syn::visit_mut::visit_expr_mut(self, expr),
+        }
+    }
+
+    // Nested functions have their own `return` statements and `?` operators.
+    fn visit_item_mut(&mut self, _: &mut syn::Item) {}
+}
+
+/// Translates assertions into boolean Rust expressions.
+struct Translator {
+    /// Whether `old(..)` expressions can be used, i.e. whether we are translating a contract.
+    allow_old: bool,
+    /// Statements that take the snapshots of the `old(..)` expressions on entry.
+    snapshots: Vec<TokenStream>,
+    /// The variables of the enclosing quantifiers.
+    quantified_vars: Vec<syn::Ident>,
+    error: Option<syn::Error>,
+}
+
+impl Translator {
+    fn new(allow_old: bool) -> Self {
+        Self {
+            allow_old,
+            snapshots: vec![],
+            quantified_vars: vec![],
+            error: None,
+        }
+    }
+
+    fn translate_assertion(&mut self, assertion: &AssertionWithoutId) -> syn::Result<TokenStream> {
+        match &*assertion.kind {
+            AssertionKind::Expr(expression) => {
+                let expr = self.translate_expr(&expression.expr)?;
+                Ok(quote_spanned! {expr.span()=> (#expr) })
+            }
+            AssertionKind::And(conjuncts) => {
+                let conjuncts = conjuncts.iter()
+                    .map(|conjunct| self.translate_assertion(conjunct))
+                    .collect::<syn::Result<Vec<_>>>()?;
+                Ok(quote! { (true #(&& #conjuncts)*) })
+            }
+            AssertionKind::Implies(lhs, rhs) => {
+                let lhs = self.translate_assertion(lhs)?;
+                let rhs = self.translate_assertion(rhs)?;
+                Ok(quote! { (!#lhs || #rhs) })
+            }
+            AssertionKind::ForAll(vars, _, body) => {
+                self.translate_quantifier(&vars.vars, body, true)
+            }
+            AssertionKind::Exists(vars, _, body) => {
+                self.translate_quantifier(&vars.vars, body, false)
+            }
+            _ => Err(syn::Error::new(
+                Span::call_site(),
+                "runtime checks do not support specification entailments",
+            )),
+        }
+    }
+
+    /// Translate a quantifier into nested `all` or `any` calls over the ranges of its
+    /// variables. The bounds of a variable are taken from the comparisons in the guard of the
+    /// quantifier, i.e. the left-hand side of the implication of a `forall` or the conjuncts
+    /// of an `exists`. The guard itself is still evaluated for each value.
+    fn translate_quantifier(
+        &mut self,
+        vars: &[Arg],
+        body: &AssertionWithoutId,
+        is_forall: bool,
+    ) -> syn::Result<TokenStream> {
+        let guard = match &*body.kind {
+            AssertionKind::Implies(lhs, _) if is_forall => collect_conjuncts(lhs),
+            _ if !is_forall => collect_conjuncts(body),
+            _ => vec![],
+        };
+
+        // Order the variables such that the bounds of each variable only depend on the
+        // variables that come before it.
+        let mut remaining: Vec<&Arg> = vars.iter().collect();
+        let mut ranges = vec![];
+        while !remaining.is_empty() {
+            let remaining_names: Vec<_> = remaining.iter().map(|var| var.name.clone()).collect();
+            let position = remaining.iter().position(|var| {
+                find_bounds(var, &guard, &remaining_names).is_some()
+            });
+            let var = match position {
+                Some(position) => remaining.remove(position),
+                None => return Err(syn::Error::new(
+                    remaining[0].name.span(),
+                    "runtime checks only support quantified variables with bounds, \
+                    such as `forall(|i: usize| (0 <= i && i < n) ==> ...)`",
+                )),
+            };
+            let (lower, upper, inclusive) = find_bounds(var, &guard, &remaining_names).unwrap();
+            ranges.push((var, lower, upper, inclusive));
+        }
+
+        self.quantified_vars.extend(vars.iter().map(|var| var.name.clone()));
+        let mut result = self.translate_assertion(body);
+        let mut translated_ranges = vec![];
+        for (var, lower, upper, inclusive) in ranges {
+            let lower = self.translate_expr(&lower);
+            let upper = self.translate_expr(&upper);
+            translated_ranges.push((var, lower, upper, inclusive));
+        }
+        let new_len = self.quantified_vars.len() - vars.len();
+        self.quantified_vars.truncate(new_len);
+
+        for (var, lower, upper, inclusive) in translated_ranges.into_iter().rev() {
+            let (lower, upper, inner) = (lower?, upper?, result?);
+            let range = if inclusive {
+                quote! { (#lower)..=(#upper) }
+            } else {
+                quote! { (#lower)..(#upper) }
+            };
+            let Arg { name, typ } = var;
+            result = Ok(if is_forall {
+                quote! { (#range).all(|#name: #typ| #inner) }
+            } else {
+                quote! { (#range).any(|#name: #typ| #inner) }
+            });
+        }
+        result
+    }
+
+    /// Replace the `old(..)` expressions of `expr` with their snapshots.
+    fn translate_expr(&mut self, expr: &syn::Expr) -> syn::Result<syn::Expr> {
+        let mut expr = expr.clone();
+        self.visit_expr_mut(&mut expr);
+        match self.error.take() {
+            Some(err) => Err(err),
+            None => Ok(expr),
+        }
+    }
+}
+
+impl VisitMut for Translator {
+    fn visit_expr_mut(&mut self, expr: &mut syn::Expr) {
+        let span = expr.span();
+        let (func, args) = match expr {
+            syn::Expr::Call(syn::ExprCall { func: box syn::Expr::Path(func), args, .. }) => {
+                (func, args)
+            }
+            _ => return syn::visit_mut::visit_expr_mut(self, expr),
+        };
+        let is_labelled_old = func.path.segments.last()
+            .map_or(false, |segment| segment.ident == "labelled_old");
+        if is_labelled_old {
+            self.error = Some(syn::Error::new(
+                func.span(),
+                "runtime checks do not support labelled old expressions",
+            ));
+        } else if func.path.is_ident("old") && args.len() == 1 {
+            let arg = args.first().unwrap();
+            if !self.allow_old {
+                self.error = Some(syn::Error::new(
+                    span,
+                    "runtime checks only support `old(..)` in postconditions",
+                ));
+            } else if mentions_any(arg, &self.quantified_vars) {
+                self.error = Some(syn::Error::new(
+                    span,
+                    "runtime checks do not support `old(..)` expressions that depend on \
+                    quantified variables",
+                ));
+            } else {
+                let snapshot = format_ident!("prusti_old_{}", self.snapshots.len());
+                self.snapshots.push(quote_spanned! {span=>
+                    let #snapshot = ::core::clone::Clone::clone(&(#arg));
+                });
+                *expr = parse_quote_spanned!(span=> #snapshot);
+            }
+        } else {
+            syn::visit_mut::visit_expr_mut(self, expr);
+        }
+    }
+}
+
+/// Collect the conjuncts of an assertion, also splitting the `&&` of Rust expressions.
+fn collect_conjuncts(assertion: &AssertionWithoutId) -> Vec<syn::Expr> {
+    fn split(expr: &syn::Expr, conjuncts: &mut Vec<syn::Expr>) {
+        match expr {
+            syn::Expr::Binary(syn::ExprBinary { left, op: syn::BinOp::And(_), right, .. }) => {
+                split(left, conjuncts);
+                split(right, conjuncts);
+            }
+            syn::Expr::Paren(syn::ExprParen { expr, .. }) => split(expr, conjuncts),
+            _ => conjuncts.push(expr.clone()),
+        }
+    }
+    let mut conjuncts = vec![];
+    match &*assertion.kind {
+        AssertionKind::Expr(expression) => split(&expression.expr, &mut conjuncts),
+        AssertionKind::And(assertions) => {
+            for assertion in assertions {
+                conjuncts.extend(collect_conjuncts(assertion));
+            }
+        }
+        _ => {}
+    }
+    conjuncts
+}
+
+/// Find the lower bound, the upper bound and whether the upper bound is inclusive for `var`,
+/// using bounds that do not depend on `unbound_vars`. Unsigned variables have an implicit
+/// lower bound of zero.
+fn find_bounds(
+    var: &Arg,
+    guard: &[syn::Expr],
+    unbound_vars: &[syn::Ident],
+) -> Option<(syn::Expr, syn::Expr, bool)> {
+    let mut lower = None;
+    let mut upper = None;
+    for conjunct in guard {
+        // Normalize the comparison to `smaller < greater` or `smaller <= greater`.
+        let (smaller, greater, inclusive) = match conjunct {
+            syn::Expr::Binary(syn::ExprBinary { left, op, right, .. }) => match op {
+                syn::BinOp::Lt(_) => (left, right, false),
+                syn::BinOp::Le(_) => (left, right, true),
+                syn::BinOp::Gt(_) => (right, left, false),
+                syn::BinOp::Ge(_) => (right, left, true),
+                _ => continue,
+            },
+            _ => continue,
+        };
+        if is_var(greater, &var.name) && !mentions_any(smaller, unbound_vars) {
+            lower.get_or_insert((**smaller).clone());
+        } else if is_var(smaller, &var.name) && !mentions_any(greater, unbound_vars) {
+            upper.get_or_insert(((**greater).clone(), inclusive));
+        }
+    }
+    if lower.is_none() && is_unsigned(&var.typ) {
+        lower = Some(parse_quote_spanned!(var.name.span()=> 0));
+    }
+    let (upper, inclusive) = upper?;
+    Some((lower?, upper, inclusive))
+}
+
+fn is_var(expr: &syn::Expr, name: &syn::Ident) -> bool {
+    match expr {
+        syn::Expr::Path(path) => path.path.is_ident(name),
+        syn::Expr::Paren(syn::ExprParen { expr, .. }) => is_var(expr, name),
+        _ => false,
+    }
+}
+
+fn is_unsigned(typ: &syn::Type) -> bool {
+    match typ {
+        syn::Type::Path(type_path) => ["u8", "u16", "u32", "u64", "u128", "usize"]
+            .iter()
+            .any(|unsigned| type_path.path.is_ident(unsigned)),
+        _ => false,
+    }
+}
+
+/// Check whether `expr` refers to any of the variables `names`.
+fn mentions_any(expr: &syn::Expr, names: &[syn::Ident]) -> bool {
+    struct Finder<'a> {
+        names: &'a [syn::Ident],
+        found: bool,
+    }
+    impl<'a> VisitMut for Finder<'a> {
+        fn visit_expr_path_mut(&mut self, path: &mut syn::ExprPath) {
+            self.found |= self.names.iter().any(|name| path.path.is_ident(name));
+        }
+    }
+    let mut finder = Finder { names, found: false };
+    finder.visit_expr_mut(&mut expr.clone());
+    finder.found
+}
@@ -9,6 +9,8 @@ pub enum SpecAttributeKind {
     AfterExpiryIf,
     Pure,
     Trusted,
+    Invariant,
+    Decreases,
 }
 
 impl TryFrom<String> for SpecAttributeKind {
@@ -22,6 +24,8 @@ impl TryFrom<String> for SpecAttributeKind {
             "after_expiry_if" => Ok(SpecAttributeKind::AfterExpiryIf),
             "pure" => Ok(SpecAttributeKind::Pure),
             "trusted" => Ok(SpecAttributeKind::Trusted),
+            "invariant" => Ok(SpecAttributeKind::Invariant),
+            "decreases" => Ok(SpecAttributeKind::Decreases),
             _ => Err(name),
         }
     }
@@ -17,6 +17,14 @@ pub enum SpecType {
     Postcondition,
     /// Loop invariant or struct invariant
     Invariant,
+    /// A `prusti_assert!` statement in a procedure body.
+    Assertion,
+    /// A `prusti_assume!` statement in a procedure body.
+    Assumption,
+    /// Termination measure of a procedure.
+    Decreases,
+    /// Body of a `predicate!`.
+    Predicate,
 }
 
 #[derive(Debug)]
@@ -48,11 +56,13 @@ impl<'a> TryFrom<&'a str> for SpecType {
 pub struct SpecificationId(Uuid);
 
 /// A reference to a procedure specification.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum SpecIdRef {
     Precondition(SpecificationId),
     Postcondition(SpecificationId),
     Pledge { lhs: Option<SpecificationId>, rhs: SpecificationId },
+    Decreases(SpecificationId),
+    Predicate(SpecificationId),
 }
 
 impl Display for SpecificationId {
@@ -188,6 +198,46 @@ impl<EID, ET> IntoIterator for Trigger<EID, ET> {
     }
 }
 
+#[derive(Debug, Clone)]
+/// A termination measure: a tuple of expressions that is compared lexicographically.
+pub struct Measure<EID, ET>(pub Vec<Expression<EID, ET>>);
+
+impl<EID, ET> Measure<EID, ET> {
+    /// Getter for the components of the tuple.
+    pub fn terms(&self) -> &Vec<Expression<EID, ET>> {
+        &self.0
+    }
+}
+
+impl<EID: Clone + Debug, ET: Clone + Debug> Measure<EID, ET> {
+    /// Reconstruct a measure from the conjunction of expressions that is used to
+    /// transfer it from the procedural macros to the compiler.
+    pub fn from_assertion<AT: Clone + Debug>(assertion: &Assertion<EID, ET, AT>) -> Self {
+        if let AssertionKind::And(ref conjuncts) = *assertion.kind {
+            Measure(conjuncts.iter().map(|conjunct| {
+                if let AssertionKind::Expr(ref expr) = *conjunct.kind {
+                    return expr.clone();
+                }
+                unreachable!("expected Expr: {:?}", conjunct);
+            }).collect())
+        } else {
+            unreachable!("expected And: {:?}", assertion);
+        }
+    }
+
+    /// Wrap the measure into a conjunction of expressions, so that it can be
+    /// transferred like any other assertion.
+    pub fn to_assertion<AT>(&self) -> Assertion<EID, ET, AT> {
+        Assertion {
+            kind: Box::new(AssertionKind::And(
+                self.0.iter().map(|expr| Assertion {
+                    kind: Box::new(AssertionKind::Expr(expr.clone())),
+                }).collect()
+            )),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 /// A set of triggers used in the quantifier.
 pub struct TriggerSet<EID, ET>(pub Vec<Trigger<EID, ET>>);
@@ -212,7 +262,7 @@ impl<EID, ET> IntoIterator for TriggerSet<EID, ET> {
 }
 
 #[derive(Debug, Clone)]
-/// Variables used in a forall.
+/// Variables used in a forall or exists.
 pub struct ForAllVars<EID, AT> {
     /// Identifier of the specification to which these variables belongs.
     pub spec_id: SpecificationId,
@@ -253,6 +303,12 @@ pub enum AssertionKind<EID, ET, AT> {
         TriggerSet<EID, ET>,
         Assertion<EID, ET, AT>,
     ),
+    /// Existential quantifier
+    Exists(
+        ForAllVars<EID, AT>,
+        TriggerSet<EID, ET>,
+        Assertion<EID, ET, AT>,
+    ),
     /// Specification entailment
     SpecEntailment {
         closure: Expression<EID, ET>,
@@ -288,17 +344,19 @@ pub struct Specification<EID, ET, AT> {
 pub struct LoopSpecification<EID, ET, AT> {
     /// Loop invariant.
     pub invariant: Vec<Assertion<EID, ET, AT>>,
+    /// Loop variant, used to prove termination.
+    pub variant: Option<Measure<EID, ET>>,
 }
 
 impl<EID, ET, AT> LoopSpecification<EID, ET, AT> {
     pub fn new(invariant: Vec<Assertion<EID, ET, AT>>) -> Self {
-        Self { invariant }
+        Self { invariant, variant: None }
     }
     pub fn empty() -> Self {
         Self::new(Vec::new())
     }
     pub fn is_empty(&self) -> bool {
-        self.invariant.is_empty()
+        self.invariant.is_empty() && self.variant.is_none()
     }
 }
 
@@ -312,6 +370,12 @@ pub struct ProcedureSpecification<EID, ET, AT> {
     /// Pledges in the postcondition.
     pub pledges: Vec<Pledge<EID, ET, AT>>,
 
+    /// Termination measure of a recursive pure function.
+    pub decreases: Option<Measure<EID, ET>>,
+
+    /// Body of a function defined with `predicate!`.
+    pub predicate_body: Option<Assertion<EID, ET, AT>>,
+
     pub pure: bool,
     pub trusted: bool,
 }
@@ -326,6 +390,8 @@ impl<EID, ET, AT> ProcedureSpecification<EID, ET, AT> {
             pres,
             posts,
             pledges,
+            decreases: None,
+            predicate_body: None,
             pure: false,
             trusted: false,
         }
@@ -362,10 +428,22 @@ impl<EID: Clone + Debug, ET: Clone + Debug, AT: Clone + Debug> ProcedureSpecific
         } else {
             other.pledges.clone()
         };
+        let decreases = if other.decreases.is_none() {
+            self.decreases.clone()
+        } else {
+            other.decreases.clone()
+        };
+        let predicate_body = if other.predicate_body.is_none() {
+            self.predicate_body.clone()
+        } else {
+            other.predicate_body.clone()
+        };
         Self {
             pres,
             posts,
             pledges,
+            decreases,
+            predicate_body,
             pure: other.pure,
             trusted: other.trusted,
         }
@@ -381,6 +459,10 @@ pub enum SpecificationSet<EID, ET, AT> {
     Loop(LoopSpecification<EID, ET, AT>),
     /// Struct invariant.
     Struct(Vec<Specification<EID, ET, AT>>),
+    /// Assertion checked by a `prusti_assert!` statement.
+    Assertion(Assertion<EID, ET, AT>),
+    /// Assertion assumed by a `prusti_assume!` statement.
+    Assumption(Assertion<EID, ET, AT>),
 }
 
 impl<EID, ET, AT> SpecificationSet<EID, ET, AT> {
@@ -389,6 +471,8 @@ impl<EID, ET, AT> SpecificationSet<EID, ET, AT> {
             SpecificationSet::Procedure(spec) => spec.is_empty(),
             SpecificationSet::Loop(ref invs) => invs.is_empty(),
             SpecificationSet::Struct(ref invs) => invs.is_empty(),
+            SpecificationSet::Assertion(_) |
+            SpecificationSet::Assumption(_) => false,
         }
     }
 }
@@ -13,6 +13,7 @@ pub enum AssertionKind {
     And(Vec<Assertion>),
     Implies(Assertion, Assertion),
     ForAll(ForAllVars, Assertion, TriggerSet),
+    Exists(ForAllVars, Assertion, TriggerSet),
     SpecEntailment {
         closure: Expression,
         arg_binders: SpecEntailmentVars,
@@ -122,6 +123,11 @@ impl untyped::AssertionKind {
                 body.to_structure(),
                 triggers.to_structure(),
             ),
+            Exists(vars, triggers, body) => AssertionKind::Exists(
+                vars.to_structure(),
+                body.to_structure(),
+                triggers.to_structure(),
+            ),
             SpecEntailment {closure, arg_binders, pres, posts} => AssertionKind::SpecEntailment {
                 closure: closure.to_structure(),
                 arg_binders: arg_binders.to_structure(),
@@ -7,7 +7,8 @@ use std::collections::VecDeque;
 use std::mem;
 use syn::parse::{ParseStream, Parse};
 use syn::{self, Token, Error};
-use quote::quote;
+use syn::visit_mut::VisitMut;
+use quote::{quote, format_ident};
 
 use super::common;
 use crate::specifications::common::{ForAllVars, SpecEntailmentVars, TriggerSet, Trigger};
@@ -414,12 +415,12 @@ impl Parser {
             kind: box common::AssertionKind::Implies(lhs.unwrap(), rhs.unwrap())
         });
     }
-    fn resolve_forall(&mut self) -> syn::Result<()> {
+    fn resolve_quantifier(&mut self, is_exists: bool) -> syn::Result<()> {
         if self.expected_operator {
             return Err(self.error_expected_operator());
         }
 
-        // check whether there is a parenthesized block after forall
+        // check whether there is a parenthesized block after forall or exists
         if let Some(group) = self.input.check_and_consume_parenthesized_block() {
 
             // construct a ParserStream off of the parenthesized block for further parsing
@@ -495,16 +496,17 @@ impl Parser {
                 trigger_set = TriggerSet(vec_of_triggers);
             }
 
+            let vars = ForAllVars {
+                spec_id: common::SpecificationId::dummy(),
+                id: (),
+                vars
+            };
             let conjunct = AssertionWithoutId {
-                kind: box common::AssertionKind::ForAll(
-                    ForAllVars {
-                        spec_id: common::SpecificationId::dummy(),
-                        id: (),
-                        vars
-                    },
-                    trigger_set,
-                    body,
-                )
+                kind: box if is_exists {
+                    common::AssertionKind::Exists(vars, trigger_set, body)
+                } else {
+                    common::AssertionKind::ForAll(vars, trigger_set, body)
+                }
             };
 
             self.conjuncts.push(conjunct);
@@ -680,7 +682,12 @@ impl Parser {
                 return self.resolve_implies();
             }
             else if self.input.check_and_consume_keyword("forall") {
-                if let Err(err) = self.resolve_forall() {
+                if let Err(err) = self.resolve_quantifier(false) {
+                    return Err(err);
+                }
+            }
+            else if self.input.check_and_consume_keyword("exists") {
+                if let Err(err) = self.resolve_quantifier(true) {
                     return Err(err);
                 }
             }
@@ -725,7 +732,9 @@ impl Parser {
             }
             return Err(err);
         }
-        maybe_expr
+        let mut expr = maybe_expr?;
+        LabelledOldRewriter.visit_expr_mut(&mut expr);
+        Ok(expr)
     }
     fn extract_rust_expression(&mut self) -> syn::Result<ExpressionWithoutId> {
         let expr = self.expr.clone();
@@ -843,3 +852,32 @@ impl Parser {
         syn::Error::new(self.input.span, "a quantifier must have at least one argument")
     }
 }
+
+/// Rewrites `old[label](expr)` into `labelled_old::<prusti_label_label, _>(expr)`, so that the
+/// label is resolved by the compiler to the marker type declared by `prusti_label!`.
+struct LabelledOldRewriter;
+
+impl VisitMut for LabelledOldRewriter {
+    fn visit_expr_mut(&mut self, expr: &mut syn::Expr) {
+        syn::visit_mut::visit_expr_mut(self, expr);
+        let rewritten_expr = if let syn::Expr::Call(call) = expr {
+            match &*call.func {
+                syn::Expr::Index(syn::ExprIndex {
+                    expr: box syn::Expr::Path(func),
+                    index: box syn::Expr::Path(label),
+                    ..
+                }) if func.path.is_ident("old") && label.path.get_ident().is_some() => {
+                    let marker = format_ident!("prusti_label_{}", label.path.get_ident().unwrap());
+                    let args = &call.args;
+                    Some(parse_quote_spanned!(call.span()=> labelled_old::<#marker, _>(#args)))
+                }
+                _ => None,
+            }
+        } else {
+            None
+        };
+        if let Some(rewritten_expr) = rewritten_expr {
+            *expr = rewritten_expr;
+        }
+    }
+}
@@ -29,6 +29,8 @@ pub type Expression = common::Expression<ExpressionId, syn::Expr>;
 pub type TriggerSet = common::TriggerSet<ExpressionId, syn::Expr>;
 /// A pledge that has not types associated with it.
 pub type Pledge = common::Pledge<ExpressionId, syn::Expr, Arg>;
+/// A termination measure that has no types associated with it.
+pub type Measure = common::Measure<ExpressionId, syn::Expr>;
 
 /// An abstraction over all kinds of function items.
 pub enum AnyFnItem {
@@ -146,6 +148,35 @@ impl Pledge {
     }
 }
 
+impl Measure {
+    pub(crate) fn parse(
+        tokens: TokenStream,
+        spec_id: SpecificationId,
+        id_generator: &mut ExpressionIdGenerator,
+    ) -> syn::Result<Self> {
+        let span = tokens.span();
+        let exprs = syn::parse::Parser::parse2(
+            syn::punctuated::Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated,
+            tokens,
+        )?;
+        if exprs.is_empty() {
+            return Err(syn::Error::new(
+                span,
+                "expected at least one expression in the termination measure",
+            ));
+        }
+        Ok(common::Measure(
+            exprs.into_iter()
+                .map(|expr| Expression {
+                    spec_id,
+                    id: id_generator.generate(),
+                    expr,
+                })
+                .collect()
+        ))
+    }
+}
+
 pub(crate) trait AssignExpressionId<Target> {
     fn assign_id(
         self,
@@ -268,6 +299,11 @@ impl AssignExpressionId<AssertionKind> for common::AssertionKind<(), syn::Expr,
                 triggers.assign_id(spec_id, id_generator),
                 body.assign_id(spec_id, id_generator)
             ),
+            Exists(vars, triggers, body) => Exists(
+                vars.assign_id(spec_id, id_generator),
+                triggers.assign_id(spec_id, id_generator),
+                body.assign_id(spec_id, id_generator)
+            ),
             SpecEntailment {closure, arg_binders, pres, posts} => SpecEntailment {
                 closure: closure.assign_id(spec_id, id_generator),
                 arg_binders: arg_binders.assign_id(spec_id, id_generator),
@@ -346,6 +382,25 @@ impl EncodeTypeCheck for TriggerSet {
     }
 }
 
+impl EncodeTypeCheck for Measure {
+    fn encode_type_check(&self, tokens: &mut TokenStream) {
+        // Unlike assertions, the terms of a measure are not boolean expressions.
+        for term in self.terms() {
+            let span = term.expr.span();
+            let expr = &term.expr;
+            let identifier = format!("{}_{}", term.spec_id, term.id);
+            let typeck_call = quote_spanned! { span =>
+                #[prusti::spec_only]
+                #[prusti::expr_id = #identifier]
+                || {
+                    #expr
+                };
+            };
+            tokens.extend(typeck_call);
+        }
+    }
+}
+
 impl ToTokens for Arg {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         self.name.to_tokens(tokens);
@@ -369,7 +424,8 @@ impl EncodeTypeCheck for Assertion {
                 lhs.encode_type_check(tokens);
                 rhs.encode_type_check(tokens);
             }
-            AssertionKind::ForAll(vars, triggers, body) => {
+            AssertionKind::ForAll(vars, triggers, body) |
+            AssertionKind::Exists(vars, triggers, body) => {
                 let vec_of_vars = &vars.vars;
                 let span = Span::call_site();
                 let identifier = format!("{}_{}", vars.spec_id, vars.id);
@@ -0,0 +1,145 @@
+//! Specifications of commonly used APIs of the standard library.
+//!
+//! Prusti only uses the specifications that are declared in the crate being verified, so the
+//! specifications are provided as a macro that declares them in a `std_specs` module of that
+//! crate. `prusti-rustc` links this crate and Prusti invokes the macro automatically in every
+//! verified crate of the 2018 edition. The specifications that the crate declares itself for
+//! the same functions take precedence over these ones.
+//!
+//! A `Vec<T>` is modelled by the sequence of its elements, `prusti_std::vec_model(&v)`:
+//!
+//! ```ignore
+//! use prusti_contracts::*;
+//! use prusti_std::vec_model;
+//!
+//! #[requires(v.len() > 0)]
+//! #[ensures(vec_model(v) == old(vec_model(v)).slice(Int::new(1), old(vec_model(v)).len()))]
+//! fn remove_first(v: &mut Vec<u32>) {
+//!     v.remove(0);
+//! }
+//! ```
+//!
+//! Like any mathematical sequence, the model stores its elements by value, so it can be used
+//! with elements of primitive, mathematical, structure, enumeration or tuple type, but not yet
+//! with elements of a generic type.
+
+use prusti_contracts::Seq;
+
+/// The sequence of the elements of `v`, which is how the specifications model a vector. It
+/// can only be used in specifications.
+pub fn vec_model<T>(_v: &Vec<T>) -> Seq<T> {
+    panic!("the model of a vector can only be used in specifications")
+}
+
+/// Declares the specifications of `Vec<T>`, `Option<T>` and `Result<T, E>` in a module
+/// `std_specs` of the calling crate.
+#[macro_export]
+macro_rules! import_std_specs {
+    () => {
+        #[allow(dead_code)]
+        #[cfg_attr(prusti, prusti::std_specs)]
+        mod std_specs {
+            use prusti_contracts::*;
+            use prusti_std::vec_model;
+
+            #[extern_spec]
+            mod prusti_std {
+                use prusti_contracts::*;
+
+                #[pure]
+                pub fn vec_model<T>(v: &Vec<T>) -> Seq<T>;
+            }
+
+            #[extern_spec]
+            impl<T> Vec<T> {
+                #[ensures(vec_model(&result).is_empty())]
+                fn new() -> Vec::<T>;
+
+                #[ensures(vec_model(&result).is_empty())]
+                fn with_capacity(capacity: usize) -> Vec::<T>;
+
+                #[pure]
+                #[ensures(Int::new_usize(result) == vec_model(self).len())]
+                fn len(&self) -> usize;
+
+                #[pure]
+                #[ensures(result == vec_model(self).is_empty())]
+                fn is_empty(&self) -> bool;
+
+                #[ensures(vec_model(self) == old(vec_model(self)).push(value))]
+                fn push(&mut self, value: T);
+
+                #[ensures(old(self.is_empty()) ==> result.is_none() && self.is_empty())]
+                #[ensures(!old(self.is_empty()) ==> result.is_some())]
+                #[ensures(!old(self.is_empty()) ==> vec_model(self) ==
+                    old(vec_model(self).slice(Int::new(0), vec_model(self).len() - Int::new(1))))]
+                fn pop(&mut self) -> Option<T>;
+
+                #[requires(index <= self.len())]
+                #[ensures(vec_model(self) ==
+                    old(vec_model(self).slice(Int::new(0), Int::new_usize(index)))
+                        .push(element)
+                        .concat(old(vec_model(self).slice(Int::new_usize(index), vec_model(self).len()))))]
+                fn insert(&mut self, index: usize, element: T);
+
+                #[requires(index < self.len())]
+                #[ensures(Seq::single(result) ==
+                    old(vec_model(self).slice(Int::new_usize(index), Int::new_usize(index + 1))))]
+                #[ensures(vec_model(self) ==
+                    old(vec_model(self).slice(Int::new(0), Int::new_usize(index)))
+                        .concat(old(vec_model(self).slice(Int::new_usize(index + 1), vec_model(self).len()))))]
+                fn remove(&mut self, index: usize) -> T;
+
+                #[ensures(vec_model(self) == old(vec_model(self)).slice(Int::new(0), Int::new_usize(len)))]
+                fn truncate(&mut self, len: usize);
+
+                #[ensures(self.is_empty())]
+                fn clear(&mut self);
+            }
+
+            #[extern_spec]
+            impl<T> Option<T> {
+                #[pure]
+                #[ensures(matches!(*self, Some(_)) == result)]
+                pub fn is_some(&self) -> bool;
+
+                #[pure]
+                #[ensures(self.is_some() == !result)]
+                pub fn is_none(&self) -> bool;
+
+                #[requires(self.is_some())]
+                pub fn unwrap(self) -> T;
+
+                #[requires(self.is_some())]
+                pub fn expect(self, msg: &str) -> T;
+            }
+
+            #[extern_spec]
+            impl<T, E> Result<T, E> {
+                #[pure]
+                #[ensures(matches!(*self, Ok(_)) == result)]
+                pub fn is_ok(&self) -> bool;
+
+                #[pure]
+                #[ensures(self.is_ok() == !result)]
+                pub fn is_err(&self) -> bool;
+
+                #[requires(self.is_ok())]
+                pub fn unwrap(self) -> T
+                    where E: std::fmt::Debug;
+
+                #[requires(self.is_ok())]
+                pub fn expect(self, msg: &str) -> T
+                    where E: std::fmt::Debug;
+
+                #[requires(self.is_err())]
+                pub fn unwrap_err(self) -> E
+                    where T: std::fmt::Debug;
+
+                #[requires(self.is_err())]
+                pub fn expect_err(self, msg: &str) -> E
+                    where T: std::fmt::Debug;
+            }
+        }
+    };
+}
@@ -59,6 +59,16 @@ impl TemporaryEnvVar {
             original,
         }
     }
+
+    /// Temporarily unset an environment variable, until the returned value is dropped.
+    fn unset(name: &str) -> Self {
+        let original: Option<String> = env::var(name).ok();
+        env::remove_var(name);
+        TemporaryEnvVar {
+            name: name.to_string(),
+            original,
+        }
+    }
 }
 
 impl Drop for TemporaryEnvVar {
@@ -142,6 +152,15 @@ fn run_verification_overflow(group_name: &str, filter: &Option<String>) {
     run_verification(group_name, filter);
 }
 
+fn run_verification_termination(group_name: &str, filter: &Option<String>) {
+    let _temporary_env_vars = (
+        TemporaryEnvVar::set("PRUSTI_CHECK_LOOP_TERMINATION", "true"),
+        TemporaryEnvVar::set("PRUSTI_CHECK_PURE_FUNCTION_TERMINATION", "true"),
+    );
+
+    run_verification(group_name, filter);
+}
+
 fn run_verification_core_proof(group_name: &str, filter: &Option<String>) {
     let _temporary_env_vars = (
         TemporaryEnvVar::set("PRUSTI_CHECK_PANICS", "false"),
@@ -150,6 +169,21 @@ fn run_verification_core_proof(group_name: &str, filter: &Option<String>) {
     run_verification(group_name, filter);
 }
 
+fn run_verification_parallel(group_name: &str, filter: &Option<String>) {
+    let _temporary_env_vars = (
+        TemporaryEnvVar::set("PRUSTI_NUM_PARALLEL_VERIFIERS", "2"),
+    );
+
+    // On the server.
+    run_verification(group_name, filter);
+
+    // In local verifier processes.
+    let _temporary_env_vars = (
+        TemporaryEnvVar::unset("PRUSTI_SERVER_ADDRESS"),
+    );
+    run_verification(group_name, filter);
+}
+
 fn test_runner(_tests: &[&()]) {
     // Spawn server process as child (so it stays around until main function terminates)
     let server_address = ServerSideService::spawn_off_thread();
@@ -174,7 +208,15 @@ fn test_runner(_tests: &[&()]) {
     println!("[verify_overflow]");
     run_verification_overflow("verify_overflow", &filter);
 
+    // Test the verifier with loop termination checks enabled.
+    println!("[verify_termination]");
+    run_verification_termination("verify_termination", &filter);
+
     // Test the verifier with panic checks disabled (i.e. verify only the core proof).
     println!("[core_proof]");
     run_verification_core_proof("core_proof", &filter);
+
+    // Test the verifier with methods verified in parallel.
+    println!("[verify_parallel]");
+    run_verification_parallel("verify_parallel", &filter);
 }
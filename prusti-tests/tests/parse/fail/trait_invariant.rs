@@ -0,0 +1,8 @@
+use prusti_contracts::*;
+
+#[invariant(self.value() <= 100)]
+trait Bounded { //~ ERROR the `#[invariant]` attribute can only be applied to structs and enums
+    fn value(&self) -> u8;
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+use prusti_contracts::*;
+
+#[derive(Clone, Copy)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn nested() {
+    let mut a = [[0u32; 3]; 2];
+    a[1][2] = 5;
+    assert!(a[0][2] == 5); //~ ERROR the asserted expression might not hold
+}
+
+fn structs() {
+    let mut a = [Point { x: 1, y: 2 }; 3];
+    a[1] = Point { x: 3, y: 4 };
+    let p = a[0];
+    assert!(p.x == 3); //~ ERROR the asserted expression might not hold
+}
+
+fn array_arg(a: [u32; 4]) {
+    assert!(a.len() == 3); //~ ERROR the asserted expression might not hold
+}
+
+fn main() {}
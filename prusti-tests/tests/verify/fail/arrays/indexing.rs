@@ -0,0 +1,29 @@
+use prusti_contracts::*;
+
+fn out_of_bounds() {
+    let a = [1, 2, 3];
+    let i = 3;
+    let _x = a[i]; //~ ERROR assertion might fail with "index out of bounds"
+}
+
+fn unchecked_index(a: &[i32], i: usize) -> i32 {
+    a[i] //~ ERROR assertion might fail with "index out of bounds"
+}
+
+fn wrong_value() {
+    let mut a = [1, 2, 3];
+    a[1] = 5;
+    assert!(a[1] == 2); //~ ERROR the asserted expression might not hold
+}
+
+#[requires(a.len() > 0)]
+#[ensures(forall(|i: usize| i < a.len() ==> a[i] == 0))] //~ ERROR postcondition might not hold
+fn clear_first(a: &mut [i32]) {
+    a[0] = 0;
+}
+
+fn bad_subslice(a: &[i32]) {
+    let _s = &a[1..]; //~ ERROR assertion might fail with "slice index out of range"
+}
+
+fn main() {}
@@ -0,0 +1,38 @@
+use prusti_contracts::*;
+
+#[requires(x < 1000)]
+#[ensures(result > x)]
+async fn bigger(x: u32) -> u32 {
+    x + 1
+}
+
+#[ensures(result == x)] //~ ERROR postcondition might not hold
+async fn wrong_postcondition(x: u32) -> u32 {
+    if x < 1000 {
+        bigger(x).await
+    } else {
+        x
+    }
+}
+
+async fn unchecked_argument(x: u32) -> u32 {
+    bigger(x).await //~ ERROR precondition might not hold
+}
+
+#[requires(x < 1000)]
+async fn only_the_contract_is_known(x: u32) {
+    let y = bigger(x).await;
+    assert!(y == x + 1); //~ ERROR the asserted expression might not hold
+}
+
+async fn unchecked_in_block(x: u32) -> u32 {
+    let block = async move {
+        assert!(x < 1000); //~ ERROR the asserted expression might not hold
+        x
+    };
+    block.await
+}
+
+fn main() {
+    let _future = bigger(1000); //~ ERROR precondition might not hold
+}
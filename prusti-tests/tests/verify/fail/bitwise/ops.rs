@@ -0,0 +1,18 @@
+use prusti_contracts::*;
+
+fn wrong_mask(x: u32) {
+    assert!(x & 1 == 1); //~ ERROR the asserted expression might not hold
+}
+
+#[ensures(result == a | b)] //~ ERROR postcondition might not hold
+fn wrong_or(a: u8, b: u8) -> u8 {
+    a ^ b
+}
+
+fn wrong_shift(x: i32) {
+    if x == -8 {
+        assert!(x >> 1 == 4); //~ ERROR the asserted expression might not hold
+    }
+}
+
+fn main() {}
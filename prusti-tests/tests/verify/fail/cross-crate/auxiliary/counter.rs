@@ -0,0 +1,32 @@
+// no-prefer-dynamic
+
+use prusti_contracts::*;
+
+#[pure]
+#[ensures(result >= a && result >= b)]
+#[ensures(result == a || result == b)]
+pub fn max(a: i32, b: i32) -> i32 {
+    if a >= b { a } else { b }
+}
+
+pub struct Counter {
+    value: u32,
+}
+
+impl Counter {
+    #[ensures(result.value() == 0)]
+    pub fn new() -> Self {
+        Counter { value: 0 }
+    }
+
+    #[pure]
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+
+    #[requires(self.value() < 100)]
+    #[ensures(self.value() == old(self.value()) + 1)]
+    pub fn increment(&mut self) {
+        self.value += 1;
+    }
+}
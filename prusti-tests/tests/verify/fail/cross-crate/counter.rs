@@ -0,0 +1,23 @@
+// aux-build:counter.rs
+
+extern crate counter;
+
+use prusti_contracts::*;
+use counter::{max, Counter};
+
+#[ensures(result >= 4)] //~ ERROR postcondition might not hold
+fn at_least_four(x: i32) -> i32 {
+    max(x, 3)
+}
+
+fn increment_unchecked(counter: &mut Counter) {
+    counter.increment(); //~ ERROR precondition might not hold
+}
+
+fn count_once() {
+    let mut counter = Counter::new();
+    counter.increment();
+    assert!(counter.value() == 2); //~ ERROR the asserted expression might not hold
+}
+
+fn main() {}
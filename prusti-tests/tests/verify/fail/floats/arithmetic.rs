@@ -0,0 +1,24 @@
+use prusti_contracts::*;
+
+fn rounding() {
+    let a = 0.1;
+    let b = 0.2;
+    assert!(a + b == 0.3); //~ ERROR the asserted expression might not hold
+}
+
+fn nan_propagation(a: f32) {
+    let b = a * 2.0;
+    assert!(b == b); //~ ERROR the asserted expression might not hold
+}
+
+#[pure]
+fn sum(a: f64, b: f64) -> f64 {
+    a + b
+}
+
+#[ensures(result == sum(a, 1.0))] //~ ERROR postcondition might not hold
+fn increment(a: f64) -> f64 {
+    a + 1.0
+}
+
+fn main() {}
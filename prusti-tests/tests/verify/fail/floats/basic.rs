@@ -0,0 +1,17 @@
+use prusti_contracts::*;
+
+fn nan_equality(a: f64) {
+    assert!(a == a); //~ ERROR the asserted expression might not hold
+}
+
+fn unknown_result(a: f64, b: f64) {
+    let c = a + b;
+    assert!(!c.is_nan()); //~ ERROR the asserted expression might not hold
+}
+
+#[ensures(result < 1.0)] //~ ERROR postcondition might not hold
+fn bounded(a: f64) -> f64 {
+    a
+}
+
+fn main() {}
@@ -0,0 +1,70 @@
+use prusti_contracts::*;
+
+struct Pair {
+    first: i32,
+    second: i32,
+}
+
+impl Pair {
+    #[pure]
+    fn model(&self) -> Seq<i32> {
+        Seq::empty().push(self.first).push(self.second)
+    }
+
+    #[ensures(self.model().lookup(Int::new(0)) == old(self.first))] //~ ERROR postcondition might not hold
+    fn swap(&mut self) {
+        let tmp = self.first;
+        self.first = self.second;
+        self.second = tmp;
+    }
+}
+
+#[pure]
+#[ensures(result.len() == Int::new(2))] //~ ERROR postcondition might not hold
+fn single(value: i32) -> Seq<i32> {
+    Seq::single(value)
+}
+
+#[pure]
+#[ensures(result.contains(3))] //~ ERROR postcondition might not hold
+fn insert(set: Set<i32>) -> Set<i32> {
+    set.insert(2)
+}
+
+#[pure]
+#[requires(map.contains_key(key))]
+#[ensures(result.lookup(key) == value)] //~ ERROR postcondition might not hold
+fn lookup(map: Map<u32, i32>, key: u32, value: i32) -> Map<u32, i32> {
+    map
+}
+
+// Mathematical integers do not overflow, so this holds only for `Int`.
+#[pure]
+#[ensures(result > n)]
+fn successor(n: Int) -> Int {
+    n + Int::new(1)
+}
+
+#[pure]
+#[ensures(result > n)] //~ ERROR postcondition might not hold
+fn predecessor(n: Int) -> Int {
+    n - Int::new(1)
+}
+
+#[pure]
+fn first(seq: Seq<i32>) -> i32 {
+    seq.lookup(Int::new(0)) //~ ERROR the index of the sequence lookup might be out of bounds
+}
+
+#[pure]
+#[requires(!seq.is_empty())]
+fn first_of_non_empty(seq: Seq<i32>) -> i32 {
+    seq.lookup(Int::new(0))
+}
+
+#[pure]
+fn half(n: Int, d: Int) -> Int {
+    n / d //~ ERROR the divisor might be zero
+}
+
+fn main() {}
@@ -0,0 +1,63 @@
+use prusti_contracts::*;
+
+fn impure() {}
+
+fn modify(x: &mut i32) {
+    ghost! {
+        *x = 0; //~ ERROR ghost code cannot modify non-ghost state
+    }
+}
+
+fn call() {
+    ghost! {
+        impure(); //~ ERROR ghost code can only call pure functions
+    }
+}
+
+fn ghost_loop() {
+    ghost! {
+        let mut i = 0;
+        while i < 10 { //~ ERROR ghost code must terminate, so it cannot contain loops
+            i += 1;
+        }
+    }
+}
+
+fn ghost_break(n: u32) {
+    let mut i = 0;
+    while i < n {
+        ghost! {
+            if i == 3 {
+                break; //~ ERROR ghost code cannot jump out of its block
+            }
+        }
+        i += 1;
+    }
+}
+
+fn ghost_continue(n: u32) {
+    let mut i = 0;
+    while i < n {
+        i += 1;
+        ghost! {
+            continue; //~ ERROR ghost code cannot jump out of its block
+        }
+    }
+}
+
+fn ghost_return(x: u32) {
+    ghost! {
+        if x == 0 {
+            return; //~ ERROR ghost code cannot jump out of its block
+        }
+    }
+}
+
+fn wrong_ghost_state(x: i32) {
+    ghost! {
+        let copy = x;
+    }
+    prusti_assert!(copy == x + 1); //~ ERROR the asserted expression might not hold
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use prusti_contracts::*;
+
+#[ensures(old[loop_entry](*x) == *x)] //~ ERROR `old[label](..)` can only be used in the body of a function, not in its contract
+fn in_postcondition(x: &mut u32) {}
+
+fn main() {}
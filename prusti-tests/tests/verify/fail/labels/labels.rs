@@ -0,0 +1,39 @@
+use prusti_contracts::*;
+
+#[requires(*x < 100)]
+fn increment_twice(x: &mut u32) {
+    *x += 1;
+    prusti_label!(after_first);
+    *x += 1;
+    prusti_assert!(*x == old[after_first](*x)); //~ ERROR the asserted expression might not hold
+}
+
+#[requires(n < 1000)]
+fn add(n: u32) {
+    let mut value = 0;
+    let mut i = 0;
+    while i < n {
+        body_invariant!(value == old[loop_entry](value) + i + 1); //~ ERROR loop invariant might not hold in the first loop iteration
+        value += 1;
+        i += 1;
+    }
+}
+
+fn outside_of_loop(x: u32) {
+    prusti_assert!(old[loop_entry](x) == x); //~ ERROR `old[loop_entry](..)` can only be used in loop invariants
+}
+
+fn use_before_declaration(x: &mut u32) {
+    prusti_assert!(old[later](*x) == *x); //~ ERROR the label `later` might not have been reached at this point
+    prusti_label!(later);
+}
+
+fn declared_after_early_return(x: &mut u32, b: bool) {
+    if b {
+        prusti_assert!(old[after_check](*x) == *x); //~ ERROR the label `after_check` might not have been reached at this point
+        return;
+    }
+    prusti_label!(after_check);
+}
+
+fn main() {}
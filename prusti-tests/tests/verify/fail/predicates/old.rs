@@ -0,0 +1,30 @@
+use prusti_contracts::*;
+
+struct Counter {
+    value: u32,
+}
+
+predicate! {
+    fn has_grown(counter: &Counter) -> bool {
+        counter.value > old(counter.value)
+    }
+}
+
+predicate! {
+    fn has_grown_by(counter: &Counter, amount: u32) -> bool {
+        has_grown(counter) && counter.value == old(counter.value) + amount
+    }
+}
+
+#[ensures(has_grown(counter))] //~ ERROR postcondition might not hold
+fn reset(counter: &mut Counter) {
+    counter.value = 0;
+}
+
+#[requires(counter.value < 100)]
+#[ensures(has_grown_by(counter, 2))] //~ ERROR postcondition might not hold
+fn increment(counter: &mut Counter) {
+    counter.value += 1;
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+use prusti_contracts::*;
+
+struct Counter {
+    value: u32,
+}
+
+predicate! {
+    fn is_bounded(counter: &Counter, bound: u32) -> bool {
+        counter.value <= bound
+    }
+}
+
+#[requires(is_bounded(counter, 10))]
+#[ensures(is_bounded(counter, 10))] //~ ERROR postcondition might not hold
+fn increment(counter: &mut Counter) {
+    counter.value += 1;
+}
+
+fn check(counter: &Counter) -> bool {
+    is_bounded(counter, 10) //~ ERROR predicates can only be used in specifications
+}
+
+#[pure]
+fn check_pure(counter: &Counter) -> bool {
+    is_bounded(counter, 10) //~ ERROR predicates can only be used in specifications
+}
+
+fn main() {}
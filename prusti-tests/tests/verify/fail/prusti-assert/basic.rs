@@ -0,0 +1,24 @@
+use prusti_contracts::*;
+
+#[requires(x < 10)]
+fn test1(x: u32) -> u32 {
+    let y = x + 1;
+    prusti_assert!(y < 10); //~ ERROR the asserted expression might not hold
+    y
+}
+
+fn test2(x: u32) {
+    prusti_assume!(x > 5);
+    prusti_assert!(x > 6); //~ ERROR the asserted expression might not hold
+}
+
+fn test3() {
+    let mut i = 0;
+    while i < 10 {
+        body_invariant!(i < 10);
+        prusti_assert!(i < 9); //~ ERROR the asserted expression might not hold
+        i += 1;
+    }
+}
+
+fn main() {}
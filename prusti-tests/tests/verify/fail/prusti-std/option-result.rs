@@ -0,0 +1,21 @@
+use prusti_contracts::*;
+
+fn unwrap_none() {
+    let x: Option<u32> = None;
+    x.unwrap(); //~ ERROR precondition might not hold
+}
+
+fn expect_none(x: Option<u32>) {
+    x.expect("a value"); //~ ERROR precondition might not hold
+}
+
+fn unwrap_err_of_ok() {
+    let x: Result<u32, u32> = Ok(1);
+    x.unwrap_err(); //~ ERROR precondition might not hold
+}
+
+fn unwrap_unknown(x: Result<u32, u32>) {
+    x.unwrap(); //~ ERROR precondition might not hold
+}
+
+fn main() {}
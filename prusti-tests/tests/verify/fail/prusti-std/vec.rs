@@ -0,0 +1,34 @@
+use prusti_contracts::*;
+use prusti_std::vec_model;
+
+fn lookup_out_of_bounds() {
+    let mut v = Vec::new();
+    v.push(1);
+    prusti_assert!(vec_model(&v).lookup(Int::new(1)) == 1); //~ ERROR the index of the sequence lookup might be out of bounds
+}
+
+fn remove_from_empty() {
+    let mut v: Vec<u32> = Vec::new();
+    v.remove(0); //~ ERROR precondition might not hold
+}
+
+fn insert_past_end() {
+    let mut v = Vec::new();
+    v.insert(1, 5); //~ ERROR precondition might not hold
+}
+
+fn pop_changes_length() {
+    let mut v = Vec::new();
+    v.push(1);
+    v.pop();
+    assert!(v.len() == 1); //~ ERROR the asserted expression might not hold
+}
+
+fn push_keeps_prefix() {
+    let mut v = Vec::new();
+    v.push(1);
+    v.push(2);
+    prusti_assert!(vec_model(&v).lookup(Int::new(0)) == 2); //~ ERROR the asserted expression might not hold
+}
+
+fn main() {}
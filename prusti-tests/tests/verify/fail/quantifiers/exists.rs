@@ -0,0 +1,18 @@
+use prusti_contracts::*;
+
+#[pure]
+fn is_even(x: u32) -> bool {
+    x % 2 == 0
+}
+
+#[ensures(exists(|k: u32| is_even(k) && !is_even(k)))] //~ ERROR postcondition might not hold
+fn no_witness() {}
+
+#[requires(exists(|k: u32| is_even(k) && !is_even(k)))]
+fn needs_impossible_witness() {}
+
+fn caller() {
+    needs_impossible_witness(); //~ ERROR precondition might not hold
+}
+
+fn main() {}
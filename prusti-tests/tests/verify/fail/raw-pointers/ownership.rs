@@ -0,0 +1,48 @@
+#![feature(raw_ref_op)]
+
+use prusti_contracts::*;
+
+#[requires(own!(*p))]
+#[ensures(own!(*p))]
+#[ensures(unsafe { *p } == value)]
+unsafe fn write(p: *mut i32, value: i32) {
+    *p = value;
+}
+
+#[requires(own!(*p))]
+#[requires(unsafe { *p } > 0)]
+#[ensures(own!(*p))]
+unsafe fn needs_positive(p: *const i32) {}
+
+#[requires(own!(*p))]
+#[ensures(own!(*p))]
+#[ensures(unsafe { *p } == 2)] //~ ERROR postcondition might not hold
+unsafe fn wrong_value(p: *mut i32) {
+    write(p, 1);
+}
+
+#[requires(own!(*p))]
+#[ensures(own!(*p))]
+unsafe fn call_with_zero(p: *mut i32) {
+    write(p, 0);
+    needs_positive(p); //~ ERROR precondition might not hold
+}
+
+#[ensures(result == q)] //~ ERROR postcondition might not hold
+fn other(p: *const i32, q: *const i32) -> *const i32 {
+    p
+}
+
+unsafe fn read_without_ownership(p: *const i32) -> i32 { //~ ERROR the target of a raw pointer is accessed without being owned
+    *p
+}
+
+// The owner of the target is not the same on both paths, so ownership cannot be passed on.
+fn pointer_to_one_of(c: bool) { //~ ERROR the target of a raw pointer is accessed without being owned
+    let mut x = 1;
+    let mut y = 1;
+    let p = if c { &raw mut x } else { &raw mut y };
+    unsafe { write(p, 2) };
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use prusti_contracts::*;
+
+#[requires(s.len() == 1 && s.as_bytes()[0] == 97)]
+#[ensures(chars(s).lookup(Int::new(0)) == 'b')] //~ ERROR postcondition might not hold
+fn wrong_char(s: &str) {}
+
+fn non_ascii_literal() {
+    let s = "é";
+    prusti_assert!(chars(s).lookup(Int::new(0)) == 'e'); //~ ERROR the asserted expression might not hold
+}
+
+fn main() {}
@@ -0,0 +1,29 @@
+use prusti_contracts::*;
+
+fn wrong_len() {
+    let s = "hello";
+    assert!(s.len() == 4); //~ ERROR the asserted expression might not hold
+}
+
+fn wrong_byte() {
+    let s = "abc";
+    let bytes = s.as_bytes();
+    assert!(bytes[1] == 97); //~ ERROR the asserted expression might not hold
+}
+
+fn out_of_bounds(s: &str) -> u8 {
+    s.as_bytes()[0] //~ ERROR assertion might fail with "index out of bounds"
+}
+
+#[ensures(!s.is_empty())] //~ ERROR postcondition might not hold
+fn unknown(s: &str) {}
+
+#[requires(s.len() > 0)]
+fn needs_content(s: &str) {}
+
+fn call_with_empty() {
+    let s = "";
+    needs_content(s); //~ ERROR precondition might not hold
+}
+
+fn main() {}
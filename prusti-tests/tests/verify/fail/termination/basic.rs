@@ -0,0 +1,32 @@
+use prusti_contracts::*;
+
+#[pure]
+#[decreases(n)]
+fn loop_forever(n: u32) -> u32 {
+    loop_forever(n) //~ ERROR termination measure might not decrease at this recursive call
+}
+
+#[pure]
+#[decreases(n)]
+fn grow(n: u32) -> u32 {
+    if n == 0 { 0 } else { grow(n + 1) } //~ ERROR termination measure might not decrease at this recursive call
+}
+
+#[pure]
+#[decreases(m, n)]
+fn lex(m: u32, n: u32) -> u32 {
+    if m == 0 { 0 } else { lex(m, n + 1) } //~ ERROR termination measure might not decrease at this recursive call
+}
+
+#[pure]
+#[decreases(n)]
+fn ping(n: u32) -> bool {
+    pong(n) //~ ERROR recursive call to a pure function without a termination measure
+}
+
+#[pure]
+fn pong(n: u32) -> bool {
+    ping(n)
+}
+
+fn main() {}
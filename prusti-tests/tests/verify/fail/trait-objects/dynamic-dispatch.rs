@@ -0,0 +1,37 @@
+use prusti_contracts::*;
+
+trait Handler {
+    #[requires(0 < x && x < 1000)]
+    #[ensures(result > x)]
+    fn handle(&mut self, x: i32) -> i32;
+}
+
+struct Incrementer {
+    count: u32,
+}
+
+#[refine_trait_spec]
+impl Handler for Incrementer {
+    #[ensures(result == x + 1)]
+    fn handle(&mut self, x: i32) -> i32 {
+        x + 1
+    }
+}
+
+fn wrong_argument(handler: &mut dyn Handler) {
+    handler.handle(0); //~ ERROR precondition might not hold
+}
+
+fn implementation_spec_does_not_apply(handler: &mut dyn Handler) {
+    let y = handler.handle(5);
+    assert!(y == 6); //~ ERROR the asserted expression might not hold
+}
+
+fn value_behind_trait_object_is_havocked() {
+    let mut incrementer = Incrementer { count: 0 };
+    let handler: &mut dyn Handler = &mut incrementer;
+    handler.handle(5);
+    assert!(incrementer.count == 0); //~ ERROR the asserted expression might not hold
+}
+
+fn main() {}
@@ -0,0 +1,50 @@
+use prusti_contracts::*;
+
+enum List {
+    Nil,
+    Cons(i32, Box<List>),
+}
+
+/// The reference `cur` is reborrowed in the inner loop, and the loan is carried across
+/// the iterations of both loops.
+fn set_every_third(list: &mut List, value: i32) {
+    let mut cur = list;
+    let mut continue_outer = true;
+    while continue_outer {
+        let mut j = 0;
+        while j < 2 {
+            cur = match cur {
+                List::Cons(_, next) => &mut **next, //~ ERROR the expiration of a loan that flows from one iteration of a loop to the next one is not supported
+                List::Nil => return,
+            };
+            j += 1;
+        }
+        match cur {
+            List::Cons(val, _) => *val = value,
+            List::Nil => continue_outer = false,
+        }
+    }
+}
+
+/// The loan carried by the inner loop expires inside the outer loop.
+fn skip_in_each_row(rows: &mut [List; 2], n: u32) {
+    let mut i = 0;
+    while i < 2 {
+        body_invariant!(i < 2);
+        let mut cur = &mut rows[i];
+        let mut j = 0;
+        while j < n {
+            cur = match cur {
+                List::Cons(_, next) => &mut **next, //~ ERROR the expiration of a loan that flows from one iteration of a loop to the next one is not supported
+                List::Nil => break,
+            };
+            j += 1;
+        }
+        if let List::Cons(val, _) = cur {
+            *val = 0;
+        }
+        i += 1;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,60 @@
+use prusti_contracts::*;
+
+#[derive(Clone, Copy)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn repeat_local() {
+    let i = 3;
+    let a = [i; 4];
+    assert!(a[0] == 3);
+    assert!(a[3] == i);
+}
+
+fn nested() {
+    let mut a = [[0u32; 3]; 2];
+    a[1][2] = 5;
+    assert!(a[1][2] == 5);
+    assert!(a[0][2] == 0);
+    assert!(a[1][1] == 0);
+    let row = a[1];
+    assert!(row[2] == 5);
+}
+
+fn structs() {
+    let mut a = [Point { x: 1, y: 2 }; 3];
+    a[1] = Point { x: 3, y: 4 };
+    let p = a[1];
+    assert!(p.x == 3);
+    let q = a[0];
+    assert!(q.y == 2);
+}
+
+fn tuples() {
+    let a = [(1, true), (2, false)];
+    let t = a[1];
+    assert!(t.0 == 2);
+    assert!(!t.1);
+}
+
+fn array_arg(a: [u32; 4]) {
+    assert!(a.len() == 4);
+}
+
+#[ensures(result == 4)]
+fn array_ref_arg(a: &[u32; 4]) -> usize {
+    a.len()
+}
+
+fn returns_array() -> [u32; 2] {
+    [1, 2]
+}
+
+fn call_returns_array() {
+    let a = returns_array();
+    assert!(a.len() == 2);
+}
+
+fn main() {}
@@ -0,0 +1,47 @@
+use prusti_contracts::*;
+
+fn construct() {
+    let a = [1, 2, 3];
+    assert!(a.len() == 3);
+    assert!(a[0] == 1);
+    assert!(a[2] == 3);
+}
+
+fn repeat() {
+    let a = [7u32; 5];
+    assert!(a.len() == 5);
+    assert!(a[4] == 7);
+}
+
+fn update() {
+    let mut a = [1, 2, 3];
+    a[1] = 5;
+    assert!(a[0] == 1);
+    assert!(a[1] == 5);
+}
+
+fn first(a: [i32; 3]) -> i32 {
+    a[0]
+}
+
+#[requires(i < a.len())]
+#[ensures(result == a[i])]
+fn get(a: &[i32], i: usize) -> i32 {
+    a[i]
+}
+
+#[requires(a.len() > 0)]
+#[ensures(a.len() == old(a.len()))]
+#[ensures(a[0] == 0)]
+#[ensures(forall(|i: usize| (1 <= i && i < a.len()) ==> a[i] == old(a[i])))]
+fn clear_first(a: &mut [i32]) {
+    a[0] = 0;
+}
+
+fn call_with_array() {
+    let a = [4, 5, 6];
+    let x = get(&a, 1);
+    assert!(x == 5);
+}
+
+fn main() {}
@@ -9,7 +9,7 @@ pub struct fd_set {
     fds_bits: [i32; FD_SETSIZE / 32],
 }
 
-pub fn test(x: fd_set) -> fd_set { //~ ERROR unsupported type
+pub fn test(x: fd_set) -> fd_set {
     x
 }
 
@@ -0,0 +1,14 @@
+use prusti_contracts::*;
+
+#[ensures(forall(|j: usize| j < a.len() ==> a[j] == 0))]
+fn clear(a: &mut [u32]) {
+    let mut i = 0;
+    while i < a.len() {
+        body_invariant!(i < a.len());
+        body_invariant!(forall(|j: usize| j < i ==> a[j] == 0));
+        a[i] = 0;
+        i += 1;
+    }
+}
+
+fn main() {}
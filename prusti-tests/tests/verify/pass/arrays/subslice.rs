@@ -0,0 +1,27 @@
+use prusti_contracts::*;
+
+fn subslice() {
+    let a = [1, 2, 3, 4];
+    let s = &a[1..3];
+    assert!(s.len() == 2);
+    assert!(s[0] == 2);
+    assert!(s[1] == 3);
+}
+
+#[requires(a.len() >= 2)]
+#[ensures(result == a[1])]
+fn second(a: &[i32]) -> i32 {
+    let tail = &a[1..];
+    tail[0]
+}
+
+#[requires(a.len() >= 2)]
+fn prefix(a: &[i32]) {
+    let head = &a[..2];
+    assert!(head.len() == 2);
+    assert!(head[1] == a[1]);
+    let all = &a[..];
+    assert!(all.len() == a.len());
+}
+
+fn main() {}
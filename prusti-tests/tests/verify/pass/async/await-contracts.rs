@@ -0,0 +1,38 @@
+use prusti_contracts::*;
+
+#[requires(x < 1000)]
+#[ensures(result == x + 1)]
+async fn increment(x: u32) -> u32 {
+    x + 1
+}
+
+#[requires(x < 500)]
+#[ensures(result == x + 2)]
+async fn increment_twice(x: u32) -> u32 {
+    let y = increment(x).await;
+    increment(y).await
+}
+
+#[ensures(result >= x)]
+async fn local_state_survives_await(x: u32) -> u32 {
+    let before = x;
+    let mut y = x;
+    if x < 1000 {
+        y = increment(x).await;
+    }
+    assert!(before == x);
+    y
+}
+
+#[requires(x < 100)]
+#[ensures(result < 200)]
+async fn nested(x: u32) -> u32 {
+    let y = increment_twice(x).await;
+    let z = local_state_survives_await(y).await;
+    if z < 200 { z } else { 0 }
+}
+
+fn main() {
+    // Creating a future checks the precondition, but does not run the body.
+    let _future = increment_twice(3);
+}
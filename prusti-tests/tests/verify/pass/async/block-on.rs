@@ -0,0 +1,57 @@
+//! Async functions and blocks run without an executor: `block_on` polls a future on the
+//! current thread, with a waker that does nothing, until the future is ready.
+
+use prusti_contracts::*;
+use std::future::Future;
+use std::ptr;
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+#[trusted]
+fn noop_waker() -> Waker {
+    unsafe fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(ptr::null(), &VTABLE)
+    }
+    unsafe fn noop(_: *const ()) {}
+    const VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+    unsafe { Waker::from_raw(RawWaker::new(ptr::null(), &VTABLE)) }
+}
+
+#[trusted]
+fn block_on<F: Future<Output = u32>>(future: F) -> u32 {
+    let mut future = Box::pin(future);
+    let waker = noop_waker();
+    let mut context = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+#[requires(x < 1000)]
+#[ensures(result == x + 1)]
+async fn increment(x: u32) -> u32 {
+    x + 1
+}
+
+#[requires(x < 500)]
+#[ensures(result == x + 2)]
+async fn increment_twice(x: u32) -> u32 {
+    let y = increment(x).await;
+    increment(y).await
+}
+
+#[requires(x < 1000)]
+async fn increment_in_block(x: u32) -> u32 {
+    let block = async move {
+        assert!(x < 1000);
+        x + 1
+    };
+    block.await
+}
+
+fn main() {
+    let _two = block_on(increment_twice(0));
+    let _one = block_on(increment_in_block(0));
+    let _three = block_on(async { 1 + 2 });
+}
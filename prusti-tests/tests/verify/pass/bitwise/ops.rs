@@ -0,0 +1,66 @@
+use prusti_contracts::*;
+
+fn masks(x: u32) {
+    assert!(x & 1 == x % 2);
+    assert!(x | 0 == x);
+    assert!(x ^ x == 0);
+    assert!(x & 0xFF <= 0xFF);
+}
+
+fn constants(a: u8, b: u8) {
+    let x = a & 0;
+    assert!(x == 0);
+    if a == 0b1100 && b == 0b1010 {
+        assert!(a & b == 0b1000);
+        assert!(a | b == 0b1110);
+        assert!(a ^ b == 0b0110);
+        assert!(!a == 0b11110011);
+    }
+}
+
+fn signed(x: i8) {
+    assert!(x & -1 == x);
+    assert!(x ^ -1 == !x);
+    assert!(!x == -x - 1);
+}
+
+fn shifts(x: u8) {
+    assert!(x >> 1 == x / 2);
+    assert!((x << 1) as u32 == (x as u32 * 2) % 256);
+}
+
+fn signed_shifts(x: i32) {
+    if x == -8 {
+        assert!(x >> 1 == -4);
+        assert!(x << 2 == -32);
+    }
+}
+
+#[pure]
+fn is_even(x: u32) -> bool {
+    x & 1 == 0
+}
+
+#[requires(is_even(x))]
+#[ensures(result * 2 == x)]
+fn half(x: u32) -> u32 {
+    x >> 1
+}
+
+#[ensures(result == a & b)]
+#[ensures(result <= 0xF)]
+fn low_and(a: u64, b: u64) -> u64 {
+    a & b & 0xF
+}
+
+#[ensures(result >= a && result >= b)]
+fn or_is_larger(a: u128, b: u128) -> u128 {
+    a | b
+}
+
+#[ensures(result < 0x100)]
+fn low_byte(x: usize) -> usize {
+    x & 0xFF
+}
+
+fn main() {}
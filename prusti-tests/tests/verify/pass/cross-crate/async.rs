@@ -0,0 +1,15 @@
+// aux-build:increment.rs
+
+extern crate increment;
+
+use prusti_contracts::*;
+use increment::increment;
+
+#[requires(x < 500)]
+#[ensures(result == x + 2)]
+async fn increment_twice(x: u32) -> u32 {
+    let y = increment(x).await;
+    increment(y).await
+}
+
+fn main() {}
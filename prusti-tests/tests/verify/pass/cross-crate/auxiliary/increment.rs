@@ -0,0 +1,9 @@
+// no-prefer-dynamic
+
+use prusti_contracts::*;
+
+#[requires(x < 1000)]
+#[ensures(result == x + 1)]
+pub async fn increment(x: u32) -> u32 {
+    x + 1
+}
@@ -0,0 +1,28 @@
+// aux-build:counter.rs
+
+extern crate counter;
+
+use prusti_contracts::*;
+use counter::{max, Counter};
+
+#[ensures(result >= 3)]
+fn at_least_three(x: i32) -> i32 {
+    max(x, 3)
+}
+
+#[ensures(result.value() == 2)]
+fn count_twice() -> Counter {
+    let mut counter = Counter::new();
+    counter.increment();
+    counter.increment();
+    counter
+}
+
+#[requires(counter.value() < 50)]
+fn increment_and_check(counter: &mut Counter) {
+    let before = counter.value();
+    counter.increment();
+    assert!(counter.value() == before + 1);
+}
+
+fn main() {}
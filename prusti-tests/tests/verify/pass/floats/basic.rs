@@ -0,0 +1,105 @@
+use prusti_contracts::*;
+
+fn arithmetic(a: f64, b: f64) -> f64 {
+    let c = a + b * 2.0;
+    let d = c / (a - b);
+    -d % 3.0
+}
+
+fn single_precision(a: f32) -> f64 {
+    let b = a * 0.5f32;
+    b as f64
+}
+
+fn nan_is_not_equal(a: f64) {
+    if a.is_nan() {
+        assert!(a != a);
+        assert!(!(a < 1.0));
+    } else {
+        assert!(a == a);
+    }
+}
+
+fn comparisons(a: f64, b: f64) {
+    if a < b {
+        assert!(!(b < a));
+        assert!(a <= b);
+        assert!(a != b);
+        assert!(!a.is_nan());
+    }
+}
+
+fn casts(x: i32, a: f64) -> i64 {
+    let f = x as f64;
+    assert!(!f.is_nan());
+    let i = a as i8;
+    assert!(-128 <= i && i <= 127);
+    f as i64
+}
+
+#[pure]
+fn is_positive(a: f64) -> bool {
+    a > 0.0
+}
+
+#[requires(is_positive(a))]
+#[ensures(!a.is_nan())]
+fn positive(a: f64) {}
+
+fn exact_arithmetic() {
+    let a = 1.5;
+    let b = a * 2.0;
+    assert!(b == 3.0);
+    assert!(b - a == a);
+    assert!(-b < 0.0);
+    assert!(0.1 + 0.2 != 0.3);
+    assert!(1.0 / 0.0 > f64::MAX);
+}
+
+fn exact_casts() {
+    let a = 2.75f64;
+    assert!(a as i32 == 2);
+    assert!(-a as i32 == -2);
+    assert!(a as f32 == 2.75f32);
+    assert!(1e10 as u8 == 255);
+    assert!((1.0 / 0.0) as i64 == i64::MAX);
+    assert!(7u8 as f64 == 7.0);
+}
+
+#[pure]
+fn sum(a: f64, b: f64) -> f64 {
+    a + b
+}
+
+#[pure]
+fn negate(a: f64) -> f64 {
+    -a
+}
+
+#[requires(!a.is_nan())]
+#[ensures(result == sum(a, 1.0))]
+#[ensures(negate(negate(result)) == result)]
+fn increment(a: f64) -> f64 {
+    a + 1.0
+}
+
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+fn move_point(p: &mut Point, dx: f64) {
+    p.x = p.x + dx;
+}
+
+fn loop_sum(n: u32) -> f64 {
+    let mut sum = 0.0;
+    let mut i = 0;
+    while i < n {
+        sum = sum + 1.5;
+        i += 1;
+    }
+    sum
+}
+
+fn main() {}
@@ -0,0 +1,90 @@
+use prusti_contracts::*;
+
+struct Pair {
+    first: i32,
+    second: i32,
+}
+
+impl Pair {
+    #[pure]
+    fn model(&self) -> Seq<i32> {
+        Seq::empty().push(self.first).push(self.second)
+    }
+
+    #[ensures(self.model().len() == Int::new(2))]
+    #[ensures(self.model().lookup(Int::new(0)) == old(self.second))]
+    #[ensures(self.model().lookup(Int::new(1)) == old(self.first))]
+    fn swap(&mut self) {
+        let tmp = self.first;
+        self.first = self.second;
+        self.second = tmp;
+    }
+}
+
+#[pure]
+#[ensures(result.len() == Int::new(1))]
+#[ensures(result.contains(value))]
+#[ensures(result == Seq::single(value))]
+fn single(value: i32) -> Seq<i32> {
+    Seq::single(value)
+}
+
+#[pure]
+#[requires(Int::new(0) <= index && index < seq.len())]
+#[ensures(result.len() == seq.len())]
+#[ensures(result.lookup(index) == value)]
+fn update(seq: Seq<i32>, index: Int, value: i32) -> Seq<i32> {
+    seq.update(index, value)
+}
+
+#[pure]
+#[ensures(result.len() == a.len() + b.len())]
+fn concat(a: Seq<i32>, b: Seq<i32>) -> Seq<i32> {
+    a.concat(b)
+}
+
+#[pure]
+#[ensures(result > n)]
+fn successor(n: Int) -> Int {
+    n + Int::new(1)
+}
+
+#[pure]
+#[ensures(result.is_empty() == (n == Int::new(0)))]
+fn prefix(n: Int) -> Seq<i32> {
+    if n == Int::new(0) {
+        Seq::empty()
+    } else {
+        Seq::single(0)
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+// Structures are stored by their snapshot.
+#[pure]
+fn points(a: &Point, b: &Point) -> Seq<Point> {
+    Seq::single(*a).push(*b)
+}
+
+#[ensures(points(a, b).len() == Int::new(2))]
+#[ensures(points(a, b) == points(a, b))]
+fn two_points(a: &Point, b: &Point) {}
+
+// Division and remainder round towards zero, like those of Rust integers.
+#[ensures(Int::new(7) / Int::new(2) == Int::new(3))]
+#[ensures(Int::new(-7) / Int::new(2) == Int::new(-3))]
+#[ensures(Int::new(-7) / Int::new(-2) == Int::new(3))]
+#[ensures(Int::new(-7) % Int::new(2) == Int::new(-1))]
+#[ensures(Int::new(7) % Int::new(-2) == Int::new(1))]
+fn division() {}
+
+fn main() {
+    let mut pair = Pair { first: 1, second: 2 };
+    pair.swap();
+    prusti_assert!(pair.model().lookup(Int::new(0)) == 2);
+}
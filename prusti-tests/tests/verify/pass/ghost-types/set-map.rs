@@ -0,0 +1,31 @@
+use prusti_contracts::*;
+
+#[pure]
+fn digits() -> Set<u32> {
+    Set::empty().insert(0).insert(1).insert(2)
+}
+
+#[pure]
+#[ensures(result.contains_key(key))]
+#[ensures(result.lookup(key) == value)]
+fn assign(map: Map<u32, i32>, key: u32, value: i32) -> Map<u32, i32> {
+    map.insert(key, value)
+}
+
+#[ensures(digits().contains(1))]
+#[ensures(!digits().contains(3))]
+#[ensures(digits().len() == Int::new(3))]
+#[ensures(Set::single(2).is_subset(digits()))]
+#[ensures(digits().difference(Set::single(0)).len() == Int::new(2))]
+#[ensures(digits().union(Set::single(0)) == digits())]
+#[ensures(digits().intersection(Set::single(5)) == Set::empty())]
+#[ensures(!digits().is_empty() && Set::<u32>::empty().is_empty())]
+fn sets() {}
+
+#[ensures(assign(Map::empty(), 1, 10).len() == Int::new(1))]
+#[ensures(assign(assign(Map::empty(), 1, 10), 1, 20).lookup(1) == 20)]
+#[ensures(!assign(Map::empty(), 1, 10).contains_key(2))]
+#[ensures(!assign(Map::empty(), 1, 10).is_empty() && Map::<u32, i32>::empty().is_empty())]
+fn maps() {}
+
+fn main() {}
@@ -0,0 +1,36 @@
+use prusti_contracts::*;
+
+#[pure]
+fn double(x: u32) -> u32 {
+    2 * x
+}
+
+fn swap(a: &mut i32, b: &mut i32) {
+    ghost! {
+        let old_a = *a;
+        let old_b = *b;
+    }
+    let tmp = *a;
+    *a = *b;
+    *b = tmp;
+    prusti_assert!(*a == old_b && *b == old_a);
+}
+
+#[requires(n < 100)]
+fn count(n: u32) {
+    let mut i = 0;
+    ghost! {
+        let mut steps = 0u32;
+        let mut doubled = 0u32;
+    }
+    while i < n {
+        body_invariant!(i < n && steps == i && doubled == 2 * i);
+        i += 1;
+        ghost! {
+            steps += 1;
+            doubled = double(steps);
+        }
+    }
+}
+
+fn main() {}
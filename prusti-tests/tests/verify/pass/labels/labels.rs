@@ -0,0 +1,26 @@
+use prusti_contracts::*;
+
+#[requires(*x < 100)]
+fn increment_twice(x: &mut u32) {
+    *x += 1;
+    prusti_label!(after_first);
+    *x += 1;
+    prusti_assert!(*x == old[after_first](*x) + 1);
+    prusti_assert!(old[after_first](*x) == old(*x) + 1);
+}
+
+#[requires(start < 1000 && n < 1000)]
+#[ensures(result == start + n)]
+fn add(start: u32, n: u32) -> u32 {
+    let mut value = start;
+    let mut i = 0;
+    while i < n {
+        body_invariant!(i < n && value == old[loop_entry](value) + i);
+        body_invariant!(old[loop_entry](value) == start);
+        value += 1;
+        i += 1;
+    }
+    value
+}
+
+fn main() {}
@@ -0,0 +1,113 @@
+use prusti_contracts::*;
+
+struct Row {
+    cells: [i32; 4],
+}
+
+impl Row {
+    #[trusted]
+    #[pure]
+    #[requires(j < 4)]
+    fn get(&self, j: usize) -> i32 {
+        self.cells[j]
+    }
+
+    #[trusted]
+    #[requires(j < 4)]
+    #[ensures(self.get(j) == value)]
+    #[ensures(forall(|k: usize| (k < 4 && k != j) ==> self.get(k) == old(self.get(k))))]
+    fn set(&mut self, j: usize, value: i32) {
+        self.cells[j] = value;
+    }
+}
+
+struct Matrix {
+    rows: Vec<Row>,
+}
+
+impl Matrix {
+    #[trusted]
+    #[pure]
+    #[requires(i < 4 && j < 4)]
+    fn get(&self, i: usize, j: usize) -> i32 {
+        self.rows[i].cells[j]
+    }
+
+    #[trusted]
+    #[requires(i < 4)]
+    fn row_mut(&mut self, i: usize) -> &mut Row {
+        &mut self.rows[i]
+    }
+
+    #[trusted]
+    #[requires(i < 4 && j < 4)]
+    #[after_expiry(
+        self.get(i, j) == before_expiry(*result) &&
+        forall(|a: usize, b: usize| (a < 4 && b < 4 && (a != i || b != j)) ==>
+            self.get(a, b) == old(self.get(a, b)))
+    )]
+    fn cell_mut(&mut self, i: usize, j: usize) -> &mut i32 {
+        &mut self.rows[i].cells[j]
+    }
+}
+
+/// The row borrowed in the outer loop is used in the inner loop.
+fn fill(m: &mut Matrix, value: i32) {
+    let mut i = 0;
+    while i < 4 {
+        body_invariant!(i < 4);
+        let row = m.row_mut(i);
+        let mut j = 0;
+        while j < 4 {
+            body_invariant!(j < 4);
+            body_invariant!(forall(|k: usize| k < j ==> row.get(k) == value));
+            row.set(j, value);
+            j += 1;
+        }
+        assert!(row.get(0) == value);
+        assert!(row.get(3) == value);
+        i += 1;
+    }
+}
+
+/// A cell is borrowed in each iteration of the inner loop, and the loan expires before
+/// the next iteration.
+#[ensures(forall(|a: usize, b: usize| (a < 4 && b < 4) ==> m.get(a, b) == value))]
+fn fill_cells(m: &mut Matrix, value: i32) {
+    let mut i = 0;
+    while i < 4 {
+        body_invariant!(i < 4);
+        body_invariant!(forall(|a: usize, b: usize| (a < i && b < 4) ==> m.get(a, b) == value));
+        let mut j = 0;
+        while j < 4 {
+            body_invariant!(i < 4 && j < 4);
+            body_invariant!(forall(|a: usize, b: usize| (a < i && b < 4) ==> m.get(a, b) == value));
+            body_invariant!(forall(|b: usize| b < j ==> m.get(i, b) == value));
+            let cell = m.cell_mut(i, j);
+            *cell = value;
+            j += 1;
+        }
+        i += 1;
+    }
+}
+
+fn take(_x: &mut i32) {}
+
+fn two_borrows_per_iteration(a: &mut i32, b: &mut i32) {
+    let mut i = 0;
+    while i < 4 {
+        let x = &mut *a;
+        let y = &mut *b;
+        take(x);
+        take(y);
+        let mut j = 0;
+        while j < 4 {
+            let z = &mut *a;
+            take(z);
+            j += 1;
+        }
+        i += 1;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,33 @@
+use prusti_contracts::*;
+
+struct Counter {
+    value: u32,
+}
+
+predicate! {
+    fn has_grown(counter: &Counter) -> bool {
+        counter.value > old(counter.value)
+    }
+}
+
+predicate! {
+    fn has_grown_by(counter: &Counter, amount: u32) -> bool {
+        has_grown(counter) && counter.value == old(counter.value) + amount
+    }
+}
+
+#[requires(counter.value < 100)]
+#[ensures(has_grown(counter))]
+#[ensures(has_grown_by(counter, 1))]
+fn increment(counter: &mut Counter) {
+    counter.value += 1;
+}
+
+#[requires(counter.value < 100)]
+#[ensures(has_grown_by(counter, 2))]
+fn increment_twice(counter: &mut Counter) {
+    increment(counter);
+    increment(counter);
+}
+
+fn main() {}
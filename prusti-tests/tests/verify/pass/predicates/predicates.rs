@@ -0,0 +1,53 @@
+use prusti_contracts::*;
+
+struct VecWrapper {
+    v: Vec<i32>,
+}
+
+impl VecWrapper {
+    #[trusted]
+    #[pure]
+    fn len(&self) -> usize {
+        self.v.len()
+    }
+
+    #[trusted]
+    #[pure]
+    #[requires(0 <= index && index < self.len())]
+    fn lookup(&self, index: usize) -> i32 {
+        self.v[index]
+    }
+}
+
+struct Counter {
+    value: u32,
+}
+
+predicate! {
+    fn sorted(v: &VecWrapper) -> bool {
+        forall(|i: usize, j: usize| (0 <= i && i < j && j < v.len()) ==> v.lookup(i) <= v.lookup(j))
+    }
+}
+
+predicate! {
+    fn is_bounded(counter: &Counter, bound: u32) -> bool {
+        counter.value <= bound
+    }
+}
+
+#[requires(sorted(v) && v.len() > 2)]
+#[ensures(v.lookup(0) <= v.lookup(2))]
+fn first_and_third(v: &VecWrapper) {}
+
+#[requires(is_bounded(counter, 10) && counter.value < 10)]
+#[ensures(is_bounded(counter, 10))]
+#[ensures(counter.value == old(counter.value) + 1)]
+fn increment(counter: &mut Counter) {
+    counter.value += 1;
+}
+
+fn main() {
+    let mut counter = Counter { value: 0 };
+    increment(&mut counter);
+    prusti_assert!(is_bounded(&counter, 1));
+}
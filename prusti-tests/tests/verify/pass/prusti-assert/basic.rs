@@ -0,0 +1,32 @@
+use prusti_contracts::*;
+
+#[requires(x < 10)]
+fn test1(x: u32) -> u32 {
+    let y = x + 1;
+    prusti_assert!(y <= 10);
+    prusti_assert!(y == old(x) + 1);
+    y
+}
+
+fn test2(x: u32) {
+    prusti_assume!(x > 5);
+    prusti_assert!(x >= 5);
+}
+
+fn test3() {
+    let mut i = 0;
+    while i < 10 {
+        body_invariant!(i < 10);
+        prusti_assert!(i + 1 <= 10);
+        i += 1;
+    }
+    prusti_assert!(i == 10);
+}
+
+#[ensures(result > 0)]
+fn test4(x: u32) -> u32 {
+    prusti_assume!(x > 0);
+    x
+}
+
+fn main() {}
@@ -0,0 +1,33 @@
+use prusti_contracts::*;
+
+#[requires(x.is_some())]
+fn unwrap_some(x: Option<u32>) -> u32 {
+    x.unwrap()
+}
+
+fn options() {
+    let mut x = Some(3);
+    assert!(x.is_some());
+    assert!(!x.is_none());
+    let y = unwrap_some(x);
+    x = None;
+    assert!(x.is_none());
+    let z = Some(y).expect("a value");
+}
+
+fn results() {
+    let ok: Result<u32, u32> = Ok(1);
+    assert!(ok.is_ok());
+    assert!(!ok.is_err());
+    let a = ok.unwrap();
+    let ok: Result<u32, u32> = Ok(a);
+    let b = ok.expect("a value");
+
+    let err: Result<u32, u32> = Err(b);
+    assert!(err.is_err());
+    let c = err.unwrap_err();
+    let err: Result<u32, u32> = Err(c);
+    let d = err.expect_err("an error");
+}
+
+fn main() {}
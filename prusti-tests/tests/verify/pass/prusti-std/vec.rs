@@ -0,0 +1,72 @@
+use prusti_contracts::*;
+use prusti_std::vec_model;
+
+#[requires(v.len() > 0)]
+#[ensures(Seq::single(result) == old(vec_model(v)).slice(Int::new(0), Int::new(1)))]
+#[ensures(vec_model(v) == old(vec_model(v)).slice(Int::new(1), old(vec_model(v)).len()))]
+fn remove_first(v: &mut Vec<u32>) -> u32 {
+    v.remove(0)
+}
+
+fn push_and_pop() {
+    let mut v = Vec::new();
+    assert!(v.is_empty());
+    v.push(1);
+    v.push(2);
+    v.push(3);
+    assert!(v.len() == 3);
+    assert!(!v.is_empty());
+    prusti_assert!(vec_model(&v) == Seq::empty().push(1).push(2).push(3));
+    let last = v.pop();
+    assert!(last.is_some());
+    assert!(v.len() == 2);
+    prusti_assert!(vec_model(&v).lookup(Int::new(1)) == 2);
+    v.pop();
+    v.pop();
+    assert!(v.pop().is_none());
+}
+
+fn insert_and_remove() {
+    let mut v = Vec::with_capacity(4);
+    v.push(10);
+    v.push(30);
+    v.insert(1, 20);
+    assert!(v.len() == 3);
+    prusti_assert!(vec_model(&v) == Seq::empty().push(10).push(20).push(30));
+    let removed = v.remove(0);
+    assert!(removed == 10);
+    prusti_assert!(vec_model(&v).lookup(Int::new(0)) == 20);
+    let first = remove_first(&mut v);
+    assert!(first == 20);
+    prusti_assert!(vec_model(&v) == Seq::single(30));
+}
+
+fn truncate_and_clear() {
+    let mut v = Vec::new();
+    v.push(1);
+    v.push(2);
+    v.push(3);
+    v.truncate(5);
+    assert!(v.len() == 3);
+    v.truncate(1);
+    assert!(v.len() == 1);
+    prusti_assert!(vec_model(&v) == Seq::single(1));
+    v.clear();
+    assert!(v.is_empty());
+}
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn vector_of_structs() {
+    let mut v = Vec::new();
+    v.push(Point { x: 1, y: 2 });
+    v.push(Point { x: 3, y: 4 });
+    assert!(v.len() == 2);
+    v.remove(0);
+    assert!(v.len() == 1);
+}
+
+fn main() {}
@@ -0,0 +1,30 @@
+use prusti_contracts::*;
+
+#[pure]
+fn is_even(x: u32) -> bool {
+    x % 2 == 0
+}
+
+#[requires(is_even(x))]
+#[ensures(exists(|k: u32| is_even(k)))]
+fn witness(x: u32) {}
+
+#[requires(is_even(x))]
+#[ensures(exists(|k: u32| is_even(k), triggers=[(is_even(k),)]))]
+fn witness_with_triggers(x: u32) {}
+
+#[requires(exists(|k: u32| is_even(k)))]
+#[ensures(exists(|k: u32| is_even(k)))]
+fn propagate() {}
+
+#[requires(exists(|k: u32| is_even(k) && k > x))]
+fn needs_larger_even(x: u32) {}
+
+#[requires(x < 100)]
+fn provides_witness(x: u32) {
+    let witness = 2 * x + 2;
+    prusti_assert!(is_even(witness) && witness > x);
+    needs_larger_even(x);
+}
+
+fn main() {}
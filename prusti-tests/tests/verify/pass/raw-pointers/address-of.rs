@@ -0,0 +1,55 @@
+#![feature(raw_ref_op)]
+
+use prusti_contracts::*;
+
+#[requires(own!(*p))]
+#[ensures(own!(*p))]
+#[ensures(unsafe { *p } == value)]
+unsafe fn write(p: *mut i32, value: i32) {
+    *p = value;
+}
+
+#[requires(c ==> own!(*p))]
+#[ensures(c ==> own!(*p))]
+#[ensures(c ==> unsafe { *p } == value)]
+unsafe fn write_if(c: bool, p: *mut i32, value: i32) {
+    if c {
+        *p = value;
+    }
+}
+
+fn raw_address_of() {
+    let mut x = 1;
+    let p = &raw mut x;
+    unsafe { write(p, 2) };
+    assert!(x == 2);
+}
+
+fn cast_reference() {
+    let mut x = 1;
+    let p = &mut x as *mut i32;
+    unsafe { write(p, 3) };
+    assert!(x == 3);
+}
+
+// The source of a raw pointer is also known in the blocks after the one that created it.
+fn pointer_across_blocks(c: bool) {
+    let mut x = 1;
+    let mut y = 1;
+    let p = &raw mut x;
+    if c {
+        y = 2;
+    }
+    unsafe { write(p, 4) };
+    assert!(x == 4);
+    assert!(c ==> y == 2);
+}
+
+fn conditional_ownership(c: bool) {
+    let mut x = 1;
+    let p = &raw mut x;
+    unsafe { write_if(c, p, 5) };
+    assert!(c ==> x == 5);
+}
+
+fn main() {}
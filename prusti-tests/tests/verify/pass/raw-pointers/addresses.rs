@@ -0,0 +1,24 @@
+use prusti_contracts::*;
+
+struct Link {
+    target: *const u32,
+}
+
+#[ensures(result == p)]
+fn identity(p: *const u32) -> *const u32 {
+    p
+}
+
+#[ensures(result.target == p)]
+fn link(p: *const u32) -> Link {
+    Link { target: p }
+}
+
+fn compare(p: *const u32) {
+    let q = identity(p);
+    assert!(q == p);
+    let l = link(q);
+    assert!(l.target == p);
+}
+
+fn main() {}
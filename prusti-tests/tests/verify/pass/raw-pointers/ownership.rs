@@ -0,0 +1,46 @@
+use prusti_contracts::*;
+
+#[requires(own!(*p))]
+#[ensures(own!(*p))]
+#[ensures(unsafe { *p } == value)]
+unsafe fn write(p: *mut i32, value: i32) {
+    *p = value;
+}
+
+#[requires(own!(*p))]
+#[ensures(own!(*p))]
+#[ensures(result == unsafe { *p })]
+unsafe fn read(p: *const i32) -> i32 {
+    *p
+}
+
+#[requires(own!(*p))]
+#[requires(unsafe { *p } < 100)]
+#[ensures(own!(*p))]
+#[ensures(unsafe { *p } == old(unsafe { *p }) + 1)]
+unsafe fn increment(p: *mut i32) {
+    let value = read(p);
+    write(p, value + 1);
+}
+
+#[requires(own!(*p))]
+#[ensures(own!(*p))]
+#[ensures(unsafe { *p } == 3)]
+unsafe fn write_twice(p: *mut i32) {
+    write(p, 1);
+    let q = p;
+    write(q, 2);
+    increment(p);
+}
+
+#[requires(own!(*p) && own!(*q))]
+#[ensures(own!(*p) && own!(*q))]
+#[ensures(unsafe { *p } == old(unsafe { *q }))]
+#[ensures(unsafe { *q } == old(unsafe { *p }))]
+unsafe fn swap(p: *mut i32, q: *mut i32) {
+    let tmp = *p;
+    *p = *q;
+    *q = tmp;
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use prusti_contracts::*;
+
+#[requires(s.len() == 1 && s.as_bytes()[0] == 97)]
+#[ensures(chars(s).lookup(Int::new(0)) == 'a')]
+fn ascii(s: &str) {}
+
+#[requires(s.is_empty())]
+#[ensures(chars(s).is_empty())]
+fn empty(s: &str) {}
+
+fn non_ascii_literal() {
+    // The two UTF-8 bytes of the literal are decoded as a single character.
+    let s = "é";
+    prusti_assert!(chars(s).lookup(Int::new(0)) == 'é');
+}
+
+fn main() {}
@@ -0,0 +1,59 @@
+use prusti_contracts::*;
+
+fn pass_through(s: &str) -> &str {
+    s
+}
+
+fn literal() {
+    let s = "hello";
+    assert!(s.len() == 5);
+    assert!(!s.is_empty());
+    let bytes = s.as_bytes();
+    assert!(bytes[0] == 104);
+    assert!(bytes[4] == 111);
+}
+
+fn empty_literal() {
+    let s = "";
+    assert!(s.is_empty());
+}
+
+fn non_ascii_literal() {
+    // The length of a string slice is the number of its UTF-8 bytes.
+    let s = "ä";
+    assert!(s.len() == 2);
+}
+
+#[requires(s.len() > 0)]
+#[ensures(result == s.as_bytes()[0])]
+fn first_byte(s: &str) -> u8 {
+    s.as_bytes()[0]
+}
+
+#[pure]
+fn is_short(s: &str) -> bool {
+    s.len() < 4
+}
+
+#[ensures(result == !s.is_empty())]
+fn has_content(s: &str) -> bool {
+    s.len() != 0
+}
+
+fn call_with_literal() {
+    let s = "abc";
+    assert!(is_short(s));
+    let b = first_byte(s);
+    assert!(b == 97);
+    assert!(has_content("abc"));
+}
+
+struct Named<'a> {
+    name: &'a str,
+}
+
+fn get_name<'a>(n: &Named<'a>) -> &'a str {
+    n.name
+}
+
+fn main() {}
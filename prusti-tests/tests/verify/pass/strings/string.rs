@@ -0,0 +1,19 @@
+// Owned strings are opaque: they can be passed around, but their contents are not modeled.
+
+fn pass_through(s: String) -> String {
+    s
+}
+
+fn borrow(s: &String) -> &String {
+    s
+}
+
+fn identity(s: &str) -> &str {
+    s
+}
+
+fn use_as_str(s: &String) -> usize {
+    identity(s.as_str()).len()
+}
+
+fn main() {}
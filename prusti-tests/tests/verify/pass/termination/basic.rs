@@ -0,0 +1,51 @@
+use prusti_contracts::*;
+
+#[pure]
+#[decreases(n)]
+fn fib(n: u32) -> u32 {
+    if n <= 1 {
+        n
+    } else {
+        fib(n - 1) + fib(n - 2)
+    }
+}
+
+#[pure]
+#[decreases(n)]
+fn is_even(n: u32) -> bool {
+    if n == 0 { true } else { is_odd(n - 1) }
+}
+
+#[pure]
+#[decreases(n)]
+fn is_odd(n: u32) -> bool {
+    if n == 0 { false } else { is_even(n - 1) }
+}
+
+#[pure]
+#[decreases(m, n)]
+fn count_down(m: u32, n: u32) -> u32 {
+    if n > 0 {
+        count_down(m, n - 1)
+    } else if m > 0 {
+        count_down(m - 1, 100)
+    } else {
+        0
+    }
+}
+
+pub enum List {
+    Nil,
+    Cons(u32, Box<List>),
+}
+
+#[pure]
+#[decreases(list)]
+fn len(list: &List) -> u32 {
+    match list {
+        List::Nil => 0,
+        List::Cons(_, tail) => 1 + len(tail),
+    }
+}
+
+fn main() {}
@@ -0,0 +1,59 @@
+use prusti_contracts::*;
+
+trait Handler {
+    #[requires(0 < x && x < 1000)]
+    #[ensures(result > x)]
+    fn handle(&mut self, x: i32) -> i32;
+
+    #[ensures(result >= 0)]
+    fn priority(&self) -> i32;
+}
+
+struct Incrementer {
+    count: u32,
+}
+
+impl Handler for Incrementer {
+    fn handle(&mut self, x: i32) -> i32 {
+        if self.count < 1000 {
+            self.count += 1;
+        }
+        x + 1
+    }
+
+    fn priority(&self) -> i32 {
+        1
+    }
+}
+
+#[requires(0 < x && x < 1000)]
+fn dispatch(handler: &mut dyn Handler, x: i32) -> i32 {
+    let y = handler.handle(x);
+    assert!(y > x);
+    y
+}
+
+fn dispatch_shared(handler: &dyn Handler) -> i32 {
+    let p = handler.priority();
+    assert!(p >= 0);
+    p
+}
+
+#[requires(0 < x && x < 1000)]
+#[ensures(result > x)]
+fn dispatch_boxed(mut handler: Box<dyn Handler>, x: i32) -> i32 {
+    handler.handle(x)
+}
+
+#[ensures(result > 5)]
+fn dispatch_new_box() -> i32 {
+    let handler: Box<dyn Handler> = Box::new(Incrementer { count: 0 });
+    dispatch_boxed(handler, 5)
+}
+
+fn main() {
+    let mut incrementer = Incrementer { count: 0 };
+    let handler: &mut dyn Handler = &mut incrementer;
+    let y = handler.handle(5);
+    assert!(y > 5);
+}
@@ -0,0 +1,9 @@
+trait Trait {}
+
+impl Trait for u32 {}
+
+pub fn to_trait_object(x: &mut u32) {
+    let _y: &mut dyn Trait = x;
+}
+
+fn main(){}
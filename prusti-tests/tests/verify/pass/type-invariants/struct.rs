@@ -0,0 +1,37 @@
+use prusti_contracts::*;
+
+#[invariant(self.value <= 100)]
+pub struct Percentage {
+    value: u8,
+}
+
+impl Percentage {
+    #[requires(value <= 100)]
+    pub fn new(value: u8) -> Self {
+        Percentage { value }
+    }
+
+    pub fn incr(&mut self) {
+        if self.value < 100 {
+            self.value += 1;
+        }
+    }
+
+    #[ensures(result <= 100)]
+    pub fn into_value(self) -> u8 {
+        self.value
+    }
+
+    // Private methods are allowed to temporarily break the invariant.
+    #[ensures(self.value == value)]
+    fn set_unchecked(&mut self, value: u8) {
+        self.value = value;
+    }
+
+    pub fn reset(&mut self) {
+        self.set_unchecked(200);
+        self.set_unchecked(0);
+    }
+}
+
+fn main() {}
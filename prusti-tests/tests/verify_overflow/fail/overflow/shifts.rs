@@ -0,0 +1,12 @@
+use prusti_contracts::*;
+
+fn shl(x: u32, s: u32) -> u32 {
+    x << s //~ ERROR assertion might fail with "attempt to shift left with overflow"
+}
+
+#[requires(s <= 64)]
+fn shr(x: i64, s: u8) -> i64 {
+    x >> s //~ ERROR assertion might fail with "attempt to shift right with overflow"
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use prusti_contracts::*;
+
+#[requires(s < 32)]
+fn shl(x: u32, s: u32) -> u32 {
+    x << s
+}
+
+#[requires(s < 64)]
+fn shr(x: i64, s: u8) -> i64 {
+    x >> s
+}
+
+fn wrapping_shl(x: u8) -> u8 {
+    x << 7
+}
+
+fn main() {}
@@ -1,6 +1,6 @@
 use prusti_contracts::*;
 
-#[requires(a as f32 as i32 == 0)]
-fn unsupported_cast(a: i64) {}
+#[requires(b as i32 == 0)]
+fn unsupported_cast(b: bool) {}
 
 fn main() {}
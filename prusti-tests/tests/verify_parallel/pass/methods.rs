@@ -0,0 +1,37 @@
+use prusti_contracts::*;
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[pure]
+fn is_origin(p: &Point) -> bool {
+    p.x == 0 && p.y == 0
+}
+
+#[ensures(is_origin(&result))]
+fn origin() -> Point {
+    Point { x: 0, y: 0 }
+}
+
+#[requires(is_origin(p))]
+#[ensures(!is_origin(p))]
+fn move_right(p: &mut Point) {
+    p.x += 1;
+}
+
+#[requires(x < 100)]
+#[ensures(result == x + 1)]
+fn increment(x: u32) -> u32 {
+    x + 1
+}
+
+fn client() {
+    let mut p = origin();
+    move_right(&mut p);
+    assert!(!is_origin(&p));
+    assert!(increment(1) == 2);
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+use prusti_contracts::*;
+
+fn no_progress(n: u32) {
+    let mut i = n;
+    while i > 0 {
+        body_variant!(i); //~ ERROR loop variant might not decrease after a loop iteration.
+        if i > 10 {
+            i -= 1;
+        }
+    }
+}
+
+fn negative(n: i32) {
+    let mut i = n;
+    while i > -10 {
+        body_invariant!(i > -10);
+        body_variant!(i); //~ ERROR loop variant might be negative at the beginning of a loop iteration.
+        i -= 1;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use prusti_contracts::*;
+
+#[pure]
+fn f(x: u32) -> u32 {
+    f(x) //~ ERROR recursive pure function without a termination measure
+}
+
+#[pure]
+#[decreases(n)]
+fn ping(n: u32) -> bool {
+    if n == 0 { true } else { pong(n - 1) } //~ ERROR recursive call to a pure function without a termination measure
+}
+
+#[pure]
+fn pong(n: u32) -> bool {
+    ping(n) //~ ERROR recursive pure function without a termination measure
+}
+
+fn main() {}
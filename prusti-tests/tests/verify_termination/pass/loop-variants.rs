@@ -0,0 +1,37 @@
+use prusti_contracts::*;
+
+fn count_down(n: u32) {
+    let mut i = n;
+    while i > 0 {
+        body_invariant!(i <= n);
+        body_variant!(i);
+        i -= 1;
+    }
+}
+
+#[requires(n < 100)]
+fn count_up(n: u32) {
+    let mut i = 0;
+    while i < n {
+        body_invariant!(i < n);
+        body_variant!(n - i);
+        i += 1;
+    }
+}
+
+fn nested(n: u32, m: u32) {
+    let mut i = n;
+    let mut j = m;
+    while i > 0 {
+        body_invariant!(i <= n);
+        body_variant!(i, j);
+        if j > 0 {
+            j -= 1;
+        } else {
+            i -= 1;
+            j = m;
+        }
+    }
+}
+
+fn main() {}
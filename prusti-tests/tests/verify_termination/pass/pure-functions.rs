@@ -0,0 +1,14 @@
+use prusti_contracts::*;
+
+#[pure]
+#[decreases(n)]
+fn fib(n: u32) -> u32 {
+    if n <= 1 { n } else { fib(n - 1) + fib(n - 2) }
+}
+
+#[pure]
+fn not_recursive(n: u32) -> u32 {
+    fib(n)
+}
+
+fn main() {}
@@ -6,6 +6,7 @@
 
 use crate::encoder::places;
 use prusti_interface::data::ProcedureDefId;
+use prusti_interface::environment::mir_utils::AsyncFnParameters;
 // use prusti_interface::specifications::{
 //     AssertionKind, SpecificationSet, TypedAssertion, TypedExpression, TypedSpecification,
 //     TypedSpecificationSet,
@@ -430,7 +431,30 @@ where
     let args_ty:Vec<(mir::Local, ty::Ty<'tcx>)>;
     let return_ty;
 
-    if !tcx.is_closure(proc_def_id) {
+    if let Some(hir::GeneratorKind::Async(hir::AsyncGeneratorKind::Fn)) = tcx.generator_kind(proc_def_id) {
+        let (mir, _) = tcx.mir_promoted(ty::WithOptConstParam::unknown(proc_def_id.expect_local()));
+        let mir = mir.borrow();
+        // The body of an `async fn` is a generator, which moves the parameters of the function
+        // out of its upvars into locals. These locals are the arguments of the contract.
+        // _0 - return, with the output type of the `async fn`
+        // _1 - the generator
+        // _2 - the resume argument
+        args_ty = mir.get_async_fn_parameters().into_iter()
+            .map(|(_, local)| (local, mir.local_decls[local].ty))
+            .collect();
+        return_ty = mir.local_decls[mir::RETURN_PLACE].ty;
+    } else if let Some(hir::GeneratorKind::Async(_)) = tcx.generator_kind(proc_def_id) {
+        let (mir, _) = tcx.mir_promoted(ty::WithOptConstParam::unknown(proc_def_id.expect_local()));
+        let mir = mir.borrow();
+        // The body of an `async` block only receives the generator, which owns the variables
+        // that the block captures.
+        // _0 - return, with the output type of the `async` block
+        // _1 - the generator
+        // _2 - the resume argument
+        let generator = mir::Local::from_usize(1);
+        args_ty = vec![(generator, mir.local_decls[generator].ty)];
+        return_ty = mir.local_decls[mir::RETURN_PLACE].ty;
+    } else if !tcx.is_closure(proc_def_id) {
         // FIXME: "skip_binder" is most likely wrong
         // FIXME: Replace with FakeMirEncoder.
         let fn_sig: FnSig = tcx.fn_sig(proc_def_id).skip_binder();
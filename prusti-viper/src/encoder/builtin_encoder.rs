@@ -12,6 +12,7 @@ pub enum BuiltinMethodKind {
     HavocBool,
     HavocInt,
     HavocRef,
+    HavocFloat(vir::FloatSize),
 }
 
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
@@ -20,11 +21,58 @@ pub enum BuiltinFunctionKind {
     Unreachable(vir::Type),
     /// type
     Undefined(vir::Type),
+    /// The characters of the UTF-8 bytes of a string slice
+    StrChars,
 }
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
 pub enum BuiltinDomainKind {
     Nat,
     Primitive,
+    Float(vir::FloatSize),
+    /// bit width
+    BitVector(u32),
+}
+
+/// The functions of the bit vector domains, which operate on the binary representation of
+/// non-negative integers of the bit width of the domain.
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+pub enum BitVectorFunc {
+    And,
+    Or,
+    Xor,
+    /// Two to the power of the argument, which is smaller than the bit width.
+    Pow2,
+}
+
+impl BitVectorFunc {
+    fn name(self) -> &'static str {
+        match self {
+            BitVectorFunc::And => "and",
+            BitVectorFunc::Or => "or",
+            BitVectorFunc::Xor => "xor",
+            BitVectorFunc::Pow2 => "pow2",
+        }
+    }
+}
+
+/// Returns the name of the bit vector domain of the given bit width.
+fn bitvector_domain_name(bits: u32) -> String {
+    format!("BitVector{}", bits)
+}
+
+/// Returns the function `func` of the bit vector domain of the given bit width.
+pub fn bitvector_domain_func(bits: u32, func: BitVectorFunc) -> vir::DomainFunc {
+    let num_args = if func == BitVectorFunc::Pow2 { 1 } else { 2 };
+    let domain_name = bitvector_domain_name(bits);
+    vir::DomainFunc {
+        name: format!("{}${}", domain_name, func.name()),
+        formal_args: (0..num_args)
+            .map(|i| vir::LocalVar::new(format!("_{}", i), vir::Type::Int))
+            .collect(),
+        return_type: vir::Type::Int,
+        unique: false,
+        domain_name,
+    }
 }
 
 pub struct BuiltinEncoder {}
@@ -39,6 +87,7 @@ impl BuiltinEncoder {
             BuiltinMethodKind::HavocBool => "builtin$havoc_bool".to_string(),
             BuiltinMethodKind::HavocInt => "builtin$havoc_int".to_string(),
             BuiltinMethodKind::HavocRef => "builtin$havoc_ref".to_string(),
+            BuiltinMethodKind::HavocFloat(size) => format!("builtin$havoc_{}", size),
         }
     }
 
@@ -47,6 +96,7 @@ impl BuiltinEncoder {
             BuiltinMethodKind::HavocBool => vir::Type::Bool,
             BuiltinMethodKind::HavocInt => vir::Type::Int,
             BuiltinMethodKind::HavocRef => vir::Type::TypedRef("".to_string()),
+            BuiltinMethodKind::HavocFloat(size) => vir::Type::Float(size),
         };
         vir::BodylessMethod {
             name: self.encode_builtin_method_name(method),
@@ -65,10 +115,23 @@ impl BuiltinEncoder {
             BuiltinFunctionKind::Unreachable(vir::Type::Domain(_)) => {
                 format!("builtin$unreach_domain")
             }
+            BuiltinFunctionKind::Unreachable(typ @ vir::Type::Seq(_))
+            | BuiltinFunctionKind::Unreachable(typ @ vir::Type::Set(_))
+            | BuiltinFunctionKind::Unreachable(typ @ vir::Type::Map(..))
+            | BuiltinFunctionKind::Unreachable(typ @ vir::Type::Float(_)) => {
+                format!("builtin$unreach_{}", typ.name())
+            }
             BuiltinFunctionKind::Undefined(vir::Type::Int) => format!("builtin$undef_int"),
             BuiltinFunctionKind::Undefined(vir::Type::Bool) => format!("builtin$undef_bool"),
             BuiltinFunctionKind::Undefined(vir::Type::TypedRef(_)) => format!("builtin$undef_ref"),
             BuiltinFunctionKind::Undefined(vir::Type::Domain(_)) => format!("builtin$undef_doman"),
+            BuiltinFunctionKind::Undefined(typ @ vir::Type::Seq(_))
+            | BuiltinFunctionKind::Undefined(typ @ vir::Type::Set(_))
+            | BuiltinFunctionKind::Undefined(typ @ vir::Type::Map(..))
+            | BuiltinFunctionKind::Undefined(typ @ vir::Type::Float(_)) => {
+                format!("builtin$undef_{}", typ.name())
+            }
+            BuiltinFunctionKind::StrChars => format!("builtin$str_chars"),
         }
     }
 
@@ -92,6 +155,88 @@ impl BuiltinEncoder {
                 posts: vec![],
                 body: None,
             },
+            BuiltinFunctionKind::StrChars => self.encode_str_chars_builtin_function(fn_name),
+        }
+    }
+
+    /// The characters of a string slice are decoded from its UTF-8 bytes one character at a
+    /// time: the first byte determines the number of bytes of the character, and the
+    /// remaining bytes are decoded recursively. Missing continuation bytes of a truncated
+    /// character are decoded as zero bits, since the bytes of a string slice are valid UTF-8.
+    fn encode_str_chars_builtin_function(&self, fn_name: String) -> vir::Function {
+        let chars_type = vir::Type::Seq(box vir::Type::Int);
+        let bytes = vir::LocalVar::new("bytes", chars_type.clone());
+        let bytes_expr: vir::Expr = bytes.clone().into();
+        let len = vir::Expr::seq_len(bytes_expr.clone());
+        let byte = |i: i64| vir::Expr::ite(
+            vir::Expr::lt_cmp(i.into(), len.clone()),
+            vir::Expr::seq_index(bytes_expr.clone(), i.into()),
+            0x80.into(),
+        );
+        // The payload of a byte after removing its `prefix` bits, shifted to the position of
+        // the byte in the character.
+        let payload = |i: i64, prefix: i64, shift: i64| {
+            vir::Expr::mul(vir::Expr::sub(byte(i), prefix.into()), shift.into())
+        };
+        let first = byte(0);
+        let is_width = |limit: i64| vir::Expr::lt_cmp(first.clone(), limit.into());
+        let width = vir::Expr::ite(
+            is_width(0x80),
+            1.into(),
+            vir::Expr::ite(
+                is_width(0xE0),
+                2.into(),
+                vir::Expr::ite(is_width(0xF0), 3.into(), 4.into()),
+            ),
+        );
+        let sum = |terms: Vec<vir::Expr>| {
+            terms.into_iter().fold(0.into(), |acc, term| vir::Expr::add(acc, term))
+        };
+        let decoded = vir::Expr::ite(
+            is_width(0x80),
+            first.clone(),
+            vir::Expr::ite(
+                is_width(0xE0),
+                sum(vec![payload(0, 0xC0, 1 << 6), payload(1, 0x80, 1)]),
+                vir::Expr::ite(
+                    is_width(0xF0),
+                    sum(vec![
+                        payload(0, 0xE0, 1 << 12),
+                        payload(1, 0x80, 1 << 6),
+                        payload(2, 0x80, 1),
+                    ]),
+                    sum(vec![
+                        payload(0, 0xF0, 1 << 18),
+                        payload(1, 0x80, 1 << 12),
+                        payload(2, 0x80, 1 << 6),
+                        payload(3, 0x80, 1),
+                    ]),
+                ),
+            ),
+        );
+        let rest = vir::Expr::func_app(
+            fn_name.clone(),
+            vec![vir::Expr::seq_slice(bytes_expr.clone(), width, len.clone())],
+            vec![bytes.clone()],
+            chars_type.clone(),
+            vir::Position::default(),
+        );
+        let body = vir::Expr::ite(
+            vir::Expr::eq_cmp(len, 0.into()),
+            vir::Expr::seq(vir::Type::Int, vec![]),
+            vir::Expr::container_op(
+                vir::ContainerOpKind::SeqConcat,
+                vir::Expr::seq(vir::Type::Int, vec![decoded]),
+                rest,
+            ),
+        );
+        vir::Function {
+            name: fn_name,
+            formal_args: vec![bytes],
+            return_type: chars_type,
+            pres: vec![],
+            posts: vec![],
+            body: Some(body),
         }
     }
 
@@ -99,6 +244,175 @@ impl BuiltinEncoder {
         match kind {
             BuiltinDomainKind::Nat => self.encode_nat_builtin_domain(),
             BuiltinDomainKind::Primitive => self.encode_primitive_builtin_domain(),
+            BuiltinDomainKind::Float(size) => self.encode_float_builtin_domain(size),
+            BuiltinDomainKind::BitVector(bits) => self.encode_bitvector_builtin_domain(bits),
+        }
+    }
+
+    /// The bit vector domain of the given width defines its bitwise operations by summing up
+    /// the result bit by bit, such that a single instantiation of an axiom determines the
+    /// value of an operation. Since these sums are large, the domain also has axioms for the
+    /// bounds of the results, which often suffice without the definitions. Signed operands
+    /// have to be mapped to their two's complement representation by the caller.
+    ///
+    /// The integers are not encoded as SMT bit vectors, since the Viper version that we use
+    /// cannot interpret domains with the theories of the backends.
+    fn encode_bitvector_builtin_domain(&self, bits: u32) -> vir::Domain {
+        let domain_name = bitvector_domain_name(bits);
+        let func = |func: BitVectorFunc, args: Vec<vir::Expr>| {
+            vir::Expr::domain_func_app(bitvector_domain_func(bits, func), args)
+        };
+        let a = vir::LocalVar::new("a", vir::Type::Int);
+        let b = vir::LocalVar::new("b", vir::Type::Int);
+        let a_expr: vir::Expr = a.clone().into();
+        let b_expr: vir::Expr = b.clone().into();
+        let in_range = |expr: vir::Expr| vir::Expr::and(
+            vir::Expr::le_cmp(0.into(), expr.clone()),
+            vir::Expr::lt_cmp(expr, encode_pow2_const(bits)),
+        );
+        let operands_in_range = vir::Expr::and(in_range(a_expr.clone()), in_range(b_expr.clone()));
+
+        let mut axioms = vec![];
+        let mut add_axiom = |name: &str, vars: Vec<vir::LocalVar>, trigger: vir::Expr, body| {
+            axioms.push(vir::DomainAxiom {
+                name: format!("{}${}", domain_name, name),
+                expr: vir::Expr::forall(vars, vec![vir::Trigger::new(vec![trigger])], body),
+                domain_name: domain_name.clone(),
+            });
+        };
+
+        let get_bit = |expr: &vir::Expr, i: u32| {
+            vir::Expr::rem(vir::Expr::div(expr.clone(), encode_pow2_const(i)), 2.into())
+        };
+        for op in &[BitVectorFunc::And, BitVectorFunc::Or, BitVectorFunc::Xor] {
+            let result = func(*op, vec![a_expr.clone(), b_expr.clone()]);
+            let mut sum: vir::Expr = 0.into();
+            for i in 0..bits {
+                let a_bit = get_bit(&a_expr, i);
+                let b_bit = get_bit(&b_expr, i);
+                let result_bit = match op {
+                    BitVectorFunc::And => vir::Expr::mul(a_bit, b_bit),
+                    BitVectorFunc::Or => vir::Expr::sub(
+                        vir::Expr::add(a_bit.clone(), b_bit.clone()),
+                        vir::Expr::mul(a_bit, b_bit),
+                    ),
+                    _ => vir::Expr::rem(vir::Expr::add(a_bit, b_bit), 2.into()),
+                };
+                sum = vir::Expr::add(sum, vir::Expr::mul(encode_pow2_const(i), result_bit));
+            }
+            add_axiom(
+                &format!("{}_def", op.name()),
+                vec![a.clone(), b.clone()],
+                result.clone(),
+                vir::Expr::eq_cmp(result.clone(), sum),
+            );
+
+            // The result of `and` is at most each operand and that of `or` at least each.
+            let bounds = match op {
+                BitVectorFunc::And => vir::Expr::and(
+                    vir::Expr::le_cmp(result.clone(), a_expr.clone()),
+                    vir::Expr::le_cmp(result.clone(), b_expr.clone()),
+                ),
+                BitVectorFunc::Or => vir::Expr::and(
+                    vir::Expr::ge_cmp(result.clone(), a_expr.clone()),
+                    vir::Expr::ge_cmp(result.clone(), b_expr.clone()),
+                ),
+                _ => true.into(),
+            };
+            add_axiom(
+                &format!("{}_bounds", op.name()),
+                vec![a.clone(), b.clone()],
+                result.clone(),
+                vir::Expr::implies(
+                    operands_in_range.clone(),
+                    vir::Expr::and(in_range(result), bounds),
+                ),
+            );
+        }
+
+        // The last branch also covers shift amounts that are out of range, which are ruled
+        // out by the callers.
+        let pow2 = func(BitVectorFunc::Pow2, vec![a_expr.clone()]);
+        let mut pow2_def = encode_pow2_const(bits - 1);
+        for i in (0..bits - 1).rev() {
+            pow2_def = vir::Expr::ite(
+                vir::Expr::eq_cmp(a_expr.clone(), i.into()),
+                encode_pow2_const(i),
+                pow2_def,
+            );
+        }
+        add_axiom("pow2_def", vec![a.clone()], pow2.clone(), vir::Expr::eq_cmp(pow2, pow2_def));
+
+        let functions = [
+            BitVectorFunc::And,
+            BitVectorFunc::Or,
+            BitVectorFunc::Xor,
+            BitVectorFunc::Pow2,
+        ]
+        .iter()
+        .map(|func| bitvector_domain_func(bits, *func))
+        .collect();
+        vir::Domain {
+            name: domain_name,
+            functions,
+            axioms,
+            type_vars: vec![],
+        }
+    }
+
+    /// Floats and most of their functions are mapped onto the SMT-LIB theory of floating-point
+    /// numbers, so the domain of floats only declares the remainder, which propagates NaN like
+    /// the other arithmetic operations, and the validity function of snapshots.
+    fn encode_float_builtin_domain(&self, size: vir::FloatSize) -> vir::Domain {
+        let domain_name = size.domain_name();
+        let float_type = vir::Type::Float(size);
+        let is_nan = |arg: vir::Expr| vir::float_func_app(size, vir::FloatFunc::IsNan, vec![arg]);
+        let a = vir::LocalVar::new("a", float_type.clone());
+        let b = vir::LocalVar::new("b", float_type.clone());
+        let a_expr: vir::Expr = a.clone().into();
+        let b_expr: vir::Expr = b.clone().into();
+
+        let mut axioms = vec![];
+        let mut add_axiom = |name: &str, vars: Vec<vir::LocalVar>, trigger: vir::Expr, body| {
+            axioms.push(vir::DomainAxiom {
+                name: format!("{}${}", domain_name, name),
+                expr: vir::Expr::forall(vars, vec![vir::Trigger::new(vec![trigger])], body),
+                domain_name: domain_name.to_string(),
+            });
+        };
+
+        let rem_a_b = vir::float_func_app(
+            size,
+            vir::FloatFunc::Rem,
+            vec![a_expr.clone(), b_expr.clone()],
+        );
+        add_axiom(
+            "rem_nan",
+            vec![a.clone(), b],
+            rem_a_b.clone(),
+            vir::Expr::implies(
+                vir::Expr::or(is_nan(a_expr.clone()), is_nan(b_expr)),
+                is_nan(rem_a_b),
+            ),
+        );
+
+        // Like other primitive values, all floats are valid.
+        let valid_func = snapshot::valid_func_for_type(&float_type);
+        let valid_a = vir::Expr::domain_func_app(valid_func.clone(), vec![a_expr]);
+        add_axiom("valid", vec![a], valid_a.clone(), valid_a);
+
+        let mut functions: Vec<_> = vir::FloatFunc::all()
+            .into_iter()
+            .filter(|f| !f.is_interpreted())
+            .map(|f| vir::float_domain_func(size, f))
+            .collect();
+        functions.push(valid_func);
+
+        vir::Domain {
+            name: domain_name.to_owned(),
+            functions,
+            axioms,
+            type_vars: vec![],
         }
     }
 
@@ -156,3 +470,13 @@ impl BuiltinEncoder {
         }
     }
 }
+
+/// Encodes the constant `2^exponent`, for an exponent of at most 128.
+pub fn encode_pow2_const(exponent: u32) -> vir::Expr {
+    if exponent < 128 {
+        (1u128 << exponent).into()
+    } else {
+        assert_eq!(exponent, 128);
+        vir::Expr::add(std::u128::MAX.into(), 1.into())
+    }
+}
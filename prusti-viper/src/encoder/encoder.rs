@@ -9,9 +9,10 @@ use crate::encoder::borrows::{compute_procedure_contract, ProcedureContract, Pro
 use crate::encoder::builtin_encoder::BuiltinEncoder;
 use crate::encoder::builtin_encoder::BuiltinFunctionKind;
 use crate::encoder::builtin_encoder::BuiltinMethodKind;
-use crate::encoder::builtin_encoder::BuiltinDomainKind;
+use crate::encoder::builtin_encoder::{bitvector_domain_func, BitVectorFunc, BuiltinDomainKind};
 use crate::encoder::errors::{ErrorCtxt, ErrorManager, SpannedEncodingError, EncodingError, WithSpan, RunIfErr};
 use crate::encoder::foldunfold;
+use crate::encoder::mir_encoder::PRECONDITION_LABEL;
 use crate::encoder::places;
 use crate::encoder::procedure_encoder::ProcedureEncoder;
 use crate::encoder::pure_function_encoder::PureFunctionEncoder;
@@ -19,7 +20,7 @@ use crate::encoder::stub_function_encoder::StubFunctionEncoder;
 use crate::encoder::spec_encoder::encode_spec_assertion;
 use crate::encoder::snapshot_encoder::{Snapshot, SnapshotEncoder};
 use crate::encoder::type_encoder::{
-    compute_discriminant_values, compute_discriminant_bounds, TypeEncoder};
+    compute_discriminant_values, compute_discriminant_bounds, ghost_type, TypeEncoder};
 use crate::encoder::SpecFunctionKind;
 use crate::encoder::spec_function_encoder::SpecFunctionEncoder;
 use prusti_common::vir;
@@ -40,7 +41,7 @@ use rustc_middle::mir;
 // use rustc::mir::interpret::GlobalId;
 use rustc_middle::ty;
 use std::cell::{RefCell, RefMut};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io::Write;
 use std::mem;
 // use viper;
@@ -69,6 +70,7 @@ pub struct Encoder<'v, 'tcx: 'v> {
     >>,
     builtin_methods: RefCell<HashMap<BuiltinMethodKind, vir::BodylessMethod>>,
     builtin_functions: RefCell<HashMap<BuiltinFunctionKind, vir::Function>>,
+    builtin_domains: RefCell<HashMap<BuiltinDomainKind, vir::Domain>>,
     procedures: RefCell<HashMap<ProcedureDefId, vir::CfgMethod>>,
     pure_function_bodies: RefCell<HashMap<(ProcedureDefId, String), vir::Expr>>,
     pure_functions: RefCell<HashMap<(ProcedureDefId, String), vir::Function>>,
@@ -85,10 +87,18 @@ pub struct Encoder<'v, 'tcx: 'v> {
     type_tags: RefCell<HashMap<String, vir::Function>>,
     type_discriminant_funcs: RefCell<HashMap<String, vir::Function>>,
     type_cast_functions: RefCell<HashMap<(ty::Ty<'tcx>, ty::Ty<'tcx>), vir::Function>>,
+    /// The local pure functions that are called in the body of each pure function. Used to
+    /// detect (mutually) recursive calls.
+    pure_function_callees: RefCell<HashMap<ProcedureDefId, Vec<ProcedureDefId>>>,
     memory_eq_encoder: RefCell<MemoryEqEncoder>,
     fields: RefCell<HashMap<String, vir::Field>>,
     snapshots: RefCell<HashMap<String, Box<Snapshot>>>, // maps predicate names to snapshots
     type_snapshots: RefCell<HashMap<String, String>>, // maps snapshot names to predicate names
+    /// The snapshot domains whose size is used in a termination measure.
+    measured_snapshot_domains: RefCell<HashSet<String>>,
+    /// The predicates whose body is being inlined, each with a flag that records whether the
+    /// predicate is used in its own body.
+    inlined_predicates: RefCell<Vec<(ProcedureDefId, bool)>>,
     snap_mirror_funcs: RefCell<HashMap<String, Option<vir::DomainFunc>>>,
     closures_collector: RefCell<SpecsClosuresCollector<'tcx>>,
     encoding_queue: RefCell<Vec<(ProcedureDefId, Vec<(ty::Ty<'tcx>, ty::Ty<'tcx>)>)>>,
@@ -138,6 +148,7 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
             procedure_contracts: RefCell::new(HashMap::new()),
             builtin_methods: RefCell::new(HashMap::new()),
             builtin_functions: RefCell::new(HashMap::new()),
+            builtin_domains: RefCell::new(HashMap::new()),
             procedures: RefCell::new(HashMap::new()),
             pure_function_bodies: RefCell::new(HashMap::new()),
             pure_functions: RefCell::new(HashMap::new()),
@@ -152,6 +163,7 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
             type_tags: RefCell::new(HashMap::new()),
             type_discriminant_funcs: RefCell::new(HashMap::new()),
             type_cast_functions: RefCell::new(HashMap::new()),
+            pure_function_callees: RefCell::new(HashMap::new()),
             memory_eq_encoder: RefCell::new(MemoryEqEncoder::new()),
             fields: RefCell::new(HashMap::new()),
             closures_collector: RefCell::new(SpecsClosuresCollector::new()),
@@ -161,6 +173,8 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
             typaram_repl: RefCell::new(Vec::new()),
             snapshots: RefCell::new(HashMap::new()),
             type_snapshots: RefCell::new(HashMap::new()),
+            measured_snapshot_domains: RefCell::new(HashSet::new()),
+            inlined_predicates: RefCell::new(vec![]),
             snap_mirror_funcs: RefCell::new(HashMap::new()),
             encoding_errors_counter: RefCell::new(0),
             name_interner: RefCell::new(NameInterner::new()),
@@ -246,13 +260,34 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
             .filter_map(|f| f.clone())
             .collect();
 
-        let mut domains: Vec<vir::Domain> = self
-            .snapshots
-            .borrow()
+        let snapshots = self.snapshots.borrow();
+        let snapshot_domains: HashMap<_, _> = snapshots
             .values()
-            .into_iter()
-            .filter_map(|s| s.domain())
+            .filter_map(|s| s.snap_domain.as_ref())
+            .map(|d| (d.domain.name.clone(), d))
+            .collect();
+        // The size functions of the measured domains refer to those of their fields.
+        let mut sized_domains = HashSet::new();
+        let mut worklist: Vec<String> = self.measured_snapshot_domains.borrow()
+            .iter()
+            .cloned()
             .collect();
+        while let Some(domain_name) = worklist.pop() {
+            if sized_domains.insert(domain_name.clone()) {
+                if let Some(domain) = snapshot_domains.get(&domain_name) {
+                    worklist.extend(domain.sized_field_domains.iter().cloned());
+                }
+            }
+        }
+        let mut domains: Vec<vir::Domain> = snapshot_domains
+            .values()
+            .map(|d| if sized_domains.contains(&d.domain.name) {
+                d.domain_with_size()
+            } else {
+                d.domain.clone()
+            })
+            .collect();
+        domains.extend(self.builtin_domains.borrow().values().cloned());
         if !mirrors.is_empty() {
             domains.push(vir::Domain {
                 name: SNAPSHOT_MIRROR_DOMAIN.to_string(),
@@ -497,12 +532,29 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
         Some(spec.expect_loop().clone())
     }
 
+    /// Get the assertion attached to a closure with a `prusti::prusti_assertion`
+    /// or `prusti::prusti_assumption` attribute.
+    pub fn get_statement_specs(&self, def_id: DefId) -> Option<typed::SpecificationSet<'tcx>> {
+        match self.def_spec.get(&def_id)? {
+            spec @ typed::SpecificationSet::Assertion(_) |
+            spec @ typed::SpecificationSet::Assumption(_) => Some(spec.clone()),
+            _ => None,
+        }
+    }
+
     /// Get the specifications attached to the `def_id` function.
     pub fn get_procedure_specs(&self, def_id: DefId) -> Option<typed::ProcedureSpecification<'tcx>> {
         let spec = self.def_spec.get(&def_id)?;
         Some(spec.expect_procedure().clone())
     }
 
+    /// Get the invariants attached to the `def_id` struct or enum with
+    /// `#[invariant(...)]` attributes.
+    pub fn get_struct_specs(&self, def_id: DefId) -> Option<Vec<typed::Specification<'tcx>>> {
+        let spec = self.def_spec.get(&def_id)?;
+        Some(spec.expect_struct().clone())
+    }
+
     /// Get a local wrapper `DefId` for functions that have external specs.
     /// Return the original `DefId` for everything else.
     fn get_wrapper_def_id(&self, def_id: DefId) -> DefId {
@@ -514,8 +566,10 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
     fn get_procedure_contract(&self, proc_def_id: ProcedureDefId)
         -> EncodingResult<ProcedureContractMirDef<'tcx>>
     {
+        // The body of an `async fn` is verified against the specification of the `async fn`.
+        let spec_def_id = self.env().get_async_fn_of_body(proc_def_id).unwrap_or(proc_def_id);
         let spec = typed::SpecificationSet::Procedure(
-            self.get_procedure_specs(proc_def_id)
+            self.get_procedure_specs(spec_def_id)
                 .unwrap_or_else(|| typed::ProcedureSpecification::empty())
         );
         compute_procedure_contract(proc_def_id, self.env().tcx(), spec, None)
@@ -566,6 +620,10 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
         //     }
         // }
 
+        // A call on a trait object is dispatched dynamically, so the implementation that is
+        // called is unknown and only the specification of the trait method applies.
+        let self_ty = self_ty.filter(|ty| !matches!(ty.kind(), ty::TyKind::Dynamic(..)));
+
         if let Some(ty) = self_ty {
             if let Some(id) = self.env().tcx().trait_of_item(proc_def_id) {
                 let proc_name = self.env().tcx().item_name(proc_def_id);
@@ -601,7 +659,7 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
     pub fn encode_value_expr(&self, base: vir::Expr, ty: ty::Ty<'tcx>) -> vir::Expr {
         match ty.kind() {
             ty::TyKind::Adt(_, _)
-            | ty::TyKind::Tuple(_) => {
+            | ty::TyKind::Tuple(_) if !self.is_ghost_type(ty) => {
                 base // don't use a field for tuples and ADTs
             }
             _ => {
@@ -781,6 +839,36 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
         }
     }
 
+    pub fn encode_builtin_domain_def(&self, domain_kind: BuiltinDomainKind) {
+        trace!("encode_builtin_domain_def({:?})", domain_kind);
+        if !self.builtin_domains.borrow().contains_key(&domain_kind) {
+            let builtin_encoder = BuiltinEncoder::new();
+            let domain = builtin_encoder.encode_builtin_domain(domain_kind.clone());
+            self.log_vir_program_before_viper(domain.to_string());
+            self.builtin_domains
+                .borrow_mut()
+                .insert(domain_kind, domain);
+        }
+    }
+
+    /// Encodes the type of floats of the given size, together with the domain that declares the
+    /// functions on floats that are not interpreted by the backend.
+    pub fn encode_float_type(&self, size: vir::FloatSize) -> vir::Type {
+        self.encode_builtin_domain_def(BuiltinDomainKind::Float(size));
+        vir::Type::Float(size)
+    }
+
+    /// Encodes the application of a function of the bit vector domain of the given width.
+    pub fn encode_bitvector_func_app(
+        &self,
+        bits: u32,
+        func: BitVectorFunc,
+        args: Vec<vir::Expr>,
+    ) -> vir::Expr {
+        self.encode_builtin_domain_def(BuiltinDomainKind::BitVector(bits));
+        vir::Expr::domain_func_app(bitvector_domain_func(bits, func), args)
+    }
+
     pub fn encode_builtin_function_use(&self, function_kind: BuiltinFunctionKind) -> String {
         trace!("encode_builtin_function_use({:?})", function_kind);
         if !self.builtin_functions.borrow().contains_key(&function_kind) {
@@ -963,6 +1051,12 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
         Ok(self.type_predicates.borrow()[&predicate_name].clone())
     }
 
+    /// Records that the size function of the given snapshot domain is used in a termination
+    /// measure, so that it is emitted together with its axioms.
+    pub fn encode_snapshot_size_use(&self, domain_name: String) {
+        self.measured_snapshot_domains.borrow_mut().insert(domain_name);
+    }
+
     pub fn encode_snapshot(&self, ty: ty::Ty<'tcx>)
         -> EncodingResult<Box<Snapshot>>
     {
@@ -1003,6 +1097,12 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
 
     /// Checks whether the given type implements structural equality
     /// by either being a primitive type or by deriving the Eq trait.
+    /// Is `ty` one of the mathematical types of `prusti_contracts`, whose values are encoded
+    /// as built-in values of Viper?
+    pub fn is_ghost_type(&self, ty: ty::Ty<'tcx>) -> bool {
+        ghost_type(self.env().tcx(), ty).is_some()
+    }
+
     pub fn has_structural_eq_impl(&self, ty: ty::Ty<'tcx>) -> bool {
         let ty = self.dereference_ty(ty);
         match ty.kind() {
@@ -1146,6 +1246,16 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
             ty::TyKind::Uint(ty::UintTy::U64) => scalar_value.to_u64().unwrap().into(),
             ty::TyKind::Uint(ty::UintTy::U128) => scalar_value.to_u128().unwrap().into(),
             ty::TyKind::Uint(ty::UintTy::Usize) => scalar_value.to_machine_usize(&self.env().tcx()).unwrap().into(),
+            ty::TyKind::Float(ty::FloatTy::F32) => {
+                self.encode_float_type(vir::FloatSize::F32);
+                let bits = scalar_value.to_u32().unwrap();
+                vir::Expr::Const(vir::Const::Float32(bits), vir::Position::default())
+            }
+            ty::TyKind::Float(ty::FloatTy::F64) => {
+                self.encode_float_type(vir::FloatSize::F64);
+                let bits = scalar_value.to_u64().unwrap();
+                vir::Expr::Const(vir::Const::Float64(bits), vir::Position::default())
+            }
             ty::TyKind::FnDef(def_id, _) => {
                 self.encode_spec_funcs(*def_id)?;
                 vir::Expr::Const(vir::Const::FnPtr, vir::Position::default())
@@ -1156,6 +1266,24 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
         Ok(expr)
     }
 
+    /// Encode a string literal as the sequence of its UTF-8 bytes.
+    pub fn encode_str_literal_bytes(&self, value: &ty::ConstKind<'tcx>) -> EncodingResult<vir::Expr> {
+        trace!("encode_str_literal_bytes {:?}", value);
+        let opt_str = match value {
+            ty::ConstKind::Value(ref const_value) => const_value.try_to_str_slice(),
+            _ => None,
+        };
+        let string = if let Some(s) = opt_str {
+            s
+        } else {
+            return Err(EncodingError::unsupported(
+                format!("unsupported string constant: {:?}", value)
+            ));
+        };
+        let bytes = string.bytes().map(|byte| byte.into()).collect();
+        Ok(vir::Expr::seq(vir::Type::Int, bytes))
+    }
+
     pub fn encode_int_cast(&self, value: u128, ty: ty::Ty<'tcx>) -> vir::Expr {
         trace!("encode_int_cast {:?} as {:?}", value, ty);
 
@@ -1228,11 +1356,11 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
         let substs_key = self.type_substitution_key().with_span(mir_span)?;
         let key = (proc_def_id, substs_key);
         if !self.pure_function_bodies.borrow().contains_key(&key) {
-            let procedure = self.env.get_procedure(proc_def_id);
+            let mir = self.env.mir(proc_def_id);
             let pure_function_encoder = PureFunctionEncoder::new(
                 self,
                 proc_def_id,
-                procedure.get_mir(),
+                &mir,
                 true,
             );
             let body = pure_function_encoder.encode_body()?;
@@ -1279,27 +1407,32 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
         if !self.pure_functions.borrow().contains_key(&key) {
             trace!("not encoded: {:?}", key);
             let wrapper_def_id = self.get_wrapper_def_id(proc_def_id);
-            let procedure = self.env.get_procedure(wrapper_def_id);
+            let mir = self.env.mir(wrapper_def_id);
+            let span = self.env.tcx().def_span(wrapper_def_id);
             let pure_function_encoder =
-                PureFunctionEncoder::new(self, proc_def_id, procedure.get_mir(), false);
-            let function = if self.is_trusted(proc_def_id) {
+                PureFunctionEncoder::new(self, proc_def_id, &mir, false);
+            // The body of a pure function of a dependency has been verified when the
+            // dependency was compiled, so only its imported contract is used.
+            let function = if let Some(predicate_body) = self.get_predicate_body(proc_def_id) {
+                pure_function_encoder.encode_predicate_function(&predicate_body)
+                    .run_if_err(cleanup)?
+            } else if self.is_trusted(proc_def_id) || !wrapper_def_id.is_local() {
                 pure_function_encoder.encode_bodyless_function()
                     .run_if_err(cleanup)?
             } else {
                 let pure_function = pure_function_encoder.encode_function()
                     .run_if_err(cleanup)?;
                 self.patch_pure_post_with_mirror_call(pure_function)
-                    .with_span(procedure.get_span())
+                    .with_span(span)
                     .run_if_err(cleanup)?
             };
 
             if config::enable_purification_optimization() {
                 // Ensure that snapshots of all types used in the function are
                 // already encoded.
-                let body = procedure.get_mir();
-                for local_decl in &body.local_decls {
+                for local_decl in &mir.local_decls {
                     let ty = local_decl.ty;
-                    self.encode_snapshot(ty).with_span(procedure.get_span()).run_if_err(cleanup)?;
+                    self.encode_snapshot(ty).with_span(span).run_if_err(cleanup)?;
                 }
                 self.encode_axiomatized_pure_function(&function);
             }
@@ -1441,7 +1574,7 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
         proc_def_id: ProcedureDefId,
     ) -> SpannedEncodingResult<(String, vir::Type)> {
         let wrapper_def_id = self.get_wrapper_def_id(proc_def_id);
-        let procedure = self.env.get_procedure(wrapper_def_id);
+        let mir = self.env.mir(wrapper_def_id);
 
         assert!(
             self.is_pure(proc_def_id),
@@ -1450,7 +1583,7 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
         );
 
         let pure_function_encoder =
-            PureFunctionEncoder::new(self, proc_def_id, procedure.get_mir(), false);
+            PureFunctionEncoder::new(self, proc_def_id, &mir, false);
 
         self.queue_pure_function_encoding(proc_def_id);
 
@@ -1460,6 +1593,56 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
         ))
     }
 
+    /// Encode the use of the predicate `def_id` in a specification by inlining its body, if the
+    /// body uses `old(..)`. The old expressions of the result refer to the pre-state of the
+    /// specification that uses the predicate. Returns `None` if the predicate does not use
+    /// `old(..)` and should be encoded as a call of its function.
+    pub fn encode_inlined_predicate_use(
+        &self,
+        def_id: ProcedureDefId,
+        args: &[vir::Expr],
+    ) -> SpannedEncodingResult<Option<vir::Expr>> {
+        let predicate_body = if let Some(predicate_body) = self.get_predicate_body(def_id) {
+            predicate_body
+        } else {
+            return Ok(None);
+        };
+        if let Some(entry) = self.inlined_predicates.borrow_mut()
+            .iter_mut()
+            .find(|(inlined_def_id, _)| *inlined_def_id == def_id)
+        {
+            // A recursive use cannot be inlined.
+            entry.1 = true;
+            return Ok(None);
+        }
+
+        let mir = self.env.mir(def_id);
+        self.inlined_predicates.borrow_mut().push((def_id, false));
+        let encoded_body = self.encode_assertion(
+            &predicate_body,
+            &mir,
+            Some(PRECONDITION_LABEL),
+            args,
+            None,
+            true,
+            None,
+            ErrorCtxt::GenericExpression,
+        );
+        let (_, is_recursive) = self.inlined_predicates.borrow_mut().pop().unwrap();
+        let encoded_body = encoded_body?;
+
+        if !encoded_body.contains_old_label(PRECONDITION_LABEL) {
+            return Ok(None);
+        }
+        if is_recursive {
+            return Err(SpannedEncodingError::unsupported(
+                "recursive predicates that use `old` are not supported",
+                self.env.tcx().def_span(def_id),
+            ));
+        }
+        Ok(Some(encoded_body))
+    }
+
     /// Encode the use (call) of either a comparison (equality or disequality)
     /// returning the name of the encoded function and its type.
     /// If the comparison is not supported, a stub function will be encoded
@@ -1544,7 +1727,8 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
                 self.encode_pure_function_def(proc_def_id, substs);
             } else {
                 assert!(substs.is_empty());
-                if self.is_trusted(proc_def_id) {
+                let spec_def_id = self.env.get_async_fn_of_body(proc_def_id).unwrap_or(proc_def_id);
+                if self.is_trusted(spec_def_id) {
                     debug!(
                         "Trusted procedure will not be encoded or verified: {:?}",
                         proc_def_id
@@ -1571,6 +1755,72 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
         result
     }
 
+    /// Get the termination measure attached to the `def_id` function with a
+    /// `#[decreases(...)]` attribute.
+    pub fn get_termination_measure(&self, def_id: ProcedureDefId) -> Option<typed::Measure> {
+        self.def_spec.get(&def_id)?.expect_procedure().decreases.clone()
+    }
+
+    /// Get the body of the `def_id` function, if it is defined with `predicate!`.
+    pub fn get_predicate_body(&self, def_id: ProcedureDefId) -> Option<typed::Assertion<'tcx>> {
+        self.def_spec.get(&def_id)?.expect_procedure().predicate_body.clone()
+    }
+
+    /// Get the local pure functions that are called in the body of the `def_id` function.
+    fn get_pure_function_callees(&self, def_id: ProcedureDefId) -> Vec<ProcedureDefId> {
+        if let Some(callees) = self.pure_function_callees.borrow().get(&def_id) {
+            return callees.clone();
+        }
+        let callees: Vec<_> = if let Some(local_def_id) = def_id.as_local() {
+            let mir = self.env.local_mir(local_def_id);
+            mir.basic_blocks()
+                .iter()
+                .filter_map(|bb_data| match bb_data.terminator().kind {
+                    mir::TerminatorKind::Call {
+                        func: mir::Operand::Constant(box mir::Constant {
+                            literal: ty::Const { ty, .. },
+                            ..
+                        }),
+                        ..
+                    } => match ty.kind() {
+                        ty::TyKind::FnDef(callee_def_id, _)
+                            if callee_def_id.is_local() && self.is_pure(*callee_def_id) =>
+                        {
+                            Some(*callee_def_id)
+                        }
+                        _ => None,
+                    },
+                    _ => None,
+                })
+                .collect()
+        } else {
+            vec![]
+        };
+        self.pure_function_callees.borrow_mut().insert(def_id, callees.clone());
+        callees
+    }
+
+    /// Check whether a call from the pure function `caller_def_id` to `callee_def_id` is
+    /// recursive, i.e. whether `callee_def_id` can (transitively) call `caller_def_id`.
+    pub fn is_recursive_pure_call(
+        &self,
+        caller_def_id: ProcedureDefId,
+        callee_def_id: ProcedureDefId,
+    ) -> bool {
+        let mut visited = HashSet::new();
+        let mut to_visit = vec![callee_def_id];
+        while let Some(def_id) = to_visit.pop() {
+            if def_id == caller_def_id {
+                trace!("call from {:?} to {:?} is recursive", caller_def_id, callee_def_id);
+                return true;
+            }
+            if visited.insert(def_id) {
+                to_visit.extend(self.get_pure_function_callees(def_id));
+            }
+        }
+        false
+    }
+
     pub fn has_extern_spec(&self, def_id: ProcedureDefId) -> bool {
         // FIXME: eventually, procedure specs (the entries in def_spec) should
         // have an `is_extern_spec` field. For now, due to the way we handle
@@ -1582,6 +1832,38 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
         result
     }
 
+    /// Type invariants are assumed on entry and checked on exit only of public
+    /// methods whose receiver (`self` or `&mut self`) has the type of their impl
+    /// block, so that private helpers and other functions are allowed to
+    /// temporarily break them.
+    pub fn is_type_invariant_boundary(&self, def_id: ProcedureDefId) -> bool {
+        let tcx = self.env().tcx();
+        let result = !tcx.is_closure(def_id)
+            && tcx.visibility(def_id) == ty::Visibility::Public
+            && self.has_self_receiver(def_id);
+        trace!("is_type_invariant_boundary {:?} = {}", def_id, result);
+        result
+    }
+
+    /// Check whether `def_id` is a method whose receiver is either `Self` or `&mut Self`.
+    fn has_self_receiver(&self, def_id: ProcedureDefId) -> bool {
+        let tcx = self.env().tcx();
+        let impl_def_id = if let Some(impl_def_id) = tcx.impl_of_method(def_id) {
+            impl_def_id
+        } else {
+            return false;
+        };
+        if !tcx.associated_item(def_id).fn_has_self_parameter {
+            return false;
+        }
+        let self_ty = tcx.type_of(impl_def_id);
+        let receiver_ty = tcx.fn_sig(def_id).skip_binder().inputs()[0];
+        match receiver_ty.kind() {
+            ty::TyKind::Ref(_, target_ty, hir::Mutability::Mut) => *target_ty == self_ty,
+            _ => receiver_ty == self_ty,
+        }
+    }
+
     /// Convert a potential type parameter to a concrete type.
     pub fn resolve_typaram(&self, ty: ty::Ty<'tcx>) -> ty::Ty<'tcx> {
         // TODO: creating each time a current_tymap might be slow. This can be optimized.
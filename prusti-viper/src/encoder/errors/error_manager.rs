@@ -5,7 +5,7 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 use prusti_common::vir::Position;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use rustc_span::source_map::SourceMap;
 use rustc_span::MultiSpan;
 use viper::VerificationError;
@@ -41,6 +41,9 @@ pub enum ErrorCtxt {
     AssertMethodPostcondition,
     /// A Viper `assert expr` that encodes the call of a Rust procedure with precondition `expr`
     AssertMethodPostconditionTypeInvariants,
+    /// A Viper `assert expr` that encodes the type invariants `expr` expected by the call of a
+    /// public Rust procedure
+    AssertMethodPreconditionTypeInvariants,
     /// A Viper `exhale expr` that encodes the end of a Rust procedure with postcondition `expr`
     ExhaleMethodPostcondition,
     /// A Viper `exhale expr` that exhales the permissions of a loop invariant `expr`
@@ -49,6 +52,14 @@ pub enum ErrorCtxt {
     /// A Viper `assert expr` that asserts the functional specification of a loop invariant `expr`
     AssertLoopInvariantOnEntry,
     AssertLoopInvariantAfterIteration,
+    /// A Viper `assert 0 <= expr` that checks that the loop variant `expr` is non-negative at
+    /// the beginning of a loop iteration
+    AssertLoopVariantNonNegative,
+    /// A Viper `assert expr < old_expr` that checks that the loop variant `expr` decreases
+    /// after a loop iteration
+    AssertLoopVariantAfterIteration,
+    /// A Viper `assert expr` that encodes a `prusti_assert!(expr)` statement
+    PrustiAssert,
     /// A Viper `assert false` that encodes the failure (panic) of an `assert` Rust terminator
     /// Arguments: the message of the Rust assertion
     AssertTerminator(String),
@@ -82,6 +93,9 @@ pub enum ErrorCtxt {
     ApplyMagicWandOnExpiry,
     /// A diverging function call performed in a pure function
     DivergingCallInPureFunction,
+    /// A Viper pure function call with `false` precondition that encodes a recursive call that
+    /// might not decrease the termination measure of a pure function
+    RecursiveCallInPureFunction,
     /// A Viper pure function call with `false` precondition that encodes a Rust panic in a pure function
     PanicInPureFunction(PanicCause),
     /// A Viper `assert e1 ==> e2` that encodes a weakening of the precondition
@@ -92,6 +106,9 @@ pub enum ErrorCtxt {
     AssertMethodPostconditionStrengthening(MultiSpan),
     /// A cast like `usize as u32`.
     TypeCast,
+    /// A Viper `exhale acc(..)` that gives the permission of the source of an unsizing cast,
+    /// like `&mut T as &mut dyn Trait` or `Box<T> as Box<dyn Trait>`, to the trait object
+    CoerceToTraitObject,
     /// A Viper `assert false` that encodes an unsupported feature
     Unsupported(String),
 }
@@ -103,6 +120,9 @@ pub struct ErrorManager<'tcx> {
     source_span: HashMap<u64, MultiSpan>,
     error_contexts: HashMap<u64, ErrorCtxt>,
     next_pos_id: u64,
+    /// The kinds and positions of the verification errors that were already translated by
+    /// `translate_new_verification_error`.
+    translated_errors: HashSet<(String, Option<String>, Option<String>)>,
 }
 
 impl<'tcx> ErrorManager<'tcx>
@@ -113,6 +133,7 @@ impl<'tcx> ErrorManager<'tcx>
             source_span: HashMap::new(),
             error_contexts: HashMap::new(),
             next_pos_id: 1,
+            translated_errors: HashSet::new(),
         }
     }
 
@@ -155,6 +176,26 @@ impl<'tcx> ErrorManager<'tcx>
         self.error_contexts.insert(pos.id(), error_ctxt);
     }
 
+    /// Translate a verification error, unless an error of the same kind at the same position
+    /// was already translated. The programs that are verified separately share functions and
+    /// predicates, whose errors are reported by each of them, possibly with different messages.
+    pub fn translate_new_verification_error(
+        &mut self,
+        ver_error: &VerificationError,
+    ) -> Option<PrustiError> {
+        let key = (
+            ver_error.full_id.clone(),
+            ver_error.pos_id.clone(),
+            ver_error.reason_pos_id.clone(),
+        );
+        if self.translated_errors.insert(key) {
+            Some(self.translate_verification_error(ver_error))
+        } else {
+            debug!("Ignoring a duplicate verification error: {:?}", ver_error);
+            None
+        }
+    }
+
     pub fn translate_verification_error(&self, ver_error: &VerificationError) -> PrustiError {
         debug!("Verification error: {:?}", ver_error);
         let opt_pos_id: Option<u64> = match ver_error.pos_id {
@@ -244,6 +285,21 @@ impl<'tcx> ErrorManager<'tcx>
             }
         };
 
+        // The operations of the mathematical types can fail wherever they are evaluated.
+        let reason_id = ver_error.full_id.splitn(2, ':').nth(1).unwrap_or("");
+        let opt_ghost_message = match reason_id {
+            "seq.index.negative" | "seq.index.length" => {
+                Some("the index of the sequence lookup might be out of bounds.")
+            }
+            "map.key.contains" => Some("the key of the map lookup might not be in the map."),
+            "division.by.zero" => Some("the divisor might be zero."),
+            _ => None,
+        };
+        if let Some(message) = opt_ghost_message {
+            return PrustiError::verification(message, error_span)
+                .set_failing_assertion(opt_cause_span);
+        }
+
         match (ver_error.full_id.as_str(), error_ctxt) {
             ("assert.failed:assertion.false", ErrorCtxt::Panic(PanicCause::Generic)) => {
                 PrustiError::verification("statement might panic", error_span)
@@ -337,6 +393,20 @@ impl<'tcx> ErrorManager<'tcx>
                 ).push_primary_span(opt_cause_span)
             }
 
+            ("assert.failed:assertion.false", ErrorCtxt::AssertLoopVariantNonNegative) => {
+                PrustiError::verification(
+                    "loop variant might be negative at the beginning of a loop iteration.",
+                    error_span
+                ).push_primary_span(opt_cause_span)
+            }
+
+            ("assert.failed:assertion.false", ErrorCtxt::AssertLoopVariantAfterIteration) => {
+                PrustiError::verification(
+                    "loop variant might not decrease after a loop iteration.",
+                    error_span
+                ).push_primary_span(opt_cause_span)
+            }
+
             ("application.precondition:assertion.false", ErrorCtxt::PureFunctionCall) => {
                 PrustiError::verification(
                     "precondition of pure function call might not hold.",
@@ -369,6 +439,16 @@ impl<'tcx> ErrorManager<'tcx>
                 ).push_primary_span(opt_cause_span)
             }
 
+            (
+                "application.precondition:assertion.false",
+                ErrorCtxt::RecursiveCallInPureFunction,
+            ) => {
+                PrustiError::verification(
+                    "termination measure might not decrease at this recursive call",
+                    error_span
+                ).push_primary_span(opt_cause_span)
+            }
+
             (
                 "application.precondition:assertion.false",
                 ErrorCtxt::PanicInPureFunction(PanicCause::Generic),
@@ -441,11 +521,23 @@ impl<'tcx> ErrorManager<'tcx>
                 ).set_failing_assertion(opt_cause_span)
             }
 
+            ("exhale.failed:insufficient.permission", ErrorCtxt::CoerceToTraitObject) => {
+                PrustiError::verification(
+                    "the value might not be accessible when it is coerced to a trait object",
+                    error_span
+                ).set_failing_assertion(opt_cause_span)
+            }
+
             ("apply.failed:assertion.false", ErrorCtxt::ApplyMagicWandOnExpiry) => {
                 PrustiError::verification("obligation might not hold on borrow expiry", error_span)
                     .set_failing_assertion(opt_cause_span)
             }
 
+            ("assert.failed:assertion.false", ErrorCtxt::PrustiAssert) => {
+                PrustiError::verification("the asserted expression might not hold", error_span)
+                    .set_failing_assertion(opt_cause_span)
+            }
+
             ("assert.failed:assertion.false", ErrorCtxt::AssertMethodPostcondition) => {
                 PrustiError::verification(format!("postcondition might not hold."), error_span)
                     .push_primary_span(opt_cause_span)
@@ -461,6 +553,20 @@ impl<'tcx> ErrorManager<'tcx>
                 ).set_failing_assertion(opt_cause_span)
             },
 
+            (
+                "assert.failed:assertion.false",
+                ErrorCtxt::AssertMethodPreconditionTypeInvariants,
+            ) |
+            (
+                "fold.failed:assertion.false",
+                ErrorCtxt::AssertMethodPreconditionTypeInvariants,
+            ) => {
+                PrustiError::verification(
+                    "type invariants expected by the function call might not hold.",
+                    error_span
+                ).set_failing_assertion(opt_cause_span)
+            }
+
             ("fold.failed:assertion.false", ErrorCtxt::PackageMagicWandForPostcondition) |
             ("fold.failed:assertion.false", ErrorCtxt::AssertMethodPostconditionTypeInvariants) => {
                 PrustiError::verification(
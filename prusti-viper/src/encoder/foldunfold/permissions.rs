@@ -254,7 +254,8 @@ impl RequiredPermissionsGetter for vir::Expr {
                 unreachable!("Let expressions should be introduced after fold/unfold.");
             }
 
-            vir::Expr::ForAll(vars, _triggers, box body, _) => {
+            vir::Expr::ForAll(vars, _triggers, box body, _) |
+            vir::Expr::Exists(vars, _triggers, box body, _) => {
                 assert!(vars.iter().all(|var| !var.typ.is_ref()));
 
                 let vars_places: HashSet<_> = vars
@@ -317,6 +318,41 @@ impl RequiredPermissionsGetter for vir::Expr {
             vir::Expr::DomainFuncApp(..) => HashSet::new(),
 
             vir::Expr::InhaleExhale(..) => HashSet::new(),
+
+            vir::Expr::Seq(_, elems, _) => elems.get_required_permissions(predicates),
+
+            vir::Expr::SeqLen(box seq, _) => seq.get_required_permissions(predicates),
+
+            vir::Expr::SeqIndex(box seq, box index, _) => {
+                vec![seq, index].get_required_permissions(predicates)
+            }
+
+            vir::Expr::SeqUpdate(box seq, box index, box elem, _) => {
+                vec![seq, index, elem].get_required_permissions(predicates)
+            }
+
+            vir::Expr::SeqSlice(box seq, box from, box to, _) => {
+                vec![seq, from, to].get_required_permissions(predicates)
+            }
+
+            vir::Expr::Set(_, elems, _) => elems.get_required_permissions(predicates),
+
+            vir::Expr::SetLen(box set, _) => set.get_required_permissions(predicates),
+
+            vir::Expr::Map(_, pairs, _) => pairs.iter()
+                .flat_map(|(key, value)| vec![key, value])
+                .collect::<Vec<_>>()
+                .get_required_permissions(predicates),
+
+            vir::Expr::MapLen(box map, _) => map.get_required_permissions(predicates),
+
+            vir::Expr::MapUpdate(box map, box key, box value, _) => {
+                vec![map, key, value].get_required_permissions(predicates)
+            }
+
+            vir::Expr::ContainerOp(_, box left, box right, _) => {
+                vec![left, right].get_required_permissions(predicates)
+            }
         };
         trace!(
             "[exit] get_required_permissions(expr={}): {:#?}",
@@ -345,7 +381,18 @@ impl ExprPermissionsGetter for vir::Expr {
             | vir::Expr::Const(_, _)
             | vir::Expr::FuncApp(..)
             | vir::Expr::DomainFuncApp(..)
-            | vir::Expr::InhaleExhale(..) => HashSet::new(),
+            | vir::Expr::InhaleExhale(..)
+            | vir::Expr::Seq(..)
+            | vir::Expr::SeqIndex(..)
+            | vir::Expr::SeqLen(..)
+            | vir::Expr::SeqUpdate(..)
+            | vir::Expr::SeqSlice(..)
+            | vir::Expr::Set(..)
+            | vir::Expr::SetLen(..)
+            | vir::Expr::Map(..)
+            | vir::Expr::MapLen(..)
+            | vir::Expr::MapUpdate(..)
+            | vir::Expr::ContainerOp(..) => HashSet::new(),
 
             vir::Expr::Unfolding(_, args, expr, perm_amount, variant, _) => {
                 assert_eq!(args.len(), 1);
@@ -386,7 +433,8 @@ impl ExprPermissionsGetter for vir::Expr {
                 &right.get_permissions(predicates),
             ),
 
-            vir::Expr::ForAll(vars, _triggers, box body, _) => {
+            vir::Expr::ForAll(vars, _triggers, box body, _) |
+            vir::Expr::Exists(vars, _triggers, box body, _) => {
                 assert!(vars.iter().all(|var| !var.typ.is_ref()));
                 let vars_places: HashSet<Perm> = vars
                     .iter()
@@ -145,7 +145,11 @@ impl ApplyOnState for vir::Stmt {
                                 p.has_proper_prefix(&lhs_place) && !p.is_local()
                             });
                         }
-                        vir::AssignKind::Ghost | vir::AssignKind::Copy => {
+                        vir::AssignKind::Copy => {
+                            // This is the copy of the address stored in a raw pointer, which
+                            // does not own its target. The permissions stay with the rhs.
+                        }
+                        vir::AssignKind::Ghost => {
                             unreachable!();
                         }
                     }
@@ -100,6 +100,7 @@ impl<'p, 'tcx: 'p> LoopEncoder<'p, 'tcx> {
             self.loops().compute_read_and_write_leaves(
                 bb,
                 self.mir(),
+                self.tcx,
                 Some(self.initialization.get_before_block(bb_inv)),
             );
 
@@ -153,7 +154,7 @@ impl<'p, 'tcx: 'p> LoopEncoder<'p, 'tcx> {
                 loop_info.get_loop_depth(bb) == loop_depth
                     && self.mir()[bb].terminator().successors().any(|&succ_bb| {
                         self.procedure.is_reachable_block(succ_bb)
-                            && self.procedure.is_spec_block(succ_bb)
+                            && self.procedure.is_loop_invariant_block(succ_bb)
                     })
             })
             .cloned()
@@ -132,7 +132,17 @@ impl MemoryEqEncoder {
             ty::TyKind::Bool
             | ty::TyKind::Int(_)
             | ty::TyKind::Uint(_)
-            | ty::TyKind::Char => {
+            | ty::TyKind::Char
+            | ty::TyKind::Float(_)
+            // Raw pointers are equal if they store the same address.
+            | ty::TyKind::RawPtr(..) => {
+                let field = encoder.encode_value_field(self_ty);
+                let first_field = first.clone().field(field.clone());
+                let second_field = second.clone().field(field);
+                Some(vir::Expr::eq_cmp(first_field, second_field))
+            }
+            // Mathematical values are stored like primitive values.
+            ty::TyKind::Adt(_, _) if encoder.is_ghost_type(self_ty) => {
                 let field = encoder.encode_value_field(self_ty);
                 let first_field = first.clone().field(field.clone());
                 let second_field = second.clone().field(field);
@@ -165,11 +175,6 @@ impl MemoryEqEncoder {
                     "memory equality between reference types is unsupported"
                 ));
             }
-            ty::TyKind::RawPtr(..) => {
-                return Err(EncodingError::unsupported(
-                    "memory equality between raw pointers is unsupported"
-                ));
-            }
 
             ref x => unimplemented!("{:?}", x),
         };
@@ -4,14 +4,16 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use crate::encoder::builtin_encoder::BuiltinFunctionKind;
+use crate::encoder::builtin_encoder::{encode_pow2_const, BitVectorFunc};
 use crate::encoder::errors::{
     ErrorCtxt, PanicCause, SpannedEncodingError, EncodingError, WithSpan,
     SpannedEncodingResult, EncodingResult
 };
 use crate::encoder::Encoder;
+use crate::encoder::type_encoder::{compute_array_len, float_size};
 use prusti_common::vir;
 use prusti_common::config;
+use prusti_interface::utils;
 use rustc_hir::def_id::DefId;
 use rustc_middle::{mir, ty};
 use rustc_index::vec::{Idx, IndexVec};
@@ -21,6 +23,11 @@ use std::collections::HashMap;
 
 pub static PRECONDITION_LABEL: &'static str = "pre";
 pub static WAND_LHS_LABEL: &'static str = "lhs";
+/// Label of the state before entering a loop, used by `old[loop_entry](..)` in loop
+/// invariants. It is replaced with the label of the specific loop when encoding the invariant.
+pub static LOOP_ENTRY_LABEL: &'static str = "loop_entry";
+/// Prefix of the labels declared with `prusti_label!`.
+pub static USER_LABEL_PREFIX: &'static str = "label$";
 
 pub trait PlaceEncoder<'v, 'tcx: 'v> {
 
@@ -195,6 +202,18 @@ pub trait PlaceEncoder<'v, 'tcx: 'v> {
                         (encoded_projection, field_ty, None)
                     }
 
+                    ty::TyKind::Generator(_, ref generator_subst, _) => {
+                        let field_ty = generator_subst.as_generator()
+                            .upvar_tys()
+                            .nth(field.index())
+                            .unwrap();
+                        let field_name = format!("generator_{}", field.index());
+                        let encoded_field = self.encoder()
+                            .encode_raw_ref_field(field_name, field_ty)?;
+                        let encoded_projection = encoded_base.field(encoded_field);
+                        (encoded_projection, field_ty, None)
+                    }
+
                     ref x => unimplemented!("{:?}", x),
                 }
             }
@@ -208,7 +227,13 @@ pub trait PlaceEncoder<'v, 'tcx: 'v> {
                 (encoded_base, base_ty, Some(variant_index.into()))
             }
 
-            x => unimplemented!("{:?}", x),
+            mir::ProjectionElem::Index(_)
+            | mir::ProjectionElem::ConstantIndex { .. }
+            | mir::ProjectionElem::Subslice { .. } => {
+                return Err(EncodingError::unsupported(
+                    "the elements of arrays and slices can only be used by value"
+                ));
+            }
         })
     }
 
@@ -361,10 +386,50 @@ impl<'p, 'v: 'p, 'tcx: 'v> MirEncoder<'p, 'v, 'tcx> {
         }
     }
 
+    pub fn is_raw_pointer(&self, base_ty: ty::Ty<'tcx>) -> bool {
+        trace!("is_raw_pointer {}", base_ty);
+        match base_ty.kind() {
+            ty::TyKind::RawPtr(..) => true,
+
+            _ => false,
+        }
+    }
+
+    pub fn is_str_ref(&self, base_ty: ty::Ty<'tcx>) -> bool {
+        trace!("is_str_ref {}", base_ty);
+        match base_ty.kind() {
+            ty::TyKind::Ref(_, ty, _) => ty.is_str(),
+
+            _ => false,
+        }
+    }
+
+    pub fn is_array_ref(&self, base_ty: ty::Ty<'tcx>) -> bool {
+        trace!("is_array_ref {}", base_ty);
+        match base_ty.kind() {
+            ty::TyKind::Ref(_, ty, _) => matches!(ty.kind(), ty::TyKind::Array(..)),
+
+            _ => false,
+        }
+    }
+
+    /// Is the type a reference, a raw pointer, or a box of a trait object?
+    pub fn is_trait_object_pointer(&self, base_ty: ty::Ty<'tcx>) -> bool {
+        trace!("is_trait_object_pointer {}", base_ty);
+        match base_ty.builtin_deref(true) {
+            Some(ty::TypeAndMut { ty, .. }) => matches!(ty.kind(), ty::TyKind::Dynamic(..)),
+
+            _ => false,
+        }
+    }
+
     pub fn eval_place(
         &self,
         place: &mir::Place<'tcx>,
     ) -> EncodingResult<vir::Expr> {
+        if let Some((seq, index)) = self.encode_array_elem(place)? {
+            return Ok(vir::Expr::seq_index(seq, index));
+        }
         let (encoded_place, place_ty, _) = self.encode_place(place)?;
         Ok(self.encoder.encode_value_expr(encoded_place, place_ty))
     }
@@ -381,8 +446,12 @@ impl<'p, 'v: 'p, 'tcx: 'v> MirEncoder<'p, 'v, 'tcx> {
                 ..
             }) => self.encoder.encode_const_expr(ty, val)?,
             &mir::Operand::Copy(ref place) | &mir::Operand::Move(ref place) => {
-                let val_place = self.eval_place(&place)?;
-                val_place.into()
+                if let Some((seq, index)) = self.encode_array_elem(place)? {
+                    vir::Expr::seq_index(seq, index)
+                } else {
+                    let val_place = self.eval_place(&place)?;
+                    val_place.into()
+                }
             }
             // FIXME: Check whether the commented out code is necessary.
             // &mir::Operand::Constant(box mir::Constant {
@@ -409,6 +478,80 @@ impl<'p, 'v: 'p, 'tcx: 'v> MirEncoder<'p, 'v, 'tcx> {
         })
     }
 
+    /// If `place` is an element of an array or slice, returns the sequence that stores the
+    /// elements together with the index of the element. The elements are not places on their
+    /// own, they are looked up in the sequence. The sequence of a nested array is itself an
+    /// element of the outer sequence.
+    pub fn encode_array_elem(
+        &self,
+        place: &mir::Place<'tcx>,
+    ) -> EncodingResult<Option<(vir::Expr, vir::Expr)>> {
+        let tcx = self.encoder.env().tcx();
+        let (elem, base) = match utils::try_pop_one_level(tcx, *place) {
+            Some(x) => x,
+            None => return Ok(None),
+        };
+        let seq = self.eval_place(&base)?;
+        let encoded_index = match elem {
+            mir::ProjectionElem::Index(index) => self.eval_place(&index.into())?,
+            mir::ProjectionElem::ConstantIndex { offset, from_end: false, .. } => offset.into(),
+            mir::ProjectionElem::ConstantIndex { offset, from_end: true, .. } => {
+                vir::Expr::sub(vir::Expr::seq_len(seq.clone()), offset.into())
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some((seq, encoded_index)))
+    }
+
+    /// Encode the update of the element `place` of an array or slice with `value`. Returns
+    /// the sequence of the outermost array together with its updated value, such that the
+    /// elements of nested arrays are updated in place.
+    pub fn encode_array_elem_update(
+        &self,
+        place: &mir::Place<'tcx>,
+        value: vir::Expr,
+    ) -> EncodingResult<(vir::Expr, vir::Expr)> {
+        let tcx = self.encoder.env().tcx();
+        let (seq, encoded_index) = self.encode_array_elem(place)?.unwrap();
+        let updated_seq = vir::Expr::seq_update(seq.clone(), encoded_index, value);
+        let (_, base) = utils::try_pop_one_level(tcx, *place).unwrap();
+        if utils::is_array_elem_place(&base) {
+            self.encode_array_elem_update(&base, updated_seq)
+        } else {
+            Ok((seq, updated_seq))
+        }
+    }
+
+    /// Encode the length of the array or slice `place`. The length of an array is also
+    /// known from its type, and returned as the second component.
+    pub fn encode_array_len(
+        &self,
+        place: &mir::Place<'tcx>,
+    ) -> EncodingResult<(vir::Expr, Option<u128>)> {
+        let tcx = self.encoder.env().tcx();
+        let array_len = match place.ty(self.mir, tcx).ty.kind() {
+            ty::TyKind::Array(_, size) => Some(compute_array_len(tcx, size)),
+            _ => None,
+        };
+        Ok((vir::Expr::seq_len(self.eval_place(place)?), array_len))
+    }
+
+    /// Encode the value of `operand` as an element of an array or slice. Structures,
+    /// enumerations and tuples are stored by their snapshot.
+    pub fn encode_array_elem_operand(
+        &self,
+        operand: &mir::Operand<'tcx>,
+    ) -> EncodingResult<vir::Expr> {
+        let encoded_operand = self.encode_operand_expr(operand)?;
+        let ty = self.get_operand_ty(operand);
+        Ok(match encoded_operand.get_type() {
+            vir::Type::TypedRef(_) if !self.encoder.is_ghost_type(ty) => {
+                self.encoder.encode_snapshot(ty)?.snap_call(encoded_operand)
+            }
+            _ => encoded_operand,
+        })
+    }
+
     pub fn get_operand_ty(&self, operand: &mir::Operand<'tcx>) -> ty::Ty<'tcx> {
         debug!("Get operand ty {:?}", operand);
         // match operand {
@@ -450,6 +593,9 @@ impl<'p, 'v: 'p, 'tcx: 'v> MirEncoder<'p, 'v, 'tcx> {
         right: vir::Expr,
         ty: ty::Ty<'tcx>,
     ) -> EncodingResult<vir::Expr> {
+        if let &vir::Type::Float(size) = left.get_type() {
+            return self.encode_float_bin_op_expr(op, left, right, size);
+        }
         let is_bool = ty.kind() == &ty::TyKind::Bool;
         Ok(match op {
             mir::BinOp::Eq => vir::Expr::eq_cmp(left, right),
@@ -468,11 +614,9 @@ impl<'p, 'v: 'p, 'tcx: 'v> MirEncoder<'p, 'v, 'tcx> {
             mir::BinOp::BitXor if is_bool => vir::Expr::xor(left, right),
             mir::BinOp::BitAnd |
             mir::BinOp::BitOr |
-            mir::BinOp::BitXor => {
-                return Err(EncodingError::unsupported(
-                    "bitwise operations on non-boolean types are not supported"
-                ))
-            }
+            mir::BinOp::BitXor |
+            mir::BinOp::Shl |
+            mir::BinOp::Shr => self.encode_bitwise_op_expr(op, left, right, ty)?,
             unsupported_op => {
                 return Err(EncodingError::unsupported(format!(
                     "operation '{:?}' is not supported",
@@ -482,10 +626,136 @@ impl<'p, 'v: 'p, 'tcx: 'v> MirEncoder<'p, 'v, 'tcx> {
         })
     }
 
-    pub fn encode_unary_op_expr(&self, op: mir::UnOp, expr: vir::Expr) -> vir::Expr {
+    /// Encodes a binary operation on floats with the functions on floats of the given size.
+    fn encode_float_bin_op_expr(
+        &self,
+        op: mir::BinOp,
+        left: vir::Expr,
+        right: vir::Expr,
+        size: vir::FloatSize,
+    ) -> EncodingResult<vir::Expr> {
+        let (func, args) = match op {
+            mir::BinOp::Eq | mir::BinOp::Ne => (vir::FloatFunc::Eq, vec![left, right]),
+            mir::BinOp::Lt => (vir::FloatFunc::Lt, vec![left, right]),
+            mir::BinOp::Le => (vir::FloatFunc::Le, vec![left, right]),
+            mir::BinOp::Gt => (vir::FloatFunc::Lt, vec![right, left]),
+            mir::BinOp::Ge => (vir::FloatFunc::Le, vec![right, left]),
+            mir::BinOp::Add => (vir::FloatFunc::Add, vec![left, right]),
+            mir::BinOp::Sub => (vir::FloatFunc::Sub, vec![left, right]),
+            mir::BinOp::Mul => (vir::FloatFunc::Mul, vec![left, right]),
+            mir::BinOp::Div => (vir::FloatFunc::Div, vec![left, right]),
+            mir::BinOp::Rem => (vir::FloatFunc::Rem, vec![left, right]),
+            unsupported_op => {
+                return Err(EncodingError::unsupported(format!(
+                    "operation '{:?}' is not supported on floats",
+                    unsupported_op
+                )))
+            }
+        };
+        let result = vir::float_func_app(size, func, args);
+        Ok(if op == mir::BinOp::Ne {
+            vir::Expr::not(result)
+        } else {
+            result
+        })
+    }
+
+    /// Encodes a bitwise or shift operation on integers. The operands are
+    /// mapped to the two's complement representation of `ty`, on which the
+    /// operation is computed by the builtin functions of the same width.
+    fn encode_bitwise_op_expr(
+        &self,
+        op: mir::BinOp,
+        left: vir::Expr,
+        right: vir::Expr,
+        ty: ty::Ty<'tcx>,
+    ) -> EncodingResult<vir::Expr> {
+        let (bits, signed) = match int_type_info(self.encoder.env().tcx(), ty) {
+            Some(info) => info,
+            None => {
+                return Err(EncodingError::unsupported(format!(
+                    "bitwise operation '{:?}' is not supported on type '{:?}'",
+                    op,
+                    ty,
+                )));
+            }
+        };
+        let modulus = encode_pow2_const(bits);
+        let half_modulus = encode_pow2_const(bits - 1);
+        let to_unsigned = |expr: vir::Expr| if signed {
+            vir::Expr::ite(
+                vir::Expr::lt_cmp(expr.clone(), 0.into()),
+                vir::Expr::add(expr.clone(), modulus.clone()),
+                expr,
+            )
+        } else {
+            expr
+        };
+        let from_unsigned = |expr: vir::Expr| if signed {
+            vir::Expr::ite(
+                vir::Expr::ge_cmp(expr.clone(), half_modulus.clone()),
+                vir::Expr::sub(expr.clone(), modulus.clone()),
+                expr,
+            )
+        } else {
+            expr
+        };
+        let call_builtin = |func: BitVectorFunc, args: Vec<vir::Expr>| {
+            self.encoder.encode_bitvector_func_app(bits, func, args)
+        };
+        // The shift amount is masked to the bit width, as in the
+        // unchecked shift operations of Rust. For shifts that pass the
+        // overflow check this is the identity.
+        let shift_amount = |expr: vir::Expr| vir::Expr::rem(expr, bits.into());
+        Ok(match op {
+            mir::BinOp::BitAnd | mir::BinOp::BitOr | mir::BinOp::BitXor => {
+                let func = match op {
+                    mir::BinOp::BitAnd => BitVectorFunc::And,
+                    mir::BinOp::BitOr => BitVectorFunc::Or,
+                    _ => BitVectorFunc::Xor,
+                };
+                from_unsigned(call_builtin(func, vec![to_unsigned(left), to_unsigned(right)]))
+            }
+            mir::BinOp::Shl => {
+                let factor = call_builtin(BitVectorFunc::Pow2, vec![shift_amount(right)]);
+                from_unsigned(vir::Expr::rem(
+                    vir::Expr::mul(to_unsigned(left), factor),
+                    modulus.clone(),
+                ))
+            }
+            mir::BinOp::Shr => {
+                // Viper's integer division rounds towards negative infinity
+                // for a positive divisor, which gives an arithmetic shift on
+                // signed integers.
+                let divisor = call_builtin(BitVectorFunc::Pow2, vec![shift_amount(right)]);
+                vir::Expr::div(left, divisor)
+            }
+            _ => unreachable!("{:?}", op),
+        })
+    }
+
+    pub fn encode_unary_op_expr(
+        &self,
+        op: mir::UnOp,
+        expr: vir::Expr,
+        ty: ty::Ty<'tcx>,
+    ) -> vir::Expr {
         match op {
-            mir::UnOp::Not => vir::Expr::not(expr),
-            mir::UnOp::Neg => vir::Expr::minus(expr),
+            mir::UnOp::Not => match int_type_info(self.encoder.env().tcx(), ty) {
+                // Bitwise negation in two's complement
+                Some((_, true)) => vir::Expr::sub(vir::Expr::minus(expr), 1.into()),
+                Some((bits, false)) => vir::Expr::sub(
+                    vir::Expr::sub(encode_pow2_const(bits), 1.into()),
+                    expr,
+                ),
+                None => vir::Expr::not(expr),
+            },
+            mir::UnOp::Neg => match ty.kind() {
+                ty::TyKind::Float(float_ty) => {
+                    vir::float_func_app(float_size(*float_ty), vir::FloatFunc::Neg, vec![expr])
+                }
+                _ => vir::Expr::minus(expr),
+            },
         }
     }
 
@@ -564,11 +834,20 @@ impl<'p, 'v: 'p, 'tcx: 'v> MirEncoder<'p, 'v, 'tcx> {
                     }
                 },
 
-                mir::BinOp::Shl | mir::BinOp::Shr => {
-                    return Err(EncodingError::unsupported(
-                        "overflow checks on a shift operation are unsupported",
-                    ));
-                }
+                mir::BinOp::Shl | mir::BinOp::Shr => match int_type_info(self.encoder.env().tcx(), ty) {
+                    // The shift amount has to be smaller than the bit width
+                    Some((bits, _)) => vir::Expr::or(
+                        vir::Expr::lt_cmp(right.clone(), 0.into()),
+                        vir::Expr::ge_cmp(right, bits.into()),
+                    ),
+                    None => {
+                        return Err(EncodingError::unsupported(format!(
+                            "overflow checks are unsupported for operation '{:?}' on type '{:?}'",
+                            op,
+                            ty,
+                        )));
+                    }
+                },
 
                 _ => unreachable!("{:?}", op),
             })
@@ -664,6 +943,70 @@ impl<'p, 'v: 'p, 'tcx: 'v> MirEncoder<'p, 'v, 'tcx> {
                 }
             }
 
+            (ty::TyKind::Int(_), ty::TyKind::Float(float_ty))
+            | (ty::TyKind::Uint(_), ty::TyKind::Float(float_ty)) => {
+                let encoded_operand = self.encode_operand_expr(operand).with_span(span)?;
+                let size = float_size(*float_ty);
+                self.encoder.encode_float_type(size);
+                vir::float_func_app(size, vir::FloatFunc::FromInt, vec![encoded_operand])
+            }
+
+            (ty::TyKind::Float(float_ty), ty::TyKind::Int(_))
+            | (ty::TyKind::Float(float_ty), ty::TyKind::Uint(_)) => {
+                // Casts from floats to integers saturate and map NaN to zero. The bounds are
+                // compared as floats, because infinities have no integer value.
+                let encoded_operand = self.encode_operand_expr(operand).with_span(span)?;
+                let size = float_size(*float_ty);
+                let (bits, signed) = int_type_info(self.encoder.env().tcx(), dst_ty).unwrap();
+                let (min, max_plus_one) = if signed {
+                    let half_modulus = encode_pow2_const(bits - 1);
+                    (vir::Expr::minus(half_modulus.clone()), half_modulus)
+                } else {
+                    (0.into(), encode_pow2_const(bits))
+                };
+                let func = |func: vir::FloatFunc, args: Vec<vir::Expr>| {
+                    vir::float_func_app(size, func, args)
+                };
+                vir::Expr::ite(
+                    func(vir::FloatFunc::IsNan, vec![encoded_operand.clone()]),
+                    0.into(),
+                    vir::Expr::ite(
+                        func(
+                            vir::FloatFunc::Lt,
+                            vec![
+                                encoded_operand.clone(),
+                                func(vir::FloatFunc::FromInt, vec![min.clone()]),
+                            ],
+                        ),
+                        min,
+                        vir::Expr::ite(
+                            func(
+                                vir::FloatFunc::Le,
+                                vec![
+                                    func(vir::FloatFunc::FromInt, vec![max_plus_one.clone()]),
+                                    encoded_operand.clone(),
+                                ],
+                            ),
+                            vir::Expr::sub(max_plus_one, 1.into()),
+                            func(vir::FloatFunc::ToInt, vec![encoded_operand]),
+                        ),
+                    ),
+                )
+            }
+
+            (ty::TyKind::Float(src_float_ty), ty::TyKind::Float(dst_float_ty)) => {
+                let encoded_operand = self.encode_operand_expr(operand).with_span(span)?;
+                if src_float_ty == dst_float_ty {
+                    encoded_operand
+                } else {
+                    vir::float_func_app(
+                        float_size(*src_float_ty),
+                        vir::FloatFunc::Convert,
+                        vec![encoded_operand],
+                    )
+                }
+            }
+
             _ => {
                 return Err(SpannedEncodingError::unsupported(
                     format!(
@@ -686,8 +1029,13 @@ impl<'p, 'v: 'p, 'tcx: 'v> MirEncoder<'p, 'v, 'tcx> {
         debug!("Encode operand place {:?}", operand);
         Ok(match operand {
             &mir::Operand::Move(ref place) | &mir::Operand::Copy(ref place) => {
-                let (src, _, _) = self.encode_place(place)?;
-                Some(src)
+                if self.encode_array_elem(place)?.is_some() {
+                    // Array elements are values, not places.
+                    None
+                } else {
+                    let (src, _, _) = self.encode_place(place)?;
+                    Some(src)
+                }
             }
 
             &mir::Operand::Constant(_) => None,
@@ -707,6 +1055,15 @@ impl<'p, 'v: 'p, 'tcx: 'v> MirEncoder<'p, 'v, 'tcx> {
         vir::Expr::labelled_old(label, expr)
     }
 
+    /// Returns a description of the failure of a MIR assertion.
+    pub fn encode_assert_message(&self, msg: &mir::AssertMessage<'tcx>) -> String {
+        match msg {
+            // `description` does not support bounds checks.
+            mir::AssertKind::BoundsCheck { .. } => "index out of bounds".to_string(),
+            _ => msg.description().to_string(),
+        }
+    }
+
     pub fn get_span_of_location(&self, location: mir::Location) -> Span {
         self.mir.source_info(location).span
     }
@@ -763,3 +1120,23 @@ impl<'p, 'v: 'p, 'tcx: 'v> MirEncoder<'p, 'v, 'tcx> {
         }
     }
 }
+
+/// Returns the bit width and the signedness of an integer type on the compilation target.
+pub fn int_type_info<'tcx>(tcx: ty::TyCtxt<'tcx>, ty: ty::Ty<'tcx>) -> Option<(u32, bool)> {
+    let pointer_width = tcx.data_layout.pointer_size.bits() as u32;
+    match ty.kind() {
+        ty::TyKind::Uint(ty::UintTy::U8) => Some((8, false)),
+        ty::TyKind::Uint(ty::UintTy::U16) => Some((16, false)),
+        ty::TyKind::Uint(ty::UintTy::U32) => Some((32, false)),
+        ty::TyKind::Uint(ty::UintTy::U64) => Some((64, false)),
+        ty::TyKind::Uint(ty::UintTy::U128) => Some((128, false)),
+        ty::TyKind::Uint(ty::UintTy::Usize) => Some((pointer_width, false)),
+        ty::TyKind::Int(ty::IntTy::I8) => Some((8, true)),
+        ty::TyKind::Int(ty::IntTy::I16) => Some((16, true)),
+        ty::TyKind::Int(ty::IntTy::I32) => Some((32, true)),
+        ty::TyKind::Int(ty::IntTy::I64) => Some((64, true)),
+        ty::TyKind::Int(ty::IntTy::I128) => Some((128, true)),
+        ty::TyKind::Int(ty::IntTy::Isize) => Some((pointer_width, true)),
+        _ => None,
+    }
+}
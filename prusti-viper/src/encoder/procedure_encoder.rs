@@ -15,10 +15,13 @@ use crate::encoder::initialisation::InitInfo;
 use crate::encoder::loop_encoder::{LoopEncoder, LoopEncoderError};
 use crate::encoder::mir_encoder::{MirEncoder, FakeMirEncoder, PlaceEncoder};
 use crate::encoder::mir_encoder::PRECONDITION_LABEL;
+use crate::encoder::mir_encoder::{LOOP_ENTRY_LABEL, USER_LABEL_PREFIX};
 use crate::encoder::mir_successor::MirSuccessor;
 use crate::encoder::places::{Local, LocalVariableManager, Place};
 use crate::encoder::Encoder;
 use crate::encoder::snapshot_spec_patcher::SnapshotSpecPatcher;
+use crate::encoder::type_encoder::compute_array_len;
+use crate::encoder::spec_encoder::encode_spec_measure;
 use prusti_common::{
     config,
     report::log,
@@ -35,6 +38,7 @@ use prusti_interface::{
     data::ProcedureDefId,
     environment::{
         borrowck::facts,
+        mir_utils::AsyncFnParameters,
         polonius_info::{
             LoanPlaces, PoloniusInfo, PoloniusInfoError, ReborrowingDAG, ReborrowingDAGNode,
             ReborrowingKind, ReborrowingZombity,
@@ -103,6 +107,21 @@ pub struct ProcedureEncoder<'p, 'v: 'p, 'tcx: 'v> {
     old_ghost_vars: HashMap<String, vir::Type>,
     /// For each loop head, the block at whose end the loop invariant holds
     cached_loop_invariant_block: HashMap<BasicBlockIndex, BasicBlockIndex>,
+    /// For each loop head, the label of the state before entering the loop that is being
+    /// encoded, used to encode `old[loop_entry](..)` in its invariant
+    loop_entry_labels: HashMap<BasicBlockIndex, String>,
+    /// For each label declared with `prusti_label!(..)`, the block at whose end it is declared
+    user_label_blocks: HashMap<String, BasicBlockIndex>,
+    /// Raw pointers that got their address from a copy of another raw pointer or from taking
+    /// the address of a place, on all paths to the statement that is being encoded. The
+    /// address is expressed in terms of the place that owns the target, such that ownership
+    /// can be passed to a called function.
+    raw_pointer_sources: HashMap<vir::Expr, vir::Expr>,
+    /// The sources of raw pointers at the end of the blocks that have been encoded.
+    raw_pointer_sources_at_exit: HashMap<BasicBlockIndex, HashMap<vir::Expr, vir::Expr>>,
+    /// Statements that move the parameters of an `async fn` out of its generator. The
+    /// locals that receive the parameters are the arguments of the contract instead.
+    async_fn_parameter_moves: HashSet<mir::Location>,
 }
 
 impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
@@ -119,6 +138,12 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         let init_info = InitInfo::new(mir, tcx, def_id, &mir_encoder)
             .with_span(procedure.get_span())?;
 
+        let async_fn_parameter_moves = if encoder.env().get_async_fn_of_body(def_id).is_some() {
+            mir.get_async_fn_parameters().into_iter().map(|(location, _)| location).collect()
+        } else {
+            HashSet::new()
+        };
+
         let cfg_method = vir::CfgMethod::new(
             // method name
             encoder.encode_item_name(def_id),
@@ -157,6 +182,11 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             old_to_ghost_var: HashMap::new(),
             old_ghost_vars: HashMap::new(),
             cached_loop_invariant_block: HashMap::new(),
+            loop_entry_labels: HashMap::new(),
+            user_label_blocks: HashMap::new(),
+            raw_pointer_sources: HashMap::new(),
+            raw_pointer_sources_at_exit: HashMap::new(),
+            async_fn_parameter_moves,
         })
     }
 
@@ -174,13 +204,6 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 SpannedEncodingError::unsupported(msg, self.mir_encoder.get_span_of_basic_block(loop_head))
             }
 
-            PoloniusInfoError::LoansInNestedLoops(location1, _loop1, _location2, _loop2) => {
-                SpannedEncodingError::unsupported(
-                    "creation of loans in nested loops is not supported".to_string(),
-                    self.mir.source_info(location1).span,
-                )
-            }
-
             PoloniusInfoError::ReborrowingDagHasNoMagicWands(location) => {
                 SpannedEncodingError::unsupported(
                     "the creation of loans in this loop is not supported \
@@ -189,12 +212,6 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 )
             }
 
-            PoloniusInfoError::MultipleMagicWandsPerLoop(location) => SpannedEncodingError::unsupported(
-                "the creation of loans in this loop is not supported \
-                    (MultipleMagicWandsPerLoop)",
-                self.mir.source_info(location).span,
-            ),
-
             PoloniusInfoError::MagicWandHasNoRepresentativeLoan(location) => {
                 SpannedEncodingError::unsupported(
                     "the creation of loans in this loop is not supported \
@@ -232,6 +249,15 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         trace!("Encode procedure {}", self.cfg_method.name());
         let mir_span = self.mir.span;
 
+        if let Some(rustc_hir::GeneratorKind::Gen) =
+            self.encoder.env().tcx().generator_kind(self.proc_def_id)
+        {
+            return Err(SpannedEncodingError::unsupported(
+                "generators are not supported",
+                mir_span,
+            ));
+        }
+
         // Retrieve the contract
         self.procedure_contract = Some(
             self.encoder
@@ -239,6 +265,8 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 .with_span(mir_span)?
         );
 
+        self.user_label_blocks = self.collect_user_label_blocks()?;
+
         // Prepare assertions to check specification refinement
         let mut precondition_weakening: Option<typed::Assertion> = None;
         let mut postcondition_strengthening: Option<typed::Assertion> = None;
@@ -354,6 +382,8 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             }
         }
 
+        self.check_ghost_code()?;
+
         // Load Polonius info
         self.polonius_info = Some(
             PoloniusInfo::new(&self.procedure, &self.cached_loop_invariant_block)
@@ -475,14 +505,23 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             &self.cfg_blocks_map,
             method_pos,
         )
-        .map_err(|foldunfold_error| {
-            SpannedEncodingError::internal(
+        .map_err(|foldunfold_error| match foldunfold_error {
+            foldunfold::FoldUnfoldError::FailedToObtain(ref perm)
+                if is_raw_pointer_target(perm.get_place(), perm.is_pred()) =>
+            {
+                SpannedEncodingError::incorrect(
+                    "the target of a raw pointer is accessed without being owned; \
+                    require its ownership with `own!(*ptr)`",
+                    mir_span,
+                )
+            }
+            _ => SpannedEncodingError::internal(
                 format!(
                     "generating fold-unfold Viper statements failed ({:?})",
                     foldunfold_error
                 ),
                 mir_span,
-            )
+            ),
         })?;
 
         // Fix variable declarations.
@@ -687,13 +726,19 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         let mut heads = vec![];
 
         // Build the "start" CFG block (*start* - G - B1 - invariant - B2 - G - B1 - end)
+        // It also labels the state before the loop, to which `old[loop_entry](..)` refers.
+        let loop_entry_label = format!("{}_entry", loop_label_prefix);
         let start_block = self.cfg_method.add_block(
             &format!("{}_start", loop_label_prefix),
-            vec![vir::Stmt::comment(format!(
-                "========== {}_start ==========",
-                loop_label_prefix
-            ))],
+            vec![
+                vir::Stmt::comment(format!(
+                    "========== {}_start ==========",
+                    loop_label_prefix
+                )),
+                vir::Stmt::Label(loop_entry_label.clone()),
+            ],
         );
+        self.loop_entry_labels.insert(loop_head, loop_entry_label);
         heads.push(Some(start_block));
 
         // Encode the first G group (start - *G* - B1 - invariant - B2 - G - B1 - end)
@@ -745,6 +790,22 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 self.encode_loop_invariant_inhale_stmts(loop_head, before_invariant_block, false)?;
             self.cfg_method.add_stmts(inv_post_block, stmts);
         }
+        // Store the value of the loop variant at the beginning of the iteration
+        let opt_variant = if config::check_loop_termination() {
+            self.encode_loop_variant_specs(loop_head, before_invariant_block)?
+        } else {
+            None
+        };
+        let mut variant_vars = vec![];
+        if let Some((ref variant, ref variant_span)) = opt_variant {
+            let stmts = self.encode_loop_variant_on_entry_stmts(
+                loop_head,
+                variant,
+                variant_span,
+                &mut variant_vars,
+            );
+            self.cfg_method.add_stmts(inv_post_block, stmts);
+        }
 
         // Encode the last B2 group (start - G - B1 - invariant - *B2* - G - B1 - end)
         let (last_b2_head, last_b2_edges) = self.encode_blocks_group(
@@ -783,6 +844,16 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 loop_label_prefix
             ))],
         );
+        if let Some((ref variant, ref variant_span)) = opt_variant {
+            // This has to be checked before the permissions of the invariant are exhaled.
+            let stmts = self.encode_loop_variant_after_iteration_stmts(
+                loop_head,
+                variant,
+                variant_span,
+                &variant_vars,
+            );
+            self.cfg_method.add_stmts(end_body_block, stmts);
+        }
         {
             let stmts = self.encode_loop_invariant_exhale_stmts(
                 loop_head,
@@ -872,6 +943,10 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 vir::Type::Bool => BuiltinMethodKind::HavocBool,
                 vir::Type::TypedRef(_) => BuiltinMethodKind::HavocRef,
                 vir::Type::Domain(_) => BuiltinMethodKind::HavocRef,
+                vir::Type::Float(size) => BuiltinMethodKind::HavocFloat(size),
+                vir::Type::Seq(_) | vir::Type::Set(_) | vir::Type::Map(..) => {
+                    unreachable!("mathematical containers are only stored in fields")
+                }
             };
             let stmt = vir::Stmt::MethodCall(
                 self.encoder.encode_builtin_method_use(builtin_method),
@@ -918,8 +993,10 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         }
 
         self.encode_execution_flag(bbi, curr_block)?;
+        self.raw_pointer_sources = self.raw_pointer_sources_at_entry(bbi);
         self.encode_block_statements(bbi, curr_block)?;
         let mir_successor: MirSuccessor = self.encode_block_terminator(bbi, curr_block)?;
+        self.raw_pointer_sources_at_exit.insert(bbi, self.raw_pointer_sources.clone());
 
         // Make sure that the
         let mir_targets = mir_successor.targets();
@@ -1106,6 +1183,15 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             | mir::StatementKind::Coverage(..)
             | mir::StatementKind::Nop => vec![],
 
+            mir::StatementKind::Assign(..) if self.async_fn_parameter_moves.contains(&location) => {
+                // The local that receives the parameter already holds it (see the contract).
+                vec![]
+            }
+
+            mir::StatementKind::Assign(box (ref lhs, ref rhs)) if utils::is_array_elem_place(lhs) => {
+                self.encode_assign_array_elem(lhs, rhs, location)?
+            }
+
             mir::StatementKind::Assign(box (ref lhs, ref rhs)) => {
                 // FIXME: the following line will panic if attempting to encode unsupported types.
                 let (encoded_lhs, ty, _) = self.mir_encoder.encode_place(lhs).unwrap();
@@ -1186,11 +1272,51 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                             stmt.source_info.span,
                         )?
                     }
-                    &mir::Rvalue::Len(..) => {
-                        return Err(SpannedEncodingError::unsupported(
-                            "obtaining the length of an array is unsupported",
+                    &mir::Rvalue::Cast(
+                        mir::CastKind::Pointer(ty::adjustment::PointerCast::Unsize),
+                        ref operand,
+                        _,
+                    ) if self.mir_encoder.is_array_ref(self.mir_encoder.get_operand_ty(operand)) => {
+                        // Arrays share the predicate of slices, so unsizing a reference to an
+                        // array is just a reborrow. The slice keeps the length of the array.
+                        let array_ref_ty = self.mir_encoder.get_operand_ty(operand);
+                        let mut stmts = self.encode_assign_operand(&encoded_lhs, operand, location)?;
+                        if let Some(len_fact) = self.encode_array_len_fact(encoded_lhs, array_ref_ty) {
+                            stmts.push(vir::Stmt::Inhale(len_fact, vir::FoldingBehaviour::Stmt));
+                        }
+                        stmts
+                    }
+                    &mir::Rvalue::Cast(
+                        mir::CastKind::Pointer(ty::adjustment::PointerCast::Unsize),
+                        ref operand,
+                        cast_ty,
+                    ) if self.mir_encoder.is_trait_object_pointer(cast_ty) => {
+                        self.encode_assign_trait_object(
+                            operand,
+                            encoded_lhs,
+                            ty,
+                            location,
                             stmt.source_info.span,
-                        ))
+                        )?
+                    }
+                    &mir::Rvalue::Cast(
+                        mir::CastKind::Pointer(ty::adjustment::PointerCast::MutToConstPointer),
+                        ref operand,
+                        _,
+                    ) => {
+                        // The cast does not change the address stored in the pointer.
+                        self.encode_assign_operand(&encoded_lhs, operand, location)?
+                    }
+                    &mir::Rvalue::AddressOf(_, ref place) => {
+                        let (encoded_place, _, _) = self.mir_encoder.encode_place(place)
+                            .with_span(stmt.source_info.span)?;
+                        self.encode_copy_raw_pointer(&encoded_lhs, encoded_place, ty, location)?
+                    }
+                    &mir::Rvalue::Len(ref place) => {
+                        self.encode_assign_len(place, encoded_lhs, ty, location)?
+                    }
+                    &mir::Rvalue::Repeat(ref operand, count) => {
+                        self.encode_assign_repeat(operand, count, encoded_lhs, ty, location)?
                     }
                     ref rhs => {
                         unimplemented!("encoding of '{:?}'", rhs);
@@ -1275,13 +1401,38 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 };
                 (expiring, restored, is_mut)
             }
-            mir::Rvalue::Use(mir::Operand::Move(ref rhs_place)) => {
+            mir::Rvalue::Cast(
+                mir::CastKind::Pointer(ty::adjustment::PointerCast::Unsize),
+                ref operand,
+                cast_ty,
+            ) if self.mir_encoder.is_trait_object_pointer(cast_ty) => {
+                // The trait object does not share the predicate of the value it was created
+                // from, so no permissions flow back when the loan expires. See
+                // `encode_assign_trait_object`.
+                let rhs_place = operand.place().unwrap();
+                let (expiring, _, _) = encode(&rhs_place);
+                let (restored_base, restored_ty, _) =
+                    self.mir_encoder.encode_place(&rhs_place).unwrap();
+                let restored = restored_base.field(self.encoder.encode_value_field(restored_ty));
+                (expiring, restored, false)
+            }
+            mir::Rvalue::Use(mir::Operand::Move(ref rhs_place)) |
+            mir::Rvalue::Cast(
+                mir::CastKind::Pointer(ty::adjustment::PointerCast::Unsize),
+                mir::Operand::Move(ref rhs_place),
+                _,
+            ) => {
                 let (expiring, restored_base, ref_field) = encode(rhs_place);
                 let restored = restored_base.clone().field(ref_field);
                 assert_eq!(expiring.get_type(), restored.get_type());
                 (expiring, restored, true)
             }
-            mir::Rvalue::Use(mir::Operand::Copy(ref rhs_place)) => {
+            mir::Rvalue::Use(mir::Operand::Copy(ref rhs_place)) |
+            mir::Rvalue::Cast(
+                mir::CastKind::Pointer(ty::adjustment::PointerCast::Unsize),
+                mir::Operand::Copy(ref rhs_place),
+                _,
+            ) => {
                 let (expiring, restored_base, ref_field) = encode(rhs_place);
                 let restored = restored_base.clone().field(ref_field);
                 assert_eq!(expiring.get_type(), restored.get_type());
@@ -1349,9 +1500,11 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             Ok(match stmt.kind {
                 mir::StatementKind::Assign(box (_, ref rhs)) => match rhs {
                     &mir::Rvalue::Ref(_, mir::BorrowKind::Shared, _) |
-                    &mir::Rvalue::Use(mir::Operand::Copy(_)) => false,
+                    &mir::Rvalue::Use(mir::Operand::Copy(_)) |
+                    &mir::Rvalue::Cast(_, mir::Operand::Copy(_), _) => false,
                     &mir::Rvalue::Ref(_, mir::BorrowKind::Mut { .. }, _) |
-                    &mir::Rvalue::Use(mir::Operand::Move(_)) => true,
+                    &mir::Rvalue::Use(mir::Operand::Move(_)) |
+                    &mir::Rvalue::Cast(_, mir::Operand::Move(_), _) => true,
                     x => unreachable!("{:?}", x),
                 },
                 ref x => unreachable!("{:?}", x),
@@ -1411,7 +1564,16 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                         None,
                     )
                 }
-                ref x => unimplemented!("{:?}", x),
+                ReborrowingKind::Loop { .. } => {
+                    // Restoring the target of the reference requires a magic wand that is
+                    // packaged from the loop invariant, which is not encoded yet.
+                    let loan_location = self.polonius_info().get_loan_location(&node.loan);
+                    return Err(SpannedEncodingError::unsupported(
+                        "the expiration of a loan that flows from one iteration of a loop to \
+                        the next one is not supported",
+                        self.mir.source_info(loan_location).span,
+                    ));
+                }
             };
             builder.add_node(node);
         }
@@ -1787,9 +1949,12 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 if self.procedure.is_spec_block(default_target) {
                     stmts.push(vir::Stmt::comment(format!(
                         "Ignore default target {:?}, as it is only used by Prusti to type-check \
-                        a loop invariant.",
+                        a specification.",
                         default_target
                     )));
+                    stmts.extend(
+                        self.encode_specification_statements(default_target, location)?
+                    );
                     kill_default_target = true;
                 };
 
@@ -2038,6 +2203,107 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                             );
                         }
 
+                        "std::ops::Index::index" |
+                        "core::ops::Index::index"
+                            if matches!(substs.type_at(0).kind(), ty::TyKind::Slice(..))
+                        => {
+                            debug!("Encoding call of sub-slicing");
+                            stmts.extend(
+                                self.encode_subslice_call(
+                                    location,
+                                    term.source_info.span,
+                                    args,
+                                    destination,
+                                    def_id,
+                                    self_ty,
+                                ).run_if_err(|| cleanup(&self))?
+                            );
+                        }
+
+                        "core::slice::<impl [T]>::len"
+                        | "core::str::<impl str>::len" => {
+                            debug!("Encoding call of slice length");
+                            stmts.extend(
+                                self.encode_slice_len_call(
+                                    location,
+                                    term.source_info.span,
+                                    args,
+                                    destination,
+                                    false,
+                                ).run_if_err(|| cleanup(&self))?
+                            );
+                        }
+
+                        "core::slice::<impl [T]>::is_empty"
+                        | "core::str::<impl str>::is_empty" => {
+                            debug!("Encoding call of slice emptiness");
+                            stmts.extend(
+                                self.encode_slice_len_call(
+                                    location,
+                                    term.source_info.span,
+                                    args,
+                                    destination,
+                                    true,
+                                ).run_if_err(|| cleanup(&self))?
+                            );
+                        }
+
+                        "core::str::<impl str>::as_bytes" => {
+                            debug!("Encoding call of as_bytes");
+                            stmts.extend(
+                                self.encode_str_as_bytes_call(
+                                    location,
+                                    term.source_info.span,
+                                    args,
+                                    destination,
+                                    def_id,
+                                    self_ty,
+                                ).run_if_err(|| cleanup(&self))?
+                            );
+                        }
+
+                        "core::f32::<impl f32>::is_nan"
+                        | "core::f64::<impl f64>::is_nan"
+                        | "std::f32::<impl f32>::is_nan"
+                        | "std::f64::<impl f64>::is_nan" => {
+                            debug!("Encoding call of is_nan");
+                            stmts.extend(
+                                self.encode_float_is_nan_call(
+                                    location,
+                                    term.source_info.span,
+                                    args,
+                                    destination,
+                                ).run_if_err(|| cleanup(&self))?
+                            );
+                        }
+
+                        "std::future::get_context"
+                        | "core::future::get_context" => {
+                            // The context of an `async fn` is opaque and only passed to the
+                            // futures that it awaits.
+                            debug!("Encoding call of get_context");
+                            let (ref target_place, _) = destination.as_ref().unwrap();
+                            let (dst, _, _) = self.mir_encoder.encode_place(target_place)
+                                .with_span(span)
+                                .run_if_err(|| cleanup(&self))?;
+                            stmts.extend(self.encode_havoc(&dst));
+                        }
+
+                        "std::future::Future::poll"
+                        | "core::future::Future::poll"
+                            if self.is_async_fn_future(substs.type_at(0))
+                        => {
+                            debug!("Encoding call of Future::poll");
+                            stmts.extend(
+                                self.encode_await_call(
+                                    location,
+                                    term.source_info.span,
+                                    args,
+                                    destination,
+                                ).run_if_err(|| cleanup(&self))?
+                            );
+                        }
+
                         "std::ops::Fn::call" => {
                             let cl_type: ty::Ty = substs[0].expect_ty();
                             match cl_type.kind() {
@@ -2064,6 +2330,13 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                         }
 
                         _ => {
+                            if self.encoder.get_predicate_body(def_id).is_some() {
+                                cleanup(&self);
+                                return Err(SpannedEncodingError::incorrect(
+                                    "predicates can only be used in specifications",
+                                    term.source_info.span,
+                                ));
+                            }
                             let is_pure_function = self.encoder.is_pure(def_id);
                             if is_pure_function {
                                 let (function_name, _) = self.encoder
@@ -2151,9 +2424,10 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 };
 
                 // Check or assume the assertion
+                let assert_msg = self.mir_encoder.encode_assert_message(msg);
                 stmts.push(vir::Stmt::comment(format!(
                     "Rust assertion: {}",
-                    msg.description()
+                    assert_msg
                 )));
                 if self.check_panics {
                     stmts.push(vir::Stmt::Assert(
@@ -2161,7 +2435,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                         vir::FoldingBehaviour::Stmt,
                         self.encoder.error_manager().register(
                             term.source_info.span,
-                            ErrorCtxt::AssertTerminator(msg.description().to_string()),
+                            ErrorCtxt::AssertTerminator(assert_msg),
                         ),
                     ));
                 } else {
@@ -2172,110 +2446,489 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 (stmts, MirSuccessor::Goto(target))
             }
 
+            TerminatorKind::Yield { resume, ref resume_arg, .. } => {
+                // An `async` body is suspended while the awaited future is pending. It only
+                // owns state that no one else can access meanwhile, so the only effect is the
+                // new value of the resume argument. The context it holds is never inspected.
+                let (encoded_resume_arg, _, _) = self.mir_encoder.encode_place(resume_arg)
+                    .with_span(span)?;
+                stmts.extend(self.encode_havoc(&encoded_resume_arg));
+                (stmts, MirSuccessor::Goto(resume))
+            }
+
             TerminatorKind::Resume
-            | TerminatorKind::Yield { .. }
             | TerminatorKind::GeneratorDrop
             | TerminatorKind::InlineAsm { .. } => unimplemented!("{:?}", term.kind),
         };
         Ok(result)
     }
 
-    fn encode_cmp_function_call(
+    /// Encode a call of `<[T]>::len` or `str::len` as the length of the sequence that stores
+    /// the elements. If `is_empty` is set, the call is instead a comparison of that length
+    /// with zero.
+    fn encode_slice_len_call(
         &mut self,
-        called_def_id: ProcedureDefId,
         location: mir::Location,
         call_site_span: Span,
         args: &[mir::Operand<'tcx>],
         destination: &Option<(mir::Place<'tcx>, BasicBlockIndex)>,
-        bin_op: vir::BinOpKind,
+        is_empty: bool,
     ) -> SpannedEncodingResult<Vec<vir::Stmt>> {
-        let arg_ty = self.mir_encoder.get_operand_ty(&args[0]);
+        assert_eq!(args.len(), 1);
+        let slice_ref = self.mir_encoder.encode_operand_expr(&args[0])
+            .with_span(call_site_span)?;
+        let slice_ty = self.mir_encoder.get_operand_ty(&args[0]).builtin_deref(true).unwrap().ty;
+        let value_field = self.encoder.encode_value_field(slice_ty);
+        let len = vir::Expr::seq_len(slice_ref.field(value_field));
+        let result = if is_empty {
+            vir::Expr::eq_cmp(len, 0.into())
+        } else {
+            len
+        };
 
-        let snapshot_res = self.encoder.encode_snapshot(&arg_ty);
-        if snapshot_res.is_ok() && snapshot_res.as_ref().unwrap().supports_equality() {
-            let snapshot = snapshot_res.unwrap();
-            let pos = self
-                .encoder
-                .error_manager()
-                .register(call_site_span, ErrorCtxt::PureFunctionCall);
+        let target_value = self.encode_pure_function_call_lhs_value(destination)
+            .with_span(call_site_span)?;
+        let inhaled_expr = vir::Expr::eq_cmp(target_value.into(), result);
 
-            let lhs = self.mir_encoder.encode_operand_expr(&args[0])
-                .with_span(call_site_span)?;
-            let rhs = self.mir_encoder.encode_operand_expr(&args[1])
-                .with_span(call_site_span)?;
+        let (mut stmts, label) = self.encode_pure_function_call_site(
+            location,
+            destination,
+            inhaled_expr
+        );
 
-            let expr = match bin_op {
-                vir::BinOpKind::EqCmp => snapshot.encode_equals(lhs, rhs, pos),
-                vir::BinOpKind::NeCmp => snapshot.encode_not_equals(lhs, rhs, pos),
-                _ => unreachable!()
-            };
+        self.encode_transfer_args_permissions(location, args, &mut stmts, label)?;
 
-            let target_value = self.encode_pure_function_call_lhs_value(destination)
-                .with_span(call_site_span)?;
-            let inhaled_expr = vir::Expr::eq_cmp(target_value.into(), expr);
+        Ok(stmts)
+    }
 
-            let (mut stmts, label) = self.encode_pure_function_call_site(
-                location,
-                destination,
-                inhaled_expr
-            );
+    fn encode_float_is_nan_call(
+        &mut self,
+        location: mir::Location,
+        call_site_span: Span,
+        args: &[mir::Operand<'tcx>],
+        destination: &Option<(mir::Place<'tcx>, BasicBlockIndex)>,
+    ) -> SpannedEncodingResult<Vec<vir::Stmt>> {
+        assert_eq!(args.len(), 1);
+        let encoded_arg = self.mir_encoder.encode_operand_expr(&args[0])
+            .with_span(call_site_span)?;
+        let size = match encoded_arg.get_type() {
+            &vir::Type::Float(size) => size,
+            typ => unreachable!("{}", typ),
+        };
+        let is_nan = vir::float_func_app(size, vir::FloatFunc::IsNan, vec![encoded_arg]);
 
-            self.encode_transfer_args_permissions(location, args,  &mut stmts, label)?;
+        let target_value = self.encode_pure_function_call_lhs_value(destination)
+            .with_span(call_site_span)?;
+        let inhaled_expr = vir::Expr::eq_cmp(target_value.into(), is_nan);
 
-            Ok(stmts)
+        let (mut stmts, label) = self.encode_pure_function_call_site(
+            location,
+            destination,
+            inhaled_expr
+        );
+
+        self.encode_transfer_args_permissions(location, args, &mut stmts, label)?;
+
+        Ok(stmts)
+    }
+
+    /// Is the type the future returned by a call of an `async fn`?
+    fn is_async_fn_future(&self, ty: ty::Ty<'tcx>) -> bool {
+        if let ty::TyKind::Opaque(def_id, _) = ty.kind() {
+            let env = self.encoder.env();
+            env.tcx().parent(*def_id).map_or(false, |parent| env.is_async_fn(parent))
         } else {
-            // the equality check involves some unsupported feature;
-            // treat it as any other function
-            debug!(
-                "The equality check of {:?} involves an unsupported feature ({:?})",
-                arg_ty,
-                snapshot_res.err().unwrap()
-            );
-            self.encode_impure_function_call(
-                location,
-                call_site_span,
-                args,
-                destination,
-                called_def_id,
-                None, // FIXME: This is almost definitely wrong.
-            )
+            false
         }
     }
 
-    /// Encode an edge of the MIR graph
-    fn encode_edge_block(
-        &mut self,
-        source: BasicBlockIndex,
-        destination: BasicBlockIndex,
-        force_block: bool,
-    ) -> SpannedEncodingResult<Option<CfgBlockIndex>> {
-        let source_loc = mir::Location {
-            block: source,
-            statement_index: self.mir[source].statements.len(),
+    /// The unique location that assigns to `local`, if there is exactly one.
+    fn find_unique_definition(&self, local: mir::Local) -> Option<mir::Location> {
+        let mut definitions = vec![];
+        for (block, data) in self.mir.basic_blocks().iter_enumerated() {
+            for (statement_index, stmt) in data.statements.iter().enumerate() {
+                if let mir::StatementKind::Assign(box (ref lhs, _)) = stmt.kind {
+                    if lhs.as_local() == Some(local) {
+                        definitions.push(mir::Location { block, statement_index });
+                    }
+                }
+            }
+            if let TerminatorKind::Call { destination: Some((ref lhs, _)), .. } =
+                data.terminator().kind
+            {
+                if lhs.as_local() == Some(local) {
+                    definitions.push(self.mir.terminator_loc(block));
+                }
+            }
+        }
+        if definitions.len() == 1 {
+            definitions.pop()
+        } else {
+            None
+        }
+    }
+
+    /// Find the call that created the future polled by an `.await`. The desugaring of
+    /// `.await` polls `Pin::new_unchecked(&mut future)`, where the future was moved out of
+    /// the result of the call.
+    fn find_awaited_call(&self, pinned_future: &mir::Operand<'tcx>) -> Option<mir::Location> {
+        let pin_location = self.find_unique_definition(pinned_future.place()?.as_local()?)?;
+        let pin_block = &self.mir[pin_location.block];
+        if pin_location.statement_index < pin_block.statements.len() {
+            return None;
+        }
+        let pin_arg = match pin_block.terminator().kind {
+            TerminatorKind::Call { ref args, .. } if args.len() == 1 => &args[0],
+            _ => return None,
         };
-        let destination_loc = mir::Location {
-            block: destination,
-            statement_index: 0,
+        let ref_location = self.find_unique_definition(pin_arg.place()?.as_local()?)?;
+        let mut future = match self.mir[ref_location.block].statements.get(ref_location.statement_index) {
+            Some(mir::Statement {
+                kind: mir::StatementKind::Assign(
+                    box (_, mir::Rvalue::Ref(_, mir::BorrowKind::Mut { .. }, ref place))
+                ),
+                ..
+            }) => place.as_local()?,
+            _ => return None,
         };
-        let stmts = self.encode_expiring_borrows_between(source_loc, destination_loc)?;
-
-        if force_block || !stmts.is_empty() {
-            let edge_label = self.cfg_method.get_fresh_label_name();
-            let edge_block = self.cfg_method.add_block(
-                &edge_label,
-                vec![
-                    vir::Stmt::comment(format!("========== {} ==========", edge_label)),
-                    vir::Stmt::comment(format!("MIR edge {:?} --> {:?}", source, destination)),
-                ],
-            );
-            if !stmts.is_empty() {
-                self.cfg_method
-                    .add_stmt(edge_block, vir::Stmt::comment("Expire borrows"));
-                self.cfg_method.add_stmts(edge_block, stmts);
+        for _ in 0..self.mir.local_decls.len() {
+            let location = self.find_unique_definition(future)?;
+            let data = &self.mir[location.block];
+            match data.statements.get(location.statement_index) {
+                Some(mir::Statement {
+                    kind: mir::StatementKind::Assign(
+                        box (_, mir::Rvalue::Use(mir::Operand::Move(ref place)))
+                    ),
+                    ..
+                }) => future = place.as_local()?,
+                Some(_) => return None,
+                None => return Some(location),
             }
-            Ok(Some(edge_block))
-        } else {
-            Ok(None)
+        }
+        None
+    }
+
+    /// Encode a call of `Future::poll` that awaits the future returned by a call of an
+    /// `async fn`. The future might be pending, in which case the awaiting body yields and
+    /// polls it again. When the future is ready, its result satisfies the postcondition of
+    /// the `async fn` with respect to the state in which it was called.
+    fn encode_await_call(
+        &mut self,
+        location: mir::Location,
+        call_site_span: Span,
+        args: &[mir::Operand<'tcx>],
+        destination: &Option<(mir::Place<'tcx>, BasicBlockIndex)>,
+    ) -> SpannedEncodingResult<Vec<vir::Stmt>> {
+        assert_eq!(args.len(), 2);
+        let creation_location = self.find_awaited_call(&args[0]).ok_or_else(||
+            SpannedEncodingError::unsupported(
+                "only futures that are awaited directly after calling an async function \
+                are supported",
+                call_site_span,
+            )
+        )?;
+        let (contract, fake_exprs) = self.procedure_contracts.get(&creation_location)
+            .cloned()
+            .ok_or_else(|| SpannedEncodingError::internal(
+                format!("the call that creates the future at {:?} is not encoded", creation_location),
+                call_site_span,
+            ))?;
+        let pre_label = self.label_after_location[&creation_location].clone();
+
+        let (ref target_place, _) = destination.as_ref().unwrap();
+        let (dst, dst_ty, _) = self.mir_encoder.encode_place(target_place)
+            .with_span(call_site_span)?;
+        let (adt_def, substs) = match dst_ty.kind() {
+            ty::TyKind::Adt(adt_def, substs) => (adt_def, substs),
+            _ => unreachable!("{:?}", dst_ty),
+        };
+        let tcx = self.encoder.env().tcx();
+        let (ready_index, ready_variant) = adt_def.variants.iter_enumerated()
+            .find(|(_, variant)| variant.ident.as_str() == "Ready")
+            .unwrap();
+        let output_ty = substs.type_at(0);
+        let discr_value: vir::Expr =
+            (adt_def.discriminant_for_variant(tcx, ready_index).val as i128).into();
+        let ready_field = self.encoder
+            .encode_struct_field(&ready_variant.fields[0].ident.as_str(), output_ty)
+            .with_span(call_site_span)?;
+        let encoded_result = dst.clone()
+            .variant(&ready_variant.ident.as_str())
+            .field(ready_field);
+
+        let is_ready = vir::Expr::eq_cmp(
+            self.encoder.encode_discriminant_func_app(dst.clone(), adt_def),
+            discr_value,
+        );
+        let mut stmts = self.encode_havoc_and_allocation(&dst);
+
+        // The arguments of the async function are expressed in the state of its call.
+        let encoded_args: Vec<vir::Expr> = contract
+            .args
+            .iter()
+            .map(|local| self.encode_prusti_local(*local).into())
+            .collect();
+        for typed_assertion in contract.functional_postcondition() {
+            let assertion = self.encoder.encode_assertion(
+                &typed_assertion,
+                &self.mir,
+                Some(&pre_label),
+                &encoded_args,
+                Some(&encoded_result),
+                false,
+                None,
+                ErrorCtxt::GenericExpression,
+            )?;
+            let mut assertion = self.wrap_arguments_into_old(
+                assertion,
+                &pre_label,
+                &contract,
+                &encoded_args,
+            )?;
+            for (fake_arg, arg_expr) in fake_exprs.iter() {
+                assertion = assertion.replace_place(fake_arg, arg_expr);
+            }
+            let assertion = SnapshotSpecPatcher::new(self.encoder)
+                .patch_spec(assertion)
+                .with_span(call_site_span)?;
+            stmts.push(vir::Stmt::Inhale(
+                vir::Expr::implies(is_ready.clone(), assertion),
+                vir::FoldingBehaviour::Expr,
+            ));
+        }
+
+        Ok(stmts)
+    }
+
+    /// Encode a call of `Index::index` that takes a sub-slice of a slice. The call is encoded
+    /// like any other call, but the elements of the resulting slice are known.
+    fn encode_subslice_call(
+        &mut self,
+        location: mir::Location,
+        call_site_span: Span,
+        args: &[mir::Operand<'tcx>],
+        destination: &Option<(mir::Place<'tcx>, BasicBlockIndex)>,
+        called_def_id: ProcedureDefId,
+        self_ty: Option<&'tcx ty::TyS<'tcx>>,
+    ) -> SpannedEncodingResult<Vec<vir::Stmt>> {
+        assert_eq!(args.len(), 2);
+        let slice_ref = self.mir_encoder.encode_operand_expr(&args[0])
+            .with_span(call_site_span)?;
+        let slice_ty = self.mir_encoder.get_operand_ty(&args[0]).builtin_deref(true).unwrap().ty;
+        let value_field = self.encoder.encode_value_field(slice_ty);
+        let seq = slice_ref.field(value_field.clone());
+        let len = vir::Expr::seq_len(seq.clone());
+
+        // The bounds of the range
+        let range_ty = self.mir_encoder.get_operand_ty(&args[1]);
+        let (range_name, index_ty) = match range_ty.kind() {
+            ty::TyKind::Adt(adt_def, substs) => (
+                self.encoder.get_item_name(adt_def.did),
+                substs.types().next(),
+            ),
+            _ => unreachable!("{:?}", range_ty),
+        };
+        let opt_range = self.mir_encoder.encode_operand_place(&args[1])
+            .with_span(call_site_span)?;
+        let encode_bound = |name: &str| -> SpannedEncodingResult<vir::Expr> {
+            let index_ty = index_ty.unwrap();
+            let field = self.encoder.encode_struct_field(name, index_ty)
+                .with_span(call_site_span)?;
+            let range = opt_range.clone().unwrap();
+            Ok(self.encoder.encode_value_expr(range.field(field), index_ty))
+        };
+        let (from, to) = match range_name.as_str() {
+            "std::ops::RangeFull" | "core::ops::RangeFull" => (0.into(), len.clone()),
+            "std::ops::Range" | "core::ops::Range" => (encode_bound("start")?, encode_bound("end")?),
+            "std::ops::RangeFrom" | "core::ops::RangeFrom" => (encode_bound("start")?, len.clone()),
+            "std::ops::RangeTo" | "core::ops::RangeTo" => (0.into(), encode_bound("end")?),
+            _ => {
+                return Err(SpannedEncodingError::unsupported(
+                    format!("sub-slicing with a range of type '{}' is not supported", range_name),
+                    call_site_span,
+                ));
+            }
+        };
+
+        let mut stmts = vec![];
+        let label = self.cfg_method.get_fresh_label_name();
+        stmts.push(vir::Stmt::Label(label.clone()));
+        if self.check_panics {
+            let pos = self.encoder.error_manager().register(
+                call_site_span,
+                ErrorCtxt::AssertTerminator("slice index out of range".to_string()),
+            );
+            stmts.push(vir::Stmt::Assert(
+                vir::Expr::and(
+                    vir::Expr::le_cmp(from.clone(), to.clone()),
+                    vir::Expr::le_cmp(to.clone(), len),
+                ),
+                vir::FoldingBehaviour::Stmt,
+                pos,
+            ));
+        }
+
+        stmts.extend(self.encode_impure_function_call(
+            location,
+            call_site_span,
+            args,
+            destination,
+            called_def_id,
+            self_ty,
+        )?);
+
+        // The elements of the sub-slice are the elements of the slice in the range.
+        let target_value = self.encode_pure_function_call_lhs_value(destination)
+            .with_span(call_site_span)?;
+        stmts.push(vir::Stmt::Inhale(
+            vir::Expr::eq_cmp(
+                target_value.field(value_field),
+                vir::Expr::seq_slice(seq, from, to).old(label),
+            ),
+            vir::FoldingBehaviour::Stmt,
+        ));
+
+        Ok(stmts)
+    }
+
+    /// Encode a call of `str::as_bytes`. The call is encoded like any other call, but the
+    /// resulting slice has the same bytes as the string slice.
+    fn encode_str_as_bytes_call(
+        &mut self,
+        location: mir::Location,
+        call_site_span: Span,
+        args: &[mir::Operand<'tcx>],
+        destination: &Option<(mir::Place<'tcx>, BasicBlockIndex)>,
+        called_def_id: ProcedureDefId,
+        self_ty: Option<&'tcx ty::TyS<'tcx>>,
+    ) -> SpannedEncodingResult<Vec<vir::Stmt>> {
+        assert_eq!(args.len(), 1);
+        let str_ref = self.mir_encoder.encode_operand_expr(&args[0])
+            .with_span(call_site_span)?;
+        let str_ty = self.mir_encoder.get_operand_ty(&args[0]).builtin_deref(true).unwrap().ty;
+        let value_field = self.encoder.encode_value_field(str_ty);
+        let bytes = str_ref.field(value_field.clone());
+
+        let mut stmts = vec![];
+        let label = self.cfg_method.get_fresh_label_name();
+        stmts.push(vir::Stmt::Label(label.clone()));
+
+        stmts.extend(self.encode_impure_function_call(
+            location,
+            call_site_span,
+            args,
+            destination,
+            called_def_id,
+            self_ty,
+        )?);
+
+        // `str` and `[u8]` share the same value field.
+        let target_value = self.encode_pure_function_call_lhs_value(destination)
+            .with_span(call_site_span)?;
+        stmts.push(vir::Stmt::Inhale(
+            vir::Expr::eq_cmp(target_value.field(value_field), bytes.old(label)),
+            vir::FoldingBehaviour::Stmt,
+        ));
+
+        Ok(stmts)
+    }
+
+    fn encode_cmp_function_call(
+        &mut self,
+        called_def_id: ProcedureDefId,
+        location: mir::Location,
+        call_site_span: Span,
+        args: &[mir::Operand<'tcx>],
+        destination: &Option<(mir::Place<'tcx>, BasicBlockIndex)>,
+        bin_op: vir::BinOpKind,
+    ) -> SpannedEncodingResult<Vec<vir::Stmt>> {
+        let arg_ty = self.mir_encoder.get_operand_ty(&args[0]);
+
+        let snapshot_res = self.encoder.encode_snapshot(&arg_ty);
+        if snapshot_res.is_ok() && snapshot_res.as_ref().unwrap().supports_equality() {
+            let snapshot = snapshot_res.unwrap();
+            let pos = self
+                .encoder
+                .error_manager()
+                .register(call_site_span, ErrorCtxt::PureFunctionCall);
+
+            let lhs = self.mir_encoder.encode_operand_expr(&args[0])
+                .with_span(call_site_span)?;
+            let rhs = self.mir_encoder.encode_operand_expr(&args[1])
+                .with_span(call_site_span)?;
+
+            let expr = match bin_op {
+                vir::BinOpKind::EqCmp => snapshot.encode_equals(lhs, rhs, pos),
+                vir::BinOpKind::NeCmp => snapshot.encode_not_equals(lhs, rhs, pos),
+                _ => unreachable!()
+            };
+
+            let target_value = self.encode_pure_function_call_lhs_value(destination)
+                .with_span(call_site_span)?;
+            let inhaled_expr = vir::Expr::eq_cmp(target_value.into(), expr);
+
+            let (mut stmts, label) = self.encode_pure_function_call_site(
+                location,
+                destination,
+                inhaled_expr
+            );
+
+            self.encode_transfer_args_permissions(location, args,  &mut stmts, label)?;
+
+            Ok(stmts)
+        } else {
+            // the equality check involves some unsupported feature;
+            // treat it as any other function
+            debug!(
+                "The equality check of {:?} involves an unsupported feature ({:?})",
+                arg_ty,
+                snapshot_res.err().unwrap()
+            );
+            self.encode_impure_function_call(
+                location,
+                call_site_span,
+                args,
+                destination,
+                called_def_id,
+                None, // FIXME: This is almost definitely wrong.
+            )
+        }
+    }
+
+    /// Encode an edge of the MIR graph
+    fn encode_edge_block(
+        &mut self,
+        source: BasicBlockIndex,
+        destination: BasicBlockIndex,
+        force_block: bool,
+    ) -> SpannedEncodingResult<Option<CfgBlockIndex>> {
+        let source_loc = mir::Location {
+            block: source,
+            statement_index: self.mir[source].statements.len(),
+        };
+        let destination_loc = mir::Location {
+            block: destination,
+            statement_index: 0,
+        };
+        let stmts = self.encode_expiring_borrows_between(source_loc, destination_loc)?;
+
+        if force_block || !stmts.is_empty() {
+            let edge_label = self.cfg_method.get_fresh_label_name();
+            let edge_block = self.cfg_method.add_block(
+                &edge_label,
+                vec![
+                    vir::Stmt::comment(format!("========== {} ==========", edge_label)),
+                    vir::Stmt::comment(format!("MIR edge {:?} --> {:?}", source, destination)),
+                ],
+            );
+            if !stmts.is_empty() {
+                self.cfg_method
+                    .add_stmt(edge_block, vir::Stmt::comment("Expire borrows"));
+                self.cfg_method.add_stmts(edge_block, stmts);
+            }
+            Ok(Some(edge_block))
+        } else {
+            Ok(None)
         }
     }
 
@@ -2374,6 +3027,8 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         let mut const_arg_vars: HashSet<vir::Expr> = HashSet::new();
         let mut type_invs: HashMap<String, vir::Function> = HashMap::new();
         let mut constant_args = vec![];
+        let mut str_literal_args = vec![];
+        let mut raw_pointer_aliases = vec![];
 
         for (mir_arg, arg, arg_ty, encoded_operand) in operands {
             arguments.push(arg.clone());
@@ -2388,10 +3043,37 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             type_invs.insert(inv_name, arg_inv);
 
             match encoded_operand {
+                Some(place) if place == arg_place
+                    && self.raw_pointer_sources.contains_key(&place) =>
+                {
+                    // The target of a raw pointer is owned by the place whose address was
+                    // copied into the argument, so the contract is expressed in terms of it.
+                    let source = self.raw_pointer_sources[&place].clone();
+                    let address = place.field(self.encoder.encode_value_field(arg_ty));
+                    debug!("arg: {} {}", address, source);
+                    raw_pointer_aliases.push(vir::Expr::eq_cmp(address.clone(), source.clone()));
+                    fake_exprs.insert(address, source);
+                }
                 Some(place) => {
                     debug!("arg: {} {}", arg_place, place);
                     fake_exprs.insert(arg_place, place.into());
                 }
+                None if self.mir_encoder.is_str_ref(arg_ty) => {
+                    // A string literal is stored in the new local variable before the call.
+                    if let mir::Operand::Constant(box mir::Constant {
+                        literal: ty::Const { val, .. }, ..
+                    }) = mir_arg {
+                        str_literal_args.push((arg_place, arg_ty, val));
+                    } else {
+                        return Err(SpannedEncodingError::unsupported(
+                            format!(
+                                "passing the string slice '{:?}' as an argument is not supported",
+                                mir_arg,
+                            ),
+                            call_site_span,
+                        ));
+                    }
+                }
                 None => {
                     // We have a constant.
                     constant_args.push(arg_place.clone());
@@ -2499,6 +3181,20 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         for constant_arg in &constant_args {
             stmts.extend(self.encode_havoc_and_allocation(constant_arg));
         }
+        for (arg_place, arg_ty, value) in str_literal_args {
+            stmts.extend(self.encode_assign_str_literal(&arg_place, arg_ty, value, location)?);
+        }
+        if !raw_pointer_aliases.is_empty() {
+            let alias_pos = self
+                .encoder
+                .error_manager()
+                .register(call_site_span, ErrorCtxt::ExhaleMethodPrecondition);
+            stmts.push(vir::Stmt::Assert(
+                raw_pointer_aliases.into_iter().conjoin(),
+                vir::FoldingBehaviour::Stmt,
+                alias_pos,
+            ));
+        }
 
         // Encode precondition.
         let (
@@ -2518,10 +3214,14 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             vir::FoldingBehaviour::Stmt, // TODO: Should be Expr.
             pos,
         ));
+        let type_inv_pos = self
+            .encoder
+            .error_manager()
+            .register(call_site_span, ErrorCtxt::AssertMethodPreconditionTypeInvariants);
         stmts.push(vir::Stmt::Assert(
             replace_fake_exprs(pre_invs_spec),
             vir::FoldingBehaviour::Stmt,
-            pos,
+            type_inv_pos,
         ));
         let pre_perm_spec = replace_fake_exprs(pre_type_spec.clone());
         assert!(!pos.is_default());
@@ -2551,6 +3251,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         // Havoc the content of the lhs, if there is one
         if let Some(ref target_place) = encoded_target {
             stmts.extend(self.encode_havoc(target_place));
+            self.forget_raw_pointer_sources(target_place);
         }
 
         // Store a label for permissions got back from the call
@@ -2605,6 +3306,12 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 vir::FoldingBehaviour::Stmt,
             ));
         }
+        if let Some(target) = &encoded_target {
+            let target_ty = self.locals.get_type(target_local);
+            if let Some(len_fact) = self.encode_array_len_fact(target.clone(), target_ty) {
+                stmts.push(vir::Stmt::Inhale(len_fact, vir::FoldingBehaviour::Stmt));
+            }
+        }
         for (from_place, to_place) in read_transfer {
             stmts.push(vir::Stmt::TransferPerm(
                 replace_fake_exprs(from_place),
@@ -2616,10 +3323,15 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             replace_fake_exprs(post_invs_spec),
             vir::FoldingBehaviour::Stmt,
         ));
-        stmts.push(vir::Stmt::Inhale(
-            replace_fake_exprs(post_func_spec),
-            vir::FoldingBehaviour::Expr,
-        ));
+        if self.encoder.env().is_async_fn(called_def_id) {
+            // The call only creates a future. The functional postcondition is inhaled when
+            // the future is awaited (see `encode_await_call`).
+        } else {
+            stmts.push(vir::Stmt::Inhale(
+                replace_fake_exprs(post_func_spec),
+                vir::FoldingBehaviour::Expr,
+            ));
+        }
 
         // Exhale the permissions that were moved into magic wands.
         assert!(!pos.is_default());
@@ -2856,11 +3568,18 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         -> SpannedEncodingResult<vir::Expr>
     {
         Ok(match self.locals.get_type(local).kind() {
-            ty::TyKind::RawPtr(ty::TypeAndMut {
-                ref ty,
-                mutbl: mutability,
-            })
-            | ty::TyKind::Ref(_, ref ty, mutability) => {
+            ty::TyKind::RawPtr(ty::TypeAndMut { ref ty, .. }) => {
+                // Only the pointer itself is owned; ownership of its target has to be
+                // specified explicitly with `own!`.
+                let encoded_local = self.encode_prusti_local(local);
+                let field = self.encoder.encode_dereference_field(ty)
+                    .with_span(
+                        self.mir_encoder.get_local_span(local.into())
+                    )?;
+                let place = vir::Expr::from(encoded_local).field(field);
+                vir::Expr::acc_permission(place, vir::PermAmount::Write)
+            }
+            ty::TyKind::Ref(_, ref ty, mutability) => {
                 // Use unfolded references.
                 let encoded_local = self.encode_prusti_local(local);
                 let field = self.encoder.encode_dereference_field(ty)
@@ -2945,6 +3664,14 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                     add(access1);
                     add(access2);
                 }
+                vir::Expr::FieldAccessPredicate(box place, _, pos)
+                    if self.mir_encoder.is_raw_pointer(self.locals.get_type(*local)) =>
+                {
+                    // The caller keeps its copy of a raw pointer, so the callee can only
+                    // read the address. This also means that the address does not need to be
+                    // wrapped into `old` in the postcondition.
+                    add(vir::Expr::FieldAccessPredicate(box place, vir::PermAmount::Read, pos));
+                }
                 _ => add(access),
             };
         }
@@ -2970,7 +3697,8 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 None,
                 ErrorCtxt::GenericExpression,
             )?;
-            func_spec.push(value);
+            // Ownership of the targets of raw pointers is transferred to the callee.
+            func_spec.push(split_ownership_assertions(value, &mut type_spec));
         }
         let precondition_spans = MultiSpan::from_spans(
             func_precondition
@@ -2984,13 +3712,15 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         );
 
         let mut invs_spec: Vec<vir::Expr> = vec![];
-        for arg in contract.args.iter() {
-            invs_spec.push(
-                self.encoder.encode_invariant_func_app(
-                    self.locals.get_type(*arg),
-                    self.encode_prusti_local(*arg).into(),
-                ).with_span(precondition_spans.clone())?
-            );
+        if self.encoder.is_type_invariant_boundary(contract.def_id) {
+            for arg in contract.args.iter() {
+                invs_spec.push(
+                    self.encoder.encode_invariant_func_app(
+                        self.locals.get_type(*arg),
+                        self.encode_prusti_local(*arg).into(),
+                    ).with_span(precondition_spans.clone())?
+                );
+            }
         }
 
         let precondition_weakening = precondition_weakening.map(|pw| {
@@ -3053,6 +3783,16 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             start_cfg_block,
             vir::Stmt::Inhale(invs_spec, vir::FoldingBehaviour::Stmt),
         );
+        for local in self.procedure_contract().args.clone() {
+            let encoded_arg: vir::Expr = self.encode_prusti_local(local).into();
+            let arg_ty = self.locals.get_type(local);
+            if let Some(len_fact) = self.encode_array_len_fact(encoded_arg, arg_ty) {
+                self.cfg_method.add_stmt(
+                    start_cfg_block,
+                    vir::Stmt::Inhale(len_fact, vir::FoldingBehaviour::Stmt),
+                );
+            }
+        }
         // Weakening assertion must be put before inhaling the precondition, otherwise the weakening
         // soundness check becomes trivially satisfied.
         if let Some(weakening_spec) = weakening_spec {
@@ -3210,6 +3950,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
     /// Wrap function arguments used in the postcondition into ``old``:
     ///
     /// +   For references wrap the base ``_1.var_ref``.
+    /// +   For raw pointers do not wrap anything.
     /// +   For non-references wrap the entire place into old.
     fn wrap_arguments_into_old(
         &self,
@@ -3220,7 +3961,10 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
     ) -> SpannedEncodingResult<vir::Expr> {
         for (encoded_arg, &arg) in encoded_args.iter().zip(&contract.args) {
             let ty = self.locals.get_type(arg);
-            if self.mir_encoder.is_reference(ty) {
+            if self.mir_encoder.is_raw_pointer(ty) {
+                // The callee cannot change a raw pointer argument (see
+                // `encode_precondition_expr`), so its target is the same in both states.
+            } else if self.mir_encoder.is_reference(ty) {
                 // If the argument is a reference, we wrap _1.val_ref into old.
                 let arg_span = self.mir_encoder.get_local_span(arg.into());
                 let (encoded_deref, ..) = self.mir_encoder
@@ -3277,6 +4021,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         let mut read_transfer = vec![]; // Permissions taken as read
                                         // references that need to
                                         // be transfered to old.
+        let check_invariants = self.encoder.is_type_invariant_boundary(contract.def_id);
 
         // Encode the permissions got back and invariants for the arguments of type reference
         for (place, mutability) in contract.returned_refs.iter() {
@@ -3303,12 +4048,14 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 }
                 Mutability::Mut => {
                     add_type_spec(vir::PermAmount::Write);
-                    let inv = self
-                        .encoder
-                        .encode_invariant_func_app(place_ty, old_place_expr)
-                        // TODO: Use a better span
-                        .with_span(self.mir.span)?;
-                    invs_spec.push(inv);
+                    if check_invariants {
+                        let inv = self
+                            .encoder
+                            .encode_invariant_func_app(place_ty, old_place_expr)
+                            // TODO: Use a better span
+                            .with_span(self.mir.span)?;
+                        invs_spec.push(inv);
+                    }
                 }
             };
         }
@@ -3350,10 +4097,9 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         }
 
         // Encode permissions for return type
-        // TODO: Clean-up: remove unnecessary Option.
-        let return_perm = Some(
+        let mut return_perm = vec![
             self.encode_local_variable_permission(contract.returned_value)?
-        );
+        ];
 
         // Encode functional specification
         let mut func_spec = vec![];
@@ -3377,19 +4123,23 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 contract,
                 &encoded_args
             )?;
-            func_spec.push(assertion);
+            // Ownership of the targets of raw pointers is given back to the caller. It may
+            // depend on the returned pointer, so it comes after the permission of the result.
+            func_spec.push(split_ownership_assertions(assertion, &mut return_perm));
         }
         let postcondition_span = MultiSpan::from_spans(func_spec_spans);
         let func_spec_pos = self.encoder.error_manager()
             .register_span(postcondition_span.clone());
 
         // Encode invariant for return value
-        invs_spec.push(
-            self.encoder.encode_invariant_func_app(
-                self.locals.get_type(contract.returned_value),
-                encoded_return.clone(),
-            ).with_span(postcondition_span.clone())?
-        );
+        if check_invariants {
+            invs_spec.push(
+                self.encoder.encode_invariant_func_app(
+                    self.locals.get_type(contract.returned_value),
+                    encoded_return.clone(),
+                ).with_span(postcondition_span.clone())?
+            );
+        }
 
         // Encode possible strengthening, in case of trait method implementation
         let strengthening_spec: Option<Expr> = postcondition_strengthening
@@ -3434,7 +4184,8 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
 
         Ok((
             type_spec.into_iter().conjoin(),
-            return_perm,
+            // TODO: Clean-up: remove unnecessary Option.
+            Some(return_perm.into_iter().conjoin()),
             invs_spec.into_iter().conjoin(),
             full_func_spec,
             magic_wands,
@@ -3582,7 +4333,9 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             for arg_index in self.mir.args_iter() {
                 let arg_ty = self.mir.local_decls[arg_index].ty;
                 let arg_span = self.mir_encoder.get_local_span(arg_index);
-                if self.mir_encoder.is_reference(arg_ty) {
+                if self.mir_encoder.is_reference(arg_ty)
+                    && !self.mir_encoder.is_raw_pointer(arg_ty)
+                {
                     let encoded_arg = self.mir_encoder.encode_local(arg_index)?;
                     let (deref_place, ..) =
                         self.mir_encoder.encode_deref(encoded_arg.into(), arg_ty)
@@ -3711,7 +4464,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 continue;
             }
             let ty = self.locals.get_type(arg);
-            if self.mir_encoder.is_reference(ty) {
+            if self.mir_encoder.is_reference(ty) && !self.mir_encoder.is_raw_pointer(ty) {
                 let encoded_arg: vir::Expr = self.encode_prusti_local(arg).into();
                 let arg_span = self.mir_encoder.get_local_span(arg.into());
                 let (encoded_deref, ..) = self.mir_encoder
@@ -3769,7 +4522,9 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         );
         let ty = self.locals.get_type(contract.returned_value);
         let encoded_return: vir::Expr = self.encode_prusti_local(contract.returned_value).into();
-        let encoded_return_expr = if self.mir_encoder.is_reference(ty) {
+        let encoded_return_expr = if self.mir_encoder.is_reference(ty)
+            && !self.mir_encoder.is_raw_pointer(ty)
+        {
             let return_span = self.mir_encoder.get_local_span(
                 contract.returned_value.into()
             );
@@ -4012,8 +4767,22 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                             }
                         }
                         match ty.kind() {
-                            ty::TyKind::RawPtr(ty::TypeAndMut { ref ty, mutbl })
-                            | ty::TyKind::Ref(_, ref ty, mutbl) => {
+                            ty::TyKind::RawPtr(ty::TypeAndMut { ref ty, .. }) => {
+                                // The target of a raw pointer is not owned by the pointer.
+                                let field = self.encoder.encode_dereference_field(ty)?;
+                                let field_place = vir::Expr::from(encoded_place).field(field);
+                                permissions.push(vir::Expr::acc_permission(
+                                    field_place.clone(),
+                                    perm_amount,
+                                ));
+                                if def_init {
+                                    equalities.push(self.construct_value_preserving_equality(
+                                        loop_head,
+                                        &field_place,
+                                    ));
+                                }
+                            }
+                            ty::TyKind::Ref(_, ref ty, mutbl) => {
                                 debug!(
                                     "encode_loop_invariant_permissions \
                                      mir_place={:?} mutability={:?} \
@@ -4084,20 +4853,260 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         Ok((permissions, equalities))
     }
 
+    /// Check that the code of the `ghost!` blocks can be erased without changing the behaviour
+    /// of the program: it can only modify the locals declared in ghost blocks, it must
+    /// terminate, so it cannot contain loops or calls to impure functions, and it must not
+    /// jump out of its block with `break`, `continue`, `return` or `?`.
+    fn check_ghost_code(&self) -> SpannedEncodingResult<()> {
+        let mir = self.mir;
+        let is_ghost_local = |local: mir::Local| {
+            self.procedure.is_ghost_span(mir.local_decls[local].source_info.span)
+        };
+        for bbi in self.procedure.get_reachable_nonspec_cfg_blocks() {
+            let bb_data = &mir[bbi];
+            let term = bb_data.terminator();
+            let span = term.source_info.span;
+            // The code written in a ghost block is always left through the marker at its end,
+            // which belongs to the block as well. A `return` also assigns the result, so this is
+            // checked before the statements.
+            if self.procedure.is_ghost_span(span) && !self.procedure.is_ghost_marker_span(span) {
+                let leaves_ghost_code = term.successors().any(|&target| {
+                    let target_data = &mir[target];
+                    let target_span = target_data.statements.first()
+                        .map(|stmt| stmt.source_info.span)
+                        .unwrap_or_else(|| target_data.terminator().source_info.span);
+                    !target_data.is_cleanup && !self.procedure.is_ghost_span(target_span)
+                });
+                if leaves_ghost_code {
+                    return Err(SpannedEncodingError::incorrect(
+                        "ghost code cannot jump out of its block",
+                        span,
+                    ));
+                }
+            }
+            for stmt in &bb_data.statements {
+                let span = stmt.source_info.span;
+                if !self.procedure.is_ghost_span(span) {
+                    continue;
+                }
+                if let mir::StatementKind::Assign(box (ref lhs, ref rhs)) = stmt.kind {
+                    let modifies_non_ghost = !is_ghost_local(lhs.local) || match rhs {
+                        mir::Rvalue::Ref(_, mir::BorrowKind::Mut { .. }, place) |
+                        mir::Rvalue::Use(mir::Operand::Move(place)) => !is_ghost_local(place.local),
+                        _ => false,
+                    };
+                    if modifies_non_ghost {
+                        return Err(SpannedEncodingError::incorrect(
+                            "ghost code cannot modify non-ghost state",
+                            span,
+                        ));
+                    }
+                }
+            }
+            if !self.procedure.is_ghost_span(span) {
+                continue;
+            }
+            if self.loop_encoder.loops().is_loop_head(bbi) {
+                return Err(SpannedEncodingError::incorrect(
+                    "ghost code must terminate, so it cannot contain loops",
+                    span,
+                ));
+            }
+            if let mir::TerminatorKind::Call { ref func, ref destination, .. } = term.kind {
+                if let ty::TyKind::FnDef(def_id, _) = self.mir_encoder.get_operand_ty(func).kind() {
+                    if !self.encoder.is_pure(*def_id) {
+                        return Err(SpannedEncodingError::incorrect(
+                            "ghost code can only call pure functions",
+                            span,
+                        ));
+                    }
+                }
+                if let Some((place, _)) = destination {
+                    if !is_ghost_local(place.local) {
+                        return Err(SpannedEncodingError::incorrect(
+                            "ghost code cannot modify non-ghost state",
+                            span,
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Encode the `prusti_assert!` and `prusti_assume!` statements that are type-checked in
+    /// the specification blocks starting from `spec_block`.
+    fn encode_specification_statements(
+        &mut self,
+        spec_block: BasicBlockIndex,
+        location: mir::Location,
+    ) -> SpannedEncodingResult<Vec<vir::Stmt>> {
+        let (closures, labels) = self.collect_specification_statements(spec_block);
+
+        let mut stmts = vec![];
+        for (label, span) in labels {
+            // The blocks of a loop are encoded more than once, which would declare the
+            // label more than once.
+            if self.loop_encoder.get_loop_depth(location.block) > 0 {
+                return Err(SpannedEncodingError::unsupported(
+                    "labels cannot be declared inside loops; use `old[loop_entry](..)` in \
+                    the loop invariant to refer to the state before the loop",
+                    span,
+                ));
+            }
+            stmts.push(vir::Stmt::Label(format!("{}{}", USER_LABEL_PREFIX, label)));
+        }
+        if closures.is_empty() {
+            return Ok(stmts);
+        }
+        let encoded_args: Vec<vir::Expr> = self
+            .mir
+            .args_iter()
+            .map(|local| self.mir_encoder.encode_local(local).map(|l| l.into()))
+            .collect::<Result<Vec<_>, _>>()?;
+        for (spec, span) in closures {
+            let (assertion, is_assumption) = match spec {
+                typed::SpecificationSet::Assertion(assertion) => (assertion, false),
+                typed::SpecificationSet::Assumption(assertion) => (assertion, true),
+                _ => unreachable!("unexpected statement specification: {:?}", spec),
+            };
+            let encoded_spec = self.encoder.encode_assertion(
+                &assertion,
+                &self.mir,
+                Some(PRECONDITION_LABEL),
+                &encoded_args,
+                None,
+                false,
+                Some(location.block),
+                ErrorCtxt::GenericExpression,
+            )?;
+            if encoded_spec.contains_old_label(LOOP_ENTRY_LABEL) {
+                return Err(SpannedEncodingError::incorrect(
+                    "`old[loop_entry](..)` can only be used in loop invariants",
+                    span,
+                ));
+            }
+            self.check_user_labels(&encoded_spec, location.block, span)?;
+            let spec_spans = typed::Spanned::get_spans(&assertion, &self.mir, self.encoder.env().tcx());
+            let spec_pos = self.encoder.error_manager().register_span(spec_spans);
+            let encoded_spec = encoded_spec.set_default_pos(spec_pos);
+            if is_assumption {
+                stmts.push(vir::Stmt::comment("prusti_assume!"));
+                stmts.push(vir::Stmt::Inhale(encoded_spec, vir::FoldingBehaviour::Expr));
+            } else {
+                let pos = self.encoder.error_manager().register(span, ErrorCtxt::PrustiAssert);
+                stmts.push(vir::Stmt::comment("prusti_assert!"));
+                stmts.push(vir::Stmt::Assert(encoded_spec, vir::FoldingBehaviour::Expr, pos));
+            }
+        }
+        Ok(stmts)
+    }
+
+    /// Collect the `prusti_assert!`, `prusti_assume!` and `prusti_label!` statements that are
+    /// type-checked in the specification blocks starting from `spec_block`.
+    fn collect_specification_statements(
+        &self,
+        spec_block: BasicBlockIndex,
+    ) -> (Vec<(typed::SpecificationSet<'tcx>, Span)>, Vec<(String, Span)>) {
+        // `prusti_assert!(..)` and `prusti_assume!(..)` are desugared to closures with
+        // special attributes, which we can detect and use to retrieve the specification.
+        // The same holds for the labels declared with `prusti_label!(..)`.
+        let mir = self.mir;
+        let mut closures = vec![];
+        let mut labels = vec![];
+        let mut visited = HashSet::new();
+        let mut to_visit = vec![spec_block];
+        while let Some(bbi) = to_visit.pop() {
+            if !self.procedure.is_spec_block(bbi) || !visited.insert(bbi) {
+                continue;
+            }
+            for stmt in &mir[bbi].statements {
+                if let mir::StatementKind::Assign(box (
+                    _,
+                    mir::Rvalue::Aggregate(box mir::AggregateKind::Closure(cl_def_id, _), _),
+                )) = stmt.kind {
+                    if let Some(spec) = self.encoder.get_statement_specs(cl_def_id) {
+                        closures.push((spec, stmt.source_info.span));
+                    }
+                    let attrs = self.encoder.env().tcx().get_attrs(cl_def_id);
+                    if let Some(label) = utils::read_prusti_attr("label", attrs) {
+                        labels.push((label, stmt.source_info.span));
+                    }
+                }
+            }
+            to_visit.extend(mir[bbi].terminator().successors().cloned());
+        }
+        (closures, labels)
+    }
+
+    /// Find the block at whose end each label declared with `prusti_label!(..)` is placed,
+    /// which is the block that branches to the specification blocks of the label.
+    fn collect_user_label_blocks(
+        &self,
+    ) -> SpannedEncodingResult<HashMap<String, BasicBlockIndex>> {
+        let mut label_blocks = HashMap::new();
+        for bbi in self.mir.basic_blocks().indices() {
+            if !self.procedure.is_reachable_block(bbi) || self.procedure.is_spec_block(bbi) {
+                continue;
+            }
+            for &succ in self.mir[bbi].terminator().successors() {
+                if !self.procedure.is_spec_block(succ) {
+                    continue;
+                }
+                for (label, span) in self.collect_specification_statements(succ).1 {
+                    if label_blocks.insert(label.clone(), bbi).is_some() {
+                        return Err(SpannedEncodingError::incorrect(
+                            format!("the label `{}` is declared more than once", label),
+                            span,
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(label_blocks)
+    }
+
+    /// Check that the labels used by `old[label](..)` in a specification that holds at the
+    /// end of `block` are declared on all paths to it.
+    fn check_user_labels<S: Into<MultiSpan> + Clone>(
+        &self,
+        encoded_spec: &vir::Expr,
+        block: BasicBlockIndex,
+        span: S,
+    ) -> SpannedEncodingResult<()> {
+        let dominators = self.mir.dominators();
+        for (label, &label_block) in &self.user_label_blocks {
+            let encoded_label = format!("{}{}", USER_LABEL_PREFIX, label);
+            if encoded_spec.contains_old_label(&encoded_label)
+                && !dominators.is_dominated_by(block, label_block)
+            {
+                return Err(SpannedEncodingError::incorrect(
+                    format!(
+                        "the label `{}` might not have been reached at this point; a label can \
+                        only be used after it is declared on all paths",
+                        label,
+                    ),
+                    span.clone(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
     /// Get the basic blocks that encode the specification of a loop invariant
     fn get_loop_spec_blocks(&self, loop_head: BasicBlockIndex) -> Vec<BasicBlockIndex> {
         let mut res = vec![];
         for bbi in self.procedure.get_reachable_cfg_blocks() {
             if Some(loop_head) == self.loop_encoder.get_loop_head(bbi)
-                && self.procedure.is_spec_block(bbi)
+                && self.procedure.is_loop_invariant_block(bbi)
             {
                 res.push(bbi)
             } else {
                 debug!(
-                    "bbi {:?} has head {:?} and 'is spec' is {}",
+                    "bbi {:?} has head {:?} and 'is loop invariant' is {}",
                     bbi,
                     self.loop_encoder.get_loop_head(bbi),
-                    self.procedure.is_spec_block(bbi)
+                    self.procedure.is_loop_invariant_block(bbi)
                 );
             }
         }
@@ -4153,6 +5162,20 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                     ErrorCtxt::GenericExpression,
                 )?;
                 let spec_spans = typed::Spanned::get_spans(assertion, &self.mir, self.encoder.env().tcx());
+                // The labels that the invariant uses must be declared before the loop.
+                self.check_user_labels(
+                    &encoded_spec,
+                    loop_head,
+                    MultiSpan::from_spans(spec_spans.clone()),
+                )?;
+                let loop_entry_label = &self.loop_entry_labels[&loop_head];
+                let encoded_spec = encoded_spec.map_old_expr_label(|label| {
+                    if label == LOOP_ENTRY_LABEL {
+                        loop_entry_label.clone()
+                    } else {
+                        label
+                    }
+                });
                 let spec_pos = self
                     .encoder
                     .error_manager()
@@ -4166,6 +5189,134 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         Ok((encoded_specs, MultiSpan::from_spans(encoded_spec_spans)))
     }
 
+    /// Encode the loop variant of a loop, if any. Each element of the returned vector
+    /// encodes a component of the lexicographic measure.
+    fn encode_loop_variant_specs(
+        &self,
+        loop_head: BasicBlockIndex,
+        loop_inv_block: BasicBlockIndex,
+    ) -> SpannedEncodingResult<Option<(Vec<vir::Expr>, MultiSpan)>> {
+        let spec_blocks = self.get_loop_spec_blocks(loop_head);
+
+        // `body_variant!(..)` is desugared to a closure with special attributes,
+        // like `body_invariant!(..)`.
+        let mut variants = vec![];
+        for bbi in spec_blocks {
+            for stmt in &self.mir.basic_blocks()[bbi].statements {
+                if let mir::StatementKind::Assign(box (
+                    _,
+                    mir::Rvalue::Aggregate(box mir::AggregateKind::Closure(cl_def_id, _), _),
+                )) = stmt.kind {
+                    variants.extend(self.encoder.get_loop_specs(cl_def_id).unwrap().variant);
+                }
+            }
+        }
+        trace!("variants: {:?}", variants);
+
+        let variant = match variants.len() {
+            0 => return Ok(None),
+            1 => variants.pop().unwrap(),
+            _ => {
+                let spans: Vec<_> = variants.iter()
+                    .flat_map(|variant| variant.terms())
+                    .flat_map(|term| typed::Spanned::get_spans(term, &self.mir, self.encoder.env().tcx()))
+                    .collect();
+                return Err(SpannedEncodingError::incorrect(
+                    "a loop can have at most one loop variant",
+                    MultiSpan::from_spans(spans),
+                ));
+            }
+        };
+
+        let encoded_args: Vec<vir::Expr> = self
+            .mir
+            .args_iter()
+            .map(|local| self.mir_encoder.encode_local(local).map(|l| l.into()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let encoded_terms = encode_spec_measure(
+            self.encoder,
+            &variant,
+            &encoded_args,
+            false,
+            Some(loop_inv_block),
+        )?;
+        let spans: Vec<_> = variant.terms()
+            .iter()
+            .flat_map(|term| typed::Spanned::get_spans(term, &self.mir, self.encoder.env().tcx()))
+            .collect();
+        trace!("encoded_variant: {:?}", encoded_terms);
+        Ok(Some((encoded_terms, MultiSpan::from_spans(spans))))
+    }
+
+    /// Check that the loop variant is non-negative at the beginning of a loop iteration and
+    /// store its value in `variant_vars`.
+    fn encode_loop_variant_on_entry_stmts(
+        &mut self,
+        loop_head: BasicBlockIndex,
+        variant: &[vir::Expr],
+        variant_span: &MultiSpan,
+        variant_vars: &mut Vec<vir::LocalVar>,
+    ) -> Vec<vir::Stmt> {
+        let pos = self.encoder.error_manager().register(
+            variant_span.clone(),
+            ErrorCtxt::AssertLoopVariantNonNegative,
+        );
+        let mut stmts = vec![vir::Stmt::comment(format!(
+            "Assert and store the loop variant (loop head: {:?})",
+            loop_head
+        ))];
+        stmts.push(vir::Stmt::Assert(
+            variant.iter()
+                .map(|term| vir::Expr::le_cmp(0.into(), term.clone()))
+                .conjoin(),
+            vir::FoldingBehaviour::Expr,
+            pos,
+        ));
+        for term in variant {
+            let var = self.cfg_method.add_fresh_local_var(vir::Type::Int);
+            stmts.push(vir::Stmt::Assign(
+                var.clone().into(),
+                term.clone(),
+                vir::AssignKind::Copy,
+            ));
+            variant_vars.push(var);
+        }
+        stmts
+    }
+
+    /// Check that, after a loop iteration, the loop variant is lexicographically smaller than
+    /// the value stored in `variant_vars` at the beginning of the iteration.
+    fn encode_loop_variant_after_iteration_stmts(
+        &self,
+        loop_head: BasicBlockIndex,
+        variant: &[vir::Expr],
+        variant_span: &MultiSpan,
+        variant_vars: &[vir::LocalVar],
+    ) -> Vec<vir::Stmt> {
+        let pos = self.encoder.error_manager().register(
+            variant_span.clone(),
+            ErrorCtxt::AssertLoopVariantAfterIteration,
+        );
+        let mut terms = variant.iter().cloned()
+            .zip(variant_vars.iter().cloned().map(vir::Expr::local))
+            .rev();
+        let (last_term, last_old_term) = terms.next().unwrap();
+        let decreases = terms.fold(
+            vir::Expr::lt_cmp(last_term, last_old_term),
+            |rest, (term, old_term)| vir::Expr::or(
+                vir::Expr::lt_cmp(term.clone(), old_term.clone()),
+                vir::Expr::and(vir::Expr::eq_cmp(term, old_term), rest),
+            ),
+        );
+        vec![
+            vir::Stmt::comment(format!(
+                "Assert the decrease of the loop variant (loop head: {:?})",
+                loop_head
+            )),
+            vir::Stmt::Assert(decreases, vir::FoldingBehaviour::Expr, pos),
+        ]
+    }
+
     fn encode_loop_invariant_exhale_stmts(
         &mut self,
         loop_head: BasicBlockIndex,
@@ -4429,10 +5580,50 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         );
         let span = self.mir_encoder.get_span_of_location(location);
         let stmts = match operand {
+            mir::Operand::Move(ref place) | mir::Operand::Copy(ref place)
+                if utils::is_array_elem_place(place) =>
+            {
+                // The elements of arrays are values, not places.
+                let ty = self.mir_encoder.get_operand_ty(operand);
+                let encoded_value = self.mir_encoder.encode_operand_expr(operand)
+                    .with_span(span)?;
+                match ty.kind() {
+                    ty::TyKind::Adt(_, _) | ty::TyKind::Tuple(_)
+                        if !self.encoder.is_ghost_type(ty) =>
+                    {
+                        // The element is a snapshot, from which the target is constructed.
+                        let snapshot = self.encoder.encode_snapshot(ty).with_span(span)?;
+                        let mut stmts = self.encode_havoc_and_allocation(lhs);
+                        stmts.push(vir::Stmt::Inhale(
+                            vir::Expr::eq_cmp(snapshot.snap_call(lhs.clone()), encoded_value),
+                            vir::FoldingBehaviour::Stmt,
+                        ));
+                        stmts
+                    }
+                    _ => self.encode_copy_value_assign(lhs.clone(), encoded_value, ty, location)?,
+                }
+            }
+
+            mir::Operand::Move(ref place) | mir::Operand::Copy(ref place)
+                if self.mir_encoder.is_raw_pointer(self.mir_encoder.get_operand_ty(operand)) =>
+            {
+                let (src, ty, _) = self.mir_encoder.encode_place(place).with_span(span)?;
+                let field = self.encoder.encode_value_field(ty);
+                let mut stmts = self.encode_copy_raw_pointer(lhs, src.field(field), ty, location)?;
+
+                // Store a label for this state
+                let label = self.cfg_method.get_fresh_label_name();
+                debug!("Current loc {:?} has label {}", location, label);
+                self.label_after_location.insert(location, label.clone());
+                stmts.push(vir::Stmt::Label(label.clone()));
+
+                stmts
+            }
+
             mir::Operand::Move(ref place) => {
                 let (src, ty, _) = self.mir_encoder.encode_place(place).with_span(span)?;
                 let mut stmts = match ty.kind() {
-                    ty::TyKind::RawPtr(..) | ty::TyKind::Ref(..) => {
+                    ty::TyKind::Ref(..) => {
                         // Reborrow.
                         let field = self.encoder.encode_value_field(ty);
                         let mut alloc_stmts = self.prepare_assign_target(
@@ -4508,6 +5699,8 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                     // Since we have a ZST, we do not need to do anything to
                     // encode it.
                     Vec::new()
+                } else if self.mir_encoder.is_str_ref(*ty) {
+                    self.encode_assign_str_literal(lhs, *ty, val, location)?
                 } else {
                     // We expect to have a constant of a primitive type here.
                     let field = self.encoder.encode_value_field(ty);
@@ -4728,7 +5921,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             .with_span(
                 self.mir_encoder.get_span_of_location(location)
             )?;
-        let encoded_value = self.mir_encoder.encode_unary_op_expr(op, encoded_val);
+        let encoded_value = self.mir_encoder.encode_unary_op_expr(op, encoded_val, ty);
         // Initialize `lhs.field`
         self.encode_copy_value_assign(encoded_lhs, encoded_value, ty, location)
     }
@@ -4833,6 +6026,135 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         Ok(stmts)
     }
 
+    /// Encode an assignment to an element of an array or slice as an update of the sequence
+    /// that stores the elements.
+    fn encode_assign_array_elem(
+        &mut self,
+        lhs: &mir::Place<'tcx>,
+        rhs: &mir::Rvalue<'tcx>,
+        location: mir::Location,
+    ) -> SpannedEncodingResult<Vec<vir::Stmt>> {
+        trace!(
+            "[enter] encode_assign_array_elem(lhs={:?}, rhs={:?}, location={:?})",
+            lhs,
+            rhs,
+            location
+        );
+        let span = self.mir_encoder.get_span_of_location(location);
+        let operand = if let mir::Rvalue::Use(ref operand) = rhs {
+            operand
+        } else {
+            return Err(SpannedEncodingError::unsupported(
+                format!("assigning '{:?}' to an element of an array is not supported", rhs),
+                span,
+            ));
+        };
+        let encoded_value = self.mir_encoder.encode_array_elem_operand(operand).with_span(span)?;
+        let (seq, updated_seq) = self.mir_encoder.encode_array_elem_update(lhs, encoded_value)
+            .with_span(span)?;
+        Ok(vec![vir::Stmt::Assign(seq, updated_seq, vir::AssignKind::Copy)])
+    }
+
+    /// Encode the length of an array or slice. The length of an array is also known from its
+    /// type, so it can be assumed.
+    fn encode_assign_len(
+        &mut self,
+        place: &mir::Place<'tcx>,
+        encoded_lhs: vir::Expr,
+        ty: ty::Ty<'tcx>,
+        location: mir::Location,
+    ) -> SpannedEncodingResult<Vec<vir::Stmt>> {
+        let span = self.mir_encoder.get_span_of_location(location);
+        let (len, array_len) = self.mir_encoder.encode_array_len(place).with_span(span)?;
+        let mut stmts = vec![];
+        if let Some(array_len) = array_len {
+            stmts.push(vir::Stmt::Inhale(
+                vir::Expr::eq_cmp(len.clone(), array_len.into()),
+                vir::FoldingBehaviour::Stmt,
+            ));
+        }
+        stmts.extend(self.encode_copy_value_assign(encoded_lhs, len, ty, location)?);
+        Ok(stmts)
+    }
+
+    /// Encode the assignment of a string literal. The target points to a fresh string slice
+    /// whose bytes are those of the literal.
+    fn encode_assign_str_literal(
+        &mut self,
+        lhs: &vir::Expr,
+        ty: ty::Ty<'tcx>,
+        value: &ty::ConstKind<'tcx>,
+        location: mir::Location,
+    ) -> SpannedEncodingResult<Vec<vir::Stmt>> {
+        let span = self.mir_encoder.get_span_of_location(location);
+        let bytes = self.encoder.encode_str_literal_bytes(value).with_span(span)?;
+        let str_ty = self.encoder.env().tcx().mk_str();
+        let value_field = self.encoder.encode_value_field(str_ty);
+        let mut stmts = self.encode_havoc_and_allocation(lhs);
+        stmts.push(vir::Stmt::Inhale(
+            vir::Expr::eq_cmp(
+                self.encoder.encode_value_expr(lhs.clone(), ty).field(value_field),
+                bytes,
+            ),
+            vir::FoldingBehaviour::Stmt,
+        ));
+        Ok(stmts)
+    }
+
+    /// The length of an array is known from its type. Returns this length as a fact about
+    /// the array stored in `place`, or referenced by `place`, if `ty` is such a type.
+    fn encode_array_len_fact(&self, place: vir::Expr, ty: ty::Ty<'tcx>) -> Option<vir::Expr> {
+        let (array, array_ty, size) = match ty.kind() {
+            ty::TyKind::Array(_, size) => (place, ty, size),
+            ty::TyKind::Ref(_, target_ty, _) => match target_ty.kind() {
+                ty::TyKind::Array(_, size) => {
+                    (place.field(self.encoder.encode_value_field(ty)), *target_ty, size)
+                }
+                _ => return None,
+            },
+            _ => return None,
+        };
+        let array_len = compute_array_len(self.encoder.env().tcx(), size);
+        let seq = self.encoder.encode_value_expr(array, array_ty);
+        Some(vir::Expr::eq_cmp(vir::Expr::seq_len(seq), array_len.into()))
+    }
+
+    /// Encode an array `[operand; count]` whose elements are all equal to `operand`.
+    fn encode_assign_repeat(
+        &mut self,
+        operand: &mir::Operand<'tcx>,
+        count: &ty::Const<'tcx>,
+        encoded_lhs: vir::Expr,
+        ty: ty::Ty<'tcx>,
+        location: mir::Location,
+    ) -> SpannedEncodingResult<Vec<vir::Stmt>> {
+        let span = self.mir_encoder.get_span_of_location(location);
+        let encoded_value = self.mir_encoder.encode_array_elem_operand(operand).with_span(span)?;
+        let len = compute_array_len(self.encoder.env().tcx(), count);
+        let mut stmts = self.encode_havoc_and_allocation(&encoded_lhs);
+        let seq = encoded_lhs.field(self.encoder.encode_value_field(ty));
+        // The name of the index must not capture a local variable of the element value.
+        let index = vir::LocalVar::new(self.cfg_method.get_fresh_bound_var_name(), vir::Type::Int);
+        let elem = vir::Expr::seq_index(seq.clone(), vir::Expr::local(index.clone()));
+        let index_in_bounds = vir::Expr::and(
+            vir::Expr::le_cmp(0.into(), vir::Expr::local(index.clone())),
+            vir::Expr::lt_cmp(vir::Expr::local(index.clone()), len.into()),
+        );
+        stmts.push(vir::Stmt::Inhale(
+            vir::Expr::eq_cmp(vir::Expr::seq_len(seq), len.into()),
+            vir::FoldingBehaviour::Stmt,
+        ));
+        stmts.push(vir::Stmt::Inhale(
+            vir::Expr::forall(
+                vec![index],
+                vec![vir::Trigger::new(vec![elem.clone()])],
+                vir::Expr::implies(index_in_bounds, vir::Expr::eq_cmp(elem, encoded_value)),
+            ),
+            vir::FoldingBehaviour::Stmt,
+        ));
+        Ok(stmts)
+    }
+
     fn encode_assign_ref(
         &mut self,
         mir_borrow_kind: mir::BorrowKind,
@@ -4847,7 +6169,8 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             place,
             location
         );
-        let (encoded_value, _, _) = self.mir_encoder.encode_place(place).unwrap(); // will panic if attempting to encode unsupported type
+        let span = self.mir_encoder.get_span_of_location(location);
+        let (encoded_value, _, _) = self.mir_encoder.encode_place(place).with_span(span)?;
         let loan = self.polonius_info().get_loan_at_location(location);
         let vir_assign_kind = match mir_borrow_kind {
             mir::BorrowKind::Shared => vir::AssignKind::SharedBorrow(loan.into()),
@@ -4894,6 +6217,89 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         self.encode_copy_value_assign(encoded_lhs, encoded_val, ty, location)
     }
 
+    /// Encode the unsizing of a pointer to a value of a concrete type into a pointer to a
+    /// trait object. Trait objects are opaque, so the target of `encoded_lhs` is a fresh
+    /// instance of the predicate of the trait object. Since the trait object may be used to
+    /// modify the value behind a mutable reference, the state of that value is havocked.
+    fn encode_assign_trait_object(
+        &mut self,
+        operand: &mir::Operand<'tcx>,
+        encoded_lhs: vir::Expr,
+        ty: ty::Ty<'tcx>,
+        location: mir::Location,
+        span: Span,
+    ) -> SpannedEncodingResult<Vec<vir::Stmt>> {
+        trace!(
+            "[enter] encode_assign_trait_object(operand={:?}, encoded_lhs={})",
+            operand,
+            encoded_lhs
+        );
+        // References, raw pointers and boxes store their target in the dereference field.
+        let object_ty = ty.builtin_deref(true).unwrap().ty;
+        let field = self.encoder.encode_dereference_field(object_ty).with_span(span)?;
+        let mut stmts = if self.init_info.is_vir_place_accessible(&encoded_lhs, location) {
+            vec![]
+        } else {
+            let mut alloc_stmts = self.encode_havoc(&encoded_lhs);
+            let acc = vir::Expr::acc_permission(
+                encoded_lhs.clone().field(field.clone()),
+                vir::PermAmount::Write,
+            );
+            alloc_stmts.push(vir::Stmt::Inhale(acc, vir::FoldingBehaviour::Stmt));
+            alloc_stmts
+        };
+        let object = encoded_lhs.field(field);
+        stmts.extend(self.encode_havoc(&object));
+        if !self.mir_encoder.is_raw_pointer(ty) {
+            stmts.push(vir::Stmt::Inhale(
+                vir::Expr::pred_permission(object, vir::PermAmount::Write).unwrap(),
+                vir::FoldingBehaviour::Stmt,
+            ));
+        }
+
+        let operand_ty = self.mir_encoder.get_operand_ty(operand);
+        match operand_ty.kind() {
+            ty::TyKind::Ref(_, _, Mutability::Mut) => {
+                // The trait object may modify the target of the reference.
+                let place = operand.place().unwrap();
+                let (encoded_place, _, _) = self.mir_encoder.encode_place(&place)
+                    .with_span(span)?;
+                let target = encoded_place.field(self.encoder.encode_value_field(operand_ty));
+                let target_perm = vir::Expr::pred_permission(target, vir::PermAmount::Write)
+                    .unwrap();
+                let pos = self
+                    .encoder
+                    .error_manager()
+                    .register(span, ErrorCtxt::CoerceToTraitObject);
+                stmts.push(vir::Stmt::Exhale(target_perm.clone(), pos));
+                stmts.push(vir::Stmt::Inhale(target_perm, vir::FoldingBehaviour::Stmt));
+            }
+            ty::TyKind::Adt(adt_def, _) if adt_def.is_box() => {
+                // The box is moved into the trait object, which now owns its target.
+                if let mir::Operand::Move(ref place) = operand {
+                    let (encoded_place, _, _) = self.mir_encoder.encode_place(place)
+                        .with_span(span)?;
+                    let box_perm = vir::Expr::pred_permission(
+                        encoded_place,
+                        vir::PermAmount::Write,
+                    ).unwrap();
+                    let pos = self
+                        .encoder
+                        .error_manager()
+                        .register(span, ErrorCtxt::CoerceToTraitObject);
+                    stmts.push(vir::Stmt::Exhale(box_perm, pos));
+                }
+            }
+            _ => {}
+        }
+        // Store a label for this state
+        let label = self.cfg_method.get_fresh_label_name();
+        debug!("Current loc {:?} has label {}", location, label);
+        self.label_after_location.insert(location, label.clone());
+        stmts.push(vir::Stmt::Label(label));
+        Ok(stmts)
+    }
+
     pub fn get_auxiliary_local_var(&mut self, suffix: &str, vir_type: vir::Type) -> vir::LocalVar {
         let name = format!("_aux_{}_{}", suffix, vir_type.name());
         if self.auxiliary_local_vars.contains_key(&name) {
@@ -4987,6 +6393,83 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         }
     }
 
+    /// Encode the assignment of an address to a raw pointer. Havoc and allocate the target if
+    /// necessary. The target of the pointer is not owned by the pointer, so no permissions
+    /// are transferred.
+    fn encode_copy_raw_pointer(
+        &mut self,
+        lhs: &vir::Expr,
+        address: vir::Expr,
+        ty: ty::Ty<'tcx>,
+        location: mir::Location,
+    ) -> SpannedEncodingResult<Vec<vir::Stmt>> {
+        let field = self.encoder.encode_value_field(ty);
+        let mut stmts = if self.init_info.is_vir_place_accessible(lhs, location) {
+            Vec::with_capacity(1)
+        } else {
+            let mut alloc_stmts = self.encode_havoc(lhs);
+            let acc = vir::Expr::acc_permission(
+                lhs.clone().field(field.clone()),
+                vir::PermAmount::Write,
+            );
+            alloc_stmts.push(vir::Stmt::Inhale(acc, vir::FoldingBehaviour::Stmt));
+            alloc_stmts
+        };
+        stmts.push(vir::Stmt::Assign(
+            lhs.clone().field(field.clone()),
+            address.clone(),
+            vir::AssignKind::Copy,
+        ));
+
+        // Remember the place that owns the target of `lhs`. If `address` is the address
+        // stored in another raw pointer, use the source of that pointer.
+        let source = self.raw_pointer_sources
+            .iter()
+            .find(|(pointer, _)| pointer.clone().field(field.clone()) == address)
+            .map(|(_, source)| source.clone())
+            .unwrap_or(address);
+        self.forget_raw_pointer_sources(lhs);
+        self.raw_pointer_sources.insert(lhs.clone(), source);
+        Ok(stmts)
+    }
+
+    /// Forget the sources of raw pointers that are invalidated by an assignment to `place`.
+    fn forget_raw_pointer_sources(&mut self, place: &vir::Expr) {
+        self.raw_pointer_sources.retain(|pointer, source| {
+            !pointer.has_prefix(place) && !source.has_prefix(place)
+        });
+    }
+
+    /// The sources of raw pointers that are the same at the end of all predecessors of `bbi`.
+    /// The predecessors that have not been encoded yet, i.e. those on the back edges of loops,
+    /// are not known to preserve any source.
+    fn raw_pointer_sources_at_entry(
+        &self,
+        bbi: BasicBlockIndex,
+    ) -> HashMap<vir::Expr, vir::Expr> {
+        let mut predecessors = self.procedure.predecessors(bbi)
+            .iter()
+            .filter(|&&pred| {
+                self.procedure.is_reachable_block(pred) && !self.procedure.is_spec_block(pred)
+            });
+        let mut sources = match predecessors.next() {
+            Some(pred) => match self.raw_pointer_sources_at_exit.get(pred) {
+                Some(pred_sources) => pred_sources.clone(),
+                None => return HashMap::new(),
+            },
+            None => return HashMap::new(),
+        };
+        for pred in predecessors {
+            match self.raw_pointer_sources_at_exit.get(pred) {
+                Some(pred_sources) => sources.retain(|pointer, source| {
+                    pred_sources.get(pointer) == Some(source)
+                }),
+                None => return HashMap::new(),
+            }
+        }
+        sources
+    }
+
     /// Encode value copy assignment. Havoc and allocate the target if necessary.
     fn encode_copy_value_assign2(
         &mut self,
@@ -5094,7 +6577,12 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             ty::TyKind::Bool
             | ty::TyKind::Int(_)
             | ty::TyKind::Uint(_)
-            | ty::TyKind::Char => {
+            | ty::TyKind::Char
+            | ty::TyKind::Float(_)
+            | ty::TyKind::Array(..) => {
+                self.encode_copy_primitive_value(src, dst, self_ty, location)?
+            }
+            ty::TyKind::Adt(_, _) if self.encoder.is_ghost_type(self_ty) => {
                 self.encode_copy_primitive_value(src, dst, self_ty, location)?
             }
             ty::TyKind::Adt(adt_def, _subst) if !adt_def.is_box() => {
@@ -5228,18 +6716,41 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 // let f = closure!(...);
             }
 
-            &mir::AggregateKind::Array(..) => {
-                return Err(SpannedEncodingError::unsupported(
-                    "construction of arrays is not supported",
-                    span
+            &mir::AggregateKind::Array(elem_ty) => {
+                let elem_type = self.encoder.encode_value_type(elem_ty).with_span(span)?;
+                let mut encoded_elems = vec![];
+                for operand in operands.iter() {
+                    encoded_elems.push(
+                        self.mir_encoder.encode_array_elem_operand(operand).with_span(span)?
+                    );
+                }
+                let value_field = self.encoder.encode_value_field(ty);
+                stmts.push(vir::Stmt::Assign(
+                    dst.clone().field(value_field),
+                    vir::Expr::seq(elem_type, encoded_elems),
+                    vir::AssignKind::Copy,
                 ));
             }
 
             &mir::AggregateKind::Generator(..) => {
-                return Err(SpannedEncodingError::unsupported(
-                    "construction of generators is not supported",
-                    span
-                ));
+                // The generator of an `async` block stores the variables that it captures.
+                let upvar_tys: Vec<_> = match ty.kind() {
+                    ty::TyKind::Generator(_, generator_subst, _) =>
+                        generator_subst.as_generator().upvar_tys().collect(),
+                    _ => unreachable!("{:?}", ty),
+                };
+                for (field_num, operand) in operands.iter().enumerate() {
+                    let field_name = format!("generator_{}", field_num);
+                    let encoded_field = self
+                        .encoder
+                        .encode_raw_ref_field(field_name, upvar_tys[field_num])
+                        .with_span(span)?;
+                    stmts.extend(self.encode_assign_operand(
+                        &dst.clone().field(encoded_field),
+                        operand,
+                        location,
+                    )?);
+                }
             }
         }
 
@@ -5282,3 +6793,53 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
 fn convert_loans_to_borrows(loans: &Vec<facts::Loan>) -> Vec<Borrow> {
     loans.iter().map(|l| l.into()).collect()
 }
+
+/// Move the ownership of targets of raw pointers (`own!(*ptr)`) out of a functional
+/// specification. Unlike the rest of the functional specification, which is only asserted,
+/// ownership has to be exhaled and inhaled like the permissions of the types.
+fn split_ownership_assertions(expr: vir::Expr, ownership: &mut Vec<vir::Expr>) -> vir::Expr {
+    match expr {
+        vir::Expr::PredicateAccessPredicate(..) => {
+            ownership.push(expr);
+            true.into()
+        }
+        vir::Expr::BinOp(vir::BinOpKind::And, box left, box right, pos) => {
+            let left = split_ownership_assertions(left, ownership);
+            let right = split_ownership_assertions(right, ownership);
+            vir::Expr::BinOp(vir::BinOpKind::And, box left, box right, pos)
+        }
+        vir::Expr::BinOp(vir::BinOpKind::Implies, box guard, box body, pos) => {
+            // Ownership that is only required under a condition keeps the condition.
+            let mut guarded_ownership = vec![];
+            let body = split_ownership_assertions(body, &mut guarded_ownership);
+            ownership.extend(
+                guarded_ownership
+                    .into_iter()
+                    .map(|owned| vir::Expr::implies(guard.clone(), owned)),
+            );
+            vir::Expr::BinOp(vir::BinOpKind::Implies, box guard, box body, pos)
+        }
+        _ => expr,
+    }
+}
+
+/// Whether `place` is (part of) the target of a raw pointer, rather than the address stored in
+/// the pointer. `is_pred` tells whether the predicate of `place` is meant.
+fn is_raw_pointer_target(place: &vir::Expr, is_pred: bool) -> bool {
+    let is_raw_pointer = |expr: &vir::Expr| match expr.get_type() {
+        // FIXME: We should not rely on string names for detecting types.
+        vir::Type::TypedRef(name) => name.starts_with("raw_ref$"),
+        _ => false,
+    };
+    let mut depth = 0;
+    let mut current = place;
+    while let Some(parent) = current.get_parent_ref() {
+        // The address is the only field of a raw pointer.
+        if is_raw_pointer(parent) && (depth > 0 || is_pred) {
+            return true;
+        }
+        depth += 1;
+        current = parent;
+    }
+    false
+}
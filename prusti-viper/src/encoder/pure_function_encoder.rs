@@ -7,15 +7,18 @@
 use crate::encoder::borrows::{compute_procedure_contract, ProcedureContract};
 use crate::encoder::builtin_encoder::BuiltinFunctionKind;
 use crate::encoder::errors::{PanicCause, RunIfErr};
-use crate::encoder::errors::{SpannedEncodingError, ErrorCtxt, WithSpan};
+use crate::encoder::errors::{EncodingError, SpannedEncodingError, ErrorCtxt, WithSpan};
 use crate::encoder::foldunfold;
 use crate::encoder::mir_encoder::{MirEncoder, PlaceEncoder};
 use crate::encoder::mir_encoder::{PRECONDITION_LABEL, WAND_LHS_LABEL};
+use crate::encoder::mir_encoder::{LOOP_ENTRY_LABEL, USER_LABEL_PREFIX};
 use crate::encoder::mir_interpreter::{
     run_backward_interpretation, BackwardMirInterpreter, MultiExprBackwardInterpreterState,
 };
 use crate::encoder::Encoder;
 use crate::encoder::snapshot_spec_patcher::SnapshotSpecPatcher;
+use crate::encoder::spec_encoder::encode_spec_measure;
+use crate::encoder::type_encoder::{ghost_type, GhostType};
 use prusti_common::vir;
 use prusti_common::vir::ExprIterator;
 use prusti_common::config;
@@ -27,6 +30,7 @@ use rustc_middle::ty;
 use std::collections::HashMap;
 use log::{debug, trace};
 use prusti_interface::PrustiError;
+use prusti_interface::utils;
 use rustc_span::Span;
 use crate::encoder::errors::EncodingResult;
 use crate::encoder::errors::SpannedEncodingResult;
@@ -135,6 +139,44 @@ impl<'p, 'v: 'p, 'tcx: 'v> PureFunctionEncoder<'p, 'v, 'tcx> {
         self.encode_function_given_body(None)
     }
 
+    /// Encode a function defined with `predicate!`, whose body is the given assertion instead
+    /// of the (non-executable) MIR body of the function.
+    pub fn encode_predicate_function(&self, predicate_body: &typed::Assertion<'tcx>)
+        -> SpannedEncodingResult<vir::Function>
+    {
+        let function_name = self.encode_function_name();
+        debug!("Encode predicate {}", function_name);
+
+        let contract = self.encoder
+            .get_procedure_contract_for_def(self.proc_def_id)
+            .with_span(self.mir.span)?;
+        let encoded_args: Vec<vir::Expr> = contract
+            .args
+            .iter()
+            .map(|local| self.encode_local(local.clone().into()).map(|l| l.into()))
+            .collect::<Result<_, _>>()?;
+        let body_expr = self.encoder.encode_assertion(
+            predicate_body,
+            &self.mir,
+            Some(PRECONDITION_LABEL),
+            &encoded_args,
+            None,
+            true,
+            None,
+            ErrorCtxt::GenericExpression,
+        )?;
+        if body_expr.contains_old_label(PRECONDITION_LABEL) {
+            // The body is inlined in the specifications that use the predicate, because it
+            // refers to their pre-state.
+            return self.encode_function_given_body(None);
+        }
+        let body_expr = SnapshotSpecPatcher::new(self.encoder)
+            .patch_spec(body_expr)
+            .with_span(self.mir.span)?;
+
+        self.encode_function_given_body(Some(body_expr))
+    }
+
     // Private
 
     fn encode_function_given_body(&self, body: Option<vir::Expr>)
@@ -402,6 +444,8 @@ pub(super) struct PureFunctionBackwardInterpreter<'p, 'v: 'p, 'tcx: 'v> {
     encoder: &'p Encoder<'v, 'tcx>,
     mir: &'p mir::Body<'tcx>,
     mir_encoder: MirEncoder<'p, 'v, 'tcx>,
+    /// The function whose body is being encoded.
+    def_id: DefId,
     /// True if the encoder is currently encoding an assertion and not a pure function body. This
     /// flag is used to distinguish when assert terminators should be translated into `false` and
     /// when to a undefined function calls. This distinction allows overflow checks to be checked
@@ -423,6 +467,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> PureFunctionBackwardInterpreter<'p, 'v, 'tcx> {
             encoder,
             mir,
             mir_encoder: MirEncoder::new(encoder, mir, def_id),
+            def_id,
             is_encoding_assertion,
         }
     }
@@ -430,6 +475,258 @@ impl<'p, 'v: 'p, 'tcx: 'v> PureFunctionBackwardInterpreter<'p, 'v, 'tcx> {
     pub(super) fn mir_encoder(&self) -> &MirEncoder<'p, 'v, 'tcx> {
         &self.mir_encoder
     }
+
+    /// Encode the condition under which a call to the pure function `callee_def_id` with
+    /// arguments `encoded_args` decreases the termination measure of the function being encoded.
+    /// Returns `None` if the call is not recursive or if the function being encoded has no
+    /// termination measure, which is an error if `CHECK_PURE_FUNCTION_TERMINATION` is enabled.
+    fn encode_termination_check(
+        &self,
+        callee_def_id: DefId,
+        encoded_args: &[vir::Expr],
+        span: Span,
+    ) -> SpannedEncodingResult<Option<vir::Expr>> {
+        if !self.encoder.is_recursive_pure_call(self.def_id, callee_def_id) {
+            return Ok(None);
+        }
+        let caller_measure = match self.encoder.get_termination_measure(self.def_id) {
+            Some(measure) => measure,
+            None if config::check_pure_function_termination() => {
+                return Err(SpannedEncodingError::incorrect(
+                    "recursive pure function without a termination measure",
+                    span,
+                ));
+            }
+            None => return Ok(None),
+        };
+        let callee_measure = self.encoder.get_termination_measure(callee_def_id)
+            .ok_or_else(|| SpannedEncodingError::incorrect(
+                "recursive call to a pure function without a termination measure",
+                span,
+            ))?;
+
+        let caller_args: Vec<vir::Expr> = self.mir.args_iter()
+            .map(|local| {
+                let local_ty = self.mir_encoder.get_local_ty(local);
+                self.mir_encoder.encode_local(local).map(|var| {
+                    self.encoder.encode_value_expr(vir::Expr::local(var), local_ty)
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        let caller_terms = encode_spec_measure(
+            self.encoder,
+            &caller_measure,
+            &caller_args,
+            true,
+            None,
+        )?;
+        let callee_terms = encode_spec_measure(
+            self.encoder,
+            &callee_measure,
+            encoded_args,
+            true,
+            None,
+        )?;
+
+        // Lexicographic order on the common prefix of the two measures.
+        let mut terms = callee_terms.into_iter().zip(caller_terms.into_iter()).rev();
+        let (last_callee_term, last_caller_term) = terms.next().unwrap();
+        let decreases = |callee_term: vir::Expr, caller_term: vir::Expr| {
+            vir::Expr::and(
+                vir::Expr::le_cmp(0.into(), caller_term.clone()),
+                vir::Expr::lt_cmp(callee_term, caller_term),
+            )
+        };
+        let check = terms.fold(
+            decreases(last_callee_term, last_caller_term),
+            |rest, (callee_term, caller_term)| {
+                vir::Expr::or(
+                    decreases(callee_term.clone(), caller_term.clone()),
+                    vir::Expr::and(vir::Expr::eq_cmp(callee_term, caller_term), rest),
+                )
+            },
+        );
+        Ok(Some(check))
+    }
+
+    /// Encode a call to an operation of a mathematical type of `prusti_contracts`, whose result
+    /// has type `lhs_ty`. Returns `None` if the callee is not such an operation.
+    fn encode_ghost_call(
+        &self,
+        def_id: DefId,
+        substs: ty::subst::SubstsRef<'tcx>,
+        lhs_ty: ty::Ty<'tcx>,
+        encoded_args: &[vir::Expr],
+    ) -> EncodingResult<Option<vir::Expr>> {
+        let tcx = self.encoder.env().tcx();
+        // Operators are calls to trait methods, whose `Self` type is the first type argument.
+        let (self_ty, opt_trait_id) = if let Some(impl_id) = tcx.impl_of_method(def_id) {
+            (tcx.type_of(impl_id), None)
+        } else if let Some(trait_id) = tcx.trait_of_item(def_id) {
+            (substs.type_at(0), Some(trait_id))
+        } else {
+            return Ok(None);
+        };
+        let ghost_ty = match ghost_type(tcx, self_ty) {
+            Some(ghost_ty) => ghost_ty,
+            None => return Ok(None),
+        };
+        let method_name = tcx.item_name(def_id).as_str();
+        let arg = |index: usize| encoded_args[index].clone();
+
+        if let Some(trait_id) = opt_trait_id {
+            // Comparisons take their operands by reference.
+            let deref = |index: usize| self.encoder.encode_value_expr(arg(index), self_ty);
+            let lang_items = tcx.lang_items();
+            let encoded_rhs = if Some(trait_id) == lang_items.eq_trait() {
+                match &*method_name {
+                    "eq" => vir::Expr::eq_cmp(deref(0), deref(1)),
+                    "ne" => vir::Expr::ne_cmp(deref(0), deref(1)),
+                    _ => return Ok(None),
+                }
+            } else if Some(trait_id) == lang_items.partial_ord_trait() {
+                match &*method_name {
+                    "lt" => vir::Expr::lt_cmp(deref(0), deref(1)),
+                    "le" => vir::Expr::le_cmp(deref(0), deref(1)),
+                    "gt" => vir::Expr::gt_cmp(deref(0), deref(1)),
+                    "ge" => vir::Expr::ge_cmp(deref(0), deref(1)),
+                    _ => return Ok(None),
+                }
+            } else if Some(trait_id) == lang_items.add_trait() {
+                vir::Expr::add(arg(0), arg(1))
+            } else if Some(trait_id) == lang_items.sub_trait() {
+                vir::Expr::sub(arg(0), arg(1))
+            } else if Some(trait_id) == lang_items.mul_trait() {
+                vir::Expr::mul(arg(0), arg(1))
+            } else if Some(trait_id) == lang_items.div_trait() {
+                vir::Expr::rust_div(arg(0), arg(1))
+            } else if Some(trait_id) == lang_items.rem_trait() {
+                vir::Expr::rem(arg(0), arg(1))
+            } else if Some(trait_id) == lang_items.neg_trait() {
+                vir::Expr::minus(arg(0))
+            } else {
+                return Ok(None);
+            };
+            return Ok(Some(encoded_rhs));
+        }
+
+        // The constructors are generic, so the type of a new container is the type of the result.
+        let lhs_type = || self.encoder.encode_value_type(lhs_ty);
+        let elem = |index: usize, elem_ty: ty::Ty<'tcx>| {
+            self.encode_ghost_elem(arg(index), elem_ty)
+        };
+        let pos = vir::Position::default();
+        let encoded_rhs = match (ghost_ty, &*method_name) {
+            (GhostType::Int, "new") | (GhostType::Int, "new_usize") => arg(0),
+
+            (GhostType::Seq(_), "empty") => vir::Expr::Seq(lhs_type()?, vec![], pos),
+            (GhostType::Seq(elem_ty), "single") => {
+                vir::Expr::Seq(lhs_type()?, vec![elem(0, elem_ty)?], pos)
+            }
+            (GhostType::Seq(elem_ty), "push") => {
+                let single = vir::Expr::Seq(lhs_type()?, vec![elem(1, elem_ty)?], pos);
+                vir::Expr::container_op(vir::ContainerOpKind::SeqConcat, arg(0), single)
+            }
+            (GhostType::Seq(_), "concat") => {
+                vir::Expr::container_op(vir::ContainerOpKind::SeqConcat, arg(0), arg(1))
+            }
+            (GhostType::Seq(_), "len") => vir::Expr::seq_len(arg(0)),
+            (GhostType::Seq(_), "is_empty") => {
+                vir::Expr::eq_cmp(vir::Expr::seq_len(arg(0)), 0.into())
+            }
+            (GhostType::Seq(_), "lookup") => vir::Expr::seq_index(arg(0), arg(1)),
+            (GhostType::Seq(elem_ty), "update") => {
+                vir::Expr::seq_update(arg(0), arg(1), elem(2, elem_ty)?)
+            }
+            (GhostType::Seq(_), "slice") => vir::Expr::seq_slice(arg(0), arg(1), arg(2)),
+            (GhostType::Seq(elem_ty), "contains") => {
+                vir::Expr::container_op(
+                    vir::ContainerOpKind::SeqContains,
+                    elem(1, elem_ty)?,
+                    arg(0),
+                )
+            }
+
+            (GhostType::Set(_), "empty") => vir::Expr::Set(lhs_type()?, vec![], pos),
+            (GhostType::Set(elem_ty), "single") => {
+                vir::Expr::Set(lhs_type()?, vec![elem(0, elem_ty)?], pos)
+            }
+            (GhostType::Set(elem_ty), "insert") => {
+                let single = vir::Expr::Set(lhs_type()?, vec![elem(1, elem_ty)?], pos);
+                vir::Expr::container_op(vir::ContainerOpKind::SetUnion, arg(0), single)
+            }
+            (GhostType::Set(_), "union") => {
+                vir::Expr::container_op(vir::ContainerOpKind::SetUnion, arg(0), arg(1))
+            }
+            (GhostType::Set(_), "intersection") => {
+                vir::Expr::container_op(vir::ContainerOpKind::SetIntersection, arg(0), arg(1))
+            }
+            (GhostType::Set(_), "difference") => {
+                vir::Expr::container_op(vir::ContainerOpKind::SetMinus, arg(0), arg(1))
+            }
+            (GhostType::Set(_), "is_subset") => {
+                vir::Expr::container_op(vir::ContainerOpKind::SetSubset, arg(0), arg(1))
+            }
+            (GhostType::Set(elem_ty), "contains") => {
+                vir::Expr::container_op(
+                    vir::ContainerOpKind::SetContains,
+                    elem(1, elem_ty)?,
+                    arg(0),
+                )
+            }
+            (GhostType::Set(_), "len") => vir::Expr::set_len(arg(0)),
+            (GhostType::Set(_), "is_empty") => {
+                vir::Expr::eq_cmp(vir::Expr::set_len(arg(0)), 0.into())
+            }
+
+            (GhostType::Map(..), "empty") => vir::Expr::Map(lhs_type()?, vec![], pos),
+            (GhostType::Map(key_ty, value_ty), "insert") => {
+                vir::Expr::map_update(arg(0), elem(1, key_ty)?, elem(2, value_ty)?)
+            }
+            (GhostType::Map(key_ty, _), "lookup") => {
+                vir::Expr::container_op(
+                    vir::ContainerOpKind::MapLookup,
+                    arg(0),
+                    elem(1, key_ty)?,
+                )
+            }
+            (GhostType::Map(key_ty, _), "contains_key") => {
+                vir::Expr::container_op(
+                    vir::ContainerOpKind::MapContains,
+                    elem(1, key_ty)?,
+                    arg(0),
+                )
+            }
+            (GhostType::Map(..), "len") => vir::Expr::map_len(arg(0)),
+            (GhostType::Map(..), "is_empty") => {
+                vir::Expr::eq_cmp(vir::Expr::map_len(arg(0)), 0.into())
+            }
+
+            (_, method_name) => {
+                return Err(EncodingError::unsupported(format!(
+                    "the operation '{}' of type '{:?}' is not supported",
+                    method_name,
+                    self_ty,
+                )));
+            }
+        };
+        Ok(Some(encoded_rhs))
+    }
+
+    /// Encode an element of a mathematical container. Structures, enumerations and tuples are
+    /// stored by their snapshot, so that the container does not depend on the memory.
+    fn encode_ghost_elem(
+        &self,
+        encoded_elem: vir::Expr,
+        elem_ty: ty::Ty<'tcx>,
+    ) -> EncodingResult<vir::Expr> {
+        Ok(match encoded_elem.get_type() {
+            vir::Type::TypedRef(_) if !self.encoder.is_ghost_type(elem_ty) => {
+                self.encoder.encode_snapshot(elem_ty)?.snap_call(encoded_elem)
+            }
+            _ => encoded_elem,
+        })
+    }
 }
 
 impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
@@ -673,7 +970,19 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
                             .collect::<Result<_, _>>()
                             .with_span(span)?;
 
+                        let encoded_ghost_rhs = self
+                            .encode_ghost_call(def_id, substs, ty, &encoded_args)
+                            .with_span(span)
+                            .run_if_err(cleanup)?;
+
                         match full_func_proc_name {
+                            _ if encoded_ghost_rhs.is_some() => {
+                                trace!("Encoding mathematical operation {:?}", full_func_proc_name);
+                                let mut state = states[&target_block].clone();
+                                state.substitute_value(&lhs_value, encoded_ghost_rhs.unwrap());
+                                state
+                            }
+
                             "prusti_contracts::old" => {
                                 trace!("Encoding old expression {:?}", args[0]);
                                 assert_eq!(args.len(), 1);
@@ -685,6 +994,33 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
                                 state
                             }
 
+                            "prusti_contracts::labelled_old" => {
+                                trace!("Encoding labelled old expression {:?}", args[0]);
+                                assert_eq!(args.len(), 1);
+                                // The label is encoded in the name of the marker type that
+                                // `prusti_label!` declares.
+                                let label = match substs.type_at(0).kind() {
+                                    ty::TyKind::Adt(adt_def, _) => {
+                                        let marker = self.encoder.env().tcx()
+                                            .item_name(adt_def.did)
+                                            .to_ident_string();
+                                        let name = marker.trim_start_matches("prusti_label_");
+                                        if name == LOOP_ENTRY_LABEL {
+                                            LOOP_ENTRY_LABEL.to_string()
+                                        } else {
+                                            format!("{}{}", USER_LABEL_PREFIX, name)
+                                        }
+                                    }
+                                    x => unreachable!("{:?}", x),
+                                };
+                                let encoded_rhs = self
+                                    .mir_encoder
+                                    .encode_old_expr(encoded_args[0].clone(), &label);
+                                let mut state = states[&target_block].clone();
+                                state.substitute_value(&lhs_value, encoded_rhs);
+                                state
+                            }
+
                             "prusti_contracts::before_expiry" => {
                                 trace!("Encoding before_expiry expression {:?}", args[0]);
                                 assert_eq!(args.len(), 1);
@@ -695,8 +1031,120 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
                                 state.substitute_value(&lhs_value, encoded_rhs);
                                 state
                             }
+                            "prusti_contracts::own" => {
+                                trace!("Encoding ownership of {:?}", args[0]);
+                                assert_eq!(args.len(), 1);
+                                // The value of a raw pointer is the place of its target.
+                                let encoded_rhs = vir::Expr::pred_permission(
+                                    encoded_args[0].clone(),
+                                    vir::PermAmount::Write,
+                                ).unwrap();
+                                let mut state = states[&target_block].clone();
+                                state.substitute_value(&lhs_value, encoded_rhs);
+                                state
+                            }
+
+                            "prusti_contracts::chars" => {
+                                trace!("Encoding characters of {:?}", args[0]);
+                                assert_eq!(args.len(), 1);
+                                // The characters are decoded from the UTF-8 bytes.
+                                let str_ty = self.mir_encoder.get_operand_ty(&args[0])
+                                    .builtin_deref(true)
+                                    .unwrap()
+                                    .ty;
+                                let value_field = self.encoder.encode_value_field(str_ty);
+                                let bytes = encoded_args[0].clone().field(value_field);
+                                let function_name = self.encoder
+                                    .encode_builtin_function_use(BuiltinFunctionKind::StrChars);
+                                let chars_type = vir::Type::Seq(box vir::Type::Int);
+                                let encoded_rhs = vir::Expr::func_app(
+                                    function_name,
+                                    vec![bytes],
+                                    vec![vir::LocalVar::new("bytes", chars_type.clone())],
+                                    chars_type,
+                                    vir::Position::default(),
+                                );
+                                let mut state = states[&target_block].clone();
+                                state.substitute_value(&lhs_value, encoded_rhs);
+                                state
+                            }
+
+                            "core::slice::<impl [T]>::len"
+                            | "core::slice::<impl [T]>::is_empty"
+                            | "core::str::<impl str>::len"
+                            | "core::str::<impl str>::is_empty" => {
+                                trace!("Encoding slice length {:?}", args[0]);
+                                assert_eq!(args.len(), 1);
+                                let slice_ty = self.mir_encoder.get_operand_ty(&args[0])
+                                    .builtin_deref(true)
+                                    .unwrap()
+                                    .ty;
+                                let value_field = self.encoder.encode_value_field(slice_ty);
+                                let len = vir::Expr::seq_len(
+                                    encoded_args[0].clone().field(value_field)
+                                );
+                                let encoded_rhs = if full_func_proc_name.ends_with("is_empty") {
+                                    vir::Expr::eq_cmp(len, 0.into())
+                                } else {
+                                    len
+                                };
+                                let mut state = states[&target_block].clone();
+                                state.substitute_value(&lhs_value, encoded_rhs);
+                                state
+                            }
+
+                            "core::str::<impl str>::as_bytes" => {
+                                trace!("Encoding as_bytes {:?}", args[0]);
+                                assert_eq!(args.len(), 1);
+                                // `str` shares the predicate of `[u8]`, so the bytes of a
+                                // string slice are the string slice itself.
+                                let mut state = states[&target_block].clone();
+                                state.substitute_value(&lhs_value, encoded_args[0].clone());
+                                state
+                            }
+
+                            "core::f32::<impl f32>::is_nan"
+                            | "core::f64::<impl f64>::is_nan"
+                            | "std::f32::<impl f32>::is_nan"
+                            | "std::f64::<impl f64>::is_nan" => {
+                                trace!("Encoding is_nan {:?}", args[0]);
+                                assert_eq!(args.len(), 1);
+                                let size = match encoded_args[0].get_type() {
+                                    &vir::Type::Float(size) => size,
+                                    typ => unreachable!("{}", typ),
+                                };
+                                let encoded_rhs = vir::float_func_app(
+                                    size,
+                                    vir::FloatFunc::IsNan,
+                                    vec![encoded_args[0].clone()],
+                                );
+                                let mut state = states[&target_block].clone();
+                                state.substitute_value(&lhs_value, encoded_rhs);
+                                state
+                            }
+
                             // simple function call
                             _ => {
+                                if !self.is_encoding_assertion
+                                    && self.encoder.get_predicate_body(def_id).is_some()
+                                {
+                                    cleanup();
+                                    return Err(SpannedEncodingError::incorrect(
+                                        "predicates can only be used in specifications",
+                                        term.source_info.span,
+                                    ));
+                                }
+                                if self.is_encoding_assertion {
+                                    let inlined_predicate = self.encoder
+                                        .encode_inlined_predicate_use(def_id, &encoded_args)
+                                        .run_if_err(cleanup)?;
+                                    if let Some(encoded_rhs) = inlined_predicate {
+                                        let mut state = states[&target_block].clone();
+                                        state.substitute_value(&lhs_value, encoded_rhs);
+                                        cleanup();
+                                        return Ok(state);
+                                    }
+                                }
                                 let mut is_cmp_call = false;
                                 let is_pure_function = self.encoder.is_pure(def_id);
                                 let (function_name, return_type) = if is_pure_function {
@@ -767,6 +1215,16 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
                                 } else {
                                     ErrorCtxt::StubPureFunctionCall
                                 };
+                                let termination_check = if is_pure_function && !self.is_encoding_assertion {
+                                    self.encode_termination_check(
+                                        def_id,
+                                        &encoded_args,
+                                        term.source_info.span,
+                                    ).run_if_err(cleanup)?
+                                } else {
+                                    None
+                                };
+
                                 let pos = self
                                     .encoder
                                     .error_manager()
@@ -780,6 +1238,28 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
                                 );
                                 let mut state = states[&target_block].clone();
                                 state.substitute_value(&lhs_value, encoded_rhs);
+                                if let Some(check) = termination_check {
+                                    // A call that might not decrease the termination measure
+                                    // is unreachable.
+                                    let pos = self.encoder.error_manager().register(
+                                        term.source_info.span,
+                                        ErrorCtxt::RecursiveCallInPureFunction,
+                                    );
+                                    let failure_result = unreachable_expr(pos)
+                                        .with_span(term.source_info.span)
+                                        .run_if_err(cleanup)?;
+                                    state = MultiExprBackwardInterpreterState::new(
+                                        state
+                                            .exprs()
+                                            .iter()
+                                            .map(|expr| vir::Expr::ite(
+                                                check.clone(),
+                                                expr.clone(),
+                                                failure_result.clone(),
+                                            ))
+                                            .collect(),
+                                    );
+                                }
                                 state
                             }
                         }
@@ -843,7 +1323,9 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
 
                 let pos = self.encoder.error_manager().register(
                     term.source_info.span,
-                    ErrorCtxt::PureFunctionAssertTerminator(msg.description().to_string()),
+                    ErrorCtxt::PureFunctionAssertTerminator(
+                        self.mir_encoder.encode_assert_message(msg)
+                    ),
                 );
 
                 MultiExprBackwardInterpreterState::new(
@@ -896,6 +1378,25 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
                 // Nothing to do
             }
 
+            mir::StatementKind::Assign(box (ref lhs, ref rhs)) if utils::is_array_elem_place(lhs) => {
+                let encoded_value = match rhs {
+                    &mir::Rvalue::Use(ref operand) => {
+                        self.mir_encoder.encode_array_elem_operand(operand).with_span(span)?
+                    }
+                    ref rhs => {
+                        return Err(SpannedEncodingError::unsupported(
+                            format!("assigning '{:?}' to an element of an array is not supported", rhs),
+                            span,
+                        ));
+                    }
+                };
+                // Substitute the sequence with the updated one
+                let (seq, updated_seq) = self.mir_encoder
+                    .encode_array_elem_update(lhs, encoded_value)
+                    .with_span(span)?;
+                state.substitute_value(&seq, updated_seq);
+            }
+
             mir::StatementKind::Assign(box (ref lhs, ref rhs)) => {
                 let (encoded_lhs, ty, _) = self.mir_encoder.encode_place(lhs).unwrap();
 
@@ -907,10 +1408,13 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
 
                 let opt_lhs_value_place = match ty.kind() {
                     ty::TyKind::Bool
+                    | ty::TyKind::Char
                     | ty::TyKind::Int(..)
                     | ty::TyKind::Uint(..)
+                    | ty::TyKind::Float(..)
                     | ty::TyKind::RawPtr(..)
-                    | ty::TyKind::Ref(..) => Some(
+                    | ty::TyKind::Ref(..)
+                    | ty::TyKind::Array(..) => Some(
                         self.encoder.encode_value_expr(
                             encoded_lhs.clone(),
                             ty
@@ -919,6 +1423,9 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
                         //     .clone()
                         //     .field(self.encoder.encode_value_field(ty)),
                     ),
+                    ty::TyKind::Adt(..) if self.encoder.is_ghost_type(ty) => Some(
+                        self.encoder.encode_value_expr(encoded_lhs.clone(), ty)
+                    ),
                     _ => None,
                 };
 
@@ -940,6 +1447,15 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
                                         .encode_operand_expr(operand)
                                         .with_span(span)?;
                                     state.substitute_value(lhs_value_place, rhs_expr);
+                                } else if operand.place().map_or(false, |place| {
+                                    utils::is_array_elem_place(&place)
+                                }) {
+                                    // The element is a snapshot, which cannot replace a place.
+                                    return Err(SpannedEncodingError::unsupported(
+                                        "reading a structure, enumeration or tuple from an array \
+                                        is not supported in pure functions",
+                                        span,
+                                    ));
                                 }
                             }
                         }
@@ -1029,10 +1545,38 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
                                 }
                             }
 
+                            &mir::AggregateKind::Array(elem_ty) => {
+                                let elem_type = self.encoder.encode_value_type(elem_ty)
+                                    .with_span(span)?;
+                                let mut encoded_elems = vec![];
+                                for operand in operands.iter() {
+                                    encoded_elems.push(
+                                        self.mir_encoder.encode_array_elem_operand(operand)
+                                            .with_span(span)?
+                                    );
+                                }
+                                // Substitute a place of a value with an expression
+                                state.substitute_value(
+                                    &opt_lhs_value_place.unwrap(),
+                                    vir::Expr::seq(elem_type, encoded_elems),
+                                );
+                            }
+
                             ref x => unimplemented!("{:?}", x),
                         }
                     }
 
+                    &mir::Rvalue::Len(ref place) => {
+                        let (len, array_len) = self.mir_encoder.encode_array_len(place)
+                            .with_span(span)?;
+                        // The length of an array is known from its type.
+                        let encoded_len = array_len.map(|array_len| array_len.into())
+                            .unwrap_or(len);
+
+                        // Substitute a place of a value with an expression
+                        state.substitute_value(&opt_lhs_value_place.unwrap(), encoded_len);
+                    }
+
                     &mir::Rvalue::BinaryOp(op, ref left, ref right) => {
                         let encoded_left = self.mir_encoder.encode_operand_expr(left)
                             .with_span(span)?;
@@ -1107,7 +1651,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
                     &mir::Rvalue::UnaryOp(op, ref operand) => {
                         let encoded_val = self.mir_encoder.encode_operand_expr(operand)
                             .with_span(span)?;
-                        let encoded_value = self.mir_encoder.encode_unary_op_expr(op, encoded_val);
+                        let encoded_value = self.mir_encoder.encode_unary_op_expr(op, encoded_val, ty);
 
                         // Substitute a place of a value with an expression
                         state.substitute_value(&opt_lhs_value_place.unwrap(), encoded_value);
@@ -1183,6 +1727,31 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
                         state.substitute_value(&opt_lhs_value_place.unwrap(), encoded_val);
                     }
 
+                    &mir::Rvalue::Cast(
+                        mir::CastKind::Pointer(ty::adjustment::PointerCast::Unsize),
+                        ref operand,
+                        _,
+                    ) if self.mir_encoder.is_array_ref(self.mir_encoder.get_operand_ty(operand)) => {
+                        // Arrays share the predicate of slices, so unsizing a reference to an
+                        // array does not change the encoded reference.
+                        let encoded_rhs = self.mir_encoder.encode_operand_place(operand)
+                            .with_span(span)?
+                            .unwrap();
+                        state.substitute_place(&encoded_lhs, encoded_rhs);
+                    }
+
+                    &mir::Rvalue::Cast(
+                        mir::CastKind::Pointer(ty::adjustment::PointerCast::MutToConstPointer),
+                        ref operand,
+                        _,
+                    ) => {
+                        // The cast does not change the address stored in the pointer.
+                        let encoded_rhs = self.mir_encoder.encode_operand_place(operand)
+                            .with_span(span)?
+                            .unwrap();
+                        state.substitute_place(&encoded_lhs, encoded_rhs);
+                    }
+
                     ref rhs => {
                         unimplemented!("encoding of '{:?}'", rhs);
                     }
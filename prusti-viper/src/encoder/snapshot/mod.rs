@@ -68,14 +68,16 @@ pub fn valid_func_for_type(typ: &vir::Type) -> vir::DomainFunc {
     let domain_name: String = match typ {
         Type::Domain(name) => name.clone(),
         Type::Bool | Type::Int => "PrimitiveValidDomain".to_string(),
-        Type::TypedRef(_) => unreachable!(),
+        Type::Float(size) => size.domain_name().to_string(),
+        Type::TypedRef(_) | Type::Seq(_) | Type::Set(_) | Type::Map(..) => unreachable!(),
     };
 
     let arg_typ: Type = match typ {
         Type::Domain(name) => vir::Type::Domain(domain_name.clone()),
         Type::Bool => Type::Bool,
         Type::Int => Type::Int,
-        Type::TypedRef(_) => unreachable!(),
+        Type::Float(size) => Type::Float(*size),
+        Type::TypedRef(_) | Type::Seq(_) | Type::Set(_) | Type::Map(..) => unreachable!(),
     };
 
     let self_arg = vir::LocalVar {
@@ -93,6 +95,22 @@ pub fn valid_func_for_type(typ: &vir::Type) -> vir::DomainFunc {
     df
 }
 
+/// Returns the T$size function for the given snapshot domain, which is used to
+/// encode ADTs in termination measures
+pub fn size_func_for_domain(domain_name: String) -> vir::DomainFunc {
+    let self_arg = vir::LocalVar {
+        name: "self".to_string(),
+        typ: Type::Domain(domain_name.clone()),
+    };
+    vir::DomainFunc {
+        name: format!("{}$size", domain_name),
+        formal_args: vec![self_arg],
+        return_type: vir::Type::Int,
+        unique: false,
+        domain_name,
+    }
+}
+
 /// Returns the LocalVar that is the Nat argument used in axiomatized functions
 pub fn encode_nat_argument() -> vir::LocalVar {
     vir::LocalVar {
@@ -47,9 +47,22 @@ pub struct SnapshotDomain {
     pub equals_func_ref: vir::Function,
     pub not_equals_func: vir::Function,
     pub not_equals_func_ref: vir::Function,
+    /// The axioms of the size function of the domain, which are only emitted if the domain is
+    /// used in a termination measure.
+    pub size_axioms: Vec<vir::DomainAxiom>,
+    /// The domains of the fields whose sizes are used by `size_axioms`.
+    pub sized_field_domains: Vec<String>,
 }
 
 impl SnapshotDomain {
+    /// Returns the domain together with its size function and the axioms of the latter.
+    pub fn domain_with_size(&self) -> vir::Domain {
+        let mut domain = self.domain.clone();
+        domain.functions.push(snapshot::size_func_for_domain(domain.name.clone()));
+        domain.axioms.extend(self.size_axioms.iter().cloned());
+        domain
+    }
+
     pub fn get_type(&self) -> vir::Type {
         vir::Type::Domain(
             self.domain.name.to_string()
@@ -168,6 +181,11 @@ impl<'p, 'v, 'r: 'v, 'a: 'r, 'tcx: 'a> SnapshotEncoder<'p, 'v, 'tcx> {
                     self.encoder.encode_value_field(self.ty)
                 )?
             }
+            // The equality of floats is not the equality of their snapshots
+            ty::TyKind::Float(_) => Snapshot {
+                is_equality_supported: false,
+                ..self.encode_primitive(self.encoder.encode_value_field(self.ty))?
+            },
             ty::TyKind::Param(_) => {
                 self.encode_generic(true)?
             }
@@ -223,6 +241,7 @@ impl<'p, 'v, 'r: 'v, 'a: 'r, 'tcx: 'a> SnapshotEncoder<'p, 'v, 'tcx> {
             ty::TyKind::Int(_)
             | ty::TyKind::Uint(_)
             | ty::TyKind::Char
+            | ty::TyKind::Float(_)
             | ty::TyKind::Bool
             | ty::TyKind::Param(_) => {
                 true
@@ -396,6 +415,8 @@ impl<'p, 'v, 'r: 'v, 'a: 'r, 'tcx: 'a> SnapshotEncoder<'p, 'v, 'tcx> {
             equals_func_ref: self.encode_equals_func_ref(),
             not_equals_func: self.encode_not_equals_func(),
             not_equals_func_ref: self.encode_not_equals_func_ref(),
+            size_axioms: vec![],
+            sized_field_domains: vec![],
         })
     }
 
@@ -775,12 +796,16 @@ impl<'s, 'v: 's, 'tcx: 'v> SnapshotAdtEncoder<'s, 'v, 'tcx> {
 
     fn encode_snap_domain(&self) -> EncodingResult<SnapshotDomain>
     {
-       Ok(SnapshotDomain{
-            domain: self.encode_domain()?,
+        let domain = self.encode_domain()?;
+        let (size_axioms, sized_field_domains) = self.encode_size_axioms(&domain.functions)?;
+        Ok(SnapshotDomain{
+            domain,
             equals_func: self.snapshot_encoder.encode_equals_func(),
             equals_func_ref: self.snapshot_encoder.encode_equals_func_ref(),
             not_equals_func: self.snapshot_encoder.encode_not_equals_func(),
             not_equals_func_ref: self.snapshot_encoder.encode_not_equals_func_ref(),
+            size_axioms,
+            sized_field_domains,
         })
     }
 
@@ -1078,6 +1103,85 @@ impl<'s, 'v: 's, 'tcx: 'v> SnapshotAdtEncoder<'s, 'v, 'tcx> {
 
 
 
+    /// Encodes the axioms of the size function of the domain, stating that sizes are
+    /// non-negative and that a constructor is larger than its ADT arguments. Also returns the
+    /// domains of those arguments.
+    fn encode_size_axioms(
+        &self,
+        constructors: &[vir::DomainFunc],
+    ) -> EncodingResult<(Vec<vir::DomainAxiom>, Vec<String>)> {
+        let domain_name = self.snapshot_encoder.encode_domain_name();
+        let size_func = snapshot::size_func_for_domain(domain_name.clone());
+        let tcx = self.snapshot_encoder.encoder.env().tcx();
+
+        let self_var = vir::LocalVar::new(
+            SNAPSHOT_ARG,
+            vir::Type::Domain(domain_name.clone()),
+        );
+        let self_size = vir::Expr::domain_func_app(
+            size_func.clone(),
+            vec![vir::Expr::local(self_var.clone())],
+        );
+        let mut axioms = vec![vir::DomainAxiom {
+            name: format!("{}$size$nonnegative", domain_name),
+            expr: vir::Expr::forall(
+                vec![self_var],
+                vec![vir::Trigger::new(vec![self_size.clone()])],
+                vir::Expr::le_cmp(0.into(), self_size),
+            ),
+            domain_name: domain_name.clone(),
+        }];
+        let mut sized_field_domains = vec![];
+
+        for (variant_index, variant_def) in self.adt_def.variants.iter().enumerate() {
+            let cons_func = &constructors[variant_index];
+            let vars = cons_func.formal_args.clone();
+            let cons_call = vir::Expr::domain_func_app(
+                cons_func.clone(),
+                vars.iter().cloned().map(vir::Expr::local).collect(),
+            );
+            let mut size: vir::Expr = 1.into();
+            for (var, field) in vars.iter().zip(variant_def.fields.iter()) {
+                let mut field_ty = field.ty(tcx, self.subst);
+                if field_ty.is_box() {
+                    field_ty = field_ty.boxed_ty();
+                }
+                // Only the fields that are ADTs have a size function
+                if let (ty::TyKind::Adt(..), vir::Type::Domain(field_domain_name)) =
+                    (field_ty.kind(), &var.typ)
+                {
+                    let field_size_func = snapshot::size_func_for_domain(
+                        field_domain_name.clone()
+                    );
+                    if !sized_field_domains.contains(field_domain_name) {
+                        sized_field_domains.push(field_domain_name.clone());
+                    }
+                    size = vir::Expr::add(
+                        size,
+                        vir::Expr::domain_func_app(
+                            field_size_func,
+                            vec![vir::Expr::local(var.clone())],
+                        ),
+                    );
+                }
+            }
+            let cons_size = vir::Expr::domain_func_app(size_func.clone(), vec![cons_call.clone()]);
+            let body = vir::Expr::eq_cmp(cons_size, size);
+            let expr = if vars.is_empty() {
+                body
+            } else {
+                vir::Expr::forall(vars, vec![vir::Trigger::new(vec![cons_call])], body)
+            };
+            axioms.push(vir::DomainAxiom {
+                name: format!("{}${}$size$axiom", domain_name, variant_index),
+                expr,
+                domain_name: domain_name.clone(),
+            });
+        }
+
+        Ok((axioms, sized_field_domains))
+    }
+
     fn encode_variant_func_and_axioms(&self)
         -> Option<(vir::DomainFunc, vir::DomainAxiom)>
     {
@@ -5,7 +5,8 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 use crate::encoder::errors::{
-    ErrorCtxt, SpannedEncodingResult, SpannedEncodingError, EncodingError, WithSpan
+    ErrorCtxt, SpannedEncodingResult, SpannedEncodingError, EncodingError, EncodingResult,
+    WithSpan
 };
 use crate::encoder::mir_encoder::{MirEncoder, PlaceEncoder};
 use crate::encoder::mir_encoder::PRECONDITION_LABEL;
@@ -15,6 +16,7 @@ use crate::encoder::mir_interpreter::{
 };
 use crate::encoder::pure_function_encoder::PureFunctionBackwardInterpreter;
 use crate::encoder::Encoder;
+use crate::encoder::snapshot;
 use prusti_common::config;
 use crate::encoder::SpecFunctionKind;
 use prusti_common::vir;
@@ -75,6 +77,39 @@ pub fn encode_spec_assertion<'v, 'tcx: 'v>(
     Ok(encoded_assertion)
 }
 
+/// Encode a termination measure coming from a `#[decreases(...)]` attribute to a tuple of
+/// integer `vir::Expr`s, to be compared lexicographically. The ADTs of the measure are encoded
+/// using the size function of their snapshot.
+///
+/// Arguments:
+/// * `encoder`: a reference to the `Encoder`.
+/// * `measure`: the measure to be encoded.
+/// * `target_args`: the expression to be used to encode arguments.
+/// * `targets_are_values`: if `true`, the elements of `target_args` encode _values_ and not
+///   _memory locations_.
+/// * `measure_location`: the basic block at which the measure should be encoded. This should be
+///   `None` iff the measure is the termination measure of a pure function.
+pub fn encode_spec_measure<'v, 'tcx: 'v>(
+    encoder: &Encoder<'v, 'tcx>,
+    measure: &typed::Measure,
+    target_args: &[vir::Expr],
+    targets_are_values: bool,
+    measure_location: Option<mir::BasicBlock>,
+) -> SpannedEncodingResult<Vec<vir::Expr>> {
+    let spec_encoder = SpecEncoder::new(
+        encoder,
+        "",
+        target_args,
+        None,
+        targets_are_values,
+        measure_location,
+    );
+    measure.terms()
+        .iter()
+        .map(|term| spec_encoder.encode_measure_term(term))
+        .collect()
+}
+
 struct SpecEncoder<'p, 'v: 'p, 'tcx: 'v> {
     encoder: &'p Encoder<'v, 'tcx>,
     /// The label to encode `old(..)` expressions
@@ -221,7 +256,9 @@ impl<'p, 'v: 'p, 'tcx: 'v> SpecEncoder<'p, 'v, 'tcx> {
                     self.encode_assertion(assertion)?
                 )
             }
-            box typed::AssertionKind::ForAll(ref vars, ref trigger_set, ref body) => {
+            box typed::AssertionKind::ForAll(ref vars, ref trigger_set, ref body) |
+            box typed::AssertionKind::Exists(ref vars, ref trigger_set, ref body) => {
+                let is_exists = matches!(assertion.kind, box typed::AssertionKind::Exists(..));
                 let mut encoded_args = Vec::new();
                 let mut bounds = Vec::new();
                 for (arg, ty) in &vars.vars {
@@ -242,16 +279,29 @@ impl<'p, 'v: 'p, 'tcx: 'v> SpecEncoder<'p, 'v, 'tcx> {
                     encoded_triggers.push(encoded_trigger);
                 }
                 let encoded_body = self.encode_assertion(body)?;
-                let final_body = if bounds.is_empty() {
-                    encoded_body
+                if is_exists {
+                    let final_body = if bounds.is_empty() {
+                        encoded_body
+                    } else {
+                        vir::Expr::and(bounds.into_iter().conjoin(), encoded_body)
+                    };
+                    vir::Expr::exists(
+                        encoded_args,
+                        encoded_triggers,
+                        final_body,
+                    )
                 } else {
-                    vir::Expr::implies(bounds.into_iter().conjoin(), encoded_body)
-                };
-                vir::Expr::forall(
-                    encoded_args,
-                    encoded_triggers,
-                    final_body,
-                )
+                    let final_body = if bounds.is_empty() {
+                        encoded_body
+                    } else {
+                        vir::Expr::implies(bounds.into_iter().conjoin(), encoded_body)
+                    };
+                    vir::Expr::forall(
+                        encoded_args,
+                        encoded_triggers,
+                        final_body,
+                    )
+                }
             },
             box typed::AssertionKind::SpecEntailment {
                 closure: ref closure,
@@ -261,7 +311,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> SpecEncoder<'p, 'v, 'tcx> {
             } => {
                 // TODO: refactor, simplify, or extract into a function
                 let tcx = self.encoder.env().tcx();
-                let mir = self.encoder.env().local_mir(closure.expr);
+                let mir = self.encoder.env().mir(closure.expr);
                 let result = &mir.local_decls[(0 as u32).into()];
                 let ty = result.ty;
                 if let Some(ty_repl) = self.encoder.current_tymap().get(ty) {
@@ -378,6 +428,13 @@ impl<'p, 'v: 'p, 'tcx: 'v> SpecEncoder<'p, 'v, 'tcx> {
         })
     }
 
+    /// Whether the closure identified by `def_id` has a definition site to which its expressions
+    /// can be translated. The closures of imported specifications are defined in their parent.
+    fn has_closure_def_site(&self, def_id: DefId) -> bool {
+        self.encoder.get_single_closure_instantiation(def_id).is_some()
+            || (!def_id.is_local() && self.encoder.env().tcx().is_closure(def_id))
+    }
+
     /// Translate an expression `expr` from a closure identified by `def_id` to its definition site.
     ///
     /// During the translation:
@@ -395,92 +452,110 @@ impl<'p, 'v: 'p, 'tcx: 'v> SpecEncoder<'p, 'v, 'tcx> {
         inner_def_id: DefId,
     ) -> SpannedEncodingResult<(vir::Expr, DefId, mir::Location)> {
         debug!("translate_expr_to_closure_def_site {} {:?}", expr, inner_def_id);
-        let inner_mir = self.encoder.env().local_mir(inner_def_id.expect_local());
+        let inner_mir = self.encoder.env().mir(inner_def_id);
         let inner_mir_encoder = MirEncoder::new(self.encoder, &inner_mir, inner_def_id);
         let inner_attrs = self.encoder.env().tcx().get_attrs(inner_def_id);
 
         let opt_instantiation = self.encoder.get_single_closure_instantiation(
             inner_def_id
         );
-        let (
-            outer_def_id,
-            outer_location,
-            captured_operands,
-            captured_operand_tys,
-        ) = opt_instantiation.expect(
-            &format!("cannot find definition site for closure {:?}", inner_def_id)
-        );
-        let outer_mir = self.encoder.env().local_mir(outer_def_id.expect_local());
+        // The closures of specifications imported from a dependency have no instantiation in
+        // the optimized MIR of the dependency. Their captured variables are matched by name, as
+        // if the closures were instantiated at the entry of their enclosing item.
+        let (outer_def_id, outer_location, opt_captured) = match opt_instantiation {
+            Some((def_id, location, operands, operand_tys)) => {
+                (def_id, location, Some((operands, operand_tys)))
+            }
+            None if !inner_def_id.is_local() => {
+                let parent_def_id = self.encoder.env().tcx().parent(inner_def_id).unwrap();
+                (parent_def_id, mir::Location::START, None)
+            }
+            None => panic!("cannot find definition site for closure {:?}", inner_def_id),
+        };
+        let outer_mir = self.encoder.env().mir(outer_def_id);
         let outer_mir_encoder = MirEncoder::new(self.encoder, &outer_mir, outer_def_id);
         let outer_span = outer_mir_encoder.get_span_of_location(outer_location);
         trace!("Replacing variables of {:?} captured from {:?}", inner_def_id, outer_def_id);
 
-        // Take the first argument, which is the closure's captured state.
-        // The closure is a record containing all the captured variables.
-        let closure_local = inner_mir.args_iter().next().unwrap();
-        let closure_var = inner_mir_encoder.encode_local(closure_local)?;
-        let closure_ty = &inner_mir.local_decls[closure_local].ty;
-        let should_closure_be_dereferenced = inner_mir_encoder.can_be_dereferenced(closure_ty);
-        let (deref_closure_var, _deref_closure_ty) = if should_closure_be_dereferenced {
-            let res = inner_mir_encoder
-                .encode_deref(closure_var.clone().into(), closure_ty)
-                .with_span(outer_span)?;
-            (res.0, res.1)
-        } else {
-            (closure_var.clone().into(), *closure_ty)
-        };
-        trace!("closure_ty: {:?}", closure_ty);
-        trace!("deref_closure_var: {:?}", deref_closure_var);
-
-        let captured_tys = captured_operand_tys;
-        trace!("captured_tys: {:?}", captured_tys);
-        assert_eq!(captured_tys.len(), captured_operands.len());
-
         // Replacements to translate from the closure to the definition site
         let mut replacements: Vec<(vir::Expr, vir::Expr)> = vec![];
 
-        // Replacement 1: translate a local variable from the closure to a place in the outer MIR
-        let inner_captured_places: Vec<_> = captured_tys
-            .iter()
-            .enumerate()
-            .map(|(index, &captured_ty)| {
-                let field_name = format!("closure_{}", index);
-                self.encoder.encode_raw_ref_field(field_name, captured_ty)
-                    .with_span(outer_span)
-                    .map(|encoded_field|
-                        deref_closure_var.clone().field(encoded_field)
-                    )
-            })
-            .collect::<Result<_, _>>()?;
-        let outer_captured_places: Vec<_> = captured_operands
-            .iter()
-            .map(|operand| outer_mir_encoder.encode_operand_place(operand))
-            .collect::<Result<Vec<_>, _>>()
-            .with_span(outer_span)?
-            .into_iter()
-            .map(|x| x.unwrap())
-            .collect();
-        for (index, (inner_place, outer_place)) in inner_captured_places
-            .iter()
-            .zip(outer_captured_places.iter())
-            .enumerate()
-        {
-            debug!(
-                "Field {} of closure, encoded as {}: {}, corresponds to {}: {} \
-                in the middle of the enclosing procedure",
-                index,
-                inner_place,
-                inner_place.get_type(),
-                outer_place,
-                outer_place.get_type()
+        if let Some((captured_operands, captured_operand_tys)) = opt_captured {
+            // Take the first argument, which is the closure's captured state.
+            // The closure is a record containing all the captured variables.
+            let closure_local = inner_mir.args_iter().next().unwrap();
+            let closure_var = inner_mir_encoder.encode_local(closure_local)?;
+            let closure_ty = &inner_mir.local_decls[closure_local].ty;
+            let should_closure_be_dereferenced = inner_mir_encoder.can_be_dereferenced(closure_ty);
+            let (deref_closure_var, _deref_closure_ty) = if should_closure_be_dereferenced {
+                let res = inner_mir_encoder
+                    .encode_deref(closure_var.clone().into(), closure_ty)
+                    .with_span(outer_span)?;
+                (res.0, res.1)
+            } else {
+                (closure_var.clone().into(), *closure_ty)
+            };
+            trace!("closure_ty: {:?}", closure_ty);
+            trace!("deref_closure_var: {:?}", deref_closure_var);
+
+            let captured_tys = captured_operand_tys;
+            trace!("captured_tys: {:?}", captured_tys);
+            assert_eq!(captured_tys.len(), captured_operands.len());
+
+            // Replacement 1: translate a local variable from the closure to a place in the outer MIR
+            let inner_captured_places: Vec<_> = captured_tys
+                .iter()
+                .enumerate()
+                .map(|(index, &captured_ty)| {
+                    let field_name = format!("closure_{}", index);
+                    self.encoder.encode_raw_ref_field(field_name, captured_ty)
+                        .with_span(outer_span)
+                        .map(|encoded_field|
+                            deref_closure_var.clone().field(encoded_field)
+                        )
+                })
+                .collect::<Result<_, _>>()?;
+            let outer_captured_places: Vec<_> = captured_operands
+                .iter()
+                .map(|operand| outer_mir_encoder.encode_operand_place(operand))
+                .collect::<Result<Vec<_>, _>>()
+                .with_span(outer_span)?
+                .into_iter()
+                .map(|x| x.unwrap())
+                .collect();
+            for (index, (inner_place, outer_place)) in inner_captured_places
+                .iter()
+                .zip(outer_captured_places.iter())
+                .enumerate()
+            {
+                debug!(
+                    "Field {} of closure, encoded as {}: {}, corresponds to {}: {} \
+                    in the middle of the enclosing procedure",
+                    index,
+                    inner_place,
+                    inner_place.get_type(),
+                    outer_place,
+                    outer_place.get_type()
+                );
+                assert_eq!(inner_place.get_type(), outer_place.get_type());
+            }
+            replacements.extend(
+                inner_captured_places
+                    .into_iter()
+                    .zip(outer_captured_places.into_iter())
+            );
+        } else {
+            // Replacement 1: translate a local variable from the closure to a place in the
+            // enclosing item, using the names of the debug info
+            replacements.extend(
+                self.encode_captures_by_name(
+                    &inner_mir,
+                    &inner_mir_encoder,
+                    &outer_mir,
+                    &outer_mir_encoder,
+                ).with_span(outer_span)?
             );
-            assert_eq!(inner_place.get_type(), outer_place.get_type());
         }
-        replacements.extend(
-            inner_captured_places
-                .into_iter()
-                .zip(outer_captured_places.into_iter())
-        );
 
         // Replacement 2: rename the variables introduced by a quantification
         let opt_forall_id = read_prusti_attr("expr_id", inner_attrs);
@@ -528,6 +603,41 @@ impl<'p, 'v: 'p, 'tcx: 'v> SpecEncoder<'p, 'v, 'tcx> {
         Ok((outer_expr, outer_def_id, outer_location))
     }
 
+    /// Match the variables captured by a closure of an imported specification with the places
+    /// of the enclosing item that have the same name in the debug info.
+    fn encode_captures_by_name(
+        &self,
+        inner_mir: &mir::Body<'tcx>,
+        inner_mir_encoder: &MirEncoder<'_, '_, 'tcx>,
+        outer_mir: &mir::Body<'tcx>,
+        outer_mir_encoder: &MirEncoder<'_, '_, 'tcx>,
+    ) -> EncodingResult<Vec<(vir::Expr, vir::Expr)>> {
+        let closure_local = inner_mir.args_iter().next().unwrap();
+        let mut replacements = vec![];
+        for inner_info in &inner_mir.var_debug_info {
+            let inner_place = match inner_info.value {
+                mir::VarDebugInfoContents::Place(place) if place.local == closure_local => place,
+                _ => continue,
+            };
+            let outer_place = outer_mir.var_debug_info.iter()
+                .find_map(|outer_info| match outer_info.value {
+                    mir::VarDebugInfoContents::Place(place) if outer_info.name == inner_info.name => {
+                        Some(place)
+                    }
+                    _ => None,
+                })
+                .ok_or_else(|| EncodingError::internal(format!(
+                    "cannot find the captured variable '{}' in the enclosing item",
+                    inner_info.name,
+                )))?;
+            let (inner_expr, _, _) = inner_mir_encoder.encode_place(&inner_place)?;
+            let (outer_expr, _, _) = outer_mir_encoder.encode_place(&outer_place)?;
+            trace!("Captured variable {} corresponds to {}", inner_expr, outer_expr);
+            replacements.push((inner_expr, outer_expr));
+        }
+        Ok(replacements)
+    }
+
     /// Given an expression and a program point, return the equivalent expression at a
     /// precedent program point.
     fn translate_expr_to_state(
@@ -538,7 +648,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> SpecEncoder<'p, 'v, 'tcx> {
         target_location: mir::BasicBlock,
     ) -> SpannedEncodingResult<vir::Expr> {
         debug!("translate_expr_to_state {} {:?} {:?}", expr, def_id, expr_location);
-        let mir = self.encoder.env().local_mir(def_id.expect_local());
+        let mir = self.encoder.env().mir(def_id);
 
         // Translate an intermediate state to the state at the beginning of the method
         let state = MultiExprBackwardInterpreterState::new_single(
@@ -566,17 +676,54 @@ impl<'p, 'v: 'p, 'tcx: 'v> SpecEncoder<'p, 'v, 'tcx> {
         Ok(pre_state_expr)
     }
 
+    /// Encode a component of a termination measure as an integer expression.
+    fn encode_measure_term(&self, term: &typed::Expression)
+        -> SpannedEncodingResult<vir::Expr>
+    {
+        trace!("encode_measure_term {:?}", term);
+        let span = self.encoder.env().tcx().def_span(term.expr);
+        let encoded_term = self.encode_expression(term)?;
+        let mut term_ty = self.encoder.env().mir(term.expr).return_ty();
+        while let ty::TyKind::Ref(_, inner_ty, _) = term_ty.kind() {
+            term_ty = inner_ty;
+        }
+        match term_ty.kind() {
+            ty::TyKind::Int(_) | ty::TyKind::Uint(_) => Ok(encoded_term),
+            ty::TyKind::Adt(..) => {
+                let snapshot = self.encoder.encode_snapshot(term_ty).with_span(span)?;
+                let domain = snapshot.domain().ok_or_else(|| SpannedEncodingError::unsupported(
+                    format!("the size of type '{:?}' cannot be used as a termination measure", term_ty),
+                    span,
+                ))?;
+                let snap_term = if let vir::Type::Domain(_) = encoded_term.get_type() {
+                    encoded_term
+                } else {
+                    snapshot.snap_call(encoded_term)
+                };
+                self.encoder.encode_snapshot_size_use(domain.name.clone());
+                Ok(vir::Expr::domain_func_app(
+                    snapshot::size_func_for_domain(domain.name),
+                    vec![snap_term],
+                ))
+            }
+            _ => Err(SpannedEncodingError::incorrect(
+                "termination measures can only contain integers and ADTs",
+                span,
+            )),
+        }
+    }
+
     /// Encode the assertion of a contract or loop invariant.
     fn encode_expression(&self, assertion_expr: &typed::Expression)
         -> SpannedEncodingResult<vir::Expr>
     {
         debug!("encode_expression {:?}", assertion_expr);
 
-        let mut curr_def_id = assertion_expr.expr.to_def_id();
+        let mut curr_def_id = assertion_expr.expr;
         let mut curr_expr = self.encoder.encode_pure_function_body(curr_def_id)?;
 
         loop {
-            let done = self.encoder.get_single_closure_instantiation(curr_def_id).is_none();
+            let done = !self.has_closure_def_site(curr_def_id);
             if done {
                 debug!("end of encode_expression loop: {:?} has no instantiation", curr_def_id);
                 break;
@@ -586,17 +733,22 @@ impl<'p, 'v: 'p, 'tcx: 'v> SpecEncoder<'p, 'v, 'tcx> {
                 outer_def_id,
                 outer_location,
             ) = self.translate_expr_to_closure_def_site(curr_expr, curr_def_id)?;
-            let done = self.encoder.get_single_closure_instantiation(outer_def_id).is_none();
-            curr_expr = self.translate_expr_to_state(
-                outer_expr,
-                outer_def_id,
-                outer_location,
-                if done {
-                    self.assertion_location.unwrap_or(mir::START_BLOCK)
-                } else {
-                    mir::START_BLOCK
-                },
-            )?;
+            let done = !self.has_closure_def_site(outer_def_id);
+            curr_expr = if outer_def_id.is_local() {
+                self.translate_expr_to_state(
+                    outer_expr,
+                    outer_def_id,
+                    outer_location,
+                    if done {
+                        self.assertion_location.unwrap_or(mir::START_BLOCK)
+                    } else {
+                        mir::START_BLOCK
+                    },
+                )?
+            } else {
+                // Imported specifications are already translated to the entry of the outer item.
+                outer_expr
+            };
             curr_def_id = outer_def_id;
         }
 
@@ -605,7 +757,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> SpecEncoder<'p, 'v, 'tcx> {
 
         // At this point `curr_def_id` should be either a SPEC item (when encoding a contract) or
         // the method being verified (when encoding a loop invariant).
-        let mir = self.encoder.env().local_mir(curr_def_id.expect_local());
+        let mir = self.encoder.env().mir(curr_def_id);
         let mir_encoder = MirEncoder::new(self.encoder, &mir, curr_def_id);
 
         // Replacements to use the provided `target_args` and `target_return`
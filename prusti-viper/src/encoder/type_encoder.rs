@@ -5,10 +5,11 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 use crate::encoder::foldunfold;
+use crate::encoder::spec_encoder::encode_spec_assertion;
 use crate::encoder::utils::range_extract;
 use crate::encoder::utils::PlusOne;
 use crate::encoder::Encoder;
-use prusti_common::vir::{self, ExprIterator, ExprFolder};
+use prusti_common::vir::{self, ExprIterator};
 use prusti_common::config;
 // use prusti_interface::specifications::*;
 // use rustc::middle::const_val::ConstVal;
@@ -20,7 +21,6 @@ use rustc_middle::ty::layout::IntegerExt;
 use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
-use prusti_interface::specs::typed;
 use rustc_attr::IntType::SignedInt;
 use rustc_target::abi::Integer;
 use log::{debug, trace};
@@ -43,11 +43,21 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
             | ty::TyKind::Int(_)
             | ty::TyKind::Uint(_)
             | ty::TyKind::Char
+            | ty::TyKind::Float(_)
             | ty::TyKind::Ref(_, _, _)
+            | ty::TyKind::RawPtr(_)
             | ty::TyKind::Adt(_, _)
             | ty::TyKind::Tuple(_)
+            | ty::TyKind::Str
             | ty::TyKind::Never
+            | ty::TyKind::Dynamic(..)
+            | ty::TyKind::Opaque(..)
+            | ty::TyKind::Generator(..)
             | ty::TyKind::Param(_) => true,
+            ty::TyKind::Array(elem_ty, _)
+            | ty::TyKind::Slice(elem_ty) => {
+                is_array_elem_type(elem_ty) && self.is_supported_type(elem_ty)
+            }
             _ => false,
         }
     }
@@ -103,6 +113,9 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
 
     pub fn encode_value_type(self) -> EncodingResult<vir::Type> {
         debug!("Encode value type '{:?}'", self.ty);
+        if let Some(ghost_ty) = self.ghost_type() {
+            return self.encode_ghost_value_type(ghost_ty);
+        }
         Ok(match self.ty.kind() {
             ty::TyKind::Bool => vir::Type::Bool,
 
@@ -110,7 +123,10 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
                 vir::Type::Int
             }
 
-            ty::TyKind::Ref(_, ref ty, _) => {
+            ty::TyKind::Float(float_ty) => self.encoder.encode_float_type(float_size(*float_ty)),
+
+            ty::TyKind::Ref(_, ref ty, _)
+            | ty::TyKind::RawPtr(ty::TypeAndMut { ref ty, .. }) => {
                 let type_name = self.encoder.encode_type_predicate_use(ty)?;
                 vir::Type::TypedRef(type_name)
             }
@@ -120,22 +136,83 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
                 snapshot.get_type()
             }
 
-            ty::TyKind::RawPtr(ty::TypeAndMut { ref ty, .. }) => {
-                return Err(EncodingError::unsupported(
-                    "raw pointers are not supported"
-                ));
+            ty::TyKind::Array(elem_ty, _) | ty::TyKind::Slice(elem_ty) => {
+                vir::Type::Seq(box self.encode_array_elem_type(elem_ty)?)
+            }
+
+            ty::TyKind::Str => {
+                vir::Type::Seq(box self.encode_array_elem_type(self.str_elem_ty())?)
             }
 
             ref x => unimplemented!("{:?}", x),
         })
     }
 
+    /// Encode the type of the elements of an array or slice. The elements are stored by value
+    /// in a Viper sequence: primitive values and nested arrays directly, structures,
+    /// enumerations and tuples by their snapshot.
+    fn encode_array_elem_type(&self, elem_ty: ty::Ty<'tcx>) -> EncodingResult<vir::Type> {
+        if !is_array_elem_type(elem_ty) {
+            return Err(EncodingError::unsupported(format!(
+                "arrays and slices are only supported with elements of primitive, array, \
+                structure, enumeration or tuple type, got '{:?}'",
+                elem_ty
+            )));
+        }
+        self.encoder.encode_value_type(elem_ty)
+    }
+
+    /// The mathematical type of `prusti_contracts` encoded by this type, if any.
+    fn ghost_type(&self) -> Option<GhostType<'tcx>> {
+        ghost_type(self.encoder.env().tcx(), self.ty)
+    }
+
+    /// Encode a mathematical type as the corresponding built-in type of Viper.
+    fn encode_ghost_value_type(&self, ghost_ty: GhostType<'tcx>) -> EncodingResult<vir::Type> {
+        Ok(match ghost_ty {
+            GhostType::Int => vir::Type::Int,
+            GhostType::Seq(elem_ty) => vir::Type::Seq(box self.encode_ghost_elem_type(elem_ty)?),
+            GhostType::Set(elem_ty) => vir::Type::Set(box self.encode_ghost_elem_type(elem_ty)?),
+            GhostType::Map(key_ty, value_ty) => vir::Type::Map(
+                box self.encode_ghost_elem_type(key_ty)?,
+                box self.encode_ghost_elem_type(value_ty)?,
+            ),
+        })
+    }
+
+    /// Encode the type of the elements of a mathematical container. The elements are stored
+    /// by value: primitive and mathematical values directly, structures, enumerations and
+    /// tuples by their snapshot.
+    fn encode_ghost_elem_type(&self, elem_ty: ty::Ty<'tcx>) -> EncodingResult<vir::Type> {
+        let tcx = self.encoder.env().tcx();
+        let is_snapshot_type = match elem_ty.kind() {
+            ty::TyKind::Adt(_, _) | ty::TyKind::Tuple(_) => true,
+            _ => false,
+        };
+        if !is_primitive_type(elem_ty) && !is_snapshot_type && ghost_type(tcx, elem_ty).is_none() {
+            return Err(EncodingError::unsupported(format!(
+                "mathematical containers are only supported with elements of primitive, \
+                mathematical, structure, enumeration or tuple type, got '{:?}'",
+                elem_ty
+            )));
+        }
+        self.encoder.encode_value_type(elem_ty)
+    }
+
+    /// A string slice is encoded like the slice of its UTF-8 bytes.
+    fn str_elem_ty(&self) -> ty::Ty<'tcx> {
+        self.encoder.env().tcx().types.u8
+    }
+
 
     /// provides the type of the underlying value or a reference in case of composed
     /// data structures
     pub fn encode_value_or_ref_type(self) -> EncodingResult<vir::Type> {
         debug!("Encode ref value type '{:?}'", self.ty);
         match self.ty.kind() {
+            // Mathematical values are stored like primitive values.
+            ty::TyKind::Adt(_, _) if self.ghost_type().is_some() => self.encode_value_type(),
+
             ty::TyKind::Adt(_, _)
             | ty::TyKind::Tuple(_) => {
                 let snapshot = self.encoder.encode_snapshot(&self.ty)?;
@@ -149,6 +226,10 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
 
     pub fn encode_value_field(self) -> EncodingResult<vir::Field> {
         trace!("Encode value field for type '{:?}'", self.ty);
+        if let Some(ghost_ty) = self.ghost_type() {
+            let typ = self.encode_ghost_value_type(ghost_ty)?;
+            return Ok(vir::Field::new(format!("val_{}", typ.name()), typ));
+        }
         Ok(match self.ty.kind() {
             ty::TyKind::Bool => vir::Field::new("val_bool", vir::Type::Bool),
 
@@ -156,7 +237,13 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
                 vir::Field::new("val_int", vir::Type::Int)
             }
 
-            ty::TyKind::Ref(_, ref ty, _) => {
+            ty::TyKind::Float(float_ty) => {
+                let size = float_size(*float_ty);
+                vir::Field::new(format!("val_{}", size), self.encoder.encode_float_type(size))
+            }
+
+            ty::TyKind::Ref(_, ref ty, _)
+            | ty::TyKind::RawPtr(ty::TypeAndMut { ref ty, .. }) => {
                 let type_name = self.encoder.encode_type_predicate_use(ty)?;
                 vir::Field::new("val_ref", vir::Type::TypedRef(type_name))
             }
@@ -166,15 +253,23 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
             ty::TyKind::Adt(_, _)
             | ty::TyKind::Tuple(_)
             | ty::TyKind::Closure(_, _)
+            | ty::TyKind::Generator(..)
             | ty::TyKind::FnDef(_, _) => {
                 let type_name = self.encoder.encode_type_predicate_use(self.ty)?;
                 vir::Field::new("val_ref", vir::Type::TypedRef(type_name))
             }
 
-            ty::TyKind::RawPtr(ty::TypeAndMut { ref ty, .. }) => {
-                return Err(EncodingError::unsupported(
-                    "raw pointers are not supported"
-                ));
+            // Arrays, slices and string slices store their elements in a sequence.
+            ty::TyKind::Array(..) | ty::TyKind::Slice(..) | ty::TyKind::Str => {
+                let elem_ty = match self.ty.kind() {
+                    ty::TyKind::Array(elem_ty, _) | ty::TyKind::Slice(elem_ty) => *elem_ty,
+                    _ => self.str_elem_ty(),
+                };
+                let elem_type = self.encode_array_elem_type(elem_ty)?;
+                vir::Field::new(
+                    format!("val_array${}", elem_type.name()),
+                    vir::Type::Seq(box elem_type),
+                )
             }
 
             ref x => unimplemented!("{:?}", x),
@@ -208,7 +303,15 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
             ty::TyKind::Char => {
                 Some((0.into(), std::char::MAX.into()))
             }
-            ty::TyKind::Bool | ty::TyKind::Ref(_, _, _) => None,
+            ty::TyKind::Bool
+            | ty::TyKind::Float(_)
+            | ty::TyKind::Ref(_, _, _)
+            | ty::TyKind::RawPtr(_)
+            | ty::TyKind::Array(..)
+            | ty::TyKind::Slice(..)
+            | ty::TyKind::Str
+            | ty::TyKind::Adt(_, _)
+            | ty::TyKind::Tuple(_) => None,
             ref x => unreachable!("{:?}", x),
         }
     }
@@ -230,7 +333,7 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
         let typ = vir::Type::TypedRef(predicate_name.clone());
 
         Ok(match self.ty.kind() {
-            ty::TyKind::Bool => vec![vir::Predicate::new_primitive_value(
+            ty::TyKind::Bool | ty::TyKind::Float(_) => vec![vir::Predicate::new_primitive_value(
                 typ,
                 self.encoder.encode_value_field(self.ty),
                 None,
@@ -263,6 +366,17 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
                 )]
             },
 
+            // A raw pointer owns only the address it stores. Ownership of the target is not
+            // implied by the type and has to be specified explicitly with `own!(*ptr)`.
+            ty::TyKind::RawPtr(ty::TypeAndMut { ref ty, .. }) => {
+                vec![vir::Predicate::new_primitive_value(
+                    typ,
+                    self.encoder.encode_dereference_field(ty)?,
+                    None,
+                    false,
+                )]
+            }
+
             ty::TyKind::Tuple(elems) => {
                 let fields = elems
                     .iter()
@@ -275,6 +389,11 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
                 vec![vir::Predicate::new_struct(typ, fields)]
             }
 
+            ty::TyKind::Adt(_, _) if self.ghost_type().is_some() => {
+                let field = TypeEncoder::new(self.encoder, self.ty).encode_value_field()?;
+                vec![vir::Predicate::new_primitive_value(typ, field, None, false)]
+            }
+
             ty::TyKind::Adt(adt_def, subst) if !adt_def.is_box() => {
                 let num_variants = adt_def.variants.len();
                 let tcx = self.encoder.env().tcx();
@@ -359,6 +478,22 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
                 )]
             }
 
+            ty::TyKind::Array(..) | ty::TyKind::Slice(..) | ty::TyKind::Str => {
+                let elem_ty = match self.ty.kind() {
+                    ty::TyKind::Array(elem_ty, _) | ty::TyKind::Slice(elem_ty) => *elem_ty,
+                    _ => self.str_elem_ty(),
+                };
+                let elem_bounds = if config::check_overflows() {
+                    TypeEncoder::new(self.encoder, elem_ty).get_integer_bounds()
+                } else {
+                    None
+                };
+                // Report unsupported element types as an error instead of panicking below.
+                self.encode_array_elem_type(elem_ty)?;
+                let field = self.encoder.encode_value_field(self.ty);
+                vec![vir::Predicate::new_array(typ, field, elem_bounds)]
+            }
+
             ty::TyKind::Never => {
                 // FIXME: This should be a predicate with the body `false`. See issue #38.
                 vec![vir::Predicate::new_abstract(typ)]
@@ -369,6 +504,33 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
                 vec![vir::Predicate::new_abstract(typ)]
             }
 
+            ty::TyKind::Dynamic(..) => {
+                // The concrete type behind a trait object is unknown, so its state is opaque.
+                // Calls on a trait object are verified against the specification of the trait.
+                vec![vir::Predicate::new_abstract(typ)]
+            }
+
+            ty::TyKind::Opaque(..) => {
+                // The state of a future is opaque. Awaiting the future of an `async fn`
+                // is verified against the specification of the `async fn`.
+                vec![vir::Predicate::new_abstract(typ)]
+            }
+
+            ty::TyKind::Generator(_, generator_subst, _) => {
+                // The generator of an `async` block owns the variables that it captures,
+                // which its body accesses as fields of the generator.
+                let fields = generator_subst
+                    .as_generator()
+                    .upvar_tys()
+                    .enumerate()
+                    .map(|(field_num, ty)| {
+                        let field_name = format!("generator_{}", field_num);
+                        self.encoder.encode_raw_ref_field(field_name, ty)
+                    })
+                    .collect::<Result<_, _>>()?;
+                vec![vir::Predicate::new_struct(typ, fields)]
+            }
+
             ty::TyKind::Closure(_def_id, internal_substs) => {
                 let closure_substs = internal_substs.as_closure();
                 match closure_substs.tupled_upvars_ty().kind() {
@@ -443,6 +605,8 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
 
             ty::TyKind::Char => "char".to_string(),
 
+            ty::TyKind::Float(float_ty) => float_size(*float_ty).to_string(),
+
             ty::TyKind::RawPtr(ty::TypeAndMut { ref ty, .. }) => {
                 format!("raw_ref${}", self.encoder.encode_type_predicate_use(ty)?)
             }
@@ -473,38 +637,20 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
 
             ty::TyKind::Never => "never".to_string(),
 
-            ty::TyKind::Str => "str".to_string(),
-
-            ty::TyKind::Array(elem_ty, size) => {
-                let scalar_size = match size.val {
-                    ty::ConstKind::Value(ref value) => {
-                        value.try_to_bits(
-                            rustc_target::abi::Size::from_bits(64)
-                        ).unwrap()
-                    },
-                    ty::ConstKind::Unevaluated(def, ref substs, promoted) => {
-                        let tcx = self.encoder.env().tcx();
-                        let param_env = tcx.param_env(def.did);
-                        tcx.const_eval_resolve(param_env, def, substs, promoted, None)
-                            .ok()
-                            .and_then(|const_value| const_value.try_to_bits(
-                                rustc_target::abi::Size::from_bits(64)
-                            ))
-                            .unwrap()
-                    }
-                    x => unimplemented!("{:?}", x),
-                };
+            // Arrays share the predicate of the corresponding slice, such that
+            // unsizing a reference to an array is just a reborrow. String slices
+            // share the predicate of `[u8]`, such that `str::as_bytes` is a reborrow.
+            ty::TyKind::Str => {
                 format!(
-                    "array${}${}",
-                    self.encoder.encode_type_predicate_use(elem_ty)?,
-                    scalar_size
+                    "slice${}",
+                    self.encoder.encode_type_predicate_use(self.str_elem_ty())?
                 )
             }
 
-            ty::TyKind::Slice(array_ty) => {
+            ty::TyKind::Array(elem_ty, _) | ty::TyKind::Slice(elem_ty) => {
                 format!(
                     "slice${}",
-                    self.encoder.encode_type_predicate_use(array_ty)?
+                    self.encoder.encode_type_predicate_use(elem_ty)?
                 )
             }
 
@@ -524,6 +670,31 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
                 )
             }
 
+            ty::TyKind::Generator(def_id, generator_subst, _) => {
+                let subst_hash = {
+                    let mut s = DefaultHasher::new();
+                    generator_subst.hash(&mut s);
+                    s.finish()
+                };
+
+                format!(
+                    "generator${}_{}${}${}",
+                    def_id.krate.as_u32(),
+                    def_id.index.as_u32(),
+                    generator_subst.len(),
+                    subst_hash
+                )
+            }
+
+            ty::TyKind::Opaque(def_id, substs) => {
+                let mut composed_name = vec![
+                    "opaque".to_string(),
+                    self.encoder.encode_item_name(*def_id),
+                ];
+                composed_name.push(self.encode_substs(substs)?);
+                composed_name.join("$")
+            }
+
             ty::TyKind::Param(param_ty) => {
                 format!("__TYPARAM__${}$__", param_ty.name.as_str())
             }
@@ -535,8 +706,15 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
                 composed_name.join("$")
             }
 
-            ty::TyKind::Dynamic(..) => {
-                "unsupported$dynamic".to_string()
+            ty::TyKind::Dynamic(predicates, _) => {
+                // All trait objects with the same principal trait share a predicate.
+                let mut composed_name = vec!["dyn".to_string()];
+                if let Some(principal) = predicates.principal() {
+                    let trait_ref = principal.skip_binder();
+                    composed_name.push(self.encoder.encode_item_name(trait_ref.def_id));
+                    composed_name.push(self.encode_substs(trait_ref.substs)?);
+                }
+                composed_name.join("$")
             }
 
             ty::TyKind::FnPtr(..) => {
@@ -602,57 +780,28 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
                     let num_variants = adt_def.variants.len();
                     let tcx = self.encoder.env().tcx();
 
-                    let mut specs: Vec<typed::SpecificationSet> = Vec::new();
-                    // FIXME: type invariants need to be collected separately
-                    // in `SpecCollector`, and encoder should get a
-                    // `get_struct_specs` method or similar.
-                    // `get_procedure_specs` now only returns procedure specs,
-                    // so the match below for `SpecSet::Struct` would never
-                    // succeed.
-
-                    //if let Some(spec) = self.encoder.get_procedure_specs(adt_def.did) {
-                    //    specs.push(spec);
-                    //}
-
-                    let traits = self.encoder.env().get_traits_decls_for_type(&self.ty);
-                    for trait_id in traits {
-                        //if let Some(spec) = self.encoder.get_procedure_specs(trait_id) {
-                        //    specs.push(spec);
-                        //}
-                    }
-
-                    for spec in specs.into_iter() {
-                        //let encoded_args = vec![vir::Expr::from(self_local_var.clone())];
-                        let mut hacky_folder = HackyExprFolder {
-                            saelf: self_local_var.clone(),
-                        };
-
-                        match spec {
-                            typed::SpecificationSet::Struct(items) => {
-                                for item in items {
-                                    // let enc = encode_simple_spec_assertion(
-                                    //     self.encoder,
-                                    //     &[],
-                                    //     &item.assertion
-                                    // );
-                                    let enc = unimplemented!(
-                                        "TODO: type invariants need to be upgraded \
-                                        to the new compiler version"
-                                    );
-                                    // OPEN TODO: hacky fix here to convert the closure var to "self"...
-                                    let enc = hacky_folder.fold(enc);
-                                    exprs.push(enc);
-                                }
-                            }
-                            _ => unreachable!(),
-                        }
-                    }
+                    // Invariants attached with `#[invariant(...)]` to the type. Traits cannot
+                    // have invariants (the attribute is rejected on them), so these are all the
+                    // invariants of the type.
+                    let specs = self.encoder.get_struct_specs(adt_def.did).unwrap_or_default();
+                    let encoded_specs = specs.iter()
+                        .map(|spec| encode_spec_assertion(
+                            self.encoder,
+                            &spec.assertion,
+                            None,
+                            &[self_local_var.clone().into()],
+                            None,
+                            false,
+                            None,
+                        ))
+                        .collect::<Result<Vec<_>, _>>();
 
                     // FIXME: this is a hack to support generics. See issue #187.
                     {
                         let mut tymap_stack = self.encoder.typaram_repl.borrow_mut();
                         tymap_stack.pop();
                     }
+                    exprs.extend(encoded_specs?);
 
                     if num_variants == 0 {
                         debug!("ADT {:?} has no variant", adt_def);
@@ -694,8 +843,13 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
         };
 
         let precondition = match self.ty.kind() {
-            ty::TyKind::RawPtr(ty::TypeAndMut { ref ty, .. })
-            | ty::TyKind::Ref(_, ref ty, _) => {
+            ty::TyKind::RawPtr(ty::TypeAndMut { ref ty, .. }) => {
+                // The target of a raw pointer is not owned by the pointer.
+                let elem_field = self.encoder.encode_dereference_field(ty)?;
+                let elem_loc = vir::Expr::from(self_local_var.clone()).field(elem_field);
+                vir::Expr::acc_permission(elem_loc, vir::PermAmount::Read)
+            }
+            ty::TyKind::Ref(_, ref ty, _) => {
                 // This is a reference, so we need to have it already unfolded.
                 let elem_field = self.encoder.encode_dereference_field(ty)?;
                 let elem_loc = vir::Expr::from(self_local_var.clone()).field(elem_field);
@@ -779,6 +933,84 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
     }
 }
 
+/// Is this a type whose values can be stored directly in a Viper sequence?
+fn is_primitive_type(ty: ty::Ty) -> bool {
+    match ty.kind() {
+        ty::TyKind::Bool
+        | ty::TyKind::Int(_)
+        | ty::TyKind::Uint(_)
+        | ty::TyKind::Char
+        | ty::TyKind::Float(_) => true,
+        _ => false,
+    }
+}
+
+/// Can values of this type be stored as the elements of an array or slice?
+fn is_array_elem_type(ty: ty::Ty) -> bool {
+    match ty.kind() {
+        ty::TyKind::Array(..) | ty::TyKind::Adt(_, _) | ty::TyKind::Tuple(_) => true,
+        _ => is_primitive_type(ty),
+    }
+}
+
+/// The size of the VIR float type that encodes a Rust float type.
+pub fn float_size(float_ty: ty::FloatTy) -> vir::FloatSize {
+    match float_ty {
+        ty::FloatTy::F32 => vir::FloatSize::F32,
+        ty::FloatTy::F64 => vir::FloatSize::F64,
+    }
+}
+
+/// A mathematical type of `prusti_contracts`, which is encoded as a built-in type of Viper.
+#[derive(Clone, Copy, Debug)]
+pub enum GhostType<'tcx> {
+    /// `Int`: an unbounded integer
+    Int,
+    /// `Seq<T>`: the type of the elements
+    Seq(ty::Ty<'tcx>),
+    /// `Set<T>`: the type of the elements
+    Set(ty::Ty<'tcx>),
+    /// `Map<K, V>`: the types of the keys and of the values
+    Map(ty::Ty<'tcx>, ty::Ty<'tcx>),
+}
+
+/// The mathematical type of `prusti_contracts` encoded by `ty`, if any.
+pub fn ghost_type<'tcx>(tcx: ty::TyCtxt<'tcx>, ty: ty::Ty<'tcx>) -> Option<GhostType<'tcx>> {
+    match ty.kind() {
+        ty::TyKind::Adt(adt_def, substs) => match tcx.def_path_str(adt_def.did).as_str() {
+            "prusti_contracts::Int" => Some(GhostType::Int),
+            "prusti_contracts::Seq" => Some(GhostType::Seq(substs.type_at(0))),
+            "prusti_contracts::Set" => Some(GhostType::Set(substs.type_at(0))),
+            "prusti_contracts::Map" => {
+                Some(GhostType::Map(substs.type_at(0), substs.type_at(1)))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Compute the length of an array type.
+pub fn compute_array_len<'tcx>(tcx: ty::TyCtxt<'tcx>, size: &ty::Const<'tcx>) -> u128 {
+    match size.val {
+        ty::ConstKind::Value(ref value) => {
+            value.try_to_bits(
+                rustc_target::abi::Size::from_bits(64)
+            ).unwrap()
+        },
+        ty::ConstKind::Unevaluated(def, ref substs, promoted) => {
+            let param_env = tcx.param_env(def.did);
+            tcx.const_eval_resolve(param_env, def, substs, promoted, None)
+                .ok()
+                .and_then(|const_value| const_value.try_to_bits(
+                    rustc_target::abi::Size::from_bits(64)
+                ))
+                .unwrap()
+        }
+        x => unimplemented!("{:?}", x),
+    }
+}
+
 /// Compute the values that a discriminant can take.
 pub fn compute_discriminant_values<'tcx>(adt_def: &'tcx ty::AdtDef, tcx: ty::TyCtxt<'tcx>) -> Vec<i128> {
     let mut discr_values: Vec<i128> = vec![];
@@ -825,13 +1057,3 @@ pub fn compute_discriminant_bounds<'tcx>(
     let discr_values = compute_discriminant_values(adt_def, tcx);
     build_discr_range_expr(discriminant_loc, discr_values)
 }
-
-struct HackyExprFolder {
-    saelf: vir::LocalVar,
-}
-
-impl ExprFolder for HackyExprFolder {
-    fn fold_local(&mut self, _var: vir::LocalVar, pos: vir::Position) -> vir::Expr {
-        vir::Expr::Local(self.saelf.clone(), pos)
-    }
-}
@@ -6,8 +6,8 @@
 
 use rustc_hir::Mutability;
 use rustc_middle::ty::{
-    AdtDef, FieldDef, ParamTy, ProjectionTy, Region, Slice, Ty, TyCtxt,
-    TypeFlags, TyKind, IntTy, UintTy, VariantDef, subst::SubstsRef
+    AdtDef, Binder, ExistentialPredicate, FieldDef, List, ParamTy, ProjectionTy, Region,
+    Slice, Ty, TyCtxt, TypeFlags, TyKind, IntTy, UintTy, FloatTy, VariantDef, subst::SubstsRef
 };
 use rustc_hir::def_id::DefId;
 use log::trace;
@@ -38,6 +38,12 @@ pub trait TypeVisitor<'tcx>: Sized {
             TyKind::Char => {
                 self.visit_char()
             }
+            TyKind::Str => {
+                self.visit_str()
+            }
+            TyKind::Float(ty) => {
+                self.visit_float(ty)
+            }
             TyKind::Adt(adt_def, substs) => {
                 self.visit_adt(adt_def, substs)
             }
@@ -47,6 +53,12 @@ pub trait TypeVisitor<'tcx>: Sized {
             TyKind::Tuple(parts) => {
                 self.visit_tuple(parts)
             }
+            TyKind::Array(ty, _) => {
+                self.visit_array(ty)
+            }
+            TyKind::Slice(ty) => {
+                self.visit_slice(ty)
+            }
             TyKind::RawPtr(ty_and_mutbl) => {
                 self.visit_raw_ptr(ty_and_mutbl.ty, ty_and_mutbl.mutbl)
             }
@@ -65,6 +77,15 @@ pub trait TypeVisitor<'tcx>: Sized {
             TyKind::FnDef(def_id, substs) => {
                 self.visit_fndef(def_id, substs)
             }
+            TyKind::Dynamic(predicates, region) => {
+                self.visit_dynamic(predicates, region)
+            }
+            TyKind::Opaque(def_id, substs) => {
+                self.visit_opaque(def_id, substs)
+            }
+            TyKind::Generator(def_id, substs, _) => {
+                self.visit_generator(def_id, substs)
+            }
             ref x => {
                 self.visit_unsupported_sty(x)
             }
@@ -96,6 +117,14 @@ pub trait TypeVisitor<'tcx>: Sized {
         Ok(())
     }
 
+    fn visit_float(&mut self, _ty: FloatTy) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn visit_str(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     fn visit_never(&mut self) -> Result<(), Self::Error> {
         Ok(())
     }
@@ -111,6 +140,30 @@ pub trait TypeVisitor<'tcx>: Sized {
         Ok(())
     }
 
+    fn visit_dynamic(
+        &mut self,
+        _predicates: &'tcx List<Binder<ExistentialPredicate<'tcx>>>,
+        _region: Region<'tcx>
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn visit_opaque(
+        &mut self,
+        _def_id: DefId,
+        _substs: SubstsRef<'tcx>
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn visit_generator(
+        &mut self,
+        _def_id: DefId,
+        _substs: SubstsRef<'tcx>
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     fn visit_adt(
         &mut self,
         adt_def: &'tcx AdtDef,
@@ -167,6 +220,16 @@ pub trait TypeVisitor<'tcx>: Sized {
         walk_tuple(self, parts)
     }
 
+    fn visit_array(&mut self, ty: Ty<'tcx>) -> Result<(), Self::Error> {
+        trace!("visit_array({:?})", ty);
+        walk_array(self, ty)
+    }
+
+    fn visit_slice(&mut self, ty: Ty<'tcx>) -> Result<(), Self::Error> {
+        trace!("visit_slice({:?})", ty);
+        walk_slice(self, ty)
+    }
+
     fn visit_raw_ptr(
         &mut self,
         ty: Ty<'tcx>,
@@ -253,6 +316,20 @@ pub fn walk_tuple<'tcx, E, V: TypeVisitor<'tcx, Error = E>>(
     Ok(())
 }
 
+pub fn walk_array<'tcx, E, V: TypeVisitor<'tcx, Error = E>>(
+    visitor: &mut V,
+    ty: Ty<'tcx>,
+) -> Result<(), E> {
+    visitor.visit_ty(ty)
+}
+
+pub fn walk_slice<'tcx, E, V: TypeVisitor<'tcx, Error = E>>(
+    visitor: &mut V,
+    ty: Ty<'tcx>,
+) -> Result<(), E> {
+    visitor.visit_ty(ty)
+}
+
 pub fn walk_raw_ptr<'tcx, E, V: TypeVisitor<'tcx, Error = E>>(
     visitor: &mut V,
     ty: Ty<'tcx>,
@@ -6,7 +6,8 @@
 
 use prusti_common::vir::{self, optimizations, ToViper, ToViperDecl};
 use prusti_common::{
-    config, report::log, verification_context::VerifierBuilder, verification_service::*, Stopwatch,
+    config, report::log, verification_cache::VerificationCache,
+    verification_context::VerifierBuilder, verification_service::*, Stopwatch,
 };
 use crate::encoder::Encoder;
 // use prusti_filter::validators::Validator;
@@ -20,9 +21,14 @@ use viper::{self, VerificationBackend, Viper};
 use std::path::PathBuf;
 use std::fs::{create_dir_all, canonicalize};
 use std::ffi::OsString;
+use std::panic;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
 use prusti_interface::specs::typed;
 use ::log::{info, debug, error};
-use prusti_server::{PrustiServerConnection, ServerSideService, VerifierRunner};
+use prusti_server::{
+    combine_results, PrustiServerConnection, ServerProcess, ServerSideService, VerifierRunner,
+};
 use rustc_span::DUMMY_SP;
 
 // /// A verifier builder is an object that lives entire program's
@@ -258,72 +264,243 @@ impl<'v, 'tcx> Verifier<'v, 'tcx> {
             .to_str()
             .unwrap()
             .to_owned();
-        let verification_result: viper::VerificationResult = if let Some(server_address) =
-            config::server_address()
-        {
+        let request = VerificationRequest {
+            program,
+            program_name,
+            backend_config: ViperBackendConfig::default(),
+        };
+        // The results are reported as soon as they are available, since verifying the whole
+        // program can take long.
+        let encoder = &self.encoder;
+        let env = self.env;
+        let mut aborted = false;
+        let mut num_verification_errors = 0;
+        let mut report_result = |verification_result: &viper::VerificationResult| {
+            match verification_result {
+                viper::VerificationResult::Success() => {}
+                viper::VerificationResult::Failure(errors) => {
+                    for verification_error in errors {
+                        debug!("Verification error: {:?}", verification_error);
+                        let prusti_error = encoder
+                            .error_manager()
+                            .translate_new_verification_error(verification_error);
+                        if let Some(prusti_error) = prusti_error {
+                            debug!("Prusti error: {:?}", prusti_error);
+                            num_verification_errors += 1;
+                            prusti_error.emit(env);
+                        }
+                    }
+                }
+                viper::VerificationResult::ConsistencyErrors(errors) => {
+                    debug_assert!(!errors.is_empty());
+                    errors.iter().for_each(|e| {
+                        PrustiError::internal(
+                            format!("consistency error: {}", e), DUMMY_SP.into()
+                        ).emit(env)
+                    });
+                    aborted = true;
+                }
+                viper::VerificationResult::JavaException(exception) => {
+                    error!("Java exception: {}", exception.get_stack_trace());
+                    PrustiError::internal(
+                        format!("{}", exception), DUMMY_SP.into()
+                    ).emit(env);
+                    aborted = true;
+                }
+            }
+        };
+        if config::cache_path().is_some() || config::num_parallel_verifiers() > 1 {
+            self.verify_methods_separately(request, &mut report_result);
+        } else {
+            self.run_verification(vec![request], &mut report_result);
+        }
+
+        stopwatch.finish();
+
+        if !aborted && encoding_errors_count == 0 && num_verification_errors == 0 {
+            VerificationResult::Success
+        } else {
+            VerificationResult::Failure
+        }
+    }
+
+    /// Verify each method of the program of `request` as a separate program. If a cache is
+    /// configured, the results stored in it are reused for the methods that did not change
+    /// since they were last verified. `on_result` is called with each result.
+    fn verify_methods_separately(
+        &self,
+        request: VerificationRequest,
+        on_result: &mut dyn FnMut(&viper::VerificationResult),
+    ) -> Vec<viper::VerificationResult> {
+        let cache = config::cache_path().map(|cache_path| {
+            let cache = VerificationCache::new(cache_path);
+            if config::invalidate_cache() {
+                cache.clear();
+            }
+            cache
+        });
+
+        let mut results = vec![];
+        let mut requests = vec![];
+        let mut cache_keys = vec![];
+        for request in request.split_by_method() {
+            if let Some(cache) = &cache {
+                let cache_key = request.cache_key();
+                if let Some(result) = cache.get(cache_key.hash) {
+                    debug!("Reusing the cached result of {}", request.program_name);
+                    let result = cache_key.denormalize_result(&result);
+                    on_result(&result);
+                    results.push(result);
+                    continue;
+                }
+                cache_keys.push(cache_key);
+            }
+            requests.push(request);
+        }
+        if cache.is_some() {
+            info!(
+                "Reusing {} cached verification results, verifying {} programs",
+                results.len(),
+                requests.len()
+            );
+        }
+
+        let new_results = self.run_verification(requests, on_result);
+        if let Some(cache) = &cache {
+            for (cache_key, result) in cache_keys.iter().zip(new_results.iter()) {
+                cache.insert(cache_key.hash, &cache_key.normalize_result(result));
+            }
+        }
+        results.extend(new_results);
+        results
+    }
+
+    /// Verify the programs of `requests`, either on the Prusti server or with local JVMs, and
+    /// return their results in the same order. `on_result` is called with the result of each
+    /// program, or of each method of a program verified on the server, as soon as it is
+    /// available.
+    ///
+    /// Up to `config::num_parallel_verifiers()` programs are verified at the same time. Locally,
+    /// each of them runs in a separate `prusti-server-driver` process, since there can be only
+    /// one JVM per process.
+    fn run_verification(
+        &self,
+        requests: Vec<VerificationRequest>,
+        on_result: &mut dyn FnMut(&viper::VerificationResult),
+    ) -> Vec<viper::VerificationResult> {
+        if requests.is_empty() {
+            return vec![];
+        }
+        let num_verifiers = config::num_parallel_verifiers().max(1).min(requests.len());
+        if let Some(server_address) = config::server_address() {
             let server_address = if server_address == "MOCK" {
                 ServerSideService::spawn_off_thread().to_string()
             } else {
                 server_address
             };
             info!("Connecting to Prusti server at {}", server_address);
-            let service = PrustiServerConnection::new(&server_address).unwrap_or_else(|error| {
-                panic!(
-                    "Could not parse server address ({}) due to {:?}",
-                    server_address, error
-                )
-            });
-
-            let request = VerificationRequest {
-                program,
-                program_name,
-                backend_config: Default::default(),
-            };
-            service.verify(request)
+            let services = (0..num_verifiers).map(|_| {
+                PrustiServerConnection::new(&server_address).unwrap_or_else(|error| {
+                    panic!(
+                        "Could not parse server address ({}) due to {:?}",
+                        server_address, error
+                    )
+                })
+            }).collect();
+
+            verify_concurrently(services, requests, on_result)
+        } else if num_verifiers > 1 {
+            let mut stopwatch = Stopwatch::start("prusti-viper", "verifier processes startup");
+            let driver_path = ServerProcess::default_driver_path();
+            let startups: Vec<_> = (0..num_verifiers).map(|_| {
+                let driver_path = driver_path.clone();
+                thread::spawn(move || ServerProcess::spawn(&driver_path))
+            }).collect();
+            let processes: Vec<ServerProcess> = startups.into_iter().map(|startup| {
+                startup.join().unwrap().unwrap_or_else(|error| {
+                    panic!("Could not start the verifier {:?} due to {}", driver_path, error)
+                })
+            }).collect();
+            let services = processes.iter().map(|process| {
+                PrustiServerConnection::new(process.address()).unwrap()
+            }).collect();
+
+            stopwatch.start_next("running verifiers");
+            let results = verify_concurrently(services, requests, on_result);
+            stopwatch.finish();
+            results
         } else {
             let mut stopwatch = Stopwatch::start("prusti-viper", "JVM startup");
             let verifier_builder = VerifierBuilder::new();
             stopwatch.start_next("running verifier");
             VerifierRunner::with_default_configured_runner(&verifier_builder, |runner| {
-                runner.verify(program, program_name.as_str())
+                requests.into_iter()
+                    .map(|request| {
+                        let result = runner.verify(request.program, request.program_name.as_str());
+                        on_result(&result);
+                        result
+                    })
+                    .collect()
             })
-        };
-
-        stopwatch.finish();
+        }
+    }
+}
 
-        let verification_errors = match verification_result {
-            viper::VerificationResult::Success() => vec![],
-            viper::VerificationResult::Failure(errors) => errors,
-            viper::VerificationResult::ConsistencyErrors(errors) => {
-                debug_assert!(!errors.is_empty());
-                errors.iter().for_each(|e| {
-                    PrustiError::internal(
-                        format!("consistency error: {}", e), DUMMY_SP.into()
-                    ).emit(self.env)
-                });
-                return VerificationResult::Failure;
-            }
-            viper::VerificationResult::JavaException(exception) => {
-                error!("Java exception: {}", exception.get_stack_trace());
-                PrustiError::internal(
-                    format!("{}", exception), DUMMY_SP.into()
-                ).emit(self.env);
-                return VerificationResult::Failure;
-            }
-        };
+/// A message from the threads of `verify_concurrently`.
+enum VerificationMessage {
+    /// The result of one of the methods of a request, which the server verified separately.
+    MethodResult(viper::VerificationResult),
+    /// The combined result of the request with the given index.
+    RequestResult(usize, viper::VerificationResult),
+}
 
-        if encoding_errors_count == 0 && verification_errors.is_empty() {
-            VerificationResult::Success
-        } else {
-            let error_manager = self.encoder.error_manager();
+/// Verify `requests` on the given servers, each of which verifies one request at a time in
+/// its own thread. `on_result` is called in this thread with the result of each method as
+/// soon as the server reports it. The results are returned in the order of the requests.
+fn verify_concurrently(
+    connections: Vec<PrustiServerConnection>,
+    requests: Vec<VerificationRequest>,
+    on_result: &mut dyn FnMut(&viper::VerificationResult),
+) -> Vec<viper::VerificationResult> {
+    let num_requests = requests.len();
+    let queue = Arc::new(Mutex::new(requests.into_iter().enumerate()));
+    let (message_sender, message_receiver) = mpsc::channel();
+    let workers: Vec<_> = connections.into_iter().map(|connection| {
+        let queue = queue.clone();
+        let message_sender = message_sender.clone();
+        thread::spawn(move || loop {
+            let next_request = queue.lock().unwrap().next();
+            let (index, request) = match next_request {
+                Some(next_request) => next_request,
+                None => break,
+            };
+            let results = connection
+                .verify_streaming(request, |program_result| {
+                    if let Ok(result) = &program_result.result {
+                        message_sender
+                            .send(VerificationMessage::MethodResult(result.clone()))
+                            .unwrap();
+                    }
+                })
+                .expect("Verification request to server failed!");
+            let result = combine_results(results)
+                .expect("Server panicked while processing request!");
+            message_sender.send(VerificationMessage::RequestResult(index, result)).unwrap();
+        })
+    }).collect();
+    drop(message_sender);
 
-            for verification_error in verification_errors {
-                debug!("Verification error: {:?}", verification_error);
-                let prusti_error = error_manager.translate_verification_error(&verification_error);
-                debug!("Prusti error: {:?}", prusti_error);
-                prusti_error.emit(self.env);
-            }
-            VerificationResult::Failure
+    let mut results = vec![None; num_requests];
+    for message in message_receiver {
+        match message {
+            VerificationMessage::MethodResult(result) => on_result(&result),
+            VerificationMessage::RequestResult(index, result) => results[index] = Some(result),
+        }
+    }
+    for worker in workers {
+        if let Err(panic) = worker.join() {
+            panic::resume_unwind(panic);
         }
     }
+    results.into_iter().map(|result| result.unwrap()).collect()
 }
@@ -1,8 +1,12 @@
-use prusti_interface::{specs, environment::Environment};
+use prusti_interface::{specs, data::VerificationResult, environment::Environment};
 use rustc_driver::Compilation;
 use rustc_hir::intravisit;
 use rustc_interface::interface::Compiler;
 use rustc_interface::Queries;
+use rustc_ast::ast;
+use rustc_parse::parser::ForceCollect;
+use rustc_session::Session;
+use rustc_span::FileName;
 use regex::Regex;
 use prusti_common::config;
 use crate::verifier::verify;
@@ -11,6 +15,18 @@ use crate::verifier::verify;
 pub struct PrustiCompilerCalls;
 
 impl rustc_driver::Callbacks for PrustiCompilerCalls {
+    fn after_parsing<'tcx>(
+        &mut self,
+        compiler: &Compiler,
+        queries: &'tcx Queries<'tcx>,
+    ) -> Compilation {
+        let session = compiler.session();
+        if session.opts.externs.get("prusti_std").is_some() && session.rust_2018() {
+            let krate = &mut *queries.parse().unwrap().peek_mut();
+            import_std_specs(session, krate);
+        }
+        Compilation::Continue
+    }
     fn after_expansion<'tcx>(
         &mut self,
         compiler: &Compiler,
@@ -42,6 +58,7 @@ impl rustc_driver::Callbacks for PrustiCompilerCalls {
             let krate = hir.krate();
             let mut visitor = specs::SpecCollector::new(tcx);
             intravisit::walk_crate(&mut visitor, &krate);
+            let exported_specs = visitor.exported_specs();
             let env = Environment::new(tcx);
             let def_spec = visitor.build_def_specs(&env);
             if config::print_typeckd_specs() {
@@ -71,7 +88,14 @@ impl rustc_driver::Callbacks for PrustiCompilerCalls {
                 }
             }
             if !config::no_verify() {
-                verify(env, def_spec);
+                let verification_result = verify(env, def_spec);
+                // Dependent crates must not rely on the specifications of a crate that does not
+                // verify.
+                if let Some(exported_specs) = exported_specs {
+                    if verification_result == VerificationResult::Success {
+                        exported_specs.write(tcx);
+                    }
+                }
             }
         });
 
@@ -82,4 +106,18 @@ impl rustc_driver::Callbacks for PrustiCompilerCalls {
             Compilation::Stop
         }
     }
-}
\ No newline at end of file
+}
+
+/// Declare the specifications of `prusti_std` in the crate, since Prusti only uses the
+/// specifications that are declared in the crate being verified.
+fn import_std_specs(session: &Session, krate: &mut ast::Crate) {
+    let mut parser = rustc_parse::new_parser_from_source_str(
+        &session.parse_sess,
+        FileName::Custom("prusti_std".to_owned()),
+        "prusti_std::import_std_specs!();".to_owned(),
+    );
+    match parser.parse_item(ForceCollect::No) {
+        Ok(item) => krate.module.items.extend(item),
+        Err(mut err) => err.emit(),
+    }
+}
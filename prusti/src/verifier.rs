@@ -13,11 +13,12 @@ use prusti_common::report::user;
 pub fn verify<'tcx>(
     env: Environment<'tcx>,
     def_spec: typed::DefSpecificationMap<'tcx>
-) {
+) -> VerificationResult {
     trace!("[verify] enter");
 
-    if env.has_errors() {
+    let verification_result = if env.has_errors() {
         warn!("The compiler reported an error, so the program will not be verified.");
+        VerificationResult::Failure
     } else {
         debug!("Prepare verification task...");
         let annotated_procedures = env.get_annotated_procedures();
@@ -63,7 +64,9 @@ pub fn verify<'tcx>(
                 debug_assert!(env.has_errors());
             }
         };
-    }
+        verification_result
+    };
 
     trace!("[verify] exit");
+    verification_result
 }
\ No newline at end of file
@@ -204,6 +204,16 @@ fn main() {
             java_class!("viper.silver.ast.Assert", vec![
                 constructor!(),
             ]),
+            java_class!("viper.silver.ast.BackendFunc", vec![
+                constructor!(),
+            ]),
+            java_class!("viper.silver.ast.BackendFuncApp$", vec![
+                object_getter!(),
+                method!("apply", "(Lviper/silver/ast/BackendFunc;Lscala/collection/immutable/Seq;Lviper/silver/ast/Position;Lviper/silver/ast/Info;Lviper/silver/ast/ErrorTrafo;)Lviper/silver/ast/BackendFuncApp;"),
+            ]),
+            java_class!("viper.silver.ast.BackendType", vec![
+                constructor!(),
+            ]),
             java_class!("viper.silver.ast.Bool$", vec![
                 object_getter!(),
             ]),
@@ -247,6 +257,9 @@ fn main() {
             java_class!("viper.silver.ast.EmptySet", vec![
                 constructor!(),
             ]),
+            java_class!("viper.silver.ast.EmptyMap", vec![
+                constructor!(),
+            ]),
             java_class!("viper.silver.ast.EpsilonPerm", vec![
                 constructor!(),
             ]),
@@ -268,6 +281,9 @@ fn main() {
             java_class!("viper.silver.ast.ExplicitSet", vec![
                 constructor!(),
             ]),
+            java_class!("viper.silver.ast.ExplicitMap", vec![
+                constructor!(),
+            ]),
             java_class!("viper.silver.ast.FalseLit", vec![
                 constructor!(),
             ]),
@@ -399,6 +415,30 @@ fn main() {
             java_class!("viper.silver.ast.MagicWand", vec![
                 constructor!(),
             ]),
+            java_class!("viper.silver.ast.MapCardinality", vec![
+                constructor!(),
+            ]),
+            java_class!("viper.silver.ast.MapContains", vec![
+                constructor!(),
+            ]),
+            java_class!("viper.silver.ast.MapDomain", vec![
+                constructor!(),
+            ]),
+            java_class!("viper.silver.ast.MapLookup", vec![
+                constructor!(),
+            ]),
+            java_class!("viper.silver.ast.MapRange", vec![
+                constructor!(),
+            ]),
+            java_class!("viper.silver.ast.MapType", vec![
+                constructor!(),
+            ]),
+            java_class!("viper.silver.ast.MapUpdate", vec![
+                constructor!(),
+            ]),
+            java_class!("viper.silver.ast.Maplet", vec![
+                constructor!(),
+            ]),
             java_class!("viper.silver.ast.Method", vec![
                 constructor!(),
             ]),
@@ -69,4 +69,25 @@ impl<'a> AstFactory<'a> {
             .unwrap_result(ast::SeqType::with(self.env).new(element_type.to_jobject()));
         Type::new(obj)
     }
+
+    pub fn map_type(&self, key_type: Type, value_type: Type) -> Type<'a> {
+        let obj = self.jni.unwrap_result(
+            ast::MapType::with(self.env).new(key_type.to_jobject(), value_type.to_jobject()),
+        );
+        Type::new(obj)
+    }
+
+    /// A type that is interpreted by the backends, for example a sort of an SMT-LIB theory.
+    /// `smt_name` is the name used by Silicon and `boogie_name` the one used by Carbon.
+    pub fn backend_type(&self, viper_name: &str, smt_name: &str, boogie_name: &str) -> Type<'a> {
+        let interpretations = [
+            (self.jni.new_string("SMTLIB"), self.jni.new_string(smt_name)),
+            (self.jni.new_string("Boogie"), self.jni.new_string(boogie_name)),
+        ];
+        let obj = self.jni.unwrap_result(ast::BackendType::with(self.env).new(
+            self.jni.new_string(viper_name),
+            self.jni.new_map(&interpretations),
+        ));
+        Type::new(obj)
+    }
 }
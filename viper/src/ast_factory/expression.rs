@@ -546,6 +546,42 @@ impl<'a> AstFactory<'a> {
         Expr::new(obj)
     }
 
+    /// Applies a function that is interpreted by the backends. The application is emitted as
+    /// `(smt_name args...)`, so `smt_name` may also be an indexed or partially applied SMT-LIB
+    /// function such as `fp.add RNE`.
+    pub fn backend_func_app(
+        &self,
+        function_name: &str,
+        smt_name: &str,
+        formal_args: &[LocalVarDecl],
+        return_type: Type,
+        args: &[Expr],
+        pos: Position,
+    ) -> Expr<'a> {
+        let backend_func = self.jni.unwrap_result(ast::BackendFunc::with(self.env).new(
+            self.jni.new_string(function_name),
+            self.jni.new_string(smt_name),
+            return_type.to_jobject(),
+            self.jni.new_seq(&map_to_jobjects!(formal_args)),
+            self.no_position().to_jobject(),
+            self.no_info(),
+            self.no_trafos(),
+        ));
+        let backend_func_app_object_wrapper = ast::BackendFuncApp_object::with(self.env);
+        let obj = self.jni.unwrap_result(
+            backend_func_app_object_wrapper.call_apply(
+                self.jni
+                    .unwrap_result(backend_func_app_object_wrapper.singleton()),
+                backend_func,
+                self.jni.new_seq(&map_to_jobjects!(args)),
+                pos.to_jobject(),
+                self.no_info(),
+                self.no_trafos(),
+            ),
+        );
+        Expr::new(obj)
+    }
+
     pub fn field_access_with_pos(&self, rcv: Expr, field: Field, pos: Position) -> Expr<'a> {
         build_ast_node_with_pos!(
             self,
@@ -702,7 +738,7 @@ impl<'a> AstFactory<'a> {
         variables: &[LocalVarDecl],
         triggers: &[Trigger],
         expr: Expr,
-        _pos: Position
+        pos: Position
     ) -> Expr<'a> {
         build_ast_node_with_pos!(
             self,
@@ -711,7 +747,7 @@ impl<'a> AstFactory<'a> {
             self.jni.new_seq(&map_to_jobjects!(variables)),
             self.jni.new_seq(&map_to_jobjects!(triggers)),
             expr.to_jobject(),
-            self.no_position().to_jobject()
+            pos.to_jobject()
         )
     }
 
@@ -924,6 +960,60 @@ impl<'a> AstFactory<'a> {
         build_ast_node!(self, Expr, ast::AnySetCardinality, set.to_jobject())
     }
 
+    pub fn empty_map(&self, key_type: Type, value_type: Type) -> Expr<'a> {
+        build_ast_node!(
+            self,
+            Expr,
+            ast::EmptyMap,
+            key_type.to_jobject(),
+            value_type.to_jobject()
+        )
+    }
+
+    pub fn maplet(&self, key: Expr, value: Expr) -> Expr<'a> {
+        build_ast_node!(self, Expr, ast::Maplet, key.to_jobject(), value.to_jobject())
+    }
+
+    pub fn explicit_map(&self, maplets: &[Expr]) -> Expr<'a> {
+        build_ast_node!(
+            self,
+            Expr,
+            ast::ExplicitMap,
+            self.jni.new_seq(&map_to_jobjects!(maplets))
+        )
+    }
+
+    pub fn map_lookup(&self, map: Expr, key: Expr) -> Expr<'a> {
+        build_ast_node!(self, Expr, ast::MapLookup, map.to_jobject(), key.to_jobject())
+    }
+
+    pub fn map_update(&self, map: Expr, key: Expr, value: Expr) -> Expr<'a> {
+        build_ast_node!(
+            self,
+            Expr,
+            ast::MapUpdate,
+            map.to_jobject(),
+            key.to_jobject(),
+            value.to_jobject()
+        )
+    }
+
+    pub fn map_contains(&self, key: Expr, map: Expr) -> Expr<'a> {
+        build_ast_node!(self, Expr, ast::MapContains, key.to_jobject(), map.to_jobject())
+    }
+
+    pub fn map_cardinality(&self, map: Expr) -> Expr<'a> {
+        build_ast_node!(self, Expr, ast::MapCardinality, map.to_jobject())
+    }
+
+    pub fn map_domain(&self, map: Expr) -> Expr<'a> {
+        build_ast_node!(self, Expr, ast::MapDomain, map.to_jobject())
+    }
+
+    pub fn map_range(&self, map: Expr) -> Expr<'a> {
+        build_ast_node!(self, Expr, ast::MapRange, map.to_jobject())
+    }
+
     pub fn simplified_expression(&self, expr: Expr) -> Expr<'a> {
         let simplifier_object_wrapper = ast::utility::Simplifier_object::with(self.env);
         let obj = self.jni.unwrap_result(
@@ -394,6 +394,8 @@ def verify_test(args):
         env['PRUSTI_CHECK_OVERFLOWS'] = 'true'
     else:
         env['PRUSTI_CHECK_OVERFLOWS'] = 'false'
+    if test_path.startswith('prusti-tests/tests/verify_termination/'):
+        env['PRUSTI_CHECK_LOOP_TERMINATION'] = 'true'
     run_command([prusti_path, '--edition=2018', test_path] + compile_flags, env)
 
 